@@ -0,0 +1,38 @@
+//! Hands the embedded corpus to the crate: its size (the extraction stamp),
+//! and with `compressed-db` a zstd-compressed copy in `OUT_DIR`, which keeps
+//! the binary to about a third of the size.
+
+use std::env;
+use std::fs;
+
+const DB_PATH: &str = "sqlite.db";
+
+fn main() {
+    println!("cargo:rerun-if-changed={DB_PATH}");
+    let size = fs::metadata(DB_PATH)
+        .unwrap_or_else(|err| panic!("Failed to read {DB_PATH}: {err}"))
+        .len();
+    println!("cargo:rustc-env=ERWINDB_EMBEDDED_DB_SIZE={size}");
+
+    if env::var_os("CARGO_FEATURE_COMPRESSED_DB").is_some() {
+        compress_db();
+    }
+}
+
+#[cfg(feature = "compressed-db")]
+fn compress_db() {
+    use std::fs::File;
+    use std::path::Path;
+
+    /// Higher levels shrink it only a little more, at many times the build time
+    const LEVEL: i32 = 9;
+
+    let out = Path::new(&env::var_os("OUT_DIR").unwrap()).join("sqlite.db.zst");
+    let input = File::open(DB_PATH).unwrap();
+    let output = File::create(&out).unwrap();
+    zstd::stream::copy_encode(input, output, LEVEL)
+        .unwrap_or_else(|err| panic!("Failed to compress {DB_PATH}: {err}"));
+}
+
+#[cfg(not(feature = "compressed-db"))]
+fn compress_db() {}
@@ -0,0 +1,23 @@
+//! Arbitrary post HTML through all of `html::html_to_content`, at a few fixed
+//! widths and one picked by the fuzzer: what the sanitizer lets through must
+//! lay out and highlight without panicking at any of them.
+//! Run with `just fuzz html_to_content` (nightly and cargo-fuzz required).
+
+#![no_main]
+
+use erwindb::html::html_to_content;
+use libfuzzer_sys::fuzz_target;
+
+/// Narrowest pane, a typical one, and a wide one
+const WIDTHS: [usize; 3] = [1, 80, 200];
+
+fuzz_target!(|input: (u8, &str)| {
+    let (width, html) = input;
+    for width in WIDTHS.into_iter().chain([usize::from(width)]) {
+        let content = html_to_content(html, width);
+        for code_block in &content.code_blocks {
+            assert!(code_block.start_line <= code_block.end_line);
+            assert!(code_block.end_line <= content.lines.len());
+        }
+    }
+});
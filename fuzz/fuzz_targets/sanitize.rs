@@ -0,0 +1,23 @@
+//! Arbitrary post HTML through the stages of `html::html_to_content` that see
+//! it raw: sanitizing, html2text layout at any width, and placeholder lookup.
+//! Run with `just fuzz` (nightly and cargo-fuzz required).
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../src/sanitize.rs"]
+#[allow(dead_code)]
+mod sanitize;
+
+fuzz_target!(|input: (u8, &str)| {
+    let (width, html) = input;
+    sanitize::sanitize_html(html);
+
+    let prepared = sanitize::prepare(html, usize::from(width));
+    for line in &prepared.lines {
+        if let Some(index) = prepared.code_block_index(&line.text) {
+            assert!(index < prepared.code_blocks.len());
+        }
+    }
+});
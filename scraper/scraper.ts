@@ -5,6 +5,7 @@ import { DB } from "https://deno.land/x/sqlite@v3.9.1/mod.ts";
 import { load } from "https://deno.land/std@0.224.0/dotenv/mod.ts";
 import type {
   ScrapedData,
+  Site,
   StackOverflowAnswer,
   StoredQuestion,
 } from "./types.ts";
@@ -17,18 +18,133 @@ async function loadEmbeddings() {
 // Load .env file
 const env = await load();
 
-// Stack Overflow user ID for Erwin Brandstetter
-const USER_ID = 939860;
+// Erwin Brandstetter's user ID on each Stack Exchange site we ingest
+const USER_IDS: Record<Site, number> = {
+  stackoverflow: 939860,
+  dba: 3684,
+};
+const SITES = Object.keys(USER_IDS) as Site[];
 const API_KEY = env["STACKOVERFLOW_API_KEY"] ||
   Deno.env.get("STACKOVERFLOW_API_KEY") || "";
 const API_KEY_PARAM = API_KEY ? `&key=${API_KEY}` : "";
-const API_URL =
-  `https://api.stackexchange.com/2.3/users/${USER_ID}/answers?order=desc&sort=activity&site=stackoverflow&filter=default${API_KEY_PARAM}`;
+const answersUrl = (site: Site) =>
+  `https://api.stackexchange.com/2.3/users/${
+    USER_IDS[site]
+  }/answers?order=desc&sort=activity&site=${site}&filter=default${API_KEY_PARAM}`;
+
+function parseSite(value: string | undefined): Site {
+  if (value === undefined) return "stackoverflow";
+  if ((SITES as string[]).includes(value)) return value as Site;
+  console.error(`❌ Unknown site "${value}" (expected: ${SITES.join(", ")})`);
+  Deno.exit(1);
+}
+
+// Words in a post, code included, for the TUI's reading-time estimate
+// (mirrors `html::word_count`)
+function wordCount(html: string): number {
+  return html.replace(/<[^>]*>/g, " ").split(/\s+/).filter(Boolean).length;
+}
+
+// One-line excerpt for the TUI's detailed list: the first sentence of the body
+// without code blocks, duplicate notices or markup (mirrors `html::excerpt`)
+function excerptFromHtml(html: string): string {
+  const text = html
+    .replace(
+      /<pre\b[\s\S]*?<\/pre>|<blockquote>\s*<p><strong>Possible Duplicate:<\/strong>[\s\S]*?<\/blockquote>/gi,
+      " ",
+    )
+    .replace(/<[^>]*>/g, " ")
+    .replaceAll("&lt;", "<")
+    .replaceAll("&gt;", ">")
+    .replaceAll("&amp;", "&")
+    .replaceAll("&quot;", '"')
+    .replaceAll("&#39;", "'")
+    .replaceAll("&nbsp;", " ")
+    .replace(/\s+/g, " ")
+    .trim()
+    .replace(/\s+([.,;:?!)])/g, "$1");
+  // Very short sentences ("Hi all.") run on into the next
+  let sentence = text;
+  for (const end of text.matchAll(/[.?!](?:\s|$)/g)) {
+    if (end.index + 1 >= 25) {
+      sentence = text.slice(0, end.index + 1);
+      break;
+    }
+  }
+  const chars = [...sentence];
+  return chars.length <= 160
+    ? sentence
+    : chars.slice(0, 159).join("").trimEnd() + "\u2026";
+}
+
+// Keywords in nearly every query, which would bury the interesting ones
+const STOP_KEYWORDS = new Set([
+  "ALL", "AND", "ASC", "BEGIN", "BIGINT", "BOOLEAN", "CASE", "COMMIT", "CREATE",
+  "DATE", "DECLARE", "DEFAULT", "DELETE", "DESC", "ELSE", "END", "EXISTS",
+  "FALSE", "FROM", "FUNCTION", "GROUP", "HAVING", "INDEX", "INNER", "INSERT",
+  "INT", "INTEGER", "INTO", "JOIN", "KEY", "LANGUAGE", "LEFT", "LIMIT", "NOT",
+  "NULL", "ORDER", "PRIMARY", "REFERENCES", "RETURN", "RETURNS", "SELECT",
+  "SERIAL", "SET", "SQL", "TABLE", "TEXT", "THEN", "TRUE", "UPDATE", "VALUES",
+  "WHEN", "WHERE", "WITH",
+]);
+
+// Functions (lower-case names directly followed by "(") and all-caps keywords
+// in a post's code, as "kind:term" keys (mirrors `terms::code_terms`)
+function termsInCode(html: string): Set<string> {
+  const terms = new Set<string>();
+  for (const [, raw] of html.matchAll(/<code>([\s\S]*?)<\/code>/g)) {
+    const code = raw
+      .replaceAll("&lt;", "<")
+      .replaceAll("&gt;", ">")
+      .replaceAll("&quot;", '"')
+      .replaceAll("&#39;", "'")
+      .replaceAll("&amp;", "&");
+    for (const [, word, paren] of code.matchAll(/([A-Za-z_][A-Za-z0-9_]*)(\()?/g)) {
+      if (paren && /[a-z]/.test(word)) {
+        terms.add(`function:${word.toLowerCase()}`);
+      } else if (
+        word.length >= 3 && /^[A-Z_]+$/.test(word) && !STOP_KEYWORDS.has(word)
+      ) {
+        terms.add(`keyword:${word}`);
+      }
+    }
+  }
+  return terms;
+}
+
+// Wording that retires an answer, links (whose text is left out when looking
+// for it) and the ends of the blocks an answer body is split into
+// (mirror `superseded.rs`)
+const SUPERSEDED_RE =
+  /\b(?:outdated|obsolete|superseded|deprecated|no longer (?:needed|necessary|recommended|the best))\b|\b(?:newer|more recent|better|new|updated|improved) (?:answer|version|solution|variant|post)\b/i;
+const ANCHOR_RE = /<a\b[^>]*>[\s\S]*?<\/a>/gi;
+const HREF_RE = /href="([^"]+)"/g;
+const BLOCK_END_RE = /<\/p>|<\/[uo]l>|<h\d|<hr/i;
+const SITE_HOSTS: Record<Site, string> = {
+  stackoverflow: "stackoverflow.com",
+  dba: "dba.stackexchange.com",
+};
+
+// A link in `text` to a post on `site` newer than `answerId`, if the text
+// says the answer is superseded (mirrors `superseded::newer_link`)
+function newerLink(site: Site, answerId: number, text: string): string | null {
+  if (!SUPERSEDED_RE.test(text.replace(ANCHOR_RE, " "))) return null;
+  for (const [, url] of text.matchAll(HREF_RE)) {
+    const host = url.match(/^(?:https?:)?\/\/(?:www\.)?([^/:?#]+)/)?.[1];
+    const post = url.match(
+      /(?:stackoverflow|dba\.stackexchange)\.com\/(?:a\/(\d+)|questions\/\d+\/[^/#?]+\/(\d+)|(?:questions|q)\/(\d+))/,
+    );
+    const postId = Number(post?.[1] ?? post?.[2] ?? post?.[3] ?? 0);
+    if (host === SITE_HOSTS[site] && postId > answerId) return url;
+  }
+  return null;
+}
 
 const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
 
 class InteractiveStackOverflowScraper {
-  private questionIds: number[] = [];
+  // Question IDs are only unique per site, so each site keeps its own list
+  private questionIds = new Map<Site, number[]>();
   private scrapedData = new Map<number, ScrapedData>();
   private db: DB | null = null;
 
@@ -44,19 +160,23 @@ class InteractiveStackOverflowScraper {
       );
     }
 
-    // Create tables
-    this.db.execute(`
+    // Create tables (question IDs are keyed by site: the same ID can be a
+    // different question on each site)
+    const questionIdsTable = `
       CREATE TABLE IF NOT EXISTS question_ids (
-        id INTEGER PRIMARY KEY,
+        id INTEGER NOT NULL,
         discovered_at DATETIME DEFAULT CURRENT_TIMESTAMP,
         scraped BOOLEAN DEFAULT FALSE,
-        scraped_at DATETIME NULL
+        scraped_at DATETIME NULL,
+        site TEXT NOT NULL DEFAULT 'stackoverflow',
+        PRIMARY KEY (site, id)
       )
-    `);
+    `;
+    this.db.execute(questionIdsTable);
 
-    this.db.execute(`
+    const questionsTable = `
       CREATE TABLE IF NOT EXISTS questions (
-        id INTEGER PRIMARY KEY,
+        id INTEGER NOT NULL,
         title TEXT NOT NULL,
         body TEXT NOT NULL,
         score INTEGER DEFAULT 0,
@@ -71,9 +191,19 @@ class InteractiveStackOverflowScraper {
         author_reputation INTEGER DEFAULT 0,
         author_user_id INTEGER DEFAULT 0,
         scraped_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-        FOREIGN KEY (id) REFERENCES question_ids (id)
+        site TEXT NOT NULL DEFAULT 'stackoverflow',
+        answer_scores TEXT,
+        closed_reason TEXT,
+        duplicate_of INTEGER,
+        locked_date INTEGER,
+        bounty INTEGER,
+        excerpt TEXT,
+        word_count INTEGER,
+        PRIMARY KEY (site, id),
+        FOREIGN KEY (site, id) REFERENCES question_ids (site, id)
       )
-    `);
+    `;
+    this.db.execute(questionsTable);
 
     this.db.execute(`
       CREATE TABLE IF NOT EXISTS question_comments (
@@ -103,6 +233,8 @@ class InteractiveStackOverflowScraper {
         author_name TEXT DEFAULT 'Unknown',
         author_reputation INTEGER DEFAULT 0,
         author_user_id INTEGER DEFAULT 0,
+        ingest_reputation INTEGER,
+        awarded_bounty INTEGER,
         FOREIGN KEY (question_id) REFERENCES questions (id)
       )
     `);
@@ -130,30 +262,194 @@ class InteractiveStackOverflowScraper {
       )
     `);
 
+    // Links between copies of the same question on different sites
+    // (migrations or cross-posts), stored in both directions
+    this.db.execute(`
+      CREATE TABLE IF NOT EXISTS question_copies (
+        question_id INTEGER NOT NULL,
+        copy_id INTEGER NOT NULL,
+        reason TEXT NOT NULL,
+        PRIMARY KEY (question_id, copy_id)
+      )
+    `);
+
+    // Mean title embedding per tag, for topic exploration
+    this.db.execute(`
+      CREATE TABLE IF NOT EXISTS tag_centroids (
+        tag TEXT PRIMARY KEY,
+        embedding BLOB NOT NULL,
+        question_count INTEGER NOT NULL
+      )
+    `);
+
+    // Functions and keywords in the code of Erwin's answers, for the Terms page
+    this.db.execute(`
+      CREATE TABLE IF NOT EXISTS term_frequencies (
+        term TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        answer_count INTEGER NOT NULL,
+        PRIMARY KEY (term, kind)
+      )
+    `);
+
+    // Erwin's answers he marked superseded, with the newer post he points to
+    this.db.execute(`
+      CREATE TABLE IF NOT EXISTS superseded_answers (
+        answer_id INTEGER PRIMARY KEY,
+        newer_url TEXT NOT NULL
+      )
+    `);
+
+    // Columns added after the original schema
+    this.ensureColumn(
+      "question_ids",
+      "site",
+      "TEXT NOT NULL DEFAULT 'stackoverflow'",
+    );
+    this.ensureColumn(
+      "questions",
+      "site",
+      "TEXT NOT NULL DEFAULT 'stackoverflow'",
+    );
+    this.ensureColumn("questions", "answer_scores", "TEXT");
+    this.ensureColumn("answers", "ingest_reputation", "INTEGER");
+    this.ensureColumn("questions", "closed_reason", "TEXT");
+    this.ensureColumn("questions", "duplicate_of", "INTEGER");
+    this.ensureColumn("questions", "locked_date", "INTEGER");
+    this.ensureColumn("answers", "awarded_bounty", "INTEGER");
+    this.ensureColumn("questions", "bounty", "INTEGER");
+    this.ensureColumn("questions", "excerpt", "TEXT");
+    this.ensureColumn("questions", "word_count", "INTEGER");
+
+    // Older databases keyed question IDs without the site
+    this.ensurePrimaryKey("question_ids", ["site", "id"], questionIdsTable);
+    this.ensurePrimaryKey("questions", ["site", "id"], questionsTable);
+    this.backfillThreadStats();
+
     // Load existing question IDs from database
     await this.loadQuestionIdsFromDatabase();
 
     await this.getDatabaseStats();
   }
 
+  // Add a column to an existing table if an older database lacks it
+  ensureColumn(table: string, column: string, definition: string) {
+    if (!this.db) return;
+    const columns = this.query(`PRAGMA table_info(${table})`);
+    if (!columns.some((c: any) => c.name === column)) {
+      this.db.execute(`ALTER TABLE ${table} ADD COLUMN ${column} ${definition}`);
+      console.log(`🔧 Added ${table}.${column}`);
+    }
+  }
+
+  // Rebuild a table whose primary key an older database created differently,
+  // keeping its rows (run after `ensureColumn`, so every column exists)
+  ensurePrimaryKey(table: string, key: string[], create: string) {
+    if (!this.db) return;
+    const columns = this.query(`PRAGMA table_info(${table})`);
+    const current = columns
+      .filter((c: any) => c.pk > 0)
+      .sort((a: any, b: any) => a.pk - b.pk)
+      .map((c: any) => c.name);
+    if (current.join(",") === key.join(",")) return;
+
+    const names = columns.map((c: any) => c.name).join(", ");
+    // Keep other tables' foreign keys pointing at the new table, not the old
+    this.db.execute("PRAGMA legacy_alter_table = ON");
+    this.db.execute("BEGIN TRANSACTION");
+    try {
+      this.db.execute(`ALTER TABLE ${table} RENAME TO ${table}_old`);
+      this.db.execute(create);
+      this.db.execute(
+        `INSERT OR IGNORE INTO ${table} (${names}) SELECT ${names} FROM ${table}_old`,
+      );
+      this.db.execute(`DROP TABLE ${table}_old`);
+      this.db.execute("COMMIT");
+    } catch (err) {
+      this.db.execute("ROLLBACK");
+      throw err;
+    } finally {
+      this.db.execute("PRAGMA legacy_alter_table = OFF");
+    }
+    console.log(`🔧 Keyed ${table} by (${key.join(", ")})`);
+  }
+
+  // Excerpts and word counts of questions saved before ingest computed them;
+  // the TUI only reads what's stored
+  backfillThreadStats() {
+    if (!this.db) return;
+    const questions = this.query(
+      "SELECT id, site, body FROM questions WHERE excerpt IS NULL OR word_count IS NULL",
+    ) as { id: number; site: string; body: string }[];
+    if (questions.length === 0) return;
+
+    console.log(
+      `🔧 Computing excerpts and word counts of ${questions.length} questions...`,
+    );
+    const answerWords = new Map<number, number>();
+    for (const answer of this.query("SELECT question_id, answer_text FROM answers")) {
+      const id = answer.question_id as number;
+      answerWords.set(
+        id,
+        (answerWords.get(id) ?? 0) + wordCount(answer.answer_text as string),
+      );
+    }
+
+    const db = this.db;
+    db.execute("BEGIN TRANSACTION");
+    try {
+      questions.forEach((q, i) => {
+        db.query(
+          "UPDATE questions SET excerpt = ?, word_count = ? WHERE site = ? AND id = ?",
+          [
+            excerptFromHtml(q.body),
+            wordCount(q.body) + (answerWords.get(q.id) ?? 0),
+            q.site,
+            q.id,
+          ],
+        );
+        if ((i + 1) % 1000 === 0) {
+          console.log(`   ${i + 1}/${questions.length}`);
+        }
+      });
+      db.execute("COMMIT");
+    } catch (err) {
+      db.execute("ROLLBACK");
+      throw err;
+    }
+  }
+
   // Load existing question IDs from database
   async loadQuestionIdsFromDatabase() {
     if (!this.db) return;
 
     const existingIds = this.query(
-      "SELECT id FROM question_ids ORDER BY discovered_at DESC",
+      "SELECT id, site FROM question_ids ORDER BY discovered_at DESC",
     );
-    this.questionIds = existingIds.map((row: any) => row.id);
+    this.questionIds.clear();
+    for (const row of existingIds) {
+      const site = row.site as Site;
+      this.questionIds.set(site, [
+        ...(this.questionIds.get(site) ?? []),
+        row.id as number,
+      ]);
+    }
 
-    if (this.questionIds.length > 0) {
-      console.log(
-        `📚 Loaded ${this.questionIds.length} existing question IDs from database`,
-      );
+    const total = this.allQuestionIds().length;
+    if (total > 0) {
+      console.log(`📚 Loaded ${total} existing question IDs from database`);
     }
   }
 
+  // Every known question ID, with the site it belongs to
+  allQuestionIds(): { id: number; site: Site }[] {
+    return [...this.questionIds].flatMap(([site, ids]) =>
+      ids.map((id) => ({ id, site }))
+    );
+  }
+
   // Save question IDs to database
-  async saveQuestionIdsToDatabase(questionIds: number[]) {
+  async saveQuestionIdsToDatabase(questionIds: number[], site: Site) {
     if (!this.db) return;
 
     console.log(`💾 Saving ${questionIds.length} question IDs to database...`);
@@ -164,8 +460,8 @@ class InteractiveStackOverflowScraper {
       for (const id of questionIds) {
         // Use INSERT OR IGNORE to avoid duplicates
         this.db.query(
-          "INSERT OR IGNORE INTO question_ids (id) VALUES (?)",
-          [id],
+          "INSERT OR IGNORE INTO question_ids (id, site) VALUES (?, ?)",
+          [id, site],
         );
         // SQLite returns the number of changed rows, check if this was a new insert
         if (this.db.changes > 0) newCount++;
@@ -182,19 +478,25 @@ class InteractiveStackOverflowScraper {
     }
   }
   // Fetch question IDs and display them
-  async fetchQuestionIds(maxPages: number = 1): Promise<number[]> {
+  async fetchQuestionIds(
+    maxPages: number = 1,
+    site: Site = "stackoverflow",
+  ): Promise<number[]> {
     let page = 1;
     let hasMore = true;
-    const questionIds = new Set<number>(this.questionIds); // Start with existing IDs
+    const known = this.questionIds.get(site) ?? [];
+    const questionIds = new Set<number>(known); // Start with this site's existing IDs
 
-    console.log(`🔍 Fetching answered question IDs (max ${maxPages} pages)...`);
     console.log(
-      `📚 Starting with ${this.questionIds.length} existing IDs from database`,
+      `🔍 Fetching answered question IDs on ${site} (max ${maxPages} pages)...`,
+    );
+    console.log(
+      `📚 Starting with ${known.length} existing ${site} IDs from database`,
     );
 
     while (hasMore && page <= maxPages) {
       try {
-        const url = API_URL + `&page=${page}&pagesize=100`;
+        const url = answersUrl(site) + `&page=${page}&pagesize=100`;
         const response = await fetch(url);
         const data = await response.json();
 
@@ -236,68 +538,71 @@ class InteractiveStackOverflowScraper {
     const newQuestionIds = Array.from(questionIds);
 
     // Save all IDs to database (will ignore duplicates)
-    await this.saveQuestionIdsToDatabase(newQuestionIds);
+    await this.saveQuestionIdsToDatabase(newQuestionIds, site);
 
     // Update our in-memory list
-    this.questionIds = newQuestionIds;
+    this.questionIds.set(site, newQuestionIds);
 
-    console.log(`\n📊 Total unique questions: ${this.questionIds.length}`);
-    return this.questionIds;
+    console.log(`\n📊 Total unique ${site} questions: ${newQuestionIds.length}`);
+    return newQuestionIds;
   }
 
   // Check if a question is already scraped
-  isQuestionScraped(questionId: number): boolean {
+  isQuestionScraped(questionId: number, site: Site): boolean {
     if (!this.db) return false;
 
-    const result = this.query("SELECT scraped FROM question_ids WHERE id = ?", [
-      questionId,
-    ]);
+    const result = this.query(
+      "SELECT scraped FROM question_ids WHERE id = ? AND site = ?",
+      [questionId, site],
+    );
 
     return result.length > 0 && result[0].scraped === 1;
   }
 
   // Mark a question as scraped
-  markQuestionAsScraped(questionId: number) {
+  markQuestionAsScraped(questionId: number, site: Site) {
     if (!this.db) return;
 
     this.db.query(
-      "UPDATE question_ids SET scraped = TRUE, scraped_at = CURRENT_TIMESTAMP WHERE id = ?",
-      [questionId],
+      "UPDATE question_ids SET scraped = TRUE, scraped_at = CURRENT_TIMESTAMP WHERE id = ? AND site = ?",
+      [questionId, site],
     );
   }
 
-  // Get unscraped question IDs (newest first - higher ID = newer question)
-  getUnscrapedQuestionIds(limit = 10): number[] {
+  // Get unscraped questions together with the site they belong to
+  // (newest first - higher ID = newer question)
+  getUnscrapedQuestions(limit = 10): { id: number; site: Site }[] {
     if (!this.db) {
       console.log("❌ Database not initialized.");
       return [];
     }
 
     const result = this.query(
-      "SELECT id FROM question_ids WHERE scraped = FALSE ORDER BY id DESC LIMIT ?",
+      "SELECT id, site FROM question_ids WHERE scraped = FALSE ORDER BY id DESC LIMIT ?",
       [limit],
     );
 
-    return result.map((row: any) => row.id);
+    return result.map((row: any) => ({ id: row.id, site: row.site }));
   }
 
-  // Get scraped question IDs
-  getScrapedQuestionIds(limit = 10): number[] {
+  // Get scraped questions together with the site they belong to
+  getScrapedQuestions(limit = 10): { id: number; site: Site }[] {
     if (!this.db) {
       console.log("❌ Database not initialized.");
       return [];
     }
 
     const result = this.query(
-      "SELECT id FROM question_ids WHERE scraped = TRUE ORDER BY scraped_at DESC LIMIT ?",
+      "SELECT id, site FROM question_ids WHERE scraped = TRUE ORDER BY scraped_at DESC LIMIT ?",
       [limit],
     );
 
-    return result.map((row: any) => row.id);
+    return result.map((row: any) => ({ id: row.id, site: row.site }));
   }
   // Display question IDs in a nice format
   displayQuestionIds(limit = 20) {
-    if (this.questionIds.length === 0) {
+    const questionIds = this.allQuestionIds();
+    if (questionIds.length === 0) {
       console.log(
         "❌ No question IDs available. Run fetchQuestionIds() first.",
       );
@@ -306,24 +611,26 @@ class InteractiveStackOverflowScraper {
 
     console.log(
       `\n📋 Question IDs (showing first ${
-        Math.min(limit, this.questionIds.length)
-      } of ${this.questionIds.length}):`,
+        Math.min(limit, questionIds.length)
+      } of ${questionIds.length}):`,
     );
 
-    const idsToShow = this.questionIds.slice(0, limit);
-    idsToShow.forEach((id, index) => {
-      const isScraped = this.isQuestionScraped(id);
+    const idsToShow = questionIds.slice(0, limit);
+    idsToShow.forEach(({ id, site }, index) => {
+      const isScraped = this.isQuestionScraped(id, site);
       const status = isScraped ? "✅" : "⏳";
-      console.log(`  ${(index + 1).toString().padStart(2)}: ${id} ${status}`);
+      console.log(
+        `  ${(index + 1).toString().padStart(2)}: ${site} ${id} ${status}`,
+      );
     });
 
-    if (this.questionIds.length > limit) {
-      console.log(`  ... and ${this.questionIds.length - limit} more`);
+    if (questionIds.length > limit) {
+      console.log(`  ... and ${questionIds.length - limit} more`);
     }
 
     // Show scraping statistics
-    const unscrapedCount = this.getUnscrapedQuestionIds(1000).length;
-    const scrapedCount = this.getScrapedQuestionIds(1000).length;
+    const unscrapedCount = this.getUnscrapedQuestions(1000).length;
+    const scrapedCount = this.getScrapedQuestions(1000).length;
     console.log(
       `\n📊 Status: ${scrapedCount} scraped, ${unscrapedCount} unscraped`,
     );
@@ -331,24 +638,24 @@ class InteractiveStackOverflowScraper {
 
   // Display unscraped question IDs
   displayUnscrapedIds(limit = 20) {
-    const unscrapedIds = this.getUnscrapedQuestionIds(limit);
+    const unscraped = this.getUnscrapedQuestions(limit);
 
-    if (unscrapedIds.length === 0) {
+    if (unscraped.length === 0) {
       console.log("🎉 All questions have been scraped!");
       return;
     }
 
     console.log(
-      `\n⏳ Unscraped Question IDs (showing ${unscrapedIds.length}):`,
+      `\n⏳ Unscraped Question IDs (showing ${unscraped.length}):`,
     );
-    unscrapedIds.forEach((id, index) => {
-      console.log(`  ${(index + 1).toString().padStart(2)}: ${id}`);
+    unscraped.forEach(({ id, site }, index) => {
+      console.log(`  ${(index + 1).toString().padStart(2)}: ${site} ${id}`);
     });
   }
 
   // Get specific ranges of question IDs
-  getQuestionIds(start = 0, count = 10): number[] {
-    return this.questionIds.slice(start, start + count);
+  getQuestionIds(start = 0, count = 10): { id: number; site: Site }[] {
+    return this.allQuestionIds().slice(start, start + count);
   }
 
   // Helper to fetch with rate limit handling
@@ -423,11 +730,26 @@ class InteractiveStackOverflowScraper {
     throw new Error("Max retries exceeded");
   }
 
-  // Save scraped data to SQLite
-  async saveToDatabase(data: ScrapedData) {
+  // Save scraped data to SQLite; false if it was skipped
+  saveToDatabase(data: ScrapedData): boolean {
     if (!this.db) {
       console.log("❌ Database not initialized. Call initDatabase() first.");
-      return;
+      return false;
+    }
+
+    // Answers, comments and embeddings hang off the bare question ID, so a
+    // question another site already stored under this ID can't be saved too
+    const taken = this.query(
+      "SELECT site FROM questions WHERE id = ? AND site != ?",
+      [data.questionId, data.site],
+    );
+    if (taken.length > 0) {
+      console.log(
+        `⚠️  ${data.site} question ${data.questionId} collides with ${
+          taken[0].site
+        } question ${data.questionId}, skipping`,
+      );
+      return false;
     }
 
     const db = this.db;
@@ -439,8 +761,10 @@ class InteractiveStackOverflowScraper {
         `INSERT OR REPLACE INTO questions (
           id, title, body, score, view_count, answer_count,
           creation_date, last_activity_date, tags, is_answered,
-          accepted_answer_id, author_name, author_reputation, author_user_id
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)`,
+          accepted_answer_id, author_name, author_reputation, author_user_id,
+          site, answer_scores, closed_reason, duplicate_of, locked_date, bounty,
+          excerpt, word_count
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)`,
         [
           data.questionId,
           data.title,
@@ -456,9 +780,33 @@ class InteractiveStackOverflowScraper {
           data.author.name,
           data.author.reputation,
           data.author.userId,
+          data.site,
+          // Cached for the TUI's score sparkline (highest first)
+          data.answers.length > 0
+            ? data.answers.map((a) => a.score).sort((a, b) => b - a).join(" ")
+            : null,
+          data.closeReason ?? null,
+          data.duplicateOf ?? null,
+          data.lockedDate ?? null,
+          // Total ever offered: the open bounty plus those awarded to answers
+          (data.bountyAmount +
+            data.answers.reduce((sum, a) => sum + a.awardedBounty, 0)) || null,
+          excerptFromHtml(data.questionBody),
+          // The whole thread: question and every answer
+          wordCount(data.questionBody) +
+          data.answers.reduce((sum, a) => sum + wordCount(a.answerText), 0),
         ],
       );
 
+      // Author reputation from the first scrape survives re-scrapes so the TUI
+      // can show how it changed since
+      const ingestReputation = new Map<number, number>(
+        db.query(
+          "SELECT answer_id, COALESCE(ingest_reputation, author_reputation) FROM answers WHERE question_id = ?",
+          [data.questionId],
+        ) as [number, number][],
+      );
+
       // Clear existing comments and answers
       db.query("DELETE FROM question_comments WHERE question_id = ?", [
         data.questionId,
@@ -495,8 +843,9 @@ class InteractiveStackOverflowScraper {
         const result = db.query(
           `INSERT INTO answers (
             question_id, answer_id, answer_text, answer_order, score, is_accepted,
-            creation_date, last_activity_date, author_name, author_reputation, author_user_id
-          ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id`,
+            creation_date, last_activity_date, author_name, author_reputation, author_user_id,
+            ingest_reputation, awarded_bounty
+          ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id`,
           [
             data.questionId,
             answer.answerId,
@@ -509,6 +858,8 @@ class InteractiveStackOverflowScraper {
             answer.author.name,
             answer.author.reputation,
             answer.author.userId,
+            ingestReputation.get(answer.answerId) ?? answer.author.reputation,
+            answer.awardedBounty || null,
           ],
         );
 
@@ -538,6 +889,70 @@ class InteractiveStackOverflowScraper {
       db.execute("ROLLBACK");
       throw err;
     }
+
+    this.linkCrossSiteCopies(data);
+    return true;
+  }
+
+  // Link a question to its copies on other sites: first via the API's
+  // migration metadata, then by matching title and author
+  linkCrossSiteCopies(
+    data: Pick<ScrapedData, "questionId" | "site" | "title" | "author"> &
+      Partial<Pick<ScrapedData, "migratedFrom" | "migratedTo">>,
+  ): number {
+    if (!this.db) return 0;
+
+    const links: { copyId: number; reason: string }[] = [];
+    for (const migration of [data.migratedFrom, data.migratedTo]) {
+      if (!migration) continue;
+      const local = this.query(
+        "SELECT id FROM questions WHERE id = ? AND site = ?",
+        [migration.questionId, migration.site],
+      );
+      if (local.length > 0) {
+        links.push({ copyId: migration.questionId, reason: "migrated" });
+      }
+    }
+
+    if (links.length === 0) {
+      const matches = this.query(
+        `SELECT id FROM questions
+         WHERE site != ? AND lower(trim(title)) = lower(trim(?)) AND author_name = ?`,
+        [data.site, data.title, data.author.name],
+      );
+      for (const row of matches) {
+        links.push({ copyId: row.id as number, reason: "cross-posted" });
+      }
+    }
+
+    for (const { copyId, reason } of links) {
+      this.db.query(
+        "INSERT OR IGNORE INTO question_copies (question_id, copy_id, reason) VALUES (?, ?, ?), (?, ?, ?)",
+        [data.questionId, copyId, reason, copyId, data.questionId, reason],
+      );
+      console.log(
+        `🔗 Linked question ${data.questionId} to ${reason} copy ${copyId}`,
+      );
+    }
+    return links.length;
+  }
+
+  // Backfill cross-site copy links for every stored question (title + author only,
+  // since migration metadata isn't persisted)
+  linkAllCrossSiteCopies() {
+    const questions = this.query(
+      "SELECT id, site, title, author_name FROM questions",
+    );
+    let linked = 0;
+    for (const q of questions) {
+      linked += this.linkCrossSiteCopies({
+        questionId: q.id as number,
+        site: q.site as Site,
+        title: q.title as string,
+        author: { name: q.author_name as string, reputation: 0, userId: 0 },
+      });
+    }
+    console.log(`✅ Linked ${linked} cross-site copies`);
   }
 
   // Query database methods
@@ -617,12 +1032,16 @@ class InteractiveStackOverflowScraper {
   }
 
   // Get a specific question from database
-  getQuestion(questionId: number): StoredQuestion | null {
-    const questions = this.query("SELECT * FROM questions WHERE id = ?", [
-      questionId,
-    ]);
+  getQuestion(
+    questionId: number,
+    site: Site = "stackoverflow",
+  ): StoredQuestion | null {
+    const questions = this.query(
+      "SELECT * FROM questions WHERE id = ? AND site = ?",
+      [questionId, site],
+    );
     if (questions.length === 0) {
-      console.log(`❌ Question ${questionId} not found in database.`);
+      console.log(`❌ ${site} question ${questionId} not found in database.`);
       return null;
     }
 
@@ -754,18 +1173,18 @@ class InteractiveStackOverflowScraper {
   }
 
   // Reset scraped status for a question (allows re-scraping)
-  resetScrapedStatus(questionId: number, silent = false) {
+  resetScrapedStatus(questionId: number, site: Site, silent = false) {
     if (!this.db) {
       console.log("❌ Database not initialized.");
       return;
     }
 
     this.db.query(
-      "UPDATE question_ids SET scraped = FALSE, scraped_at = NULL WHERE id = ?",
-      [questionId],
+      "UPDATE question_ids SET scraped = FALSE, scraped_at = NULL WHERE id = ? AND site = ?",
+      [questionId, site],
     );
     if (!silent) {
-      console.log(`🔄 Reset scraped status for question ${questionId}`);
+      console.log(`🔄 Reset scraped status for ${site} question ${questionId}`);
     }
   }
 
@@ -773,13 +1192,14 @@ class InteractiveStackOverflowScraper {
   async scrapeQuestion(
     questionId: number,
     skipCache = false,
+    site: Site = "stackoverflow",
   ): Promise<ScrapedData | null> {
     // Check if already scraped (unless skipCache is true)
-    if (!skipCache && this.isQuestionScraped(questionId)) {
+    if (!skipCache && this.isQuestionScraped(questionId, site)) {
       console.log(
         `✅ Question ${questionId} already scraped, loading from database...`,
       );
-      const stored = this.getQuestion(questionId);
+      const stored = this.getQuestion(questionId, site);
       // Convert StoredQuestion to ScrapedData-like structure for comparison
       // Note: This won't have full metadata, but that's expected for cached results
       return stored ? null : null; // Return null to indicate we should use the cached version
@@ -798,7 +1218,7 @@ class InteractiveStackOverflowScraper {
 
       // Single API call to get question + answers + all comments
       const questionData = await this.fetchWithRetry(
-        `https://api.stackexchange.com/2.3/questions/${questionId}?site=stackoverflow&filter=${COMPREHENSIVE_FILTER}${API_KEY_PARAM}`,
+        `https://api.stackexchange.com/2.3/questions/${questionId}?site=${site}&filter=${COMPREHENSIVE_FILTER}${API_KEY_PARAM}`,
       );
 
       if (!questionData.items || questionData.items.length === 0) {
@@ -820,6 +1240,7 @@ class InteractiveStackOverflowScraper {
       const data = this.mapApiResponseToScrapedData(
         question,
         answerCommentsMap,
+        site,
       );
 
       console.log(
@@ -843,6 +1264,7 @@ class InteractiveStackOverflowScraper {
   private mapApiResponseToScrapedData(
     question: any,
     answerCommentsMap: Map<number, any[]>,
+    site: Site,
   ): ScrapedData {
     // Map question comments
     const questionComments = (question.comments || [])
@@ -882,6 +1304,7 @@ class InteractiveStackOverflowScraper {
         answerText: answer.body || "",
         score: answer.score || 0,
         isAccepted: answer.is_accepted || false,
+        awardedBounty: answer.awarded_bounty_amount || 0,
         creationDate: answer.creation_date || 0,
         lastActivityDate: answer.last_activity_date || 0,
         author: {
@@ -895,6 +1318,7 @@ class InteractiveStackOverflowScraper {
 
     return {
       questionId: question.question_id,
+      site,
       title: question.title || "",
       questionBody: question.body || "",
       questionScore: question.score || 0,
@@ -906,6 +1330,22 @@ class InteractiveStackOverflowScraper {
       isAnswered: question.is_answered || false,
       acceptedAnswerId: question.accepted_answer_id,
       closeReason: question.closed_reason,
+      // Only present when the filter includes question.closed_details
+      duplicateOf: question.closed_details?.original_questions?.[0]?.question_id,
+      lockedDate: question.locked_date,
+      bountyAmount: question.bounty_amount || 0,
+      migratedFrom: question.migrated_from
+        ? {
+          site: question.migrated_from.other_site?.api_site_parameter,
+          questionId: question.migrated_from.question_id,
+        }
+        : undefined,
+      migratedTo: question.migrated_to
+        ? {
+          site: question.migrated_to.other_site?.api_site_parameter,
+          questionId: question.migrated_to.question_id,
+        }
+        : undefined,
       questionComments,
       author: {
         name: question.owner?.display_name || "Unknown",
@@ -918,42 +1358,51 @@ class InteractiveStackOverflowScraper {
 
   // Scrape next batch of unscraped questions
   async scrapeNextBatch(batchSize = 5): Promise<ScrapedData[]> {
-    const unscrapedIds = this.getUnscrapedQuestionIds(batchSize);
+    const unscraped = this.getUnscrapedQuestions(batchSize);
 
-    if (unscrapedIds.length === 0) {
+    if (unscraped.length === 0) {
       console.log(
         "🎉 No unscraped questions found! All questions have been scraped.",
       );
       return [];
     }
 
-    console.log(`🚀 Scraping ${unscrapedIds.length} questions...`);
+    console.log(`🚀 Scraping ${unscraped.length} questions...`);
 
     const results: ScrapedData[] = [];
+    // Questions left out because another site's question has their ID
+    const collided: string[] = [];
 
-    for (let i = 0; i < unscrapedIds.length; i++) {
-      const id = unscrapedIds[i];
+    for (let i = 0; i < unscraped.length; i++) {
+      const { id, site } = unscraped[i];
       console.log(
-        `\n📊 Progress: ${i + 1}/${unscrapedIds.length} (${
-          Math.round(((i + 1) / unscrapedIds.length) * 100)
+        `\n📊 Progress: ${i + 1}/${unscraped.length} (${
+          Math.round(((i + 1) / unscraped.length) * 100)
         }%)`,
       );
 
-      const data = await this.scrapeQuestion(id, true);
+      const data = await this.scrapeQuestion(id, true, site);
       if (data) {
-        results.push(data);
         // Save to database
         if (this.db) {
-          this.saveToDatabase(data);
-          this.markQuestionAsScraped(id);
+          if (this.saveToDatabase(data)) {
+            results.push(data);
+          } else {
+            collided.push(`${site} ${id}`);
+          }
+          // A question skipped for colliding is marked too, or every batch
+          // would fetch it again
+          this.markQuestionAsScraped(id, site);
+        } else {
+          results.push(data);
         }
       } else {
         // Mark as scraped anyway to avoid retrying deleted questions
-        this.markQuestionAsScraped(id);
+        this.markQuestionAsScraped(id, site);
       }
 
       // Rate limit: wait 1 second between questions
-      if (i < unscrapedIds.length - 1) {
+      if (i < unscraped.length - 1) {
         await sleep(1000);
       }
     }
@@ -961,11 +1410,18 @@ class InteractiveStackOverflowScraper {
     console.log(
       `\n🎉 Batch scrape complete! Scraped ${results.length} new questions successfully.`,
     );
+    if (collided.length > 0) {
+      console.log(
+        `⚠️  Not stored, since another site's question has the ID: ${
+          collided.join(", ")
+        }`,
+      );
+    }
     return results;
   }
 
   // Delete a question and all its related data
-  deleteQuestion(questionId: number, silent = false) {
+  deleteQuestion(questionId: number, site: Site, silent = false) {
     if (!this.db) {
       console.log("❌ Database not initialized.");
       return;
@@ -991,16 +1447,25 @@ class InteractiveStackOverflowScraper {
       // Delete answers
       db.query("DELETE FROM answers WHERE question_id = ?", [questionId]);
       // Delete question
-      db.query("DELETE FROM questions WHERE id = ?", [questionId]);
+      db.query(
+        "DELETE FROM question_copies WHERE question_id = ? OR copy_id = ?",
+        [questionId, questionId],
+      );
+      db.query("DELETE FROM questions WHERE id = ? AND site = ?", [
+        questionId,
+        site,
+      ]);
       // Reset scraped status
       db.query(
-        "UPDATE question_ids SET scraped = FALSE, scraped_at = NULL WHERE id = ?",
-        [questionId],
+        "UPDATE question_ids SET scraped = FALSE, scraped_at = NULL WHERE id = ? AND site = ?",
+        [questionId, site],
       );
 
       db.execute("COMMIT");
       if (!silent) {
-        console.log(`🗑️  Deleted question ${questionId} and all related data`);
+        console.log(
+          `🗑️  Deleted ${site} question ${questionId} and all related data`,
+        );
       }
     } catch (err) {
       db.execute("ROLLBACK");
@@ -1195,6 +1660,145 @@ class InteractiveStackOverflowScraper {
     );
   }
 
+  // Precompute the normalized mean embedding of every tag's questions
+  async embedTagCentroids() {
+    if (!this.db) {
+      console.log("❌ Database not initialized.");
+      return;
+    }
+
+    const { deserializeEmbedding, serializeEmbedding } = await loadEmbeddings();
+    const rows = this.query(
+      `SELECT q.tags, qe.embedding
+       FROM questions q JOIN question_embeddings qe ON qe.question_id = q.id`,
+    );
+
+    const sums = new Map<string, { sum: Float32Array; count: number }>();
+    for (const row of rows) {
+      const embedding = deserializeEmbedding(row.embedding as Uint8Array);
+      for (const tag of JSON.parse(row.tags as string) as string[]) {
+        let entry = sums.get(tag);
+        if (!entry) {
+          entry = { sum: new Float32Array(embedding.length), count: 0 };
+          sums.set(tag, entry);
+        }
+        for (let i = 0; i < embedding.length; i++) {
+          entry.sum[i] += embedding[i];
+        }
+        entry.count++;
+      }
+    }
+
+    this.db.execute("BEGIN TRANSACTION");
+    try {
+      this.db.execute("DELETE FROM tag_centroids");
+      for (const [tag, { sum, count }] of sums) {
+        const norm = Math.hypot(...sum) || 1;
+        const centroid = sum.map((v) => v / norm);
+        this.db.query(
+          "INSERT INTO tag_centroids (tag, embedding, question_count) VALUES (?, ?, ?)",
+          [tag, serializeEmbedding(centroid), count],
+        );
+      }
+      this.db.execute("COMMIT");
+    } catch (err) {
+      this.db.execute("ROLLBACK");
+      throw err;
+    }
+
+    console.log(`✅ Computed centroids for ${sums.size} tags`);
+  }
+
+  // Count the functions and keywords used in the code of Erwin's answers
+  countTerms(minAnswers = 3) {
+    if (!this.db) {
+      console.log("❌ Database not initialized.");
+      return;
+    }
+
+    const rows = this.query(
+      "SELECT answer_text FROM answers WHERE lower(author_name) LIKE '%erwin%'",
+    );
+    const counts = new Map<string, number>();
+    for (const row of rows) {
+      for (const key of termsInCode(row.answer_text as string)) {
+        counts.set(key, (counts.get(key) ?? 0) + 1);
+      }
+    }
+
+    this.db.execute("BEGIN TRANSACTION");
+    let stored = 0;
+    try {
+      this.db.execute("DELETE FROM term_frequencies");
+      for (const [key, count] of counts) {
+        if (count < minAnswers) continue;
+        const [kind, term] = key.split(":");
+        this.db.query(
+          "INSERT INTO term_frequencies (term, kind, answer_count) VALUES (?, ?, ?)",
+          [term, kind, count],
+        );
+        stored++;
+      }
+      this.db.execute("COMMIT");
+    } catch (err) {
+      this.db.execute("ROLLBACK");
+      throw err;
+    }
+
+    console.log(`✅ Counted ${stored} terms in ${rows.length} answers`);
+  }
+
+  // Find Erwin's answers that a comment of his or a paragraph of the answer
+  // calls superseded, linking a newer post (mirrors `superseded::detect`)
+  findSuperseded() {
+    if (!this.db) {
+      console.log("❌ Database not initialized.");
+      return;
+    }
+
+    const answers = this.query(
+      `SELECT a.id, a.answer_id, a.answer_text, q.site
+       FROM answers a JOIN questions q ON q.id = a.question_id
+       WHERE lower(a.author_name) LIKE '%erwin%'`,
+    );
+    const found: [number, string][] = [];
+    for (const answer of answers) {
+      const answerId = answer.answer_id as number;
+      const comments = this.query(
+        `SELECT comment_text FROM answer_comments
+         WHERE answer_id = ? AND lower(author_name) LIKE '%erwin%'`,
+        [answer.id],
+      ).map((row) => row.comment_text as string);
+      const blocks = (answer.answer_text as string).split(BLOCK_END_RE);
+      for (const text of [...comments, ...blocks]) {
+        const url = newerLink(answer.site as Site, answerId, text);
+        if (url) {
+          found.push([answerId, url]);
+          break;
+        }
+      }
+    }
+
+    this.db.execute("BEGIN TRANSACTION");
+    try {
+      this.db.execute("DELETE FROM superseded_answers");
+      for (const [answerId, url] of found) {
+        this.db.query(
+          "INSERT INTO superseded_answers (answer_id, newer_url) VALUES (?, ?)",
+          [answerId, url],
+        );
+      }
+      this.db.execute("COMMIT");
+    } catch (err) {
+      this.db.execute("ROLLBACK");
+      throw err;
+    }
+
+    console.log(
+      `✅ Found ${found.length} superseded answers in ${answers.length}`,
+    );
+  }
+
   // Truncate all scraped data (keeps question IDs)
   truncateScrapedData() {
     if (!this.db) {
@@ -1210,6 +1814,7 @@ class InteractiveStackOverflowScraper {
       db.execute("DELETE FROM answer_comments");
       db.execute("DELETE FROM question_comments");
       db.execute("DELETE FROM answers");
+      db.execute("DELETE FROM question_copies");
       db.execute("DELETE FROM questions");
       db.execute("UPDATE question_ids SET scraped = FALSE, scraped_at = NULL");
 
@@ -1300,8 +1905,12 @@ export async function init(dbPath = "sqlite.db") {
   await scraper.initDatabase(dbPath);
 }
 
-export async function fetchIds(pages = 1) {
-  return await scraper.fetchQuestionIds(pages);
+export async function fetchIds(pages = 1, site?: Site) {
+  // Without an explicit site, pull Erwin's answers from every site we ingest
+  for (const s of site ? [site] : SITES) {
+    await scraper.fetchQuestionIds(pages, s);
+  }
+  return scraper.allQuestionIds();
 }
 
 export function showIds(limit = 20) {
@@ -1312,11 +1921,14 @@ export function getIds(start = 0, count = 10) {
   return scraper.getQuestionIds(start, count);
 }
 
-export async function scrapeOne(questionId: number) {
-  const data = await scraper.scrapeQuestion(questionId, true);
+export async function scrapeOne(
+  questionId: number,
+  site: Site = "stackoverflow",
+) {
+  const data = await scraper.scrapeQuestion(questionId, true, site);
   if (data && scraper["db"]) {
     scraper.saveToDatabase(data);
-    scraper.markQuestionAsScraped(questionId);
+    scraper.markQuestionAsScraped(questionId, site);
   }
   return data;
 }
@@ -1337,12 +1949,12 @@ export function list(limit = 10) {
   scraper.listQuestions(limit);
 }
 
-export function get(questionId: number) {
-  return scraper.getQuestion(questionId);
+export function get(questionId: number, site: Site = "stackoverflow") {
+  return scraper.getQuestion(questionId, site);
 }
 
-export function print(questionId: number) {
-  const data = scraper.getQuestion(questionId);
+export function print(questionId: number, site: Site = "stackoverflow") {
+  const data = scraper.getQuestion(questionId, site);
   scraper.printQuestion(data);
 }
 
@@ -1350,13 +1962,32 @@ export function search(term: string, limit = 5) {
   return scraper.searchQuestions(term, limit);
 }
 
-export async function rescrape(questionId: number) {
-  scraper.resetScrapedStatus(questionId);
-  return await scrapeOne(questionId);
+export async function rescrape(questionId: number, site?: Site) {
+  // Without an explicit site, use the one site that knows this ID
+  const sites = site ? [site] : scraper.query(
+    "SELECT site FROM question_ids WHERE id = ?",
+    [questionId],
+  ).map((row: any) => row.site as Site);
+  if (sites.length > 1) {
+    console.error(
+      `❌ Question ${questionId} exists on ${sites.join(" and ")}; pass the site`,
+    );
+    return null;
+  }
+  const target = sites[0] ?? "stackoverflow";
+  scraper.resetScrapedStatus(questionId, target);
+  return await scrapeOne(questionId, target);
+}
+
+export function deleteQuestion(
+  questionId: number,
+  site: Site = "stackoverflow",
+) {
+  scraper.deleteQuestion(questionId, site);
 }
 
-export function deleteQuestion(questionId: number) {
-  scraper.deleteQuestion(questionId);
+export function linkCopies() {
+  scraper.linkAllCrossSiteCopies();
 }
 
 export function truncate() {
@@ -1383,6 +2014,18 @@ export function embedStats() {
   scraper.getEmbeddingStats();
 }
 
+export async function embedTags() {
+  await scraper.embedTagCentroids();
+}
+
+export function countTerms() {
+  scraper.countTerms();
+}
+
+export function findSuperseded() {
+  scraper.findSuperseded();
+}
+
 export async function fetchMd(questionId: number) {
   await scraper.fetchQuestionMarkdown(questionId);
 }
@@ -1491,16 +2134,20 @@ Commands:
   stats                   Show database statistics
   list [limit]            List scraped questions (default: 10)
   scrapeNext [count]      Scrape next N unscraped questions (default: 5)
-  scrapeOne <id>          Scrape a specific question by ID
-  print <id>              Print a specific question
+  scrapeOne <id> [site]   Scrape a specific question by ID (site: stackoverflow|dba)
+  print <id> [site]       Print a specific question
   search <term> [limit]   Search questions (default limit: 5)
-  fetchIds [pages]        Fetch question IDs from API (default: 1 page)
-  rescrape <id>           Re-scrape a specific question (fetch fresh data)
-  delete <id>             Delete a question and all its data
+  fetchIds [pages] [site] Fetch question IDs from API (default: 1 page, all sites)
+  rescrape <id> [site]    Re-scrape a specific question (fetch fresh data)
+  delete <id> [site]      Delete a question and all its data
   truncate                Delete ALL scraped data (keeps question IDs)
+  linkCopies              Link cross-site copies of questions (matching title + author)
   embedNext [count|all]   Embed next N unembedded question titles (default: 50) or 'all' remaining
   reembed [limit|all]     Regenerate embeddings for N or all question titles
   embedStats              Show embedding coverage statistics
+  embedTags               Precompute per-tag centroid embeddings for topic exploration
+  countTerms              Count functions and keywords in the code of Erwin's answers
+  findSuperseded          Flag Erwin's answers he marked superseded by a newer post
   semanticSearch <query>  Search questions using semantic similarity (default: 10 results)
   fetchMd <id>            Fetch a question with markdown body and output to stdout
 
@@ -1547,7 +2194,7 @@ Examples:
         console.error("❌ Please provide a question ID");
         Deno.exit(1);
       }
-      await scrapeOne(id);
+      await scrapeOne(id, parseSite(args[2]));
       break;
     }
 
@@ -1557,7 +2204,7 @@ Examples:
         console.error("❌ Please provide a question ID");
         Deno.exit(1);
       }
-      print(id);
+      print(id, args[2] ? parseSite(args[2]) : undefined);
       break;
     }
 
@@ -1574,7 +2221,7 @@ Examples:
 
     case "fetchIds": {
       const pages = parseInt(args[1]) || 1;
-      await fetchIds(pages);
+      await fetchIds(pages, args[2] ? parseSite(args[2]) : undefined);
       break;
     }
 
@@ -1584,7 +2231,7 @@ Examples:
         console.error("❌ Please provide a question ID");
         Deno.exit(1);
       }
-      await rescrape(id);
+      await rescrape(id, args[2] ? parseSite(args[2]) : undefined);
       break;
     }
 
@@ -1594,7 +2241,12 @@ Examples:
         console.error("❌ Please provide a question ID");
         Deno.exit(1);
       }
-      deleteQuestion(id);
+      deleteQuestion(id, args[2] ? parseSite(args[2]) : undefined);
+      break;
+    }
+
+    case "linkCopies": {
+      linkCopies();
       break;
     }
 
@@ -1619,6 +2271,21 @@ Examples:
       break;
     }
 
+    case "embedTags": {
+      await embedTags();
+      break;
+    }
+
+    case "countTerms": {
+      countTerms();
+      break;
+    }
+
+    case "findSuperseded": {
+      findSuperseded();
+      break;
+    }
+
     case "embedStats": {
       embedStats();
       break;
@@ -1,11 +1,15 @@
 // Shared type definitions for Stack Overflow scraper
 
+// Stack Exchange sites we ingest (values match the API `site=` parameter)
+export type Site = "stackoverflow" | "dba";
+
 export interface StackOverflowAnswer {
   question_id: number;
 }
 
 export interface ScrapedData {
   questionId: number;
+  site: Site;
   title: string;
   questionBody: string;
   questionScore: number;
@@ -17,6 +21,13 @@ export interface ScrapedData {
   isAnswered: boolean;
   acceptedAnswerId?: number;
   closeReason?: string;
+  // Original question of a thread closed as a duplicate
+  duplicateOf?: number;
+  lockedDate?: number;
+  // Bounty currently open on the question (awarded ones are on the answers)
+  bountyAmount: number;
+  migratedFrom?: { site: Site; questionId: number };
+  migratedTo?: { site: Site; questionId: number };
   questionComments: {
     text: string;
     score: number;
@@ -37,6 +48,7 @@ export interface ScrapedData {
     answerText: string;
     score: number;
     isAccepted: boolean;
+    awardedBounty: number;
     creationDate: number;
     lastActivityDate: number;
     author: {
@@ -0,0 +1,59 @@
+use anyhow::Result;
+
+use crate::config::AlertsConfig;
+use crate::db::{Database, PostKey, PostText};
+use crate::html::html_to_plain_text;
+use crate::userdb::UserDb;
+
+/// Diff the corpus against the posts seen on earlier runs and record alert hits
+/// for the new ones. The first run only records what exists, so the initial
+/// corpus never floods the Alerts page. Returns the number of new hits.
+pub fn check_new_content(db: &Database, user_db: &UserDb, config: &AlertsConfig) -> Result<usize> {
+    let known = user_db.get_known_posts()?;
+    let first_run = known.is_empty();
+    let new_posts: Vec<PostKey> = db
+        .get_post_keys()?
+        .into_iter()
+        .filter(|key| !known.contains(key))
+        .collect();
+    if new_posts.is_empty() {
+        return Ok(0);
+    }
+
+    let mut hits = Vec::new();
+    if !first_run {
+        for &post in &new_posts {
+            if let Some(text) = db.get_post_text(post)? {
+                hits.extend(
+                    matching_rules(config, &text).map(|rule| (rule, post, text.title.clone())),
+                );
+            }
+        }
+    }
+
+    user_db.record_new_posts(&new_posts, &hits)?;
+    Ok(hits.len())
+}
+
+/// Labels of the alerts a post matches: the keyword itself, or `[tag]`.
+/// Keywords are looked for in the body's text, not its markup, so tag names
+/// and attributes like `class="lang-sql"` don't count.
+fn matching_rules<'a>(
+    config: &'a AlertsConfig,
+    text: &'a PostText,
+) -> impl Iterator<Item = String> + 'a {
+    let title = text.title.to_lowercase();
+    let body = html_to_plain_text(&text.body).to_lowercase();
+
+    let keywords = config.keywords.iter().filter(move |keyword| {
+        let keyword = keyword.to_lowercase();
+        title.contains(&keyword) || body.contains(&keyword)
+    });
+    let tags = config
+        .tags
+        .iter()
+        .filter(|tag| text.tags.contains(tag))
+        .map(|tag| format!("[{tag}]"));
+
+    keywords.cloned().chain(tags)
+}
@@ -0,0 +1,90 @@
+//! Rendered lines as ANSI-styled text, for `erwindb cat`: the spans' ratatui
+//! styles become SGR sequences, so the thread looks as it does in the TUI
+//! without taking over the terminal. Piped, it's printed as plain text.
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+
+/// SGR codes of each modifier ratatui can set
+const MODIFIER_CODES: [(Modifier, u8); 8] = [
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9),
+];
+
+/// `lines` as text, one per output line, each ending with styles reset
+pub fn lines_to_ansi(lines: &[Line]) -> String {
+    let mut out = String::new();
+    for line in lines {
+        for span in &line.spans {
+            let style = line.style.patch(span.style);
+            let sgr = sgr(style);
+            if sgr.is_empty() {
+                out.push_str(&span.content);
+            } else {
+                out.push_str(&format!("\x1b[{sgr}m{}\x1b[0m", span.content));
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// `lines` as plain text, for output that isn't a terminal
+pub fn lines_to_text(lines: &[Line]) -> String {
+    let mut out = String::new();
+    for line in lines {
+        for span in &line.spans {
+            out.push_str(&span.content);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Parameters of the SGR sequence for `style`, empty for the default style
+fn sgr(style: Style) -> String {
+    let mut codes: Vec<String> = MODIFIER_CODES
+        .iter()
+        .filter(|(modifier, _)| style.add_modifier.contains(*modifier))
+        .map(|(_, code)| code.to_string())
+        .collect();
+    if let Some(fg) = style.fg.and_then(|color| color_code(color, false)) {
+        codes.push(fg);
+    }
+    if let Some(bg) = style.bg.and_then(|color| color_code(color, true)) {
+        codes.push(bg);
+    }
+    codes.join(";")
+}
+
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let (base, bright) = if background { (40, 100) } else { (30, 90) };
+    let extended = if background { 48 } else { 38 };
+    Some(match color {
+        Color::Reset => return None,
+        Color::Black => format!("{base}"),
+        Color::Red => format!("{}", base + 1),
+        Color::Green => format!("{}", base + 2),
+        Color::Yellow => format!("{}", base + 3),
+        Color::Blue => format!("{}", base + 4),
+        Color::Magenta => format!("{}", base + 5),
+        Color::Cyan => format!("{}", base + 6),
+        Color::Gray => format!("{}", base + 7),
+        Color::DarkGray => format!("{bright}"),
+        Color::LightRed => format!("{}", bright + 1),
+        Color::LightGreen => format!("{}", bright + 2),
+        Color::LightYellow => format!("{}", bright + 3),
+        Color::LightBlue => format!("{}", bright + 4),
+        Color::LightMagenta => format!("{}", bright + 5),
+        Color::LightCyan => format!("{}", bright + 6),
+        Color::White => format!("{}", bright + 7),
+        Color::Rgb(r, g, b) => format!("{extended};2;{r};{g};{b}"),
+        Color::Indexed(i) => format!("{extended};5;{i}"),
+    })
+}
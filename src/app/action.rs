@@ -0,0 +1,59 @@
+use super::Page;
+use crate::external::ExternalCommand;
+use crate::fetch::FetchTarget;
+use crate::html::Link;
+use crate::site::Site;
+
+/// Effects that cross page boundaries. Page key handlers update their own
+/// state directly and return an action for anything that touches other pages.
+#[derive(Debug, Clone)]
+pub enum Action {
+    Quit,
+    /// Open a question on the show page (pushes history when already showing one)
+    OpenQuestion(i64),
+    /// Open a question and scroll to one of its answers
+    OpenAnswer {
+        question_id: i64,
+        answer_id: i64,
+    },
+    /// Open a thread fetched from the API, at one of its answers if given.
+    /// Its ID may be another site's corpus question's too, so it's never
+    /// looked up in the corpus.
+    OpenFetched {
+        site: Site,
+        question_id: i64,
+        answer_id: Option<i64>,
+    },
+    /// Pop the question history, or return to the index
+    GoBack,
+    /// Reopen the question last gone back from
+    GoForward,
+    /// Navigate locally if the link targets our corpus, otherwise open the browser
+    FollowLink(Link),
+    OpenUrl(String),
+    /// Fetch a thread missing from the corpus from the API, then open it
+    FetchQuestion(FetchTarget),
+    SwitchPage(Page),
+    /// Suspend the TUI and run a command (previewers, hooks)
+    RunExternal(ExternalCommand),
+    /// Leave a question out of the lists (asked for confirmation first)
+    HideQuestion {
+        site: Site,
+        question_id: i64,
+    },
+    /// Finish `--pick` mode with this question
+    Pick(i64),
+}
+
+impl Action {
+    /// Whether the action moves away from the current page (and would lose unsaved edits)
+    pub fn leaves_page(&self) -> bool {
+        !matches!(
+            self,
+            Action::OpenUrl(_)
+                | Action::RunExternal(_)
+                | Action::FetchQuestion(_)
+                | Action::HideQuestion { .. }
+        )
+    }
+}
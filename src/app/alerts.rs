@@ -0,0 +1,82 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{Action, App, Page};
+
+impl App {
+    pub(super) fn handle_alerts_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let visible_rows = self.height.saturating_sub(2) as usize;
+        let max = self.alerts.hits.len().saturating_sub(1);
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => action = Some(Action::SwitchPage(Page::Index)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.alerts.selected = (self.alerts.selected + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.alerts.selected = self.alerts.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.alerts.selected = 0,
+            KeyCode::Char('G') => self.alerts.selected = max,
+            KeyCode::Enter => {
+                if let Some(hit) = self.alerts.hits.get(self.alerts.selected) {
+                    let post = hit.post;
+                    if !hit.read {
+                        self.set_alert_read(self.alerts.selected, true);
+                    }
+                    action = Some(match post.answer_id {
+                        Some(answer_id) => Action::OpenAnswer {
+                            question_id: post.question_id,
+                            answer_id,
+                        },
+                        None => Action::OpenQuestion(post.question_id),
+                    });
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(hit) = self.alerts.hits.get(self.alerts.selected) {
+                    let read = !hit.read;
+                    self.set_alert_read(self.alerts.selected, read);
+                }
+            }
+            KeyCode::Char('R') => {
+                if let Some(ref user_db) = self.user_db {
+                    if user_db.mark_all_alerts_read().is_ok() {
+                        self.alerts.hits.iter_mut().for_each(|hit| hit.read = true);
+                        self.alerts.unread = 0;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if self.alerts.selected < self.alerts.scroll {
+            self.alerts.scroll = self.alerts.selected;
+        } else if visible_rows > 0 && self.alerts.selected >= self.alerts.scroll + visible_rows {
+            self.alerts.scroll = self.alerts.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    /// Load the alert hits and switch to the Alerts page
+    pub(super) fn open_alerts(&mut self) -> Option<Action> {
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("User database is unavailable".to_string());
+            return None;
+        };
+        self.alerts.hits = user_db.get_alert_hits().unwrap_or_default();
+        self.alerts.selected = 0;
+        self.alerts.scroll = 0;
+        Some(Action::SwitchPage(Page::Alerts))
+    }
+
+    fn set_alert_read(&mut self, index: usize, read: bool) {
+        let (Some(user_db), Some(hit)) = (&self.user_db, self.alerts.hits.get_mut(index)) else {
+            return;
+        };
+        if user_db.set_alert_read(hit.id, read).is_ok() {
+            hit.read = read;
+            self.alerts.unread = self.alerts.hits.iter().filter(|h| !h.read).count();
+        }
+    }
+}
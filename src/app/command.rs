@@ -0,0 +1,202 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::{Action, App};
+use crate::fetch::FetchTarget;
+use crate::html::{extract_site, extract_so_answer_id, extract_so_question_id};
+use crate::memory::{resident_size, HeapSize, MemoryReport, MB};
+use crate::site::Site;
+
+/// `:` commands `run_command` handles itself, which plugins can't register
+pub(crate) const BUILTIN_COMMANDS: [&str; 7] = [
+    "memory", "mem", "errors", "plugins", "present", "wish", "layout",
+];
+
+impl App {
+    pub(super) fn handle_command_key(&mut self, key: KeyEvent) {
+        let Some(ref mut command) = self.command else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.command = None,
+            KeyCode::Enter => {
+                let command = self.command.take().unwrap_or_default();
+                self.run_command(command.trim());
+            }
+            // Backspace on an empty line closes it, as in vim
+            KeyCode::Backspace if command.is_empty() => self.command = None,
+            KeyCode::Backspace => {
+                command.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => command.push(c),
+            _ => {}
+        }
+    }
+
+    fn run_command(&mut self, command: &str) {
+        let (name, args) = command
+            .split_once(char::is_whitespace)
+            .map_or((command, ""), |(name, args)| (name, args.trim()));
+        match name {
+            "" => {}
+            "memory" | "mem" => self.memory_report = Some(self.memory_report()),
+            "errors" => self.open_error_details(),
+            "plugins" => self.open_plugin_list(),
+            "present" if args.is_empty() => {
+                self.flash = Some("Usage: :present <collection>".to_string());
+            }
+            "present" => self.start_presentation(args),
+            "wish" => self.add_to_wishlist(args),
+            "layout" if args.is_empty() => self.set_layout(None),
+            "layout" => self.set_layout(Some(args.to_string())),
+            _ if self.run_plugin_command(command) => {}
+            _ => match self.jump_target(command) {
+                Ok(Action::FetchQuestion(target)) => self.offer_fetch(target),
+                Ok(action) => self.dispatch(action),
+                Err(message) => self.flash = Some(message),
+            },
+        }
+    }
+
+    /// Where a question ID (`12316953`, `#12316953`) or a question or answer
+    /// URL leads: the database's copy, a fetched one, or a fetch
+    fn jump_target(&self, input: &str) -> Result<Action, String> {
+        let Some((site, question_id, answer_id)) = parse_jump(input) else {
+            return Err(format!("Unknown command: {input}"));
+        };
+        // Short answer links (`/a/<id>`) name no question
+        let question_id = question_id.or_else(|| {
+            self.db
+                .get_question_id_for_answer(site?, answer_id?)
+                .ok()
+                .flatten()
+        });
+
+        let known = question_id.is_some_and(|question_id| {
+            self.questions
+                .iter()
+                .any(|q| q.id == question_id && site.is_none_or(|site| q.site == site))
+        });
+        if !known {
+            if question_id.is_none() && answer_id.is_none() {
+                return Err(format!("No question in {input}"));
+            }
+            // Bare IDs are Stack Overflow's
+            let target = FetchTarget {
+                site: site.unwrap_or_default(),
+                question_id,
+                answer_id,
+            };
+            return Ok(self
+                .fetched_action(target)
+                .unwrap_or(Action::FetchQuestion(target)));
+        }
+        let question_id = question_id.ok_or_else(|| format!("No question in {input}"))?;
+        Ok(match answer_id {
+            Some(answer_id) => Action::OpenAnswer {
+                question_id,
+                answer_id,
+            },
+            None => Action::OpenQuestion(question_id),
+        })
+    }
+
+    /// Keep the open thread and the cached bodies within `[memory] budget_mb`:
+    /// the thread always stays, the bodies get whatever it leaves
+    pub(super) fn fit_memory_budget(&self) {
+        let budget = self.config.memory.budget_mb * MB;
+        self.db
+            .set_body_cache_budget(budget.saturating_sub(self.show.heap_size()));
+    }
+
+    fn memory_report(&self) -> MemoryReport {
+        let thread = match self.show.question {
+            Some(_) => format!(
+                "#{}, {} answers, {} lines",
+                self.show.question_id,
+                self.show.answers.len(),
+                self.show.rendered_content.len() + self.show.rendered_erwin_content.len()
+            ),
+            None => "none open".to_string(),
+        };
+        let thread_bytes = self.show.heap_size();
+        let (bodies, body_bytes) = self.db.body_cache_usage();
+        let (centroids, centroid_bytes) = self.db.centroid_cache_usage();
+        MemoryReport {
+            parts: vec![
+                ("Open thread", thread_bytes, thread),
+                ("Question bodies", body_bytes, format!("{bodies} cached")),
+                (
+                    "Tag centroids",
+                    centroid_bytes,
+                    format!("{centroids} loaded"),
+                ),
+                (
+                    "Question list",
+                    self.questions.heap_size(),
+                    format!("{} questions", self.questions.len()),
+                ),
+            ],
+            budgeted: thread_bytes + body_bytes,
+            budget: self.config.memory.budget_mb * MB,
+            resident: resident_size(),
+        }
+    }
+}
+
+/// The site, question and answer a question ID (`12316953`, `#12316953`) or a
+/// question or answer URL names; `None` if the input is neither
+fn parse_jump(input: &str) -> Option<(Option<Site>, Option<i64>, Option<i64>)> {
+    let id = input.strip_prefix('#').unwrap_or(input);
+    if let Ok(id) = id.parse::<i64>() {
+        return Some((None, Some(id), None));
+    }
+    if !input.contains('/') {
+        return None;
+    }
+    // Pasted without the scheme, the host still names the site
+    let url = if input.contains("//") {
+        input.to_string()
+    } else {
+        format!("https://{input}")
+    };
+    Some((
+        extract_site(&url),
+        extract_so_question_id(&url),
+        extract_so_answer_id(&url),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_jump_ids() {
+        assert_eq!(parse_jump("12316953"), Some((None, Some(12316953), None)));
+        assert_eq!(parse_jump("#12316953"), Some((None, Some(12316953), None)));
+        assert_eq!(parse_jump("quit"), None);
+        assert_eq!(parse_jump("#"), None);
+    }
+
+    #[test]
+    fn parse_jump_urls() {
+        assert_eq!(
+            parse_jump("https://stackoverflow.com/questions/3800551/select-first-row"),
+            Some((Some(Site::StackOverflow), Some(3800551), None))
+        );
+        assert_eq!(
+            parse_jump("https://stackoverflow.com/questions/3800551/select-first-row/7630564"),
+            Some((Some(Site::StackOverflow), Some(3800551), Some(7630564)))
+        );
+        assert_eq!(
+            parse_jump("https://dba.stackexchange.com/a/7630564"),
+            Some((Some(Site::Dba), None, Some(7630564)))
+        );
+        // Without the scheme
+        assert_eq!(
+            parse_jump("dba.stackexchange.com/questions/12345"),
+            Some((Some(Site::Dba), Some(12345), None))
+        );
+        assert_eq!(parse_jump("example.com/foo"), Some((None, None, None)));
+    }
+}
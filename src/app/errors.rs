@@ -0,0 +1,56 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{App, ErrorDetailsState};
+use crate::report::{issue_body, ErrorReport, ISSUES_URL, MAX_ERRORS};
+
+impl App {
+    /// Keep a failed background job for the details screen and say so in
+    /// the status bar
+    pub(super) fn record_error(&mut self, report: ErrorReport) {
+        self.flash = Some(format!(
+            "{} failed: {} (! for details)",
+            report.job,
+            report.chain.join(": ")
+        ));
+        if self.errors.len() == MAX_ERRORS {
+            self.errors.remove(0);
+        }
+        self.errors.push(report);
+    }
+
+    /// Show the latest failure
+    pub(super) fn open_error_details(&mut self) {
+        if self.errors.is_empty() {
+            self.flash = Some("No errors this session".to_string());
+            return;
+        }
+        self.error_details = Some(ErrorDetailsState {
+            index: self.errors.len() - 1,
+            scroll: 0,
+        });
+    }
+
+    pub(super) fn handle_error_details_key(&mut self, key: KeyEvent) {
+        let Some(ref mut details) = self.error_details else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('!') => self.error_details = None,
+            KeyCode::Char('j') | KeyCode::Down => details.scroll += 1,
+            KeyCode::Char('k') | KeyCode::Up => details.scroll = details.scroll.saturating_sub(1),
+            KeyCode::Char('h') | KeyCode::Left if details.index > 0 => {
+                details.index -= 1;
+                details.scroll = 0;
+            }
+            KeyCode::Char('l') | KeyCode::Right if details.index + 1 < self.errors.len() => {
+                details.index += 1;
+                details.scroll = 0;
+            }
+            KeyCode::Char('y') => {
+                self.pending_clipboard = Some(issue_body(&self.errors, details.index));
+                self.flash = Some(format!("Copied an issue report; paste it at {ISSUES_URL}"));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+
+use super::{Action, App};
+use crate::fetch::{FetchTarget, Fetched, FetchedThread, QuestionFetch};
+use crate::report::ErrorReport;
+use crate::site::Site;
+
+impl App {
+    /// Where a target leads if its thread was fetched before
+    pub(super) fn fetched_action(&self, target: FetchTarget) -> Option<Action> {
+        let user_db = self.user_db.as_ref()?;
+        let question_id = match target.question_id {
+            Some(question_id) => {
+                user_db
+                    .get_fetched_question(target.site, question_id)
+                    .ok()??;
+                question_id
+            }
+            None => user_db
+                .get_fetched_question_for_answer(target.site, target.answer_id?)
+                .ok()??,
+        };
+        Some(Action::OpenFetched {
+            site: target.site,
+            question_id,
+            answer_id: target.answer_id,
+        })
+    }
+
+    /// Where a question of `site` leads: the corpus question, or else the
+    /// thread fetched before
+    pub(super) fn open_action(&self, site: Site, question_id: i64) -> Option<Action> {
+        if self
+            .questions
+            .iter()
+            .any(|q| q.id == question_id && q.site == site)
+        {
+            return Some(Action::OpenQuestion(question_id));
+        }
+        self.fetched_action(FetchTarget {
+            site,
+            question_id: Some(question_id),
+            answer_id: None,
+        })
+    }
+
+    /// A thread fetched from `site`
+    pub(super) fn fetched_thread(&self, site: Site, question_id: i64) -> Option<FetchedThread> {
+        let json = self
+            .user_db
+            .as_ref()?
+            .get_fetched_question(site, question_id)
+            .ok()??;
+        FetchedThread::from_json(site, &json).ok()
+    }
+
+    /// Ask before spending API quota on a thread outside the corpus
+    pub(super) fn offer_fetch(&mut self, target: FetchTarget) {
+        let what = match (target.question_id, target.answer_id) {
+            (Some(question_id), _) => format!("Question #{question_id}"),
+            (None, Some(answer_id)) => format!("Answer #{answer_id}"),
+            (None, None) => return,
+        };
+        let message = format!(
+            "{what} isn't in the corpus. Fetch it from {}?",
+            target.site.host()
+        );
+        self.request_confirm(message, Action::FetchQuestion(target));
+    }
+
+    pub(super) fn start_fetch(&mut self, target: FetchTarget) {
+        if self.fetch.is_some() {
+            self.flash = Some("Already fetching a question".to_string());
+            return;
+        }
+        // The thread is kept in the user database, so there's nowhere to put it
+        if self.user_db.is_none() {
+            self.flash = Some("Fetching needs the user database, which failed to open".to_string());
+            return;
+        }
+        self.fetch = Some(QuestionFetch::start(self.config.api.clone(), target));
+        self.flash = Some(format!("Fetching from {}…", target.site.host()));
+    }
+
+    /// Store the fetched thread and open it (at the linked answer)
+    pub(super) fn finish_fetch(&mut self, result: Result<Fetched>) {
+        let Some(fetch) = self.fetch.take() else {
+            return;
+        };
+        let fetched = match result {
+            Ok(fetched) => fetched,
+            Err(err) => {
+                self.record_error(ErrorReport::new("Question fetch", &err));
+                return;
+            }
+        };
+        let Some(ref user_db) = self.user_db else {
+            return;
+        };
+        if let Err(err) =
+            user_db.save_fetched_question(fetched.question_id, fetch.target.site, fetched.json)
+        {
+            self.record_error(ErrorReport::new("Question fetch", &err));
+            return;
+        }
+        self.flash = Some(format!(
+            "Fetched #{} (not part of the corpus)",
+            fetched.question_id
+        ));
+        self.dispatch(Action::OpenFetched {
+            site: fetch.target.site,
+            question_id: fetched.question_id,
+            answer_id: fetch.target.answer_id,
+        });
+    }
+}
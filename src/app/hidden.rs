@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::HashSet;
+
+use super::{Action, App, Page};
+use crate::db::QuestionSummary;
+use crate::site::Site;
+use crate::userdb::HiddenQuestion;
+
+/// IDs of the corpus questions hidden from the lists. Hidden questions are
+/// kept per site, so each only hides the corpus question of its own site.
+pub(super) fn hidden_in_corpus(
+    questions: &[QuestionSummary],
+    hidden: &[HiddenQuestion],
+) -> HashSet<i64> {
+    let hidden: HashSet<_> = hidden.iter().map(|h| (h.site, h.question_id)).collect();
+    questions
+        .iter()
+        .filter(|q| hidden.contains(&(q.site, q.id)))
+        .map(|q| q.id)
+        .collect()
+}
+
+impl App {
+    /// Whether the question of that site and ID is a hidden corpus question
+    pub(super) fn is_hidden(&self, site: Site, question_id: i64) -> bool {
+        self.hidden.contains(&question_id)
+            && self
+                .questions
+                .iter()
+                .any(|q| q.id == question_id && q.site == site)
+    }
+
+    pub(super) fn handle_hidden_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let visible_rows = self.height.saturating_sub(2) as usize;
+        let max = self.hidden_page.questions.len().saturating_sub(1);
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => action = Some(Action::SwitchPage(Page::Index)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.hidden_page.selected = (self.hidden_page.selected + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.hidden_page.selected = self.hidden_page.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.hidden_page.selected = 0,
+            KeyCode::Char('G') => self.hidden_page.selected = max,
+            KeyCode::Enter => {
+                if let Some(hidden) = self.hidden_page.questions.get(self.hidden_page.selected) {
+                    if self.hidden.contains(&hidden.question_id) {
+                        action = Some(Action::OpenQuestion(hidden.question_id));
+                    } else {
+                        self.flash = Some("Not in this corpus".to_string());
+                    }
+                }
+            }
+            KeyCode::Char('u') | KeyCode::Char('x') => {
+                let selected = self.hidden_page.selected;
+                if let Some(&hidden) = self.hidden_page.questions.get(selected) {
+                    self.set_hidden(hidden.site, hidden.question_id, false);
+                    self.hidden_page.questions.remove(selected);
+                    let max = self.hidden_page.questions.len().saturating_sub(1);
+                    self.hidden_page.selected = selected.min(max);
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if self.hidden_page.selected < self.hidden_page.scroll {
+            self.hidden_page.scroll = self.hidden_page.selected;
+        } else if visible_rows > 0
+            && self.hidden_page.selected >= self.hidden_page.scroll + visible_rows
+        {
+            self.hidden_page.scroll = self.hidden_page.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    /// Load the hidden questions and switch to the Hidden page
+    pub(super) fn open_hidden(&mut self) -> Option<Action> {
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("User database is unavailable".to_string());
+            return None;
+        };
+        match user_db.get_hidden() {
+            Ok(questions) => self.hidden_page.questions = questions,
+            Err(err) => {
+                self.flash = Some(format!("{err:#}"));
+                return None;
+            }
+        }
+        self.hidden_page.selected = 0;
+        self.hidden_page.scroll = 0;
+        Some(Action::SwitchPage(Page::Hidden))
+    }
+}
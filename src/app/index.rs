@@ -0,0 +1,1133 @@
+use anyhow::{anyhow, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::{Action, App, Page, SearchMode, SearchScope, SortColumn, SortDirection, WHEEL_LINES};
+use crate::clock;
+use crate::db::QuestionSummary;
+use crate::export::reading_list_markdown;
+use crate::hooks::HookEvent;
+use crate::report::ErrorReport;
+use crate::search::fuzzy::{fuzzy_filter, BackgroundMatches};
+use crate::search::hybrid::reciprocal_rank_fusion;
+use crate::search::query::SearchQuery;
+use crate::search::semantic::{ModelLoad, SemanticPreview, SemanticSearch};
+use crate::site::Site;
+use crate::userdb::WishlistEntry;
+
+/// Semantic neighbours fed into a hybrid search; more than a plain semantic
+/// search shows, so the fusion has a tail to rerank
+const HYBRID_SEMANTIC_LIMIT: usize = 50;
+
+/// Everything the list order depends on besides the questions themselves,
+/// compared to tell whether the cached order still holds
+#[derive(PartialEq)]
+pub(super) struct OrderKey {
+    search_revision: u64,
+    sort: Option<(SortColumn, SortDirection)>,
+    tag_filter: Option<String>,
+    term_filter: Option<String>,
+    hide_closed: bool,
+    /// Hidden questions, or `None` while they're shown; like bookmarks,
+    /// hiding one always changes the count
+    hidden: Option<usize>,
+    include_bodies: bool,
+    pins: Vec<i64>,
+    /// Bookmark count on the bookmarks page; bookmarks come and go one at a
+    /// time, so a change always changes it
+    bookmarks: Option<usize>,
+}
+
+/// The question list in display order, sharing the cached order
+pub struct SortedQuestions<'a> {
+    questions: &'a [QuestionSummary],
+    order: Rc<[usize]>,
+}
+
+impl<'a> SortedQuestions<'a> {
+    fn new(questions: &'a [QuestionSummary], order: Rc<[usize]>) -> Self {
+        Self { questions, order }
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn get(&self, position: usize) -> Option<&'a QuestionSummary> {
+        self.order.get(position).map(|&i| &self.questions[i])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a QuestionSummary> + '_ {
+        self.order.iter().map(|&i| &self.questions[i])
+    }
+
+    /// Up to `count` questions from `start` on, with their list positions
+    pub fn window(
+        &self,
+        start: usize,
+        count: usize,
+    ) -> impl Iterator<Item = (usize, &'a QuestionSummary)> + '_ {
+        let start = start.min(self.order.len());
+        let end = start.saturating_add(count).min(self.order.len());
+        (start..end).map(|position| (position, &self.questions[self.order[position]]))
+    }
+}
+
+/// Aggregates over the listed questions, for the stats footer
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ListStats {
+    pub count: usize,
+    pub mean_score: f64,
+    pub median_score: f64,
+    /// Creation dates of the oldest and the newest question
+    pub first_date: i64,
+    pub last_date: i64,
+    /// Share of the questions whose accepted answer is Erwin's, from 0 to 1
+    pub erwin_accepted: f64,
+}
+
+impl ListStats {
+    fn of(questions: &SortedQuestions) -> Self {
+        let count = questions.len();
+        if count == 0 {
+            return Self::default();
+        }
+        let mut scores: Vec<i32> = questions.iter().map(|q| q.score).collect();
+        scores.sort_unstable();
+        let middle = count / 2;
+        let median_score = if count.is_multiple_of(2) {
+            (scores[middle - 1] as f64 + scores[middle] as f64) / 2.0
+        } else {
+            scores[middle] as f64
+        };
+        let (first_date, last_date) = questions
+            .iter()
+            .map(|q| q.creation_date)
+            .fold((i64::MAX, i64::MIN), |(first, last), date| {
+                (first.min(date), last.max(date))
+            });
+        Self {
+            count,
+            mean_score: scores.iter().map(|&s| s as f64).sum::<f64>() / count as f64,
+            median_score,
+            first_date,
+            last_date,
+            erwin_accepted: questions.iter().filter(|q| q.erwin_accepted).count() as f64
+                / count as f64,
+        }
+    }
+}
+
+impl App {
+    pub(super) fn handle_index_key(&mut self, key: KeyEvent) -> Option<Action> {
+        // Handle search input mode
+        if self.search.mode != SearchMode::None {
+            self.handle_search_input(key);
+            return None;
+        }
+
+        // Normal mode
+        match key.code {
+            KeyCode::Char('q') => {
+                if self.search.has_results() {
+                    self.clear_search_results();
+                } else if self.index.tag_filter.is_some() {
+                    self.clear_tag_filter();
+                } else if self.index.term_filter.is_some() {
+                    self.clear_term_filter();
+                } else if self.page == Page::Bookmarks {
+                    return Some(self.switch_list(Page::Index));
+                } else {
+                    return Some(Action::Quit);
+                }
+            }
+            KeyCode::Char('/') => {
+                self.search.mode = SearchMode::Title;
+                self.search.input.clear();
+            }
+            KeyCode::Char('?') => {
+                self.search.mode = SearchMode::Semantic;
+                self.search.input.clear();
+                self.search.semantic_preview.clear();
+                self.search.scope = self.search_scope();
+                self.start_model_load();
+            }
+            KeyCode::Char('H') => {
+                self.search.mode = SearchMode::Hybrid;
+                self.search.input.clear();
+                self.search.scope = self.search_scope();
+                self.start_model_load();
+            }
+            KeyCode::Esc if self.search.has_results() => {
+                self.clear_search_results();
+            }
+            KeyCode::Esc if self.index.tag_filter.is_some() => {
+                self.clear_tag_filter();
+            }
+            KeyCode::Esc if self.index.term_filter.is_some() => {
+                self.clear_term_filter();
+            }
+            KeyCode::Esc if self.page == Page::Bookmarks => {
+                return Some(self.switch_list(Page::Index));
+            }
+            KeyCode::Char('B') if self.page == Page::Index => {
+                return Some(self.switch_list(Page::Bookmarks));
+            }
+            KeyCode::Char('m') => {
+                if let Some(q) = self.get_selected_question() {
+                    let (site, id) = (q.site, q.id);
+                    self.toggle_bookmark(site, id);
+                    // Unbookmarking drops the row from the bookmarks page
+                    let max = self.visible_questions_count().saturating_sub(1);
+                    self.index.selected = self.index.selected.min(max);
+                    self.adjust_index_scroll();
+                }
+            }
+            KeyCode::Char('T') => {
+                return Some(Action::SwitchPage(Page::Tags));
+            }
+            // Quick tag picker: the tags page, already filtering by name
+            KeyCode::Char('t') => {
+                self.tags.filter_active = true;
+                self.tags.filter_input.clear();
+                self.update_tag_matches();
+                return Some(Action::SwitchPage(Page::Tags));
+            }
+            KeyCode::Char('F') => {
+                return Some(Action::SwitchPage(Page::Terms));
+            }
+            // Wishlist the search, the command line taking an optional note
+            KeyCode::Char('W') if self.search.has_results() => {
+                self.command = Some("wish ".to_string());
+            }
+            KeyCode::Char('A') => return self.open_alerts(),
+            KeyCode::Char('R') => return self.open_review(),
+            KeyCode::Char('h') => return self.open_recent(),
+            KeyCode::Char('D') => {
+                self.index.detailed = !self.index.detailed;
+                self.adjust_index_scroll();
+            }
+            KeyCode::Char('I') => {
+                self.index.stats = !self.index.stats;
+                self.adjust_index_scroll();
+            }
+            KeyCode::Char('x') => {
+                if let Some(question) = self.get_selected_question() {
+                    let (site, id) = (question.site, question.id);
+                    if self.hidden.contains(&id) {
+                        self.set_hidden(site, id, false);
+                    } else {
+                        let message = format!("Hide \"{}\" from the lists?", question.title);
+                        let action = Action::HideQuestion {
+                            site,
+                            question_id: id,
+                        };
+                        self.request_confirm(message, action);
+                    }
+                }
+            }
+            KeyCode::Char('U') => return self.open_hidden(),
+            KeyCode::Char('V') => {
+                let selected_id = self.get_selected_question().map(|q| q.id);
+                self.index.show_hidden = !self.index.show_hidden;
+                if !selected_id.is_some_and(|id| self.select_question(id)) {
+                    self.index.reset_cursor();
+                }
+                self.flash = Some(if self.index.show_hidden {
+                    format!("Showing {} hidden questions (x unhides)", self.hidden.len())
+                } else {
+                    "Hiding hidden questions".to_string()
+                });
+            }
+            KeyCode::Char('C') => {
+                let selected_id = self.get_selected_question().map(|q| q.id);
+                self.index.hide_closed = !self.index.hide_closed;
+                if !selected_id.is_some_and(|id| self.select_question(id)) {
+                    self.index.reset_cursor();
+                }
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = self.visible_questions_count().saturating_sub(1);
+                self.index.selected = if self.index.selected == max && self.config.navigation.wrap {
+                    0
+                } else {
+                    (self.index.selected + 1).min(max)
+                };
+                self.adjust_index_scroll();
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.index.selected = if self.index.selected == 0 && self.config.navigation.wrap {
+                    self.visible_questions_count().saturating_sub(1)
+                } else {
+                    self.index.selected.saturating_sub(1)
+                };
+                self.adjust_index_scroll();
+            }
+            KeyCode::Char('g') => {
+                self.index.reset_cursor();
+                self.adjust_index_scroll();
+            }
+            KeyCode::Char('G') => {
+                self.index.selected = self.visible_questions_count().saturating_sub(1);
+                self.adjust_index_scroll();
+            }
+            KeyCode::Char(' ') => {
+                let visible = self.height.saturating_sub(self.index.chrome_height()) as usize
+                    / self.index.row_height();
+                self.page_index(visible, true, self.config.navigation.keep_position);
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let half = (self.height.saturating_sub(self.index.chrome_height()) / 2) as usize
+                    / self.index.row_height();
+                self.page_index(half, true, self.config.navigation.keep_position);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let half = (self.height.saturating_sub(self.index.chrome_height()) / 2) as usize
+                    / self.index.row_height();
+                self.page_index(half, false, self.config.navigation.keep_position);
+            }
+            KeyCode::Char('0') => self.reset_sort(),
+            KeyCode::Char('f')
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.search.fuzzy_matches.is_some() =>
+            {
+                self.toggle_body_matches()
+            }
+            KeyCode::Char('1') if self.search.sortable() => self.toggle_sort(SortColumn::Id),
+            KeyCode::Char('2') if self.search.sortable() => self.toggle_sort(SortColumn::Date),
+            KeyCode::Char('3') if self.search.sortable() => self.toggle_sort(SortColumn::Score),
+            KeyCode::Char('4') if self.search.sortable() => self.toggle_sort(SortColumn::Views),
+            KeyCode::Char('5') if self.search.sortable() => self.toggle_sort(SortColumn::Answers),
+            KeyCode::Char('6') if self.search.sortable() => self.toggle_sort(SortColumn::Bounty),
+            KeyCode::Char('p') => self.toggle_pin(),
+            KeyCode::Char('P') if !self.index.pins.is_empty() => {
+                self.index.pins.clear();
+                self.index.reset_cursor();
+            }
+            KeyCode::Char('S') if !self.index.pins.is_empty() => {
+                self.flash = Some(match self.save_pins() {
+                    Ok(name) => format!("Saved pins as collection \"{name}\""),
+                    Err(err) => format!("{err:#}"),
+                });
+            }
+            KeyCode::Char('X') if !self.index.pins.is_empty() => {
+                self.flash = Some(match self.export_pins() {
+                    Ok(path) => format!("Exported pins to {path}"),
+                    Err(err) => format!("{err:#}"),
+                });
+            }
+            KeyCode::Enter if self.pick_mode => {
+                return self.get_selected_question().map(|q| Action::Pick(q.id));
+            }
+            KeyCode::Enter => {
+                return self
+                    .get_selected_question()
+                    .map(|q| Action::OpenQuestion(q.id));
+            }
+            KeyCode::Char('o') => {
+                return self
+                    .get_selected_question()
+                    .map(|q| Action::OpenUrl(q.site.question_url(q.id)));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_search_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.search.mode = SearchMode::None;
+                self.search.input.clear();
+                self.search.filters.clear();
+                self.search.fuzzy_matches = None;
+                self.title_matcher.search("");
+                self.search.body_matches = None;
+                self.search.results_changed();
+                self.index.leave_search_order();
+                self.index.reset_cursor();
+            }
+            KeyCode::Enter => {
+                if !self.search.input.is_empty() {
+                    match self.search.mode {
+                        SearchMode::Semantic => self.perform_semantic_search(),
+                        SearchMode::Hybrid => self.perform_hybrid_search(),
+                        _ => {}
+                    }
+                }
+                self.search.mode = SearchMode::None;
+            }
+            KeyCode::Backspace => {
+                self.search.input.pop();
+                if self.search.mode == SearchMode::Title {
+                    self.update_fuzzy_search();
+                }
+                self.update_semantic_preview();
+            }
+            KeyCode::Char(c) => {
+                if key.modifiers.contains(KeyModifiers::CONTROL) {
+                    match c {
+                        'u' => {
+                            self.search.input.clear();
+                            if self.search.mode == SearchMode::Title {
+                                self.update_fuzzy_search();
+                            }
+                            self.update_semantic_preview();
+                        }
+                        'f' if self.search.mode == SearchMode::Title => self.toggle_body_matches(),
+                        'w' => {
+                            // Delete last word
+                            if let Some(last_space) = self.search.input.rfind(' ') {
+                                self.search.input.truncate(last_space);
+                            } else {
+                                self.search.input.clear();
+                            }
+                            if self.search.mode == SearchMode::Title {
+                                self.update_fuzzy_search();
+                            }
+                            self.update_semantic_preview();
+                        }
+                        _ => {}
+                    }
+                } else {
+                    self.search.input.push(c);
+                    if self.search.mode == SearchMode::Title {
+                        self.update_fuzzy_search();
+                    }
+                    self.update_semantic_preview();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn clear_search_results(&mut self) {
+        self.search.clear_results();
+        self.title_matcher.search("");
+        self.index.leave_search_order();
+        self.index.reset_cursor();
+    }
+
+    pub(super) fn clear_tag_filter(&mut self) {
+        self.index.tag_filter = None;
+        self.index.reset_cursor();
+    }
+
+    fn clear_term_filter(&mut self) {
+        self.index.term_filter = None;
+        self.index.reset_cursor();
+    }
+
+    /// Start matching the title search input. Operators alone apply right
+    /// away; title matches arrive in `receive_title_matches`, and until then
+    /// the list keeps the previous ones.
+    fn update_fuzzy_search(&mut self) {
+        let query = SearchQuery::parse(&self.search.input);
+        self.search.body_matches = None;
+        self.title_matcher.search(&query.text);
+        if query.text.is_empty() {
+            // Operators alone list every question that passes them, in list order
+            self.search.fuzzy_matches =
+                (!query.filters.is_empty()).then(|| query.search(&self.questions));
+            self.index.leave_search_order();
+        } else {
+            if let Some(ref body_search) = self.body_search {
+                body_search.search(&query.text);
+            }
+            self.index.enter_search_order();
+        }
+        self.search.filters = query.filters;
+        self.search.results_changed();
+        self.index.reset_cursor();
+    }
+
+    /// Show the background title matches, unless the input changed since
+    pub(super) fn receive_title_matches(&mut self, found: BackgroundMatches) {
+        if found.pattern != SearchQuery::parse(&self.search.input).text {
+            return;
+        }
+        let mut matches = found.matches;
+        matches.retain(|m| {
+            let question = &self.questions[m.index];
+            self.search.filters.iter().all(|f| f.matches(question))
+        });
+        self.search.fuzzy_matches = Some(matches);
+        self.search.results_changed();
+        // Keep the best match selected while it's being typed
+        if self.search.mode == SearchMode::Title {
+            self.index.reset_cursor();
+        }
+    }
+
+    /// Open on the semantic search results for `query`, as if it was typed into
+    /// the `?` modal. Without the embedding model the title search stands in.
+    pub fn search_on_start(&mut self, query: &str) {
+        self.search.input = query.to_string();
+        self.perform_semantic_search();
+        if self.search.semantic_results.is_none() && self.search.waiting_for_model.is_none() {
+            self.fall_back_to_title_search();
+        }
+    }
+
+    fn fall_back_to_title_search(&mut self) {
+        self.flash = Some("Semantic search unavailable; showing title matches".to_string());
+        self.update_fuzzy_search();
+    }
+
+    /// Start loading the embedding model in the background, unless it's
+    /// loaded, loading or failed before. Returns whether it's loading.
+    pub(super) fn start_model_load(&mut self) -> bool {
+        if self.semantic.is_some() || self.model_failed {
+            return false;
+        }
+        if self.model_load.is_none() {
+            self.flash = Some(if SemanticSearch::needs_download() {
+                "Downloading the embedding model (~50MB)\u{2026}".to_string()
+            } else {
+                "Loading the embedding model\u{2026}".to_string()
+            });
+            self.model_load = Some(ModelLoad::start());
+        }
+        true
+    }
+
+    /// Take the loaded model (or its failure) and run the search waiting on it
+    pub(super) fn finish_model_load(&mut self, result: Result<SemanticSearch>) {
+        self.model_load = None;
+        match result {
+            Ok(semantic) => {
+                let semantic = Arc::new(semantic);
+                self.semantic_preview = self
+                    .db
+                    .path()
+                    .map(|path| SemanticPreview::start(Arc::clone(&semantic), path));
+                self.semantic = Some(semantic);
+                self.flash = None;
+                // Preview what was typed while the model loaded
+                self.update_semantic_preview();
+            }
+            Err(err) => {
+                self.model_failed = true;
+                self.record_error(ErrorReport::new("Embedding model", &err));
+            }
+        }
+        match self.search.waiting_for_model.take() {
+            Some(SearchMode::Hybrid) => self.perform_hybrid_search(),
+            Some(_) => {
+                self.perform_semantic_search();
+                if self.search.semantic_results.is_none() {
+                    self.fall_back_to_title_search();
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Queue a preview search for the semantic modal's input; the results
+    /// arrive in `tick`
+    fn update_semantic_preview(&mut self) {
+        if self.search.mode != SearchMode::Semantic {
+            return;
+        }
+        if self.search.input.trim().is_empty() {
+            self.search.semantic_preview.clear();
+        } else if let Some(ref preview) = self.semantic_preview {
+            let within = self.search.scope.as_ref().map(|s| Arc::clone(&s.ids));
+            preview.search(&self.search.input, within);
+        }
+    }
+
+    fn perform_semantic_search(&mut self) {
+        if self.search.input.is_empty() {
+            self.search.semantic_results = None;
+            self.search.explore_tag = None;
+            self.search.results_changed();
+            self.index.leave_search_order();
+            return;
+        }
+
+        let Some(ref semantic) = self.semantic else {
+            if self.start_model_load() {
+                self.search.waiting_for_model = Some(SearchMode::Semantic);
+            }
+            return;
+        };
+
+        // Search database for similar questions (by title)
+        let within = self.search.scope.as_ref().map(|s| &*s.ids);
+        let Ok(results) = semantic.search(&self.db, &self.search.input, within, 20) else {
+            return;
+        };
+
+        // Extract question IDs directly - no deduplication or re-ranking needed
+        let question_ids: Vec<i64> = results.into_iter().map(|r| r.question_id).collect();
+        self.search.semantic_results = Some(question_ids);
+        self.search.explore_tag = None;
+        self.search.results_changed();
+        self.index.enter_search_order();
+        self.index.reset_cursor();
+    }
+
+    /// Rank by title and by meaning at once, fusing the two lists. Without the
+    /// embedding model the title ranking stands alone.
+    fn perform_hybrid_search(&mut self) {
+        if self.semantic.is_none() && self.start_model_load() {
+            self.search.waiting_for_model = Some(SearchMode::Hybrid);
+            return;
+        }
+        let fuzzy = fuzzy_filter(&self.questions, &self.search.input, |q| &q.title);
+
+        let semantic_ids = match self.semantic {
+            Some(ref semantic) => semantic.search(
+                &self.db,
+                &self.search.input,
+                self.search.scope.as_ref().map(|s| &*s.ids),
+                HYBRID_SEMANTIC_LIMIT,
+            ),
+            None => Err(anyhow!("model not loaded")),
+        };
+        let semantic: Vec<usize> = match semantic_ids {
+            Ok(results) => {
+                let positions: HashMap<i64, usize> = self
+                    .questions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, q)| (q.id, i))
+                    .collect();
+                results
+                    .iter()
+                    .filter_map(|r| positions.get(&r.question_id).copied())
+                    .collect()
+            }
+            Err(err) => {
+                self.flash = Some(format!(
+                    "Semantic search unavailable ({err}); ranked by title only"
+                ));
+                Vec::new()
+            }
+        };
+
+        self.search.fuzzy_matches = None;
+        self.search.semantic_results = None;
+        self.search.explore_tag = None;
+        self.search.hybrid_results = Some(reciprocal_rank_fusion(fuzzy, &semantic));
+        self.search.results_changed();
+        self.index.enter_search_order();
+        self.index.reset_cursor();
+    }
+
+    /// Move between the full list and the bookmarked questions, from the top
+    fn switch_list(&mut self, page: Page) -> Action {
+        self.index.reset_cursor();
+        Action::SwitchPage(page)
+    }
+
+    /// Bookmark or unbookmark a question in the user database
+    pub(super) fn toggle_bookmark(&mut self, site: Site, question_id: i64) {
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("User database is unavailable".to_string());
+            return;
+        };
+        let bookmarked = !self.bookmarks.contains(&(site, question_id));
+        if let Err(err) = user_db.set_bookmark(site, question_id, bookmarked) {
+            self.flash = Some(format!("{err:#}"));
+            return;
+        }
+        if bookmarked {
+            self.bookmarks.insert((site, question_id));
+            self.flash = Some("Bookmarked".to_string());
+        } else {
+            self.bookmarks.remove(&(site, question_id));
+            self.flash = Some("Removed bookmark".to_string());
+        }
+
+        // The open question may be a fetched one, missing from the list
+        let summary = match self.show.question {
+            Some(ref question)
+                if question.summary.id == question_id && question.summary.site == site =>
+            {
+                Some(&question.summary)
+            }
+            _ => self
+                .questions
+                .iter()
+                .find(|q| q.id == question_id && q.site == site),
+        };
+        if let Some(summary) = summary {
+            let event = HookEvent::Bookmark {
+                question: summary.into(),
+                bookmarked,
+            };
+            if let Err(err) = self.hooks.fire(&event) {
+                self.record_error(ErrorReport::new("Hook", &err));
+            }
+        }
+    }
+
+    /// Hide a question from the lists, or bring it back
+    pub(super) fn set_hidden(&mut self, site: Site, question_id: i64, hidden: bool) {
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("User database is unavailable".to_string());
+            return;
+        };
+        if let Err(err) = user_db.set_hidden(site, question_id, hidden) {
+            self.flash = Some(format!("{err:#}"));
+            return;
+        }
+        // Only the corpus question of that site is affected
+        let in_corpus = self
+            .questions
+            .iter()
+            .any(|q| q.id == question_id && q.site == site);
+        if hidden {
+            if in_corpus {
+                self.hidden.insert(question_id);
+            }
+            // A pin would bring it back to the top of the list
+            self.index.pins.retain(|&pin| pin != question_id);
+            self.flash = Some("Hidden (V shows hidden questions, U lists them)".to_string());
+        } else {
+            if in_corpus {
+                self.hidden.remove(&question_id);
+            }
+            self.flash = Some("Unhidden".to_string());
+        }
+        let max = self.visible_questions_count().saturating_sub(1);
+        self.index.selected = self.index.selected.min(max);
+        self.adjust_index_scroll();
+    }
+
+    /// Save the search's query to the wishlist (`:wish [note]`), for a topic
+    /// the corpus answers poorly
+    pub(super) fn add_to_wishlist(&mut self, note: &str) {
+        let query = self.search.input.trim();
+        if !self.search.has_results() || query.is_empty() {
+            self.flash =
+                Some("Search first; :wish then saves the query to the wishlist".to_string());
+            return;
+        }
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("User database is unavailable".to_string());
+            return;
+        };
+        let kind = if self.search.semantic_results.is_some() {
+            "semantic"
+        } else if self.search.hybrid_results.is_some() {
+            "hybrid"
+        } else {
+            "title"
+        };
+        let note = note.trim();
+        let entry = WishlistEntry {
+            query: query.to_string(),
+            kind: kind.to_string(),
+            results: self.visible_questions_count(),
+            note: (!note.is_empty()).then(|| note.to_string()),
+            added_at: clock::now().timestamp(),
+        };
+        self.flash = Some(match user_db.add_to_wishlist(entry) {
+            Ok(()) => format!("Added \"{query}\" to the wishlist (erwindb wishlist lists it)"),
+            Err(err) => format!("{err:#}"),
+        });
+    }
+
+    /// Pin or unpin the selected question, then move on to the row that followed it
+    fn toggle_pin(&mut self) {
+        let sorted = self.get_sorted_questions();
+        let Some(id) = sorted.get(self.index.selected).map(|q| q.id) else {
+            return;
+        };
+        let next_id = sorted.get(self.index.selected + 1).map(|q| q.id);
+
+        match self.index.pins.iter().position(|&pin| pin == id) {
+            Some(pos) => {
+                self.index.pins.remove(pos);
+            }
+            None => self.index.pins.push(id),
+        }
+
+        let max = self.visible_questions_count().saturating_sub(1);
+        if !next_id.is_some_and(|next| self.select_question(next)) {
+            self.index.selected = self.index.selected.min(max);
+            self.adjust_index_scroll();
+        }
+    }
+
+    /// Turn the pinned set into a bookmark collection and clear the pins
+    fn save_pins(&mut self) -> Result<String> {
+        let user_db = self
+            .user_db
+            .as_ref()
+            .context("User database is unavailable")?;
+        let name = format!("Pinned {}", clock::now().format("%Y-%m-%d %H:%M:%S"));
+        let questions: Vec<(Site, i64)> = self
+            .index
+            .pins
+            .iter()
+            .filter_map(|&id| self.questions.iter().find(|q| q.id == id))
+            .map(|q| (q.site, q.id))
+            .collect();
+        user_db.create_collection(&name, &questions)?;
+        self.index.pins.clear();
+        self.index.reset_cursor();
+        Ok(name)
+    }
+
+    /// Write the pinned set as a Markdown reading list in the working directory
+    fn export_pins(&mut self) -> Result<String> {
+        let now = clock::now();
+        let title = format!("Reading set {}", now.format("%Y-%m-%d %H:%M"));
+        let path = format!("erwindb-pins-{}.md", now.format("%Y%m%d-%H%M%S"));
+        let questions: Vec<&QuestionSummary> = self
+            .index
+            .pins
+            .iter()
+            .filter_map(|id| self.questions.iter().find(|q| q.id == *id))
+            .collect();
+        let markdown = reading_list_markdown(&title, &questions);
+        fs::write(&path, markdown).with_context(|| format!("Failed to write {path}"))?;
+        let event = HookEvent::Export {
+            format: "markdown",
+            output: Some(path.clone()),
+            questions: questions.into_iter().map(Into::into).collect(),
+        };
+        if let Err(err) = self.hooks.fire(&event) {
+            self.record_error(ErrorReport::new("Hook", &err));
+        }
+        Ok(path)
+    }
+
+    /// Include questions matching only in their bodies in the title search, or not
+    fn toggle_body_matches(&mut self) {
+        self.search.include_bodies = !self.search.include_bodies;
+        let max = self.visible_questions_count().saturating_sub(1);
+        self.index.selected = self.index.selected.min(max);
+        self.adjust_index_scroll();
+    }
+
+    /// Drop the column sort: natural order, or relevance order during a search
+    fn reset_sort(&mut self) {
+        let selected_id = self.get_selected_question().map(|q| q.id);
+        self.index.sort_active = false;
+        if let Some(id) = selected_id {
+            if self.select_question(id) {
+                return;
+            }
+        }
+        self.index.reset_cursor();
+    }
+
+    fn toggle_sort(&mut self, column: SortColumn) {
+        // Remember the currently selected question
+        let selected_id = self.get_selected_question().map(|q| q.id);
+
+        if self.index.sort_column == column && self.index.sort_active {
+            self.index.sort_direction = match self.index.sort_direction {
+                SortDirection::Asc => SortDirection::Desc,
+                SortDirection::Desc => SortDirection::Asc,
+            };
+        } else {
+            self.index.sort_column = column;
+            self.index.sort_direction = SortDirection::Desc;
+        }
+        self.index.sort_active = true;
+
+        // Find the question's new position after sorting
+        if let Some(id) = selected_id {
+            if self.select_question(id) {
+                return;
+            }
+        }
+        self.index.reset_cursor();
+    }
+
+    /// The wheel scrolls the list (the selection keeps its screen row); a click
+    /// on a question opens it
+    pub(super) fn handle_index_mouse(&mut self, mouse: MouseEvent) -> Option<Action> {
+        if self.typing() {
+            return None;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.page_index(WHEEL_LINES, true, true),
+            MouseEventKind::ScrollUp => self.page_index(WHEEL_LINES, false, true),
+            MouseEventKind::Down(MouseButton::Left) => {
+                // Below the header and the column headers, above the footer
+                let row = (mouse.row as usize).checked_sub(2)?;
+                if row >= self.height.saturating_sub(self.index.chrome_height()) as usize {
+                    return None;
+                }
+                let index = self.index.scroll + row / self.index.row_height();
+                if index >= self.visible_questions_count() {
+                    return None;
+                }
+                self.index.selected = index;
+                return self.handle_index_key(KeyEvent::from(KeyCode::Enter));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Move the cursor to a question if it's in the current list
+    fn select_question(&mut self, question_id: i64) -> bool {
+        let position = self
+            .get_sorted_questions()
+            .iter()
+            .position(|q| q.id == question_id);
+        match position {
+            Some(pos) => {
+                self.index.selected = pos;
+                self.adjust_index_scroll();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn visible_questions_count(&self) -> usize {
+        self.get_sorted_questions().len()
+    }
+
+    /// Move the selection `rows` down (or up). With `keep_position` the list
+    /// scrolls by as much, so the selection keeps its screen row.
+    fn page_index(&mut self, rows: usize, down: bool, keep_position: bool) {
+        let max = self.visible_questions_count().saturating_sub(1);
+        if keep_position {
+            let visible_rows = self.height.saturating_sub(self.index.chrome_height() + 1) as usize
+                / self.index.row_height();
+            let max_scroll = (max + 1).saturating_sub(visible_rows);
+            self.index.scroll = if down {
+                (self.index.scroll + rows).min(max_scroll)
+            } else {
+                self.index.scroll.saturating_sub(rows)
+            };
+        }
+        self.index.selected = if down {
+            (self.index.selected + rows).min(max)
+        } else {
+            self.index.selected.saturating_sub(rows)
+        };
+        self.adjust_index_scroll();
+    }
+
+    /// Adjust index scroll to keep cursor within scroll offset of viewport edges
+    pub fn adjust_index_scroll(&mut self) {
+        // header + columns + status
+        let visible_rows = self.height.saturating_sub(self.index.chrome_height() + 1) as usize
+            / self.index.row_height();
+
+        if visible_rows == 0 {
+            return;
+        }
+        // Like Vim, a margin too big for the screen keeps the cursor centered
+        let scroll_offset = self.layout.scrolloff.min((visible_rows - 1) / 2);
+
+        // If cursor is above the visible area (with offset), scroll up
+        let min_visible = self.index.scroll + scroll_offset;
+        if self.index.selected < min_visible {
+            self.index.scroll = self.index.selected.saturating_sub(scroll_offset);
+        }
+
+        // If cursor is below the visible area (with offset), scroll down
+        let max_visible = self.index.scroll + visible_rows.saturating_sub(scroll_offset + 1);
+        if self.index.selected > max_visible {
+            self.index.scroll =
+                (self.index.selected + scroll_offset + 1).saturating_sub(visible_rows);
+        }
+
+        // Clamp scroll to valid range
+        let max_scroll = self.visible_questions_count().saturating_sub(visible_rows);
+        self.index.scroll = self.index.scroll.min(max_scroll);
+    }
+
+    /// The list as shown, from the cached order while nothing it depends on
+    /// changed, so drawing and moving through the list don't sort it again
+    pub fn get_sorted_questions(&self) -> SortedQuestions<'_> {
+        let key = self.order_key();
+        if let Some((ref cached, ref order)) = *self.list_order.borrow() {
+            if *cached == key {
+                return SortedQuestions::new(&self.questions, Rc::clone(order));
+            }
+        }
+        let order: Rc<[usize]> = self.build_order().into();
+        *self.list_order.borrow_mut() = Some((key, Rc::clone(&order)));
+        SortedQuestions::new(&self.questions, order)
+    }
+
+    /// Stats of the list as shown, recomputed only once its order changes
+    pub fn list_stats(&self) -> ListStats {
+        let sorted = self.get_sorted_questions();
+        if let Some((ref order, stats)) = *self.list_stats.borrow() {
+            if Rc::ptr_eq(order, &sorted.order) {
+                return stats;
+            }
+        }
+        let stats = ListStats::of(&sorted);
+        *self.list_stats.borrow_mut() = Some((Rc::clone(&sorted.order), stats));
+        stats
+    }
+
+    fn order_key(&self) -> OrderKey {
+        OrderKey {
+            search_revision: self.search.revision,
+            sort: self
+                .index
+                .sort_active
+                .then_some((self.index.sort_column, self.index.sort_direction)),
+            tag_filter: self.index.tag_filter.clone(),
+            term_filter: self.index.term_filter.as_ref().map(|f| f.label.clone()),
+            hide_closed: self.index.hide_closed,
+            hidden: (!self.index.show_hidden).then_some(self.hidden.len()),
+            include_bodies: self.search.include_bodies,
+            pins: self.index.pins.clone(),
+            bookmarks: (self.page == Page::Bookmarks).then_some(self.bookmarks.len()),
+        }
+    }
+
+    /// Indices into `questions` in list order: search results (or all),
+    /// filtered, sorted, pins first
+    fn build_order(&self) -> Vec<usize> {
+        let questions = &self.questions;
+        let mut order: Vec<usize> = if let Some(ref matches) = self.search.fuzzy_matches {
+            let mut order: Vec<_> = matches.iter().map(|m| m.index).collect();
+            if self.search.include_bodies {
+                order.extend(self.body_only_matches().unwrap_or_default());
+            }
+            order
+        } else if let Some(ref ids) = self.search.semantic_results {
+            ids.iter()
+                .filter_map(|id| questions.iter().position(|q| q.id == *id))
+                .collect()
+        } else if let Some(ref matches) = self.search.hybrid_results {
+            matches.iter().map(|m| m.index).collect()
+        } else {
+            (0..questions.len()).collect()
+        };
+
+        order.retain(|&i| self.passes_list_filters(&questions[i]));
+
+        // Apply sorting (for search results, only if user has explicitly sorted)
+        if self.index.sort_active {
+            order.sort_by(|&a, &b| {
+                let (a, b) = (&questions[a], &questions[b]);
+                let cmp = match self.index.sort_column {
+                    SortColumn::Id => a.id.cmp(&b.id),
+                    SortColumn::Date => a.creation_date.cmp(&b.creation_date),
+                    SortColumn::Score => a.score.cmp(&b.score),
+                    SortColumn::Views => a.view_count.cmp(&b.view_count),
+                    SortColumn::Answers => a.answer_count.cmp(&b.answer_count),
+                    SortColumn::Bounty => a.bounty.cmp(&b.bounty),
+                };
+                match self.index.sort_direction {
+                    SortDirection::Asc => cmp,
+                    SortDirection::Desc => cmp.reverse(),
+                }
+            });
+        }
+
+        // Pinned questions stay on top whatever the search or filter
+        let pins = &self.index.pins;
+        if !pins.is_empty() {
+            order.retain(|&i| !pins.contains(&questions[i].id));
+            let pinned = pins
+                .iter()
+                .filter_map(|id| questions.iter().position(|q| q.id == *id));
+            order.splice(0..0, pinned);
+        }
+
+        if self.page == Page::Bookmarks {
+            order.retain(|&i| {
+                let q = &questions[i];
+                self.bookmarks.contains(&(q.site, q.id))
+            });
+        }
+
+        order
+    }
+
+    /// Whether the tag, term, closed and hidden filters keep the question in the list
+    fn passes_list_filters(&self, question: &QuestionSummary) -> bool {
+        self.index
+            .tag_filter
+            .as_ref()
+            .is_none_or(|tag| question.tags.contains(tag))
+            && self
+                .index
+                .term_filter
+                .as_ref()
+                .is_none_or(|filter| filter.question_ids.contains(&question.id))
+            && !(self.index.hide_closed && question.status.closed)
+            && (self.index.show_hidden || !self.hidden.contains(&question.id))
+    }
+
+    /// The questions the list is filtered to, for a semantic search to stay
+    /// within; `None` when it shows them all
+    fn search_scope(&self) -> Option<SearchScope> {
+        let mut label = Vec::new();
+        if let Some(ref tag) = self.index.tag_filter {
+            label.push(format!("[{tag}]"));
+        }
+        if let Some(ref filter) = self.index.term_filter {
+            label.push(filter.label.clone());
+        }
+        if self.index.hide_closed {
+            label.push("open".to_string());
+        }
+        let bookmarks = self.page == Page::Bookmarks;
+        if bookmarks {
+            label.push("bookmarks".to_string());
+        }
+        if label.is_empty() {
+            return None;
+        }
+        let ids = self
+            .questions
+            .iter()
+            .filter(|q| self.passes_list_filters(q))
+            .filter(|q| !bookmarks || self.bookmarks.contains(&(q.site, q.id)))
+            .map(|q| q.id)
+            .collect();
+        Some(SearchScope {
+            label: label.join(" \u{00b7} "),
+            ids,
+        })
+    }
+
+    /// Questions the title search misses but whose bodies or answers match it,
+    /// best first, as indices into `questions`; `None` until they've been
+    /// counted
+    pub fn body_only_matches(&self) -> Option<Vec<usize>> {
+        let body_matches = self.search.body_matches.as_ref()?;
+        let title_matches: HashSet<usize> = self
+            .search
+            .fuzzy_matches
+            .iter()
+            .flatten()
+            .map(|m| m.index)
+            .collect();
+        let rank: HashMap<i64, usize> = body_matches
+            .iter()
+            .enumerate()
+            .map(|(rank, id)| (*id, rank))
+            .collect();
+
+        let mut matches: Vec<(usize, usize)> = self
+            .questions
+            .iter()
+            .enumerate()
+            .filter(|(i, q)| {
+                !title_matches.contains(i) && self.search.filters.iter().all(|f| f.matches(q))
+            })
+            .filter_map(|(i, q)| rank.get(&q.id).map(|rank| (*rank, i)))
+            .collect();
+        matches.sort_by_key(|(rank, _)| *rank);
+        Some(matches.into_iter().map(|(_, i)| i).collect())
+    }
+
+    pub fn get_selected_question(&self) -> Option<&QuestionSummary> {
+        self.get_sorted_questions().get(self.index.selected)
+    }
+}
@@ -0,0 +1,985 @@
+mod action;
+mod alerts;
+mod command;
+mod errors;
+mod fetch;
+mod hidden;
+mod index;
+mod playground;
+mod plugins;
+mod recent;
+mod review;
+mod scrollbar;
+mod show;
+mod state;
+mod tags;
+mod techniques;
+mod terms;
+
+pub use action::Action;
+pub(crate) use command::BUILTIN_COMMANDS;
+use hidden::hidden_in_corpus;
+pub use index::ListStats;
+use index::OrderKey;
+use review::count_due;
+pub use state::{
+    AlertsState, ConfirmState, ErrorDetailsState, HiddenPageState, HistoryEntry, IndexState,
+    LinkHint, LinkHints, PlaygroundState, PluginPageState, Presentation, RecentState, ReviewState,
+    SearchScope, SearchState, ShowState, TagsState, TechniquesState, TermFilter, TermsState,
+    TranslationState,
+};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::alerts::check_new_content;
+use crate::clock;
+use crate::config::{Config, Density, Layout};
+use crate::db::{Database, QuestionSummary};
+use crate::dwell::DwellTracker;
+use crate::external::ExternalCommand;
+use crate::fetch::{FetchTarget, QuestionFetch};
+use crate::glossary::Glossary;
+use crate::hooks::{HookEvent, Hooks};
+use crate::html::Link;
+use crate::hyperlink::Hyperlink;
+use crate::keymap::Keymap;
+use crate::links::{LinkGraph, LinkGraphLoad};
+use crate::memory::{MemoryReport, MB};
+use crate::plugins::Plugins;
+use crate::report::ErrorReport;
+use crate::search::body::BodySearch;
+use crate::search::fuzzy::BackgroundMatcher;
+use crate::search::query::SearchQuery;
+use crate::search::semantic::{ModelLoad, SemanticPreview, SemanticSearch};
+use crate::site::Site;
+use crate::superseded;
+use crate::tts::Speaker;
+use crate::ui::styles::{self, Theme};
+use crate::ui::{MIN_HEIGHT, MIN_WIDTH};
+use crate::userdb::UserDb;
+
+/// Lines (or list rows) one notch of the mouse wheel scrolls
+const WHEEL_LINES: usize = 3;
+
+/// Identifies which pane a position is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Question,
+    Erwin,
+}
+
+/// A scrollbar that can be dragged: the question list's or a pane's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scrollbar {
+    List,
+    Pane(Pane),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Id,
+    Date,
+    Score,
+    Views,
+    Answers,
+    Bounty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Order of answers on the show page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnswerSort {
+    /// As scraped (accepted first, then by votes)
+    #[default]
+    Default,
+    /// Highest author reputation first
+    Reputation,
+    /// Best `AnswerQuality` score first
+    Quality,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Page {
+    Index,
+    Show,
+    Tags,
+    /// Functions and keywords used across Erwin's answers
+    Terms,
+    Alerts,
+    /// SQL scratch buffer under the current question
+    Playground,
+    /// Engaged questions due for another look
+    Review,
+    /// The question list narrowed to bookmarked questions
+    Bookmarks,
+    /// Recently viewed questions, latest first
+    Recent,
+    /// Questions hidden from the lists, latest first, to unhide them
+    Hidden,
+    /// One tag's questions by the techniques in Erwin's code
+    Techniques,
+    /// A page drawn by a plugin, or the list of plugin commands
+    Plugin,
+}
+
+impl Page {
+    /// The page as plugins see it (`ctx.page`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Page::Index => "index",
+            Page::Show => "show",
+            Page::Tags => "tags",
+            Page::Terms => "terms",
+            Page::Alerts => "alerts",
+            Page::Playground => "playground",
+            Page::Review => "review",
+            Page::Bookmarks => "bookmarks",
+            Page::Recent => "recent",
+            Page::Hidden => "hidden",
+            Page::Techniques => "techniques",
+            Page::Plugin => "plugin",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    None,
+    Title,
+    Semantic,
+    /// Title and semantic rankings fused
+    Hybrid,
+}
+
+pub struct App {
+    pub should_quit: bool,
+    pub config: Config,
+    /// `[keys]` bindings, applied to every key outside text input
+    pub keymap: Keymap,
+    /// `[theme]` as configured; cycling with Ctrl-t comes back to it
+    pub theme: Theme,
+    /// `[layout]` with the preset picked with Ctrl-l (or `preset`) applied
+    pub layout: Layout,
+    pub layout_preset: Option<String>,
+    pub db: Database,
+    /// Collections etc.; `None` if the user database couldn't be opened
+    pub user_db: Option<UserDb>,
+    /// Bookmarked questions by site and ID, mirrored from the user database
+    pub bookmarks: HashSet<(Site, i64)>,
+    /// Corpus questions the user hid from the lists (of the hidden questions
+    /// in the user database, those matching a corpus question's site and ID)
+    pub hidden: HashSet<i64>,
+    /// Questions opened at some point, by site and ID, marked as read in the list
+    pub viewed: HashSet<(Site, i64)>,
+    /// The embedding model, loaded on first use (`start_model_load`)
+    pub semantic: Option<Arc<SemanticSearch>>,
+    /// The model while it loads in the background
+    pub model_load: Option<ModelLoad>,
+    /// Set once the model failed to load, so it isn't tried again
+    pub model_failed: bool,
+    /// Top results while a semantic query is typed; `None` without the model
+    pub semantic_preview: Option<SemanticPreview>,
+    /// Body match counter for title searches; `None` if it failed to start
+    pub body_search: Option<BodySearch>,
+    /// Title search over `questions`, on nucleo's workers
+    pub title_matcher: BackgroundMatcher,
+    /// Links between the corpus's threads; empty until `link_graph_load` is done
+    pub link_graph: LinkGraph,
+    pub link_graph_load: Option<LinkGraphLoad>,
+    pub questions: Vec<QuestionSummary>,
+    /// Order of the question list and what it was built from, rebuilt by
+    /// `get_sorted_questions` once that changes
+    list_order: RefCell<Option<(OrderKey, Rc<[usize]>)>>,
+    /// `list_stats` of the order it was computed for
+    list_stats: RefCell<Option<(Rc<[usize]>, ListStats)>>,
+    pub page: Page,
+
+    // Terminal dimensions
+    pub width: u16,
+    pub height: u16,
+    /// Last known pointer cell, so hover follows content that scrolls under it
+    pub mouse_position: Option<(u16, u16)>,
+    /// Scrollbar the mouse is dragging, from a press on it until release
+    pub dragged_scrollbar: Option<Scrollbar>,
+
+    // Per-page state
+    pub index: IndexState,
+    pub search: SearchState,
+    pub tags: TagsState,
+    pub terms: TermsState,
+    pub show: ShowState,
+    pub alerts: AlertsState,
+    pub playground: PlaygroundState,
+    pub review: ReviewState,
+    pub recent: RecentState,
+    pub hidden_page: HiddenPageState,
+    pub plugin_page: PluginPageState,
+    pub techniques: TechniquesState,
+    /// Reading time of the open question (`[engaged]`)
+    pub dwell: DwellTracker,
+
+    // History stacks for back and forward navigation
+    pub history: Vec<HistoryEntry>,
+    /// Questions backed out of, latest last; opening another question clears it
+    pub forward_history: Vec<HistoryEntry>,
+    /// List page the open question was reached from, where going back ends up
+    pub list_page: Page,
+
+    /// Description of unsaved edits (e.g. "note on #123"); editors set this while
+    /// their buffer is dirty so navigation asks before discarding it
+    pub unsaved: Option<String>,
+    /// Confirmation modal shown over the current page
+    pub confirm: Option<ConfirmState>,
+    /// `:` command line being typed, shown in place of the status bar
+    pub command: Option<String>,
+    /// `:memory` readout shown over the current page until a key is pressed
+    pub memory_report: Option<MemoryReport>,
+    /// Background jobs that failed this session, oldest first
+    pub errors: Vec<ErrorReport>,
+    /// Error details screen shown over the current page
+    pub error_details: Option<ErrorDetailsState>,
+
+    /// Command for the main loop to run with the terminal suspended
+    pub pending_external: Option<ExternalCommand>,
+    /// Text for the main loop to put on the terminal's clipboard
+    pub pending_clipboard: Option<String>,
+    /// Whether links are clickable OSC 8 hyperlinks (`[terminal] hyperlinks`)
+    pub osc8: bool,
+    /// Links drawn in the last frame, for the main loop to make clickable
+    pub hyperlinks: Vec<Hyperlink>,
+    /// One-off status message (e.g. an error), cleared on the next key press
+    pub flash: Option<String>,
+    /// Text-to-speech reading the current answer, if any
+    pub speaker: Option<Speaker>,
+    /// Thread being fetched from the API after following a link out of the corpus
+    pub fetch: Option<QuestionFetch>,
+    /// `[hooks]` commands, and those still running
+    pub hooks: Hooks,
+    /// Lua plugins and what they registered
+    pub plugins: Plugins,
+
+    /// `--pick`: Enter chooses a question and quits instead of opening it
+    pub pick_mode: bool,
+    pub picked: Option<i64>,
+}
+
+impl App {
+    /// `wake` is called from the title search's workers when they have
+    /// matches for `poll_title_matches`
+    pub fn new(pick_mode: bool, wake: Arc<dyn Fn() + Send + Sync>) -> Result<Self> {
+        let config = Config::load()?;
+        let keymap = Keymap::new(&config.keys)?;
+        let theme = Theme::from_config(&config.theme)?;
+        styles::set_theme(theme);
+        let db = Database::open_default()?;
+        db.set_body_cache_budget(config.memory.budget_mb * MB);
+        let questions = db.get_questions()?;
+        let user_db = UserDb::open().ok();
+        let bookmarks = user_db
+            .as_ref()
+            .and_then(|db| db.get_bookmarks().ok())
+            .unwrap_or_default();
+        let hidden = user_db
+            .as_ref()
+            .and_then(|db| db.get_hidden().ok())
+            .map(|hidden| hidden_in_corpus(&questions, &hidden))
+            .unwrap_or_default();
+        let viewed = user_db
+            .as_ref()
+            .and_then(|db| db.get_viewed_questions().ok())
+            .unwrap_or_default();
+
+        // A newer corpus may have arrived since the last run; collect alert hits for it
+        let mut alerts = AlertsState::default();
+        let mut errors = Vec::new();
+        if let Some(ref user_db) = user_db {
+            if let Err(err) = check_new_content(&db, user_db, &config.alerts) {
+                eprintln!("Alert check failed: {err:#}");
+                errors.push(ErrorReport::new("Alert check", &err));
+            }
+            alerts.unread = user_db.unread_alert_count().unwrap_or(0);
+        }
+        let (plugins, plugin_errors) = Plugins::load(db.path().as_deref());
+        for err in plugin_errors {
+            eprintln!("{err:#}");
+            errors.push(ErrorReport::new("Plugin", &err));
+        }
+        let review = ReviewState {
+            due: user_db.as_ref().map_or(0, count_due),
+            ..ReviewState::default()
+        };
+
+        let body_search = db.path().map(BodySearch::start);
+        let link_graph_load = db.path().map(LinkGraphLoad::start);
+        let title_matcher =
+            BackgroundMatcher::new(questions.iter().map(|q| q.title.as_str()), wake);
+        let osc8 = config.terminal.hyperlinks();
+        let hooks = Hooks::new(config.hooks.clone());
+
+        let layout_preset = config.layout.preset.clone();
+        let layout = config.layout.resolve(layout_preset.as_deref())?;
+        let mut show = ShowState {
+            glossary: Rc::new(Glossary::load()?),
+            ..ShowState::default()
+        };
+        show.apply_layout(&layout);
+        let index = IndexState {
+            detailed: layout.density == Density::Detailed,
+            ..IndexState::default()
+        };
+
+        Ok(Self {
+            should_quit: false,
+            config,
+            keymap,
+            theme,
+            layout,
+            layout_preset,
+            db,
+            user_db,
+            bookmarks,
+            hidden,
+            viewed,
+            semantic: None,
+            model_load: None,
+            model_failed: false,
+            semantic_preview: None,
+            body_search,
+            title_matcher,
+            link_graph: LinkGraph::default(),
+            link_graph_load,
+            questions,
+            list_order: RefCell::new(None),
+            list_stats: RefCell::new(None),
+            page: Page::Index,
+
+            width: 80,
+            height: 24,
+            mouse_position: None,
+            dragged_scrollbar: None,
+
+            index,
+            search: SearchState::default(),
+            tags: TagsState::default(),
+            terms: TermsState::default(),
+            show,
+            alerts,
+            playground: PlaygroundState::default(),
+            review,
+            recent: RecentState::default(),
+            hidden_page: HiddenPageState::default(),
+            plugin_page: PluginPageState::default(),
+            techniques: TechniquesState::default(),
+            dwell: DwellTracker::default(),
+
+            history: Vec::new(),
+            forward_history: Vec::new(),
+            list_page: Page::Index,
+
+            unsaved: None,
+            confirm: None,
+            command: None,
+            memory_report: None,
+            errors,
+            error_details: None,
+
+            pending_external: None,
+            pending_clipboard: None,
+            osc8,
+            hyperlinks: Vec::new(),
+            flash: None,
+            speaker: None,
+            fetch: None,
+            hooks,
+            plugins,
+
+            pick_mode,
+            picked: None,
+        })
+    }
+
+    /// Show the title search's new matches, when its workers wake the loop
+    pub fn poll_title_matches(&mut self) {
+        if let Some(found) = self.title_matcher.poll() {
+            self.receive_title_matches(found);
+        }
+    }
+
+    /// Periodic housekeeping between events
+    pub fn tick(&mut self) {
+        if let Some(err) = self.user_db.as_ref().and_then(UserDb::take_write_error) {
+            self.record_error(ErrorReport::message("User database write", err));
+        }
+
+        if let Some(result) = self.speaker.as_mut().and_then(|s| s.poll()) {
+            self.speaker = None;
+            if let Err(err) = result {
+                self.record_error(ErrorReport::new("Read-aloud", &err));
+            }
+        }
+
+        for err in self.hooks.poll() {
+            self.record_error(ErrorReport::new("Hook", &err));
+        }
+        for err in self.plugins.take_errors() {
+            self.record_error(ErrorReport::new("Plugin", &err));
+        }
+
+        if let Some(result) = self.fetch.as_mut().and_then(|f| f.poll()) {
+            self.finish_fetch(result);
+        }
+
+        if let Some(translation) = self.show.translation.as_mut() {
+            if let Some(result) = translation.translator.as_mut().and_then(|t| t.poll()) {
+                translation.translator = None;
+                match result {
+                    Ok(text) => translation.text = text,
+                    Err(err) => {
+                        self.show.translation = None;
+                        self.record_error(ErrorReport::new("Translation", &err));
+                    }
+                }
+            }
+        }
+
+        if let Some(result) = self.model_load.as_ref().and_then(|load| load.poll()) {
+            self.finish_model_load(result);
+        }
+
+        match self.semantic_preview.as_ref().and_then(|p| p.poll()) {
+            // Only the latest query counts, and only while the modal is still open
+            Some(Ok(preview))
+                if self.search.mode == SearchMode::Semantic
+                    && preview.query == self.search.input =>
+            {
+                self.search.semantic_preview = preview.results;
+            }
+            Some(Ok(_)) | None => {}
+            Some(Err(err)) => {
+                self.semantic_preview = None;
+                self.record_error(ErrorReport::new("Semantic preview", &err));
+            }
+        }
+
+        if let Some(result) = self.link_graph_load.as_ref().and_then(|load| load.poll()) {
+            self.finish_link_graph(result);
+        }
+
+        match self.body_search.as_ref().and_then(|b| b.poll()) {
+            // Results for input typed since are dropped; a newer search is queued
+            Some(Ok(matches)) if matches.query == SearchQuery::parse(&self.search.input).text => {
+                self.search.body_matches = Some(matches.ids);
+                self.search.results_changed();
+            }
+            Some(Ok(_)) | None => {}
+            Some(Err(err)) => {
+                self.body_search = None;
+                self.record_error(ErrorReport::new("Body search", &err));
+            }
+        }
+
+        if let Some(result) = self.playground.run.as_mut().and_then(|r| r.poll()) {
+            self.playground.run = None;
+            self.playground.result_scroll = 0;
+            match result {
+                Ok(result) => self.playground.result = Some(result),
+                Err(err) => self.flash = Some(err.to_string()),
+            }
+        }
+    }
+
+    pub fn handle_resize(&mut self, width: u16, height: u16) {
+        let width_changed = self.width != width;
+        self.width = width;
+        self.height = height;
+
+        // Rebuild content if width changed and we're on show page
+        if width_changed
+            && matches!(self.page, Page::Show | Page::Playground)
+            && self.show.question.is_some()
+        {
+            self.show.rebuild_content(width);
+            if self.show.erwin_pane_visible {
+                self.show.rebuild_erwin_content(width);
+            }
+        }
+        self.refresh_hover();
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        // Nothing to act on but the too-small notice
+        if self.too_small() {
+            self.should_quit = key.code == KeyCode::Char('q');
+            return;
+        }
+        self.flash = None;
+        if self.confirm.is_some() {
+            self.handle_confirm_key(key);
+            return;
+        }
+        if self.memory_report.take().is_some() {
+            return;
+        }
+        if self.error_details.is_some() {
+            self.handle_error_details_key(key);
+            return;
+        }
+        if self.command.is_some() {
+            self.handle_command_key(key);
+            return;
+        }
+        let key = if self.typing() {
+            key
+        } else {
+            match self.keymap.translate(key, self.page) {
+                Some(key) => key,
+                None => return,
+            }
+        };
+        if key.code == KeyCode::Char('t') && key.modifiers == KeyModifiers::CONTROL {
+            self.cycle_theme();
+            return;
+        }
+        if key.code == KeyCode::Char('l')
+            && key.modifiers == KeyModifiers::CONTROL
+            && !self.typing()
+        {
+            self.cycle_layout();
+            return;
+        }
+        // Ctrl-i is Tab to the terminal, so forward is Ctrl-n
+        if key.modifiers == KeyModifiers::CONTROL && !self.typing() {
+            let action = match key.code {
+                KeyCode::Char('o') if self.page == Page::Show => Some(Action::GoBack),
+                KeyCode::Char('n') if !self.forward_history.is_empty() => Some(Action::GoForward),
+                _ => None,
+            };
+            if let Some(action) = action {
+                self.dispatch(action);
+                return;
+            }
+        }
+        if key.code == KeyCode::Char(':') && !self.typing() {
+            self.command = Some(String::new());
+            return;
+        }
+        if key.code == KeyCode::Char('!') && !self.typing() {
+            self.open_error_details();
+            return;
+        }
+
+        let action = match self.page {
+            Page::Index | Page::Bookmarks => self.handle_index_key(key),
+            Page::Show => self.handle_show_key(key),
+            Page::Tags => self.handle_tags_key(key),
+            Page::Terms => self.handle_terms_key(key),
+            Page::Alerts => self.handle_alerts_key(key),
+            Page::Playground => self.handle_playground_key(key),
+            Page::Review => self.handle_review_key(key),
+            Page::Recent => self.handle_recent_key(key),
+            Page::Hidden => self.handle_hidden_key(key),
+            Page::Techniques => self.handle_techniques_key(key),
+            Page::Plugin => self.handle_plugin_page_key(key),
+        };
+        if let Some(action) = action {
+            self.dispatch(action);
+        }
+        self.refresh_hover();
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        // Modals and the command line take keys only
+        if self.too_small()
+            || self.confirm.is_some()
+            || self.memory_report.is_some()
+            || self.error_details.is_some()
+            || self.command.is_some()
+        {
+            return;
+        }
+        if self.handle_scrollbar_mouse(mouse) {
+            return;
+        }
+        let action = match self.page {
+            Page::Index | Page::Bookmarks => self.handle_index_mouse(mouse),
+            Page::Show => {
+                self.handle_show_mouse(mouse);
+                None
+            }
+            _ => None,
+        };
+        if let Some(action) = action {
+            self.dispatch(action);
+        }
+    }
+
+    /// Switch to the next built-in theme, the configured one with its overrides
+    fn cycle_theme(&mut self) {
+        let current = styles::theme().name;
+        let index = Theme::BUILTIN
+            .iter()
+            .position(|theme| theme.name == current)
+            .unwrap_or(0);
+        let next = Theme::BUILTIN[(index + 1) % Theme::BUILTIN.len()];
+        let next = if next.name == self.theme.name {
+            self.theme
+        } else {
+            next
+        };
+        styles::set_theme(next);
+
+        // The show page's content is rendered ahead, colors included
+        if self.show.question.is_some() {
+            self.show.rebuild_content(self.width);
+            if self.show.erwin_pane_visible {
+                self.show.rebuild_erwin_content(self.width);
+            }
+        }
+        self.flash = Some(format!("Theme: {}", next.name));
+    }
+
+    /// Switch to the next `[layout.presets]` entry, after the last back to
+    /// `[layout]` as written
+    fn cycle_layout(&mut self) {
+        let presets = &self.config.layout.presets;
+        if presets.is_empty() {
+            self.flash = Some("No layout presets in [layout.presets]".to_string());
+            return;
+        }
+        let next = match &self.layout_preset {
+            None => presets.keys().next(),
+            Some(current) => presets.keys().find(|name| *name > current),
+        }
+        .cloned();
+        self.set_layout(next);
+    }
+
+    /// Switch to a `[layout.presets]` entry, or `[layout]` as written
+    fn set_layout(&mut self, preset: Option<String>) {
+        let layout = match self.config.layout.resolve(preset.as_deref()) {
+            Ok(layout) => layout,
+            Err(err) => {
+                self.flash = Some(format!("{err:#}"));
+                return;
+            }
+        };
+        self.flash = Some(format!(
+            "Layout: {}",
+            preset.as_deref().unwrap_or("default")
+        ));
+        self.layout = layout;
+        self.layout_preset = preset;
+
+        self.index.detailed = self.layout.density == Density::Detailed;
+        self.adjust_index_scroll();
+        self.show.apply_layout(&self.layout);
+        if self.show.question.is_some() {
+            self.show.rebuild_content(self.width);
+            if self.show.erwin_pane_visible {
+                self.show.rebuild_erwin_content(self.width);
+            }
+        }
+    }
+
+    /// Whether the terminal is below the smallest size the pages are drawn in
+    pub fn too_small(&self) -> bool {
+        self.width < MIN_WIDTH || self.height < MIN_HEIGHT
+    }
+
+    /// Whether keys go into text (a search or filter prompt, the playground
+    /// buffer, a mark name) rather than to `[keys]` actions
+    fn typing(&self) -> bool {
+        match self.page {
+            Page::Index | Page::Bookmarks => self.search.mode != SearchMode::None,
+            Page::Tags => self.tags.filter_active,
+            Page::Show => self.show.pending_mark.is_some() || self.show.link_hints.is_some(),
+            Page::Playground => true,
+            Page::Terms
+            | Page::Alerts
+            | Page::Review
+            | Page::Recent
+            | Page::Hidden
+            | Page::Techniques
+            | Page::Plugin => false,
+        }
+    }
+
+    /// Ask the user before running an action (destructive or discarding edits)
+    pub fn request_confirm(&mut self, message: String, action: Action) {
+        self.confirm = Some(ConfirmState { message, action });
+    }
+
+    fn handle_confirm_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                if let Some(confirm) = self.confirm.take() {
+                    // Confirming means the unsaved edits may be discarded
+                    self.unsaved = None;
+                    self.dispatch(confirm.action);
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc | KeyCode::Char('q') => {
+                self.confirm = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Apply a cross-page action
+    pub fn dispatch(&mut self, action: Action) {
+        if action.leaves_page() {
+            if let Some(ref what) = self.unsaved {
+                let message = format!("Discard unsaved {what}?");
+                self.request_confirm(message, action);
+                return;
+            }
+        }
+
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::OpenQuestion(question_id) => self.navigate_to_question(question_id, None),
+            Action::OpenAnswer {
+                question_id,
+                answer_id,
+            } => {
+                self.navigate_to_question(question_id, None);
+                self.show.show_answer(answer_id, self.width);
+            }
+            Action::OpenFetched {
+                site,
+                question_id,
+                answer_id,
+            } => {
+                self.navigate_to_question(question_id, Some(site));
+                if let Some(answer_id) = answer_id {
+                    self.show.show_answer(answer_id, self.width);
+                }
+            }
+            Action::GoBack => self.go_back(),
+            Action::GoForward => self.go_forward(),
+            Action::FollowLink(link) => match self.resolve_link(&link) {
+                Action::FetchQuestion(target) => self.offer_fetch(target),
+                action => self.dispatch(action),
+            },
+            Action::OpenUrl(url) => {
+                let _ = open::that(url);
+            }
+            Action::FetchQuestion(target) => self.start_fetch(target),
+            Action::SwitchPage(page) => self.page = page,
+            Action::RunExternal(command) => self.pending_external = Some(command),
+            Action::HideQuestion { site, question_id } => self.set_hidden(site, question_id, true),
+            Action::Pick(question_id) => {
+                self.picked = Some(question_id);
+                self.should_quit = true;
+            }
+        }
+    }
+
+    /// Local navigation if the link is a question or answer we have (in the
+    /// corpus or fetched earlier), a fetch for other questions, otherwise the browser
+    fn resolve_link(&self, link: &Link) -> Action {
+        let Some(site) = link.site else {
+            return Action::OpenUrl(link.url.clone());
+        };
+        if let Some(qid) = link.question_id {
+            if self.questions.iter().any(|q| q.id == qid && q.site == site) {
+                return match link.answer_id {
+                    Some(answer_id) => Action::OpenAnswer {
+                        question_id: qid,
+                        answer_id,
+                    },
+                    None => Action::OpenQuestion(qid),
+                };
+            }
+        }
+        if let Some(answer_id) = link.answer_id {
+            if let Ok(Some(qid)) = self.db.get_question_id_for_answer(site, answer_id) {
+                return Action::OpenAnswer {
+                    question_id: qid,
+                    answer_id,
+                };
+            }
+        }
+        let target = FetchTarget {
+            site,
+            question_id: link.question_id,
+            answer_id: link.answer_id,
+        };
+        if let Some(action) = self.fetched_action(target) {
+            return action;
+        }
+        if link.question_id.is_some() || link.answer_id.is_some() {
+            return Action::FetchQuestion(target);
+        }
+        Action::OpenUrl(link.url.clone())
+    }
+
+    /// Open a corpus question, or with `fetched`, the thread fetched from that
+    /// site
+    fn navigate_to_question(&mut self, question_id: i64, fetched: Option<Site>) {
+        self.forward_history.clear();
+        match self.page {
+            Page::Show => self.history.push(self.show.history_entry()),
+            Page::Playground => {}
+            Page::Bookmarks | Page::Recent | Page::Hidden | Page::Techniques | Page::Plugin => {
+                self.list_page = self.page
+            }
+            _ => self.list_page = Page::Index,
+        }
+
+        let (question, answers, comments, answer_comments) = match fetched {
+            // Not in the corpus: a thread fetched from the API
+            Some(site) => match self.fetched_thread(site, question_id) {
+                Some(thread) => (
+                    Some(thread.question),
+                    thread.answers,
+                    thread.comments,
+                    thread.answer_comments,
+                ),
+                None => (None, Vec::new(), Vec::new(), Vec::new()),
+            },
+            None => {
+                let answers = self.db.get_answers(question_id).unwrap_or_default();
+                // Pre-fetch all answer comments
+                let answer_comments = answers
+                    .iter()
+                    .map(|a| self.db.get_answer_comments(a.id).unwrap_or_default())
+                    .collect();
+                let comments = self
+                    .db
+                    .get_question_comments(question_id)
+                    .unwrap_or_default();
+                let question = self.db.get_question(question_id).ok().flatten();
+                (question, answers, comments, answer_comments)
+            }
+        };
+
+        let site = match (fetched, &question) {
+            (Some(site), _) => site,
+            (None, Some(question)) => question.summary.site,
+            (None, None) => Site::default(),
+        };
+        if let Some(ref user_db) = self.user_db {
+            if let Err(err) = user_db.record_view(site, question_id) {
+                self.flash = Some(format!("Failed to record history: {err:#}"));
+            }
+            self.viewed.insert((site, question_id));
+        }
+
+        // Found at ingest for the corpus, otherwise looked for now
+        let superseded = match question {
+            Some(ref question) => match self.db.get_superseded(question_id) {
+                Ok(Some(found)) if fetched.is_none() => found,
+                _ => superseded::detect(question.summary.site, &answers, &answer_comments),
+            },
+            None => HashMap::new(),
+        };
+        // Only threads without answers need their duplicate original, and
+        // only the corpus has one
+        let duplicate = if answers.is_empty() && fetched.is_none() {
+            self.db.get_duplicate_target(question_id).ok().flatten()
+        } else {
+            None
+        };
+        let copy = match fetched {
+            Some(_) => None,
+            None => self.db.get_cross_site_copy(question_id).ok().flatten(),
+        };
+
+        // Answer order, rep deltas, hidden comments and the pane split are
+        // preferences that carry over between questions; a presentation only
+        // carries over to its own next question
+        let presentation = self
+            .show
+            .presentation
+            .take()
+            .filter(|p| p.question_ids.get(p.index) == Some(&question_id));
+        self.show = ShowState {
+            answer_sort: self.show.answer_sort,
+            rep_deltas: self.show.rep_deltas,
+            hide_comments: self.show.hide_comments,
+            split_percent: self.show.split_percent,
+            scrolloff: self.show.scrolloff,
+            scrollbars: self.show.scrollbars,
+            dual_pane_width: self.show.dual_pane_width,
+            content_width: self.show.content_width,
+            glossary: Rc::clone(&self.show.glossary),
+            presentation,
+            question_id,
+            stale_warning: question.as_ref().and_then(|q| {
+                let summary = &q.summary;
+                let age = clock::years_since(summary.creation_date);
+                self.config.stale.warning(&summary.tags, age)
+            }),
+            question,
+            answers,
+            comments,
+            answer_comments,
+            fetched,
+            copy,
+            duplicate,
+            superseded,
+            marks: self
+                .user_db
+                .as_ref()
+                .and_then(|db| db.get_marks(site, question_id).ok())
+                .unwrap_or_default(),
+            ..ShowState::default()
+        };
+        self.page = Page::Show;
+
+        // Build the content
+        self.show.sort_answers();
+        self.load_linked_questions();
+        self.show.rebuild_content(self.width);
+        self.fit_memory_budget();
+
+        if let Some(ref question) = self.show.question {
+            let event = HookEvent::Open {
+                question: (&question.summary).into(),
+            };
+            if let Err(err) = self.hooks.fire(&event) {
+                self.record_error(ErrorReport::new("Hook", &err));
+            }
+        }
+    }
+
+    fn go_back(&mut self) {
+        let mut forward = std::mem::take(&mut self.forward_history);
+        if self.page == Page::Show {
+            forward.push(self.show.history_entry());
+        }
+        if let Some(prev) = self.history.pop() {
+            self.navigate_to_question(prev.question_id, prev.fetched);
+            self.history.pop(); // Remove the entry navigate_to_question just added
+            self.show.restore_position(&prev);
+        } else {
+            self.page = self.list_page;
+            // Unbookmarking the question may have shortened the list
+            let max = self.visible_questions_count().saturating_sub(1);
+            self.index.selected = self.index.selected.min(max);
+            self.adjust_index_scroll();
+        }
+        self.forward_history = forward;
+    }
+
+    /// Reopen the question last backed out of
+    fn go_forward(&mut self) {
+        let mut forward = std::mem::take(&mut self.forward_history);
+        if let Some(next) = forward.pop() {
+            self.navigate_to_question(next.question_id, next.fetched);
+            self.show.restore_position(&next);
+        }
+        self.forward_history = forward;
+    }
+}
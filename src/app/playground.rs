@@ -0,0 +1,82 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::{Action, App, Page};
+use crate::playground::QueryRun;
+
+impl App {
+    pub(super) fn handle_playground_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let page = (self.height / 4).max(1) as usize;
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let playground = &mut self.playground;
+
+        match key.code {
+            KeyCode::Esc => return Some(Action::SwitchPage(Page::Show)),
+            KeyCode::F(5) => self.run_playground_query(),
+            KeyCode::Char('r') if ctrl => self.run_playground_query(),
+            KeyCode::Char('l') if ctrl => playground.clear(),
+            KeyCode::Char('d') if ctrl => playground.result_scroll += page,
+            KeyCode::Char('u') if ctrl => {
+                playground.result_scroll = playground.result_scroll.saturating_sub(page);
+            }
+            KeyCode::PageDown => *self.show.focused_scroll_mut() += page,
+            KeyCode::PageUp => {
+                let scroll = self.show.focused_scroll_mut();
+                *scroll = scroll.saturating_sub(page);
+            }
+            KeyCode::Char(c) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                playground.insert(c.encode_utf8(&mut [0; 4]));
+            }
+            KeyCode::Tab => playground.insert("    "),
+            KeyCode::Enter => playground.newline(),
+            KeyCode::Backspace => playground.backspace(),
+            KeyCode::Delete => playground.delete(),
+            KeyCode::Left => playground.move_left(),
+            KeyCode::Right => playground.move_right(),
+            KeyCode::Up => playground.move_vertical(-1),
+            KeyCode::Down => playground.move_vertical(1),
+            KeyCode::Home => playground.move_home(),
+            KeyCode::End => playground.move_end(),
+            _ => {}
+        }
+        None
+    }
+
+    /// Bracketed paste; only the playground takes multi-line text
+    pub fn handle_paste(&mut self, text: &str) {
+        if let Some(ref mut command) = self.command {
+            // One line: a pasted URL for a jump
+            command.push_str(text.lines().next().unwrap_or_default().trim());
+        } else if self.page == Page::Playground {
+            self.playground.insert(text);
+        }
+    }
+
+    /// Open the playground under the current question, seeded from the code
+    /// block on screen
+    pub(super) fn open_playground(&mut self) -> Option<Action> {
+        if self.config.playground.command.is_none() {
+            self.flash = Some("No playground connection set ([playground] in config)".into());
+            return None;
+        }
+        if let Some(block) = self.show.visible_code_block(self.height) {
+            let code = block.code.clone();
+            self.playground.seed(&code);
+        }
+        self.playground.ensure_line();
+        Some(Action::SwitchPage(Page::Playground))
+    }
+
+    fn run_playground_query(&mut self) {
+        let Some(ref command) = self.config.playground.command else {
+            return;
+        };
+        let sql = self.playground.sql();
+        if sql.trim().is_empty() {
+            return;
+        }
+        match QueryRun::start(command, sql) {
+            Ok(run) => self.playground.run = Some(run),
+            Err(err) => self.flash = Some(err.to_string()),
+        }
+    }
+}
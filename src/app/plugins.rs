@@ -0,0 +1,140 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{Action, App, Page};
+use crate::plugins::{Context, PageLine, Request};
+use crate::report::ErrorReport;
+
+impl App {
+    /// What plugin callbacks get as `ctx`: the page, and the question open
+    /// or selected there
+    pub fn plugin_context(&self) -> Context<'_> {
+        let question = match self.page {
+            Page::Show => self.show.question.as_ref().map(|q| &q.summary),
+            Page::Index | Page::Bookmarks => self.get_selected_question(),
+            _ => None,
+        };
+        Context {
+            page: self.page.name(),
+            question,
+        }
+    }
+
+    /// Run `:name args` if a plugin registered `name` as a command or page
+    pub(super) fn run_plugin_command(&mut self, command: &str) -> bool {
+        let (name, args) = command
+            .split_once(char::is_whitespace)
+            .map_or((command, ""), |(name, args)| (name, args.trim()));
+        if self.plugins.has_page(name) {
+            self.open_plugin_page(name.to_string());
+            return true;
+        }
+        if !self.plugins.has_command(name) {
+            return false;
+        }
+        let result = self.plugins.run_command(name, args, &self.plugin_context());
+        match result {
+            Ok(message) => {
+                if message.is_some() {
+                    self.flash = message;
+                }
+            }
+            Err(err) => self.record_error(ErrorReport::new("Plugin", &err)),
+        }
+        self.apply_plugin_requests();
+        true
+    }
+
+    fn open_plugin_page(&mut self, name: String) {
+        let result = self.plugins.render_page(&name, &self.plugin_context());
+        self.apply_plugin_requests();
+        let (title, lines) = match result {
+            Ok(page) => page,
+            Err(err) => {
+                self.record_error(ErrorReport::new("Plugin", &err));
+                return;
+            }
+        };
+        // Rendering it again keeps the place
+        let (selected, scroll) = match self.plugin_page.name {
+            Some(ref open) if *open == name && self.page == Page::Plugin => {
+                (self.plugin_page.selected, self.plugin_page.scroll)
+            }
+            _ => (0, 0),
+        };
+        self.plugin_page.name = Some(name);
+        self.plugin_page.title = title;
+        self.plugin_page.selected = selected.min(lines.len().saturating_sub(1));
+        self.plugin_page.scroll = scroll;
+        self.plugin_page.lines = lines;
+        self.page = Page::Plugin;
+    }
+
+    /// `:plugins`: the plugins' commands and pages
+    pub(super) fn open_plugin_list(&mut self) {
+        if self.plugins.is_empty() {
+            self.flash =
+                Some("No plugins (put *.lua files in <config dir>/erwindb/plugins)".into());
+            return;
+        }
+        let lines = self
+            .plugins
+            .commands()
+            .into_iter()
+            .map(|(name, plugin, label)| PageLine {
+                text: format!(":{name:<16} {label}  ({plugin})"),
+                question: None,
+            })
+            .collect();
+        self.plugin_page = super::PluginPageState {
+            name: None,
+            title: "Plugin commands".to_string(),
+            lines,
+            ..Default::default()
+        };
+        self.page = Page::Plugin;
+    }
+
+    pub(super) fn handle_plugin_page_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.code == KeyCode::Char('r') {
+            if let Some(name) = self.plugin_page.name.clone() {
+                self.open_plugin_page(name);
+            }
+            return None;
+        }
+        let visible_rows = self.height.saturating_sub(2) as usize;
+        let page = &mut self.plugin_page;
+        let max = page.lines.len().saturating_sub(1);
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => action = Some(Action::SwitchPage(Page::Index)),
+            KeyCode::Char('j') | KeyCode::Down => page.selected = (page.selected + 1).min(max),
+            KeyCode::Char('k') | KeyCode::Up => page.selected = page.selected.saturating_sub(1),
+            KeyCode::Char('g') => page.selected = 0,
+            KeyCode::Char('G') => page.selected = max,
+            KeyCode::Enter => {
+                if let Some(id) = page.lines.get(page.selected).and_then(|l| l.question) {
+                    action = Some(Action::OpenQuestion(id));
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if page.selected < page.scroll {
+            page.scroll = page.selected;
+        } else if visible_rows > 0 && page.selected >= page.scroll + visible_rows {
+            page.scroll = page.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    /// Do what the last plugin callbacks asked for
+    fn apply_plugin_requests(&mut self) {
+        for request in self.plugins.take_requests() {
+            match request {
+                Request::Flash(text) => self.flash = Some(text),
+                Request::Open(id) => self.dispatch(Action::OpenQuestion(id)),
+            }
+        }
+    }
+}
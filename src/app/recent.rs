@@ -0,0 +1,63 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{Action, App, Page};
+
+/// Distinct questions listed on the Recent page
+const RECENT_LIMIT: usize = 500;
+
+impl App {
+    pub(super) fn handle_recent_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let visible_rows = self.height.saturating_sub(2) as usize;
+        let max = self.recent.views.len().saturating_sub(1);
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => action = Some(Action::SwitchPage(Page::Index)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.recent.selected = (self.recent.selected + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.recent.selected = self.recent.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.recent.selected = 0,
+            KeyCode::Char('G') => self.recent.selected = max,
+            KeyCode::Enter => {
+                if let Some(view) = self.recent.views.get(self.recent.selected) {
+                    action = self.open_action(view.site, view.question_id);
+                    if action.is_none() {
+                        self.flash = Some("Not in this corpus".to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if self.recent.selected < self.recent.scroll {
+            self.recent.scroll = self.recent.selected;
+        } else if visible_rows > 0 && self.recent.selected >= self.recent.scroll + visible_rows {
+            self.recent.scroll = self.recent.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    /// Load the reading history and switch to the Recent page
+    pub(super) fn open_recent(&mut self) -> Option<Action> {
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("User database is unavailable".to_string());
+            return None;
+        };
+        match user_db.get_recent_views(RECENT_LIMIT) {
+            Ok(mut views) => {
+                views.retain(|view| !self.is_hidden(view.site, view.question_id));
+                self.recent.views = views;
+            }
+            Err(err) => {
+                self.flash = Some(format!("{err:#}"));
+                return None;
+            }
+        }
+        self.recent.selected = 0;
+        self.recent.scroll = 0;
+        Some(Action::SwitchPage(Page::Recent))
+    }
+}
@@ -0,0 +1,106 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{Action, App, Page};
+use crate::clock;
+use crate::userdb::UserDb;
+
+impl App {
+    pub(super) fn handle_review_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let visible_rows = self.height.saturating_sub(2) as usize;
+        let max = self.review.questions.len().saturating_sub(1);
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => action = Some(Action::SwitchPage(Page::Index)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.review.selected = (self.review.selected + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.review.selected = self.review.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.review.selected = 0,
+            KeyCode::Char('G') => self.review.selected = max,
+            // Opening a question counts as reviewing it
+            KeyCode::Enter => {
+                if let Some(question) = self.review.questions.get(self.review.selected) {
+                    let (site, question_id) = (question.site, question.question_id);
+                    action = self.open_action(site, question_id);
+                    if action.is_none() {
+                        self.flash = Some("Not in this corpus".to_string());
+                    } else if let Some(ref user_db) = self.user_db {
+                        if let Err(err) = user_db.mark_reviewed(site, question_id) {
+                            self.flash = Some(format!("{err:#}"));
+                        }
+                        self.review.due = count_due(user_db);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if self.review.selected < self.review.scroll {
+            self.review.scroll = self.review.selected;
+        } else if visible_rows > 0 && self.review.selected >= self.review.scroll + visible_rows {
+            self.review.scroll = self.review.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    /// Load the engaged questions and switch to the Review page
+    pub(super) fn open_review(&mut self) -> Option<Action> {
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("User database is unavailable".to_string());
+            return None;
+        };
+        if self.config.engaged.minutes == 0 {
+            self.flash = Some("Reading time isn't tracked ([engaged] minutes in config)".into());
+            return None;
+        }
+        match user_db.get_engaged_questions() {
+            Ok(mut questions) => {
+                questions.retain(|q| !self.is_hidden(q.site, q.question_id));
+                self.review.questions = questions;
+            }
+            Err(err) => {
+                self.flash = Some(format!("{err:#}"));
+                return None;
+            }
+        }
+        self.review.selected = 0;
+        self.review.scroll = 0;
+        Some(Action::SwitchPage(Page::Review))
+    }
+
+    /// Count reading time while a question is open; when it's left (or the app
+    /// quits), add the time to the user database. Called after every key press
+    /// and mouse event.
+    pub fn track_dwell(&mut self) {
+        let minutes = self.config.engaged.minutes;
+        if minutes == 0 {
+            return;
+        }
+        let open = matches!(self.page, Page::Show | Page::Playground)
+            .then_some((self.show.site(), self.show.question_id))
+            .filter(|_| !self.should_quit);
+        let Some(((site, question_id), time)) = self.dwell.observe(open) else {
+            return;
+        };
+        if let Some(ref user_db) = self.user_db {
+            let engaged_seconds = i64::from(minutes) * 60;
+            let seconds = time.as_secs() as i64;
+            if let Err(err) = user_db.add_reading_time(site, question_id, seconds, engaged_seconds)
+            {
+                self.flash = Some(format!("Failed to save reading time: {err:#}"));
+            }
+        }
+    }
+}
+
+/// Engaged questions due for review now
+pub(super) fn count_due(user_db: &UserDb) -> usize {
+    let now = clock::now().timestamp();
+    user_db
+        .get_engaged_questions()
+        .map(|questions| questions.iter().filter(|q| q.due_at() <= now).count())
+        .unwrap_or(0)
+}
@@ -0,0 +1,102 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use super::{App, Page, Pane, Scrollbar};
+
+impl App {
+    /// A press on a scrollbar moves its thumb to the pointer, and dragging
+    /// keeps it there until the button is released; returns whether the
+    /// event went to a scrollbar
+    pub(super) fn handle_scrollbar_mouse(&mut self, mouse: MouseEvent) -> bool {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.dragged_scrollbar = self.scrollbar_at(mouse.column, mouse.row);
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {}
+            MouseEventKind::Up(MouseButton::Left) => {
+                return self.dragged_scrollbar.take().is_some();
+            }
+            _ => return false,
+        }
+        let Some(scrollbar) = self.dragged_scrollbar else {
+            return false;
+        };
+        self.drag_scrollbar(scrollbar, mouse.row);
+        true
+    }
+
+    /// Screen rows the scrollbars span: the list or the panes, between the
+    /// header(s) and the status bar (and the list's stats footer)
+    fn scrollbar_rows(&self) -> (u16, u16) {
+        let (top, below) = match self.page {
+            Page::Show => (1, 1),
+            _ => (2, self.index.chrome_height() - 2),
+        };
+        (top, self.height.saturating_sub(below).max(top))
+    }
+
+    /// The scrollbar drawn at a screen cell, if its content overflows
+    fn scrollbar_at(&self, col: u16, row: u16) -> Option<Scrollbar> {
+        let (top, bottom) = self.scrollbar_rows();
+        if row < top || row >= bottom {
+            return None;
+        }
+        let scrollbar = match self.page {
+            Page::Index | Page::Bookmarks if self.layout.scrollbars => {
+                (col + 1 == self.width).then_some(Scrollbar::List)?
+            }
+            Page::Show if self.show.has_scrollbars() => {
+                let split = self.show.is_split(self.width);
+                if split && col + 1 == self.show.split_pos(self.width) {
+                    Scrollbar::Pane(Pane::Question)
+                } else if col + 1 == self.show.panes_width(self.width) {
+                    Scrollbar::Pane(if split { Pane::Erwin } else { Pane::Question })
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+        (self.scroll_range(scrollbar) > 0).then_some(scrollbar)
+    }
+
+    /// The furthest position a scrollbar reaches: the last scroll offset, or
+    /// the last question
+    fn scroll_range(&self, scrollbar: Scrollbar) -> usize {
+        let lines = match scrollbar {
+            Scrollbar::List => return self.visible_questions_count().saturating_sub(1),
+            Scrollbar::Pane(Pane::Question) => self.show.rendered_content.len(),
+            Scrollbar::Pane(Pane::Erwin) => self.show.rendered_erwin_content.len(),
+        };
+        let (top, bottom) = self.scrollbar_rows();
+        lines.saturating_sub(usize::from(bottom - top))
+    }
+
+    /// Scroll to the position a screen row stands for along the scrollbar,
+    /// its first row the top and its last row the end
+    fn drag_scrollbar(&mut self, scrollbar: Scrollbar, row: u16) {
+        let (top, bottom) = self.scrollbar_rows();
+        let last_row = bottom.saturating_sub(1).max(top);
+        let along = usize::from(row.clamp(top, last_row) - top);
+        let max = self.scroll_range(scrollbar);
+        let position = match usize::from(last_row - top) {
+            0 => 0,
+            rows => (along * max + rows / 2) / rows,
+        };
+
+        match scrollbar {
+            Scrollbar::List => {
+                self.index.selected = position;
+                self.adjust_index_scroll();
+            }
+            Scrollbar::Pane(Pane::Question) => {
+                self.show.focused_link_index = None;
+                self.show.scroll_offset = position;
+            }
+            Scrollbar::Pane(Pane::Erwin) => {
+                self.show.focused_erwin_link_index = None;
+                self.show.erwin_scroll_offset = position;
+            }
+        }
+        self.refresh_hover();
+    }
+}
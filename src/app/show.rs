@@ -0,0 +1,901 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use super::{
+    Action, AnswerSort, App, LinkHints, Page, Pane, Presentation, TranslationState, WHEEL_LINES,
+};
+use crate::external::ExternalCommand;
+use crate::html::{html_to_plain_text, is_erwin, PostNode};
+use crate::links::{LinkGraph, LinkedQuestion};
+use crate::report::{ErrorReport, RenderReport, ISSUES_URL};
+use crate::sanitize::{element_html, sanitize_html};
+use crate::translate::Translator;
+use crate::tts::Speaker;
+use crate::ui::{ERWIN_PANE_BORDER, QUESTION_PANE_PADDING};
+use anyhow::Result;
+
+/// Percent the split between the panes moves per key press
+const SPLIT_STEP: i16 = 5;
+
+impl App {
+    pub(super) fn handle_show_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if self.show.focus && !is_scroll_key(key) {
+            self.set_focus_mode(false);
+            return None;
+        }
+        let page = self.show.visible_height(self.height);
+        let show = &mut self.show;
+        if let Some(ref mut translation) = show.translation {
+            if !handle_translation_key(translation, key, page) {
+                show.translation = None;
+            }
+            return None;
+        }
+        if let Some(selected) = show.outline_selected {
+            self.handle_outline_key(selected, key);
+            return None;
+        }
+        if show.link_hints.is_some() {
+            return self.handle_link_hint_key(key);
+        }
+        if let Some(prefix) = show.pending_mark.take() {
+            if let KeyCode::Char(mark @ 'a'..='z') = key.code {
+                if prefix == 'm' {
+                    self.set_mark(mark);
+                } else {
+                    self.jump_to_mark(mark);
+                }
+            }
+            return None;
+        }
+
+        // A glossary definition closes on the next key; w/W move on to another term
+        let focused_term = show.focused_term.take();
+
+        match key.code {
+            // Close the definition or clear the focused pane's link first, then go back
+            KeyCode::Esc if focused_term.is_some() => {}
+            KeyCode::Esc if show.focused_pane_link_index().is_some() => {
+                *show.focused_link_mut() = None;
+            }
+            KeyCode::Esc | KeyCode::Char('q') if show.presentation.is_some() => {
+                self.end_presentation();
+            }
+            KeyCode::Right | KeyCode::Char(']') if show.presentation.is_some() => {
+                self.step_presentation(true);
+            }
+            KeyCode::Left | KeyCode::Char('[') if show.presentation.is_some() => {
+                self.step_presentation(false);
+            }
+            KeyCode::Esc => return Some(Action::GoBack),
+            KeyCode::Char('q') | KeyCode::Char('b') => {
+                return Some(Action::GoBack);
+            }
+            KeyCode::Enter if self.pick_mode => {
+                return Some(Action::Pick(show.question_id));
+            }
+            KeyCode::Enter => {
+                // Jump to the duplicate original of an answerless thread, or
+                // to the newer answer a superseded one defers to
+                if let Some(target) = show.duplicate.as_ref() {
+                    return Some(match target.local_title {
+                        Some(_) => Action::OpenQuestion(target.question_id),
+                        None => Action::OpenUrl(target.url()),
+                    });
+                }
+                return show.superseded_link().cloned().map(Action::FollowLink);
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                *show.focused_link_mut() = None;
+                *show.focused_scroll_mut() += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                *show.focused_link_mut() = None;
+                let scroll = show.focused_scroll_mut();
+                *scroll = scroll.saturating_sub(1);
+            }
+            KeyCode::Char(' ') | KeyCode::Char('d') => {
+                *show.focused_link_mut() = None;
+                *show.focused_scroll_mut() += page;
+            }
+            KeyCode::Char('u') => {
+                *show.focused_link_mut() = None;
+                let scroll = show.focused_scroll_mut();
+                *scroll = scroll.saturating_sub(page);
+            }
+            KeyCode::Char('g') => {
+                *show.focused_link_mut() = None;
+                *show.focused_scroll_mut() = 0;
+            }
+            KeyCode::Char('G') => {
+                *show.focused_link_mut() = None;
+                // Scroll to end - will be clamped in view
+                *show.focused_scroll_mut() = usize::MAX / 2;
+            }
+            KeyCode::Char('e') => self.next_erwin_answer(),
+            KeyCode::Char('E') => self.prev_erwin_answer(),
+            KeyCode::Char('a') => self.jump_to_accepted(),
+            KeyCode::Char('L') => self.jump_to_linked(),
+            KeyCode::Char('f') => self.start_link_hints(),
+            KeyCode::Char(c @ ('n' | 'p')) => self.jump_to_answer(c == 'n'),
+            // Move the split between the panes
+            KeyCode::Char(c @ ('<' | '>')) if show.is_split(self.width) => {
+                let delta = if c == '<' { -SPLIT_STEP } else { SPLIT_STEP };
+                if show.resize_split(delta) {
+                    show.rebuild_content(self.width);
+                    show.rebuild_erwin_content(self.width);
+                }
+            }
+            KeyCode::Char('o') => {
+                // If a link is focused, open that; otherwise open the question
+                if let Some(link) = show.get_focused_link() {
+                    return Some(Action::FollowLink(link.clone()));
+                } else if let Some(ref question) = show.question {
+                    let summary = &question.summary;
+                    return Some(Action::OpenUrl(summary.site.question_url(summary.id)));
+                }
+            }
+            KeyCode::Char(prefix @ ('m' | '\'')) => show.pending_mark = Some(prefix),
+            KeyCode::Char('B') => {
+                let (site, question_id) = (show.site(), show.question_id);
+                self.toggle_bookmark(site, question_id);
+            }
+            KeyCode::Char('M') => {
+                // Jump to the migrated / cross-posted copy on the other site
+                return show
+                    .copy
+                    .as_ref()
+                    .map(|c| Action::OpenQuestion(c.question_id));
+            }
+            KeyCode::Char('v') => {
+                let Some(block) = show.visible_code_block(self.height) else {
+                    self.flash = Some("No code block on screen".to_string());
+                    return None;
+                };
+                return Some(Action::RunExternal(ExternalCommand {
+                    command: self.config.previewer.code_command(block.lang.as_deref()),
+                    input: block.code.clone(),
+                }));
+            }
+            KeyCode::Char('V') => {
+                let html = show.current_post_html()?;
+                return Some(Action::RunExternal(ExternalCommand {
+                    command: self.config.previewer.post.clone(),
+                    input: html2text::from_read(sanitize_html(html).as_bytes(), 100)
+                        .unwrap_or_default(),
+                }));
+            }
+            // Toggle reading the current post aloud; dropping the speaker stops it
+            KeyCode::Char('r') => {
+                if self.speaker.take().is_some() {
+                    return None;
+                }
+                let text = html_to_plain_text(show.current_post_html()?);
+                match Speaker::start(&self.config.tts.command, text) {
+                    Ok(speaker) => self.speaker = Some(speaker),
+                    Err(err) => self.flash = Some(err.to_string()),
+                }
+            }
+            KeyCode::Char('t') => {
+                let Some(ref command) = self.config.translate.command else {
+                    self.flash = Some("No translation command set ([translate] in config)".into());
+                    return None;
+                };
+                let text = html_to_plain_text(show.current_post_html()?);
+                match Translator::start(command, text) {
+                    Ok(translator) => {
+                        show.translation = Some(TranslationState {
+                            translator: Some(translator),
+                            ..TranslationState::default()
+                        })
+                    }
+                    Err(err) => self.flash = Some(err.to_string()),
+                }
+            }
+            KeyCode::Char('s') => {
+                // Keep the answer being read at the top after reordering
+                let current = show.answer_at_scroll();
+                show.answer_sort = match show.answer_sort {
+                    AnswerSort::Default => AnswerSort::Reputation,
+                    AnswerSort::Reputation => AnswerSort::Quality,
+                    AnswerSort::Quality => AnswerSort::Default,
+                };
+                show.sort_answers();
+                show.rebuild_content(self.width);
+                if let Some(answer_id) = current {
+                    show.scroll_to_answer(answer_id);
+                }
+            }
+            KeyCode::Char('z') => {
+                let Some(answer_id) = show.answer_at_scroll() else {
+                    self.flash = Some("Scroll to an answer to fold it".to_string());
+                    return None;
+                };
+                if !show.collapsed.remove(&answer_id) {
+                    show.collapsed.insert(answer_id);
+                }
+                show.left_pane_focused = true;
+                show.focused_link_index = None;
+                show.rebuild_content(self.width);
+                show.scroll_to_answer(answer_id);
+            }
+            KeyCode::Char('Z') => {
+                let current = show.answer_at_scroll();
+                if show.collapsed.is_empty() {
+                    show.collapsed = show.answer_positions.iter().map(|(id, _)| *id).collect();
+                } else {
+                    show.collapsed.clear();
+                }
+                show.focused_link_index = None;
+                show.rebuild_content(self.width);
+                if let Some(answer_id) = current {
+                    show.scroll_to_answer(answer_id);
+                }
+            }
+            KeyCode::Char('c') => {
+                let current = show.answer_at_scroll();
+                show.hide_comments = !show.hide_comments;
+                show.rebuild_content(self.width);
+                show.rebuild_erwin_content(self.width);
+                if let Some(answer_id) = current {
+                    show.scroll_to_answer(answer_id);
+                }
+            }
+            KeyCode::Char('F') => self.set_focus_mode(true),
+            KeyCode::Char('O') if show.has_outline(self.width) => {
+                show.outline_selected = Some(show.current_section());
+            }
+            KeyCode::Char('P') => return self.open_playground(),
+            KeyCode::Char('R') => self.save_render_report(),
+            KeyCode::Char('D') => {
+                show.rep_deltas = !show.rep_deltas;
+                show.rebuild_content(self.width);
+                show.rebuild_erwin_content(self.width);
+            }
+            KeyCode::Char(c @ ('w' | 'W')) => {
+                let found = show.cycle_term(focused_term, c == 'w', self.height);
+                if !found {
+                    self.flash = Some("No glossary terms in this pane".to_string());
+                }
+            }
+            KeyCode::Tab => {
+                show.cycle_link(true, self.height);
+            }
+            KeyCode::BackTab => {
+                show.cycle_link(false, self.height);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Write a report on the post element at the focused link or reading
+    /// position, for issues about how a post rendered
+    fn save_render_report(&mut self) {
+        let show = &self.show;
+        let Some((line, span)) = show.source_at_cursor(self.height) else {
+            self.flash = Some("No post below the reading position".to_string());
+            return;
+        };
+        let Some(post_html) = show.source_html(span.source) else {
+            return;
+        };
+        let element_html = match span.node {
+            PostNode::Text => None,
+            PostNode::CodeBlock(n) => element_html(post_html, "pre", n),
+            PostNode::Table(n) => element_html(post_html, "table", n),
+        };
+        let (pane, width, lines) = if show.erwin_focused() {
+            (
+                "Erwin pane",
+                show.erwin_pane_width(self.width),
+                &show.rendered_erwin_content,
+            )
+        } else {
+            (
+                "Question pane",
+                show.question_pane_width(self.width),
+                &show.rendered_content,
+            )
+        };
+        let report = RenderReport {
+            question_id: show.question_id,
+            site: show.site(),
+            pane,
+            width,
+            line,
+            span,
+            html: element_html.as_deref().unwrap_or(post_html),
+            rendered: lines
+                .get(span.start_line..span.end_line)
+                .unwrap_or_default()
+                .iter()
+                .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect(),
+        };
+        match report.save() {
+            Ok(file) => {
+                self.flash = Some(format!(
+                    "Wrote {}; attach it at {ISSUES_URL}",
+                    file.display()
+                ))
+            }
+            Err(err) => self.record_error(ErrorReport::new("Rendering report", &err)),
+        }
+    }
+
+    /// Save the focused pane's position under `mark`
+    fn set_mark(&mut self, mark: char) {
+        let show = &mut self.show;
+        let pos = show.scroll_mark();
+        if let Some(ref user_db) = self.user_db {
+            if let Err(err) = user_db.set_mark(show.site(), show.question_id, mark, pos) {
+                self.flash = Some(format!("{err:#}"));
+                return;
+            }
+        }
+        show.marks.insert(mark, pos);
+        self.flash = Some(format!("Mark '{mark}' set"));
+    }
+
+    /// Scroll back to a mark, in Erwin's pane if its answer is shown there
+    fn jump_to_mark(&mut self, mark: char) {
+        let show = &mut self.show;
+        let Some(&pos) = show.marks.get(&mark) else {
+            self.flash = Some(format!("Mark '{mark}' not set"));
+            return;
+        };
+        if !show.go_to_mark(pos, self.width) {
+            self.flash = Some(format!("Mark '{mark}' is in an answer no longer here"));
+        }
+    }
+
+    /// Show the accepted answer: in Erwin's pane when it's his and the pane is
+    /// beside the question, else in the question pane
+    fn jump_to_accepted(&mut self) {
+        let Some(accepted) = self.show.answers.iter().find(|a| a.is_accepted) else {
+            self.flash = Some("No accepted answer".to_string());
+            return;
+        };
+        self.show.show_answer(accepted.answer_id, self.width);
+    }
+
+    /// Scroll the question pane to the linked questions, focusing the first
+    fn jump_to_linked(&mut self) {
+        let show = &mut self.show;
+        let Some(line) = show.linked_position else {
+            self.flash = Some(if self.link_graph_load.is_some() {
+                "Still finding linked questions".to_string()
+            } else {
+                "No linked questions".to_string()
+            });
+            return;
+        };
+        show.left_pane_focused = true;
+        show.focused_link_index = show
+            .content_links
+            .iter()
+            .position(|link| link.line_index > line);
+        show.scroll_to_line(line);
+    }
+
+    /// Label the links on screen for link-hint mode
+    fn start_link_hints(&mut self) {
+        let hints = self.show.link_hints(self.width, self.height);
+        if hints.is_empty() {
+            self.flash = Some("No links on screen".to_string());
+            return;
+        }
+        self.show.link_hints = Some(LinkHints {
+            hints,
+            typed: String::new(),
+        });
+    }
+
+    /// A key in link-hint mode: label letters narrow the hints down until one
+    /// is complete, which follows its link (or only focuses it when typed in
+    /// capitals); any other key leaves the mode
+    fn handle_link_hint_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let show = &mut self.show;
+        let hints = show.link_hints.as_mut()?;
+        match key.code {
+            KeyCode::Char(c) => hints.typed.push(c),
+            KeyCode::Backspace if !hints.typed.is_empty() => {
+                hints.typed.pop();
+                return None;
+            }
+            _ => {
+                show.link_hints = None;
+                return None;
+            }
+        }
+        let focus_only = hints.typed.chars().any(char::is_uppercase);
+        let typed = hints.typed.len();
+        let (first, more) = {
+            let mut matching = hints.matching();
+            let first = matching
+                .next()
+                .map(|hint| (hint.pane, hint.link_index, hint.label.len()));
+            (first, matching.next().is_some())
+        };
+        let Some((pane, link_index, label_len)) = first else {
+            show.link_hints = None;
+            self.flash = Some("No link with that hint".to_string());
+            return None;
+        };
+        if more || label_len > typed {
+            return None;
+        }
+        show.link_hints = None;
+        show.focus_link(pane, link_index);
+        if focus_only {
+            return None;
+        }
+        show.get_focused_link().cloned().map(Action::FollowLink)
+    }
+
+    /// Look up the open thread in the link graph: what it links to in post
+    /// order, and what links to it best-scored first
+    pub(super) fn load_linked_questions(&mut self) {
+        let question_id = self.show.question_id;
+        let linked_questions = |ids: &[i64]| -> Vec<LinkedQuestion> {
+            ids.iter()
+                .filter_map(|&id| self.questions.iter().find(|q| q.id == id))
+                .map(|summary| LinkedQuestion {
+                    id: summary.id,
+                    site: summary.site,
+                    title: summary.title.clone(),
+                    score: summary.score,
+                })
+                .collect()
+        };
+        let linked = linked_questions(self.link_graph.linked(question_id));
+        let mut linked_from = linked_questions(self.link_graph.linked_from(question_id));
+        linked_from.sort_by_key(|question| std::cmp::Reverse(question.score));
+        self.show.linked = linked;
+        self.show.linked_from = linked_from;
+    }
+
+    /// Take the built link graph, adding the open thread's links to it
+    pub(super) fn finish_link_graph(&mut self, result: Result<LinkGraph>) {
+        self.link_graph_load = None;
+        match result {
+            Ok(graph) => {
+                self.link_graph = graph;
+                if self.show.question.is_some() {
+                    self.load_linked_questions();
+                    self.show.rebuild_content(self.width);
+                }
+            }
+            Err(err) => self.record_error(ErrorReport::new("Link graph", &err)),
+        }
+    }
+
+    /// Scroll the question pane to the next or previous answer header after
+    /// or before the reading position
+    fn jump_to_answer(&mut self, forward: bool) {
+        let show = &mut self.show;
+        let reading = show.scroll_offset + show.scrolloff;
+        let target = if forward {
+            show.answer_positions.iter().find(|(_, pos)| *pos > reading)
+        } else {
+            show.answer_positions
+                .iter()
+                .rev()
+                .find(|(_, pos)| *pos < reading)
+        }
+        .copied();
+
+        // With his pane open, Erwin's answers aren't in the question pane;
+        // say so rather than passing over them silently
+        let skipped = if show.is_split(self.width) {
+            let index = |id| show.answers.iter().position(|a| a.answer_id == id);
+            let from = show.answer_at_scroll().and_then(index);
+            let to = target.and_then(|(id, _)| index(id));
+            let (start, end) = if forward {
+                (from.map_or(0, |i| i + 1), to.unwrap_or(show.answers.len()))
+            } else {
+                (to.map_or(0, |i| i + 1), from.unwrap_or(0))
+            };
+            show.answers
+                .get(start..end.max(start))
+                .unwrap_or_default()
+                .iter()
+                .filter(|a| is_erwin(&a.author_name))
+                .count()
+        } else {
+            0
+        };
+
+        let Some((_, pos)) = target else {
+            let end = if forward { "last" } else { "first" };
+            self.flash = Some(if skipped > 0 {
+                format!("Already past the {end} answer here; Erwin's are in his pane")
+            } else {
+                format!("Already past the {end} answer")
+            });
+            return;
+        };
+        show.left_pane_focused = true;
+        show.focused_link_index = None;
+        show.scroll_to_line(pos);
+        if skipped > 0 {
+            self.flash = Some(format!(
+                "Passed {skipped} of Erwin's answers; they're in his pane"
+            ));
+        }
+    }
+
+    /// Keys while the outline sidebar has focus
+    fn handle_outline_key(&mut self, selected: usize, key: KeyEvent) {
+        let show = &mut self.show;
+        let last = show.outline_len() - 1;
+        show.outline_selected = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('O') => None,
+            KeyCode::Char('j') | KeyCode::Down => Some((selected + 1).min(last)),
+            KeyCode::Char('k') | KeyCode::Up => Some(selected.saturating_sub(1)),
+            KeyCode::Char('g') => Some(0),
+            KeyCode::Char('G') => Some(last),
+            KeyCode::Enter => {
+                show.jump_to_section(selected, self.width);
+                None
+            }
+            _ => Some(selected),
+        };
+    }
+
+    /// Present a saved collection: its questions one at a time, full
+    /// screen, in the collection's order
+    pub(super) fn start_presentation(&mut self, name: &str) {
+        let Some(ref user_db) = self.user_db else {
+            self.flash = Some("Collections need the user database".to_string());
+            return;
+        };
+        let question_ids: Vec<i64> = match user_db.get_collection(name) {
+            // Collections can outlive questions dropped from the corpus
+            Ok(Some(questions)) => questions
+                .into_iter()
+                .filter(|&(site, id)| self.questions.iter().any(|q| q.id == id && q.site == site))
+                .map(|(_, id)| id)
+                .collect(),
+            Ok(None) => {
+                self.flash = Some(format!("No collection named `{name}`"));
+                return;
+            }
+            Err(err) => {
+                self.flash = Some(format!("Failed to read collection: {err:#}"));
+                return;
+            }
+        };
+        let Some(&first) = question_ids.first() else {
+            self.flash = Some(format!("Collection \"{name}\" has no questions to present"));
+            return;
+        };
+        self.show.presentation = Some(Presentation {
+            name: name.to_string(),
+            question_ids,
+            index: 0,
+        });
+        self.navigate_to_question(first, None);
+    }
+
+    /// Move on to the presentation's next or previous question
+    fn step_presentation(&mut self, forward: bool) {
+        let Some(presentation) = self.show.presentation.as_mut() else {
+            return;
+        };
+        let index = if forward {
+            presentation.index + 1
+        } else {
+            presentation.index.wrapping_sub(1)
+        };
+        let Some(&question_id) = presentation.question_ids.get(index) else {
+            self.flash = Some(if forward {
+                "Last question of the presentation".to_string()
+            } else {
+                "First question of the presentation".to_string()
+            });
+            return;
+        };
+        presentation.index = index;
+        self.navigate_to_question(question_id, None);
+        // Stepping isn't navigation to come back through
+        self.history.pop();
+    }
+
+    /// Back to the usual question page, on the question showing
+    fn end_presentation(&mut self) {
+        self.show.presentation = None;
+        self.show.rebuild_content(self.width);
+        self.show.rebuild_erwin_content(self.width);
+    }
+
+    /// Put the chrome away or bring it back; the panes rewrap to their new
+    /// width
+    fn set_focus_mode(&mut self, focus: bool) {
+        self.show.focus = focus;
+        self.show.outline_selected = None;
+        self.show.rebuild_content(self.width);
+        self.show.rebuild_erwin_content(self.width);
+    }
+
+    fn next_erwin_answer(&mut self) {
+        let width = self.width;
+        let show = &mut self.show;
+        let erwin_count = show.erwin_answer_count();
+        if erwin_count == 0 {
+            return;
+        }
+
+        if show.can_split(width) {
+            // Wide terminal: toggle/cycle Erwin pane
+            if !show.erwin_pane_visible {
+                show.erwin_pane_visible = true;
+                show.left_pane_focused = false;
+                show.erwin_scroll_offset = 0;
+                show.rebuild_content(width); // Hide Erwin from left pane
+                show.rebuild_erwin_content(width);
+            } else if show.left_pane_focused {
+                show.left_pane_focused = false;
+            } else {
+                show.erwin_answer_index = (show.erwin_answer_index + 1) % erwin_count;
+                if show.erwin_answer_index == 0 {
+                    show.erwin_pane_visible = false;
+                    show.left_pane_focused = true;
+                    show.rebuild_content(width); // Show Erwin in left pane again
+                }
+                show.erwin_scroll_offset = 0;
+                show.rebuild_erwin_content(width);
+            }
+        } else {
+            // Narrow terminal: cycle to next Erwin answer and scroll to it
+            show.erwin_answer_index = (show.erwin_answer_index + 1) % erwin_count;
+            if let Some(&pos) = show.erwin_answer_positions.get(show.erwin_answer_index) {
+                show.focused_link_index = None;
+                show.scroll_to_line(pos);
+            }
+        }
+    }
+
+    fn prev_erwin_answer(&mut self) {
+        let width = self.width;
+        let show = &mut self.show;
+        let erwin_count = show.erwin_answer_count();
+        if erwin_count == 0 {
+            return;
+        }
+
+        if show.can_split(width) && show.erwin_pane_visible {
+            if !show.left_pane_focused && show.erwin_answer_index == 0 {
+                show.left_pane_focused = true;
+            } else if !show.left_pane_focused {
+                show.erwin_answer_index = show.erwin_answer_index.saturating_sub(1);
+                show.erwin_scroll_offset = 0;
+                show.rebuild_erwin_content(width);
+            } else {
+                show.erwin_pane_visible = false;
+                show.rebuild_content(width); // Show Erwin in left pane again
+            }
+        } else if !show.can_split(width) {
+            // Narrow terminal: go to previous Erwin answer
+            show.erwin_answer_index = if show.erwin_answer_index == 0 {
+                erwin_count - 1
+            } else {
+                show.erwin_answer_index - 1
+            };
+            if let Some(&pos) = show.erwin_answer_positions.get(show.erwin_answer_index) {
+                show.focused_link_index = None;
+                show.scroll_to_line(pos);
+            }
+        }
+    }
+
+    pub(super) fn handle_show_mouse(&mut self, mouse: MouseEvent) {
+        let down = match mouse.kind {
+            MouseEventKind::ScrollDown => Some(true),
+            MouseEventKind::ScrollUp => Some(false),
+            _ => None,
+        };
+        if let Some(down) = down {
+            self.scroll_pane_at(mouse.column as usize, down);
+            self.refresh_hover();
+            return;
+        }
+
+        if !matches!(
+            mouse.kind,
+            MouseEventKind::Moved | MouseEventKind::Down(MouseButton::Left)
+        ) {
+            return;
+        }
+        self.mouse_position = Some((mouse.column, mouse.row));
+        let hovered = self.hover_at(mouse.column as usize, mouse.row as usize);
+
+        if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+            if let Some(section) = self.outline_section_at(mouse.column, mouse.row as usize) {
+                self.show.outline_selected = None;
+                self.show.jump_to_section(section, self.width);
+                self.refresh_hover();
+            } else if let Some((pane, idx)) = hovered {
+                self.handle_link_click(pane, idx);
+                self.refresh_hover();
+            }
+        }
+    }
+
+    /// Scroll the pane under the pointer a wheel notch; without a split, the
+    /// one pane shown
+    fn scroll_pane_at(&mut self, col: usize, down: bool) {
+        let pane = self
+            .show
+            .is_split(self.width)
+            .then(|| self.get_pane_at_position(col));
+        let show = &mut self.show;
+        let (scroll, link) = match pane {
+            Some(Pane::Erwin) => (
+                &mut show.erwin_scroll_offset,
+                &mut show.focused_erwin_link_index,
+            ),
+            Some(Pane::Question) => (&mut show.scroll_offset, &mut show.focused_link_index),
+            None if show.erwin_focused() => (
+                &mut show.erwin_scroll_offset,
+                &mut show.focused_erwin_link_index,
+            ),
+            None => (&mut show.scroll_offset, &mut show.focused_link_index),
+        };
+        *link = None;
+        // Scrolling past the end is clamped when the pane is drawn
+        *scroll = if down {
+            *scroll + WHEEL_LINES
+        } else {
+            scroll.saturating_sub(WHEEL_LINES)
+        };
+    }
+
+    /// Re-resolve the link under the pointer after the content under it moved
+    /// (scrolling, resizing, switching answers)
+    pub(super) fn refresh_hover(&mut self) {
+        if self.page != Page::Show {
+            self.clear_hover_state();
+            return;
+        }
+        if let Some((col, row)) = self.mouse_position {
+            self.hover_at(col as usize, row as usize);
+        }
+    }
+
+    /// Update hover state from a screen cell, returning the hovered link
+    fn hover_at(&mut self, col: usize, row: usize) -> Option<(Pane, usize)> {
+        // Check if position is in content area (skip header and status bar)
+        if !self.is_in_content_area(row) {
+            self.clear_hover_state();
+            return None;
+        }
+
+        let pane = self.get_pane_at_position(col);
+        let link_index = self.find_link_at_position(pane, col, row);
+        self.update_hover_state(pane, link_index);
+        link_index.map(|idx| (pane, idx))
+    }
+
+    /// Outline section listed at a screen cell, if the sidebar is there
+    fn outline_section_at(&self, col: u16, row: usize) -> Option<usize> {
+        let panes_width = self.show.panes_width(self.width);
+        if col < panes_width || !self.is_in_content_area(row) {
+            return None;
+        }
+        let visible_rows = self.show.visible_height(self.height);
+        let section = row - self.show.chrome_rows().0 + self.show.outline_scroll(visible_rows);
+        (section < self.show.outline_len()).then_some(section)
+    }
+
+    fn is_in_content_area(&self, row: usize) -> bool {
+        let (header, status) = self.show.chrome_rows();
+        row >= header && row < (self.height as usize).saturating_sub(status)
+    }
+
+    fn get_pane_at_position(&self, col: usize) -> Pane {
+        let can_split = self.show.can_split(self.width);
+        let split_pos = self.show.split_pos(self.width) as usize;
+
+        if self.show.erwin_pane_visible && can_split && col >= split_pos {
+            Pane::Erwin
+        } else {
+            Pane::Question
+        }
+    }
+
+    fn find_link_at_position(&self, pane: Pane, col: usize, row: usize) -> Option<usize> {
+        let can_split = self.show.can_split(self.width);
+        let split_pos = self.show.split_pos(self.width) as usize;
+        let show = &self.show;
+
+        let (links, lines, scroll_offset, pane_col) = match pane {
+            Pane::Question => (
+                &show.content_links,
+                &show.rendered_content,
+                show.scroll_offset,
+                col.checked_sub(QUESTION_PANE_PADDING as usize)?,
+            ),
+            Pane::Erwin if can_split => (
+                &show.erwin_links,
+                &show.rendered_erwin_content,
+                show.erwin_scroll_offset,
+                col.checked_sub(split_pos + ERWIN_PANE_BORDER as usize)?,
+            ),
+            Pane::Erwin => (
+                &show.content_links,
+                &show.rendered_content,
+                show.scroll_offset,
+                col,
+            ),
+        };
+
+        // Scroll offsets may run past the end until the next draw clamps them
+        let visible_rows = show.visible_height(self.height);
+        let scroll_offset = scroll_offset.min(lines.len().saturating_sub(visible_rows));
+        let line_index = row - show.chrome_rows().0 + scroll_offset;
+
+        links
+            .iter()
+            .position(|link| link.contains(line_index, pane_col))
+    }
+
+    fn clear_hover_state(&mut self) {
+        self.show.hovered_link_index = None;
+        self.show.hovered_erwin_link_index = None;
+    }
+
+    fn update_hover_state(&mut self, pane: Pane, link_index: Option<usize>) {
+        match pane {
+            Pane::Erwin => {
+                self.show.hovered_erwin_link_index = link_index;
+                self.show.hovered_link_index = None;
+            }
+            Pane::Question => {
+                self.show.hovered_link_index = link_index;
+                self.show.hovered_erwin_link_index = None;
+            }
+        }
+    }
+
+    fn handle_link_click(&mut self, pane: Pane, link_index: usize) {
+        let link = match pane {
+            Pane::Erwin => self.show.erwin_links.get(link_index),
+            Pane::Question => self.show.content_links.get(link_index),
+        };
+
+        if let Some(link) = link.cloned() {
+            self.dispatch(Action::FollowLink(link));
+        }
+    }
+}
+
+/// Keys while the translation pane is open; returns false to close it.
+/// Scrolling is clamped when the pane is drawn.
+fn handle_translation_key(translation: &mut TranslationState, key: KeyEvent, page: usize) -> bool {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('t') => return false,
+        KeyCode::Char('j') | KeyCode::Down => translation.scroll += 1,
+        KeyCode::Char('k') | KeyCode::Up => {
+            translation.scroll = translation.scroll.saturating_sub(1);
+        }
+        KeyCode::Char(' ') | KeyCode::Char('d') => translation.scroll += page,
+        KeyCode::Char('u') => translation.scroll = translation.scroll.saturating_sub(page),
+        KeyCode::Char('g') => translation.scroll = 0,
+        KeyCode::Char('G') => translation.scroll = usize::MAX / 2,
+        _ => {}
+    }
+    true
+}
+
+/// Keys that only scroll, which leave focus mode on
+fn is_scroll_key(key: KeyEvent) -> bool {
+    matches!(
+        key.code,
+        KeyCode::Char('j' | 'k' | ' ' | 'd' | 'u' | 'g' | 'G')
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+    )
+}
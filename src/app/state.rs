@@ -0,0 +1,1381 @@
+use ratatui::text::Line;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::{Action, AnswerSort, Pane, SearchMode, SortColumn, SortDirection};
+use crate::config::Layout;
+use crate::content::{
+    build_erwin_content, build_question_content, RelatedThreads, RenderOptions, Source, SourceSpan,
+    MAX_CONTENT_WIDTH,
+};
+use crate::db::{Answer, Comment, CrossSiteCopy, DuplicateTarget, QuestionFull, SemanticResult};
+use crate::dwell::EngagedQuestion;
+use crate::glossary::{Glossary, TermHit};
+use crate::html::{is_erwin, CodeBlock, Link};
+use crate::links::LinkedQuestion;
+use crate::playground::{QueryResult, QueryRun};
+use crate::plugins::PageLine;
+use crate::quality::AnswerQuality;
+use crate::search::fuzzy::FuzzyMatch;
+use crate::search::hybrid::HybridMatch;
+use crate::search::query::Filter;
+use crate::site::Site;
+use crate::techniques::Technique;
+use crate::terms::TermKind;
+use crate::translate::Translator;
+use crate::ui::{
+    DUAL_PANE_MIN_WIDTH, ERWIN_PANE_BORDER, OUTLINE_MIN_WIDTH, OUTLINE_WIDTH,
+    QUESTION_PANE_PADDING, SCROLLBAR_WIDTH,
+};
+use crate::userdb::{AlertHit, HiddenQuestion, RecentView, ScrollMark};
+
+/// Error details screen: which of the session's failures and how far down
+#[derive(Default)]
+pub struct ErrorDetailsState {
+    pub index: usize,
+    pub scroll: usize,
+}
+
+/// Pending yes/no confirmation; the action runs only if the user confirms
+pub struct ConfirmState {
+    pub message: String,
+    pub action: Action,
+}
+
+/// Question list selection, sorting and filtering
+pub struct IndexState {
+    pub selected: usize,
+    pub scroll: usize,
+    pub sort_column: SortColumn,
+    pub sort_direction: SortDirection,
+    /// Off: natural order (newest first), or relevance order during a search
+    pub sort_active: bool,
+    /// Whether the list was sorted before the current search switched to relevance
+    pub sort_before_search: Option<bool>,
+    pub tag_filter: Option<String>,
+    /// Questions whose Erwin answers use a term picked on the Terms page
+    pub term_filter: Option<TermFilter>,
+    /// Leave closed questions (including duplicates) out of the list
+    pub hide_closed: bool,
+    /// List hidden questions too, so they can be unhidden
+    pub show_hidden: bool,
+    /// Show each question's excerpt on a second line
+    pub detailed: bool,
+    /// Show the stats footer for the listed questions
+    pub stats: bool,
+    /// Questions pinned to the top of the list, in pin order (kept across searches)
+    pub pins: Vec<i64>,
+}
+
+impl Default for IndexState {
+    fn default() -> Self {
+        Self {
+            selected: 0,
+            scroll: 0,
+            sort_column: SortColumn::Score,
+            sort_direction: SortDirection::Desc,
+            sort_active: true,
+            sort_before_search: None,
+            tag_filter: None,
+            term_filter: None,
+            hide_closed: false,
+            show_hidden: false,
+            detailed: false,
+            stats: false,
+            pins: Vec::new(),
+        }
+    }
+}
+
+impl IndexState {
+    /// Screen lines taken by one question
+    pub fn row_height(&self) -> usize {
+        if self.detailed {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Screen lines besides the list: header, column headers, status bar and
+    /// the stats footer if shown
+    pub fn chrome_height(&self) -> u16 {
+        3 + u16::from(self.stats)
+    }
+
+    pub fn reset_cursor(&mut self) {
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    /// Show a new search's results in relevance order, remembering the list's own sort
+    pub fn enter_search_order(&mut self) {
+        self.sort_before_search.get_or_insert(self.sort_active);
+        self.sort_active = false;
+    }
+
+    /// Back to the list's own sort once the search is gone
+    pub fn leave_search_order(&mut self) {
+        if let Some(sort_active) = self.sort_before_search.take() {
+            self.sort_active = sort_active;
+        }
+    }
+}
+
+/// Questions a semantic search is limited to
+#[derive(Debug, Clone)]
+pub struct SearchScope {
+    /// The filters, as the modal title names them: `[jsonb] · open`
+    pub label: String,
+    pub ids: Arc<[i64]>,
+}
+
+/// Title / semantic search input and results
+pub struct SearchState {
+    pub mode: SearchMode,
+    pub input: String,
+    /// `field:value` operators of the title search, already applied to
+    /// `fuzzy_matches`
+    pub filters: Vec<Filter>,
+    pub fuzzy_matches: Option<Vec<FuzzyMatch>>,
+    pub semantic_results: Option<Vec<i64>>,
+    /// Title and semantic matches merged into one ranking
+    pub hybrid_results: Option<Vec<HybridMatch>>,
+    /// Semantic or hybrid search waiting for the model to load, run once it has
+    pub waiting_for_model: Option<SearchMode>,
+    /// Closest questions to the semantic modal's input so far, previewed in
+    /// the modal while typing
+    pub semantic_preview: Vec<SemanticResult>,
+    /// Tag whose centroid produced the current semantic results
+    pub explore_tag: Option<String>,
+    /// The list's filters when the semantic or hybrid modal opened, which
+    /// its search stays within
+    pub scope: Option<SearchScope>,
+    /// Questions whose bodies or answers match the title search input, best first
+    /// (`None` while they're being counted)
+    pub body_matches: Option<Vec<i64>>,
+    /// List body matches after the title matches
+    pub include_bodies: bool,
+    /// Bumped whenever the results above change, so the list order cached
+    /// from them is rebuilt
+    pub revision: u64,
+}
+
+impl Default for SearchState {
+    fn default() -> Self {
+        Self {
+            mode: SearchMode::None,
+            input: String::new(),
+            filters: Vec::new(),
+            fuzzy_matches: None,
+            semantic_results: None,
+            hybrid_results: None,
+            waiting_for_model: None,
+            semantic_preview: Vec::new(),
+            explore_tag: None,
+            scope: None,
+            body_matches: None,
+            include_bodies: false,
+            revision: 0,
+        }
+    }
+}
+
+impl SearchState {
+    pub fn has_results(&self) -> bool {
+        self.fuzzy_matches.is_some()
+            || self.semantic_results.is_some()
+            || self.hybrid_results.is_some()
+    }
+
+    /// Whether the 1-6 column sorts apply; semantic and hybrid results keep
+    /// their ranking
+    pub fn sortable(&self) -> bool {
+        self.semantic_results.is_none() && self.hybrid_results.is_none()
+    }
+
+    /// Call after changing any of the results
+    pub fn results_changed(&mut self) {
+        self.revision += 1;
+    }
+
+    pub fn clear_results(&mut self) {
+        self.results_changed();
+        self.waiting_for_model = None;
+        self.filters.clear();
+        self.fuzzy_matches = None;
+        self.body_matches = None;
+        self.semantic_results = None;
+        self.hybrid_results = None;
+        self.explore_tag = None;
+        self.input.clear();
+    }
+}
+
+/// A term from the Terms page and the questions using it
+pub struct TermFilter {
+    /// As shown: `generate_series()`, `LATERAL`
+    pub label: String,
+    pub question_ids: HashSet<i64>,
+}
+
+/// Terms page selection, narrowed to one kind of term if set
+#[derive(Default)]
+pub struct TermsState {
+    pub selected: usize,
+    pub scroll: usize,
+    pub kind: Option<TermKind>,
+}
+
+/// Tags page selection and fuzzy filter
+#[derive(Default)]
+pub struct TagsState {
+    pub selected: usize,
+    pub scroll: usize,
+    pub filter_active: bool,
+    pub filter_input: String,
+    pub matches: Option<Vec<FuzzyMatch>>,
+}
+
+/// Translated text of a post, shown in a pane beside it
+#[derive(Default)]
+pub struct TranslationState {
+    pub text: String,
+    pub scroll: usize,
+    /// Running translation command; `None` once `text` is filled in
+    pub translator: Option<Translator>,
+}
+
+/// A collection stepped through question by question (`:present`)
+pub struct Presentation {
+    pub name: String,
+    pub question_ids: Vec<i64>,
+    /// Index of the question showing
+    pub index: usize,
+}
+
+/// Letters hint labels are made of, home row first
+const HINT_CHARS: &[u8] = b"asdfghjklqwertyuiopzxcvbnm";
+
+/// Link-hint mode (`f`): a label over each link on screen, typed to follow it
+pub struct LinkHints {
+    pub hints: Vec<LinkHint>,
+    /// Label characters typed so far, as typed
+    pub typed: String,
+}
+
+/// A visible link and its label
+pub struct LinkHint {
+    pub label: String,
+    pub pane: Pane,
+    /// Index into the pane's links
+    pub link_index: usize,
+}
+
+impl LinkHints {
+    /// Hints still matching what was typed
+    pub fn matching(&self) -> impl Iterator<Item = &LinkHint> {
+        let typed = self.typed.to_lowercase();
+        self.hints
+            .iter()
+            .filter(move |hint| hint.label.starts_with(&typed))
+    }
+}
+
+/// SQL playground: a scratch buffer run against the configured connection
+#[derive(Default)]
+pub struct PlaygroundState {
+    /// Buffer lines; holds at least one (empty) line once the page has opened
+    pub lines: Vec<String>,
+    pub cursor_row: usize,
+    /// Cursor position in characters
+    pub cursor_col: usize,
+    pub editor_scroll: usize,
+    /// Code the buffer was seeded from, so reopening on the same block keeps edits
+    pub seeded_from: Option<String>,
+    /// Query in flight; `None` once `result` is filled in
+    pub run: Option<QueryRun>,
+    pub result: Option<QueryResult>,
+    pub result_scroll: usize,
+}
+
+impl PlaygroundState {
+    /// Replace the buffer with a code block, unless it was already seeded from it
+    pub fn seed(&mut self, code: &str) {
+        if self.seeded_from.as_deref() == Some(code) {
+            return;
+        }
+        self.lines = code.lines().map(str::to_string).collect();
+        self.seeded_from = Some(code.to_string());
+        self.cursor_row = self.lines.len().saturating_sub(1);
+        self.cursor_col = 0;
+        self.editor_scroll = 0;
+        self.ensure_line();
+    }
+
+    pub fn sql(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn clear(&mut self) {
+        self.lines = vec![String::new()];
+        self.cursor_row = 0;
+        self.cursor_col = 0;
+        self.editor_scroll = 0;
+    }
+
+    pub fn ensure_line(&mut self) {
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+    }
+
+    /// Byte offset of the cursor in its line
+    fn cursor_byte(&self) -> usize {
+        let line = &self.lines[self.cursor_row];
+        line.char_indices()
+            .nth(self.cursor_col)
+            .map_or(line.len(), |(i, _)| i)
+    }
+
+    fn line_chars(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Insert text at the cursor; newlines split the line
+    pub fn insert(&mut self, text: &str) {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            let part = part.replace('\r', "").replace('\t', "    ");
+            let at = self.cursor_byte();
+            self.lines[self.cursor_row].insert_str(at, &part);
+            self.cursor_col += part.chars().count();
+        }
+    }
+
+    pub fn newline(&mut self) {
+        let at = self.cursor_byte();
+        let rest = self.lines[self.cursor_row].split_off(at);
+        self.cursor_row += 1;
+        self.lines.insert(self.cursor_row, rest);
+        self.cursor_col = 0;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor_col > 0 {
+            self.cursor_col -= 1;
+            let at = self.cursor_byte();
+            self.lines[self.cursor_row].remove(at);
+        } else if self.cursor_row > 0 {
+            let line = self.lines.remove(self.cursor_row);
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_chars(self.cursor_row);
+            self.lines[self.cursor_row].push_str(&line);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor_col < self.line_chars(self.cursor_row) {
+            let at = self.cursor_byte();
+            self.lines[self.cursor_row].remove(at);
+        } else if self.cursor_row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.cursor_row + 1);
+            self.lines[self.cursor_row].push_str(&next);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.cursor_col > 0 {
+            self.cursor_col -= 1;
+        } else if self.cursor_row > 0 {
+            self.cursor_row -= 1;
+            self.cursor_col = self.line_chars(self.cursor_row);
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.cursor_col < self.line_chars(self.cursor_row) {
+            self.cursor_col += 1;
+        } else if self.cursor_row + 1 < self.lines.len() {
+            self.cursor_row += 1;
+            self.cursor_col = 0;
+        }
+    }
+
+    /// Move the cursor up (negative) or down by whole lines
+    pub fn move_vertical(&mut self, delta: isize) {
+        let last = self.lines.len() - 1;
+        self.cursor_row = self.cursor_row.saturating_add_signed(delta).min(last);
+        self.cursor_col = self.cursor_col.min(self.line_chars(self.cursor_row));
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor_col = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor_col = self.line_chars(self.cursor_row);
+    }
+}
+
+/// Alerts page: hits loaded from the user database when the page opens
+#[derive(Default)]
+pub struct AlertsState {
+    pub selected: usize,
+    pub scroll: usize,
+    pub hits: Vec<AlertHit>,
+    /// Unread hits, shown in the index header
+    pub unread: usize,
+}
+
+/// Review page: engaged questions loaded from the user database when it opens
+#[derive(Default)]
+pub struct ReviewState {
+    pub selected: usize,
+    pub scroll: usize,
+    pub questions: Vec<EngagedQuestion>,
+    /// Questions due for review, shown in the index header
+    pub due: usize,
+}
+
+/// Techniques page: the matrix of one tag's questions by the techniques in
+/// Erwin's code, computed when it opens
+#[derive(Default)]
+pub struct TechniquesState {
+    pub tag: String,
+    /// Matrix columns
+    pub techniques: Vec<Technique>,
+    /// Questions using at least one technique, best-scored first
+    pub questions: Vec<i64>,
+    pub selected: usize,
+    pub scroll: usize,
+    /// Selected technique
+    pub column: usize,
+    /// Only list the questions using the selected technique
+    pub only_column: bool,
+}
+
+/// Recent page: recently viewed questions loaded from the user database when it opens
+#[derive(Default)]
+pub struct RecentState {
+    pub selected: usize,
+    pub scroll: usize,
+    pub views: Vec<RecentView>,
+}
+
+/// Hidden page: hidden questions loaded from the user database when it opens
+#[derive(Default)]
+pub struct HiddenPageState {
+    pub selected: usize,
+    pub scroll: usize,
+    pub questions: Vec<HiddenQuestion>,
+}
+
+/// A plugin's page (`:name`), or the list of plugin commands (`:plugins`)
+#[derive(Default)]
+pub struct PluginPageState {
+    /// The page's `:` name; `None` for the `:plugins` list
+    pub name: Option<String>,
+    pub title: String,
+    pub lines: Vec<PageLine>,
+    pub selected: usize,
+    pub scroll: usize,
+}
+
+/// A question in the back/forward history, with where it was left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub question_id: i64,
+    /// Site of a thread fetched from the API rather than in the corpus
+    pub fetched: Option<Site>,
+    pub scroll_offset: usize,
+    pub focused_link_index: Option<usize>,
+}
+
+/// Limits of the question pane's share of the width in dual-pane mode
+pub const SPLIT_PERCENT_RANGE: std::ops::RangeInclusive<u16> = 25..=75;
+
+/// The open question, its pre-rendered content and pane/link state
+pub struct ShowState {
+    pub question_id: i64,
+    pub question: Option<QuestionFull>,
+    pub answers: Vec<Answer>,
+    pub comments: Vec<Comment>,
+    pub answer_comments: Vec<Vec<Comment>>, // Comments for each answer
+    pub copy: Option<CrossSiteCopy>,
+    /// Where a closed thread without local answers points
+    pub duplicate: Option<DuplicateTarget>,
+    /// Newer posts Erwin's superseded answers point to, by answer ID
+    pub superseded: HashMap<i64, String>,
+    /// Corpus threads this one links to, and those linking to it (empty
+    /// until the link graph is built)
+    pub linked: Vec<LinkedQuestion>,
+    pub linked_from: Vec<LinkedQuestion>,
+    /// Site the thread was fetched from, when it came from the API rather
+    /// than the corpus
+    pub fetched: Option<Site>,
+    pub answer_sort: AnswerSort,
+    /// Show reputation changes since ingest next to answer authors
+    pub rep_deltas: bool,
+    /// Answers folded to a one-line excerpt (`z`, `Z`)
+    pub collapsed: HashSet<i64>,
+    /// Comment sections hidden in both panes (`c`)
+    pub hide_comments: bool,
+    /// Percent of the width taken by the question pane in dual-pane mode
+    pub split_percent: u16,
+    /// Lines kept above the reading position when jumping, and around a
+    /// focused link or term (`[layout] scrolloff`)
+    pub scrolloff: usize,
+    /// Leave a column at the right of each pane for its scrollbar
+    /// (`[layout] scrollbars`)
+    pub scrollbars: bool,
+    /// Narrowest width with the Erwin pane beside the question
+    /// (`[layout] dual_pane_width`)
+    pub dual_pane_width: u16,
+    /// Widest the text of either pane gets (`[layout] content_width`)
+    pub content_width: usize,
+    /// Banner for a thread whose advice may be out of date (`[stale]` config)
+    pub stale_warning: Option<String>,
+    /// Focus mode (`F`): no header, status bar, outline or scrollbars, until
+    /// a key other than a scroll key
+    pub focus: bool,
+    /// Collection being presented, full screen with a progress bar instead
+    /// of the status bar
+    pub presentation: Option<Presentation>,
+    pub scroll_offset: usize,
+    pub erwin_pane_visible: bool,
+    pub erwin_answer_index: usize,
+    pub left_pane_focused: bool,
+    pub erwin_scroll_offset: usize,
+    /// Tab-focused link of each pane, kept while the other pane has focus
+    pub focused_link_index: Option<usize>, // For content_links (left/single pane)
+    pub focused_erwin_link_index: Option<usize>, // For erwin_links (right pane)
+    pub hovered_link_index: Option<usize>,       // For content_links (left/single pane)
+    pub hovered_erwin_link_index: Option<usize>, // For erwin_links (right pane)
+
+    // Pre-rendered content (rebuilt when question or width changes)
+    pub rendered_content: Vec<Line<'static>>,
+    pub rendered_erwin_content: Vec<Line<'static>>,
+    pub erwin_answer_positions: Vec<usize>,
+    pub answer_positions: Vec<(i64, usize)>,
+    /// Line of the linked questions section (`L`)
+    pub linked_position: Option<usize>,
+    pub rendered_width: u16,
+    pub content_links: Vec<Link>,
+    pub erwin_links: Vec<Link>,
+    pub code_blocks: Vec<CodeBlock>,
+    pub erwin_code_blocks: Vec<CodeBlock>,
+    /// Which post element each pane's lines came from (`R` reports)
+    pub content_sources: Vec<SourceSpan>,
+    pub erwin_sources: Vec<SourceSpan>,
+
+    /// Glossary terms underlined in each pane, and the one whose definition
+    /// is showing (an index into the focused pane's terms)
+    pub glossary: Rc<Glossary>,
+    pub content_terms: Vec<TermHit>,
+    pub erwin_terms: Vec<TermHit>,
+    pub focused_term: Option<usize>,
+
+    /// Selected outline section while the outline sidebar has focus
+    pub outline_selected: Option<usize>,
+
+    /// Marks of this question (`m{a-z}` sets, `'{a-z}` jumps), and the `m` or
+    /// `'` waiting for its letter
+    pub marks: HashMap<char, ScrollMark>,
+    pub pending_mark: Option<char>,
+    pub link_hints: Option<LinkHints>,
+
+    pub translation: Option<TranslationState>,
+}
+
+impl Default for ShowState {
+    fn default() -> Self {
+        Self {
+            question_id: 0,
+            question: None,
+            answers: Vec::new(),
+            comments: Vec::new(),
+            answer_comments: Vec::new(),
+            copy: None,
+            duplicate: None,
+            superseded: HashMap::new(),
+            linked: Vec::new(),
+            linked_from: Vec::new(),
+            fetched: None,
+            answer_sort: AnswerSort::Default,
+            rep_deltas: false,
+            collapsed: HashSet::new(),
+            hide_comments: false,
+            split_percent: 50,
+            scrolloff: 0,
+            scrollbars: false,
+            dual_pane_width: DUAL_PANE_MIN_WIDTH,
+            content_width: MAX_CONTENT_WIDTH,
+            stale_warning: None,
+            focus: false,
+            presentation: None,
+            scroll_offset: 0,
+            erwin_pane_visible: false,
+            erwin_answer_index: 0,
+            left_pane_focused: true,
+            erwin_scroll_offset: 0,
+            focused_link_index: None,
+            focused_erwin_link_index: None,
+            hovered_link_index: None,
+            hovered_erwin_link_index: None,
+
+            rendered_content: Vec::new(),
+            rendered_erwin_content: Vec::new(),
+            erwin_answer_positions: Vec::new(),
+            answer_positions: Vec::new(),
+            linked_position: None,
+            rendered_width: 0,
+            content_links: Vec::new(),
+            erwin_links: Vec::new(),
+            code_blocks: Vec::new(),
+            erwin_code_blocks: Vec::new(),
+            content_sources: Vec::new(),
+            erwin_sources: Vec::new(),
+
+            glossary: Rc::new(Glossary::default()),
+            content_terms: Vec::new(),
+            erwin_terms: Vec::new(),
+            focused_term: None,
+
+            outline_selected: None,
+
+            marks: HashMap::new(),
+            pending_mark: None,
+            link_hints: None,
+
+            translation: None,
+        }
+    }
+}
+
+impl ShowState {
+    /// Site of the open thread: the one it was fetched from, or its corpus
+    /// question's
+    pub fn site(&self) -> Site {
+        self.fetched
+            .or(self.question.as_ref().map(|q| q.summary.site))
+            .unwrap_or_default()
+    }
+
+    /// True when keys should act on the Erwin pane rather than the question pane
+    pub fn erwin_focused(&self) -> bool {
+        self.erwin_pane_visible && !self.left_pane_focused
+    }
+
+    /// Focused link index of the focused pane
+    pub fn focused_link_mut(&mut self) -> &mut Option<usize> {
+        if self.erwin_focused() {
+            &mut self.focused_erwin_link_index
+        } else {
+            &mut self.focused_link_index
+        }
+    }
+
+    pub fn focused_pane_link_index(&self) -> Option<usize> {
+        if self.erwin_focused() {
+            self.focused_erwin_link_index
+        } else {
+            self.focused_link_index
+        }
+    }
+
+    /// Scroll offset of the focused pane
+    pub fn focused_scroll_mut(&mut self) -> &mut usize {
+        if self.erwin_focused() {
+            &mut self.erwin_scroll_offset
+        } else {
+            &mut self.scroll_offset
+        }
+    }
+
+    pub fn erwin_answer_count(&self) -> usize {
+        self.answers
+            .iter()
+            .filter(|a| is_erwin(&a.author_name))
+            .count()
+    }
+
+    pub fn get_current_erwin_answer(&self) -> Option<&Answer> {
+        self.answers
+            .iter()
+            .filter(|a| is_erwin(&a.author_name))
+            .nth(self.erwin_answer_index)
+    }
+
+    /// Scroll the question pane so the given answer is at the reading position
+    pub fn scroll_to_answer(&mut self, answer_id: i64) {
+        if let Some(&(_, pos)) = self
+            .answer_positions
+            .iter()
+            .find(|(id, _)| *id == answer_id)
+        {
+            self.scroll_to_line(pos);
+        }
+    }
+
+    /// Scroll the question pane so `line` is the reading position, `scrolloff`
+    /// lines below the top
+    pub fn scroll_to_line(&mut self, line: usize) {
+        self.scroll_offset = line.saturating_sub(self.scrolloff);
+    }
+
+    /// The open question and the reading position in it, for the history
+    pub fn history_entry(&self) -> HistoryEntry {
+        HistoryEntry {
+            question_id: self.question_id,
+            fetched: self.fetched,
+            scroll_offset: self.scroll_offset,
+            focused_link_index: self.focused_link_index,
+        }
+    }
+
+    /// Go back to where the question was left, on reopening it from the
+    /// history
+    pub fn restore_position(&mut self, entry: &HistoryEntry) {
+        self.scroll_offset = entry
+            .scroll_offset
+            .min(self.rendered_content.len().saturating_sub(1));
+        self.focused_link_index = entry
+            .focused_link_index
+            .filter(|&i| i < self.content_links.len());
+    }
+
+    /// `scrolloff`, capped so a pane `visible_height` rows high keeps some
+    /// room between the margins
+    fn scroll_margin(&self, visible_height: usize) -> usize {
+        self.scrolloff.min(visible_height.saturating_sub(1) / 2)
+    }
+
+    /// Reorder answers (and their comments, which are kept parallel) by `answer_sort`
+    pub fn sort_answers(&mut self) {
+        let mut pairs: Vec<_> = std::mem::take(&mut self.answers)
+            .into_iter()
+            .zip(std::mem::take(&mut self.answer_comments))
+            .collect();
+        match self.answer_sort {
+            // Row ids are assigned in scrape order, so they restore `answer_order`
+            AnswerSort::Default => pairs.sort_by_key(|(a, _)| a.id),
+            AnswerSort::Reputation => {
+                pairs.sort_by_key(|(a, _)| std::cmp::Reverse(a.author_reputation))
+            }
+            AnswerSort::Quality => {
+                let mut scored: Vec<_> = pairs
+                    .into_iter()
+                    .map(|pair| (AnswerQuality::of(&pair.0).score(), pair))
+                    .collect();
+                scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+                pairs = scored.into_iter().map(|(_, pair)| pair).collect();
+            }
+        }
+        (self.answers, self.answer_comments) = pairs.into_iter().unzip();
+    }
+
+    /// Whether the Erwin pane sits beside the question at this width
+    pub fn is_split(&self, width: u16) -> bool {
+        self.erwin_pane_visible && self.can_split(width)
+    }
+
+    /// Whether the terminal is wide enough for the Erwin pane beside the
+    /// question
+    pub fn can_split(&self, width: u16) -> bool {
+        width >= self.dual_pane_width
+    }
+
+    /// Take the question page's part of a layout; the content needs
+    /// rebuilding after
+    pub fn apply_layout(&mut self, layout: &Layout) {
+        self.split_percent = layout
+            .split
+            .clamp(*SPLIT_PERCENT_RANGE.start(), *SPLIT_PERCENT_RANGE.end());
+        self.scrolloff = layout.scrolloff;
+        self.scrollbars = layout.scrollbars;
+        self.dual_pane_width = layout.dual_pane_width.unwrap_or(DUAL_PANE_MIN_WIDTH);
+        self.content_width = layout.content_width.unwrap_or(MAX_CONTENT_WIDTH);
+    }
+
+    /// Focus mode or a presentation: the panes without the outline or
+    /// scrollbars
+    fn full_screen(&self) -> bool {
+        self.focus || self.presentation.is_some()
+    }
+
+    /// Whether the thread outline sidebar fits beside the panes at this width
+    /// (and isn't put away for focus mode or a presentation)
+    pub fn has_outline(&self, width: u16) -> bool {
+        !self.full_screen() && width >= OUTLINE_MIN_WIDTH
+    }
+
+    /// Rows above the panes (the header) and below them (the status bar, or
+    /// a presentation's progress bar)
+    pub fn chrome_rows(&self) -> (usize, usize) {
+        if self.focus {
+            (0, 0)
+        } else if self.presentation.is_some() {
+            (0, 1)
+        } else {
+            (1, 1)
+        }
+    }
+
+    /// Rows of text the panes show on a terminal `height` rows high
+    pub fn visible_height(&self, height: u16) -> usize {
+        let (header, status) = self.chrome_rows();
+        (height as usize).saturating_sub(header + status)
+    }
+
+    /// Width left for the question and Erwin panes, left of the outline
+    pub fn panes_width(&self, width: u16) -> u16 {
+        if self.has_outline(width) {
+            width - OUTLINE_WIDTH
+        } else {
+            width
+        }
+    }
+
+    /// Column where the Erwin pane starts in dual-pane mode
+    pub fn split_pos(&self, width: u16) -> u16 {
+        (u32::from(self.panes_width(width)) * u32::from(self.split_percent) / 100) as u16
+    }
+
+    /// Text width of the question pane, inside its padding
+    pub fn question_pane_width(&self, width: u16) -> u16 {
+        let pane = if self.is_split(width) {
+            self.split_pos(width)
+        } else {
+            self.panes_width(width)
+        };
+        pane.saturating_sub(QUESTION_PANE_PADDING + self.scrollbar_width())
+    }
+
+    /// Text width of the Erwin pane, inside its border
+    pub fn erwin_pane_width(&self, width: u16) -> u16 {
+        (self.panes_width(width) - self.split_pos(width))
+            .saturating_sub(ERWIN_PANE_BORDER + self.scrollbar_width())
+    }
+
+    /// Whether the panes have scrollbars right now
+    pub fn has_scrollbars(&self) -> bool {
+        self.scrollbars && !self.full_screen()
+    }
+
+    pub fn scrollbar_width(&self) -> u16 {
+        if self.has_scrollbars() {
+            SCROLLBAR_WIDTH
+        } else {
+            0
+        }
+    }
+
+    /// Move the split between the panes, keeping both readable; returns false
+    /// if it's already at the limit
+    pub fn resize_split(&mut self, delta: i16) -> bool {
+        let percent = self
+            .split_percent
+            .saturating_add_signed(delta)
+            .clamp(*SPLIT_PERCENT_RANGE.start(), *SPLIT_PERCENT_RANGE.end());
+        let changed = percent != self.split_percent;
+        self.split_percent = percent;
+        changed
+    }
+
+    pub fn rebuild_content(&mut self, width: u16) {
+        let focused_url = self
+            .focused_link_index
+            .and_then(|i| self.content_links.get(i))
+            .map(|link| link.url.clone());
+        if let Some(ref question) = self.question {
+            let options = RenderOptions {
+                hide_erwin: self.is_split(width),
+                rep_deltas: self.rep_deltas,
+                stale_warning: self.stale_warning.as_deref(),
+                collapsed: Some(&self.collapsed),
+                hide_comments: self.hide_comments,
+                spacious: self.presentation.is_some(),
+                max_width: Some(self.content_width),
+            };
+            let content = build_question_content(
+                question,
+                &self.answers,
+                &self.comments,
+                &self.answer_comments,
+                RelatedThreads {
+                    copy: self.copy.as_ref(),
+                    duplicate: self.duplicate.as_ref(),
+                    superseded: Some(&self.superseded),
+                    linked: &self.linked,
+                    linked_from: &self.linked_from,
+                },
+                self.question_pane_width(width) as usize,
+                options,
+            );
+            self.rendered_content = content.lines;
+            self.erwin_answer_positions = content.erwin_positions;
+            self.answer_positions = content.answer_positions;
+            self.linked_position = content.linked_position;
+            self.content_links = content.links;
+            self.code_blocks = content.code_blocks;
+            self.content_sources = content.sources;
+            self.content_terms = self
+                .glossary
+                .mark(&mut self.rendered_content, &self.code_blocks);
+            self.focused_term = None;
+            self.rendered_width = width;
+            // Keep the focused link if it survived the rebuild (e.g. Erwin's answers
+            // moving out of this pane); the old index may point elsewhere now
+            self.focused_link_index =
+                focused_url.and_then(|url| self.content_links.iter().position(|l| l.url == url));
+        }
+    }
+
+    pub fn rebuild_erwin_content(&mut self, width: u16) {
+        let focused_url = self
+            .focused_erwin_link_index
+            .and_then(|i| self.erwin_links.get(i))
+            .map(|link| link.url.clone());
+        if let Some(answer) = self.get_current_erwin_answer() {
+            let comments = self
+                .answers
+                .iter()
+                .position(|a| a.id == answer.id)
+                .and_then(|i| self.answer_comments.get(i))
+                .map(|c| c.as_slice())
+                .unwrap_or(&[]);
+
+            let pane_width = self.erwin_pane_width(width) as usize;
+            let content = build_erwin_content(
+                answer,
+                comments,
+                self.superseded.get(&answer.answer_id).map(String::as_str),
+                pane_width,
+                self.content_width,
+                self.rep_deltas,
+                self.hide_comments,
+            );
+            self.rendered_erwin_content = content.lines;
+            self.erwin_links = content.links;
+            self.erwin_code_blocks = content.code_blocks;
+            self.erwin_sources = content.sources;
+            self.erwin_terms = self
+                .glossary
+                .mark(&mut self.rendered_erwin_content, &self.erwin_code_blocks);
+            self.focused_term = None;
+            self.focused_erwin_link_index =
+                focused_url.and_then(|url| self.erwin_links.iter().position(|l| l.url == url));
+        }
+    }
+
+    pub fn cycle_link(&mut self, forward: bool, height: u16) {
+        let visible_height = self.visible_height(height);
+        let margin = self.scroll_margin(visible_height);
+        // Determine which link collection, focus and scroll offset to use
+        let (links, focused, scroll_offset) = if self.erwin_focused() {
+            (
+                &self.erwin_links,
+                &mut self.focused_erwin_link_index,
+                &mut self.erwin_scroll_offset,
+            )
+        } else {
+            (
+                &self.content_links,
+                &mut self.focused_link_index,
+                &mut self.scroll_offset,
+            )
+        };
+
+        if links.is_empty() {
+            return;
+        }
+
+        // Calculate next link index
+        let new_index = match *focused {
+            Some(current) => {
+                if forward {
+                    if current + 1 >= links.len() {
+                        0
+                    } else {
+                        current + 1
+                    }
+                } else if current == 0 {
+                    links.len() - 1
+                } else {
+                    current - 1
+                }
+            }
+            None => {
+                let scroll = *scroll_offset;
+                let visible_end = scroll + visible_height;
+
+                if forward {
+                    // Find first link in visible area, or first link after viewport
+                    links
+                        .iter()
+                        .position(|link| link.line_index >= scroll)
+                        .unwrap_or(0)
+                } else {
+                    // Find last link in visible area, or last link before viewport
+                    links
+                        .iter()
+                        .rposition(|link| link.line_index < visible_end)
+                        .unwrap_or(links.len() - 1)
+                }
+            }
+        };
+
+        *focused = Some(new_index);
+
+        // Scroll to make the link visible, with `scrolloff` lines around it
+        if let Some(link) = links.get(new_index) {
+            if link.line_index < *scroll_offset + margin {
+                *scroll_offset = link.line_index.saturating_sub(margin);
+            } else if link.line_index + margin >= *scroll_offset + visible_height {
+                *scroll_offset = link.line_index.saturating_sub(visible_height / 2);
+            }
+        }
+    }
+
+    /// Glossary terms of the focused pane
+    pub fn focused_pane_terms(&self) -> &[TermHit] {
+        if self.erwin_focused() {
+            &self.erwin_terms
+        } else {
+            &self.content_terms
+        }
+    }
+
+    /// Focus the glossary term after (or before) `from`, or the first one on
+    /// screen, scrolling it into view; false if the pane has none
+    pub fn cycle_term(&mut self, from: Option<usize>, forward: bool, height: u16) -> bool {
+        let visible_height = self.visible_height(height);
+        let margin = self.scroll_margin(visible_height);
+        let (terms, scroll_offset) = if self.erwin_focused() {
+            (&self.erwin_terms, &mut self.erwin_scroll_offset)
+        } else {
+            (&self.content_terms, &mut self.scroll_offset)
+        };
+        if terms.is_empty() {
+            return false;
+        }
+
+        let index = match (from, forward) {
+            (Some(current), true) => (current + 1) % terms.len(),
+            (Some(current), false) => current.checked_sub(1).unwrap_or(terms.len() - 1),
+            (None, true) => terms
+                .iter()
+                .position(|term| term.line_index >= *scroll_offset)
+                .unwrap_or(0),
+            (None, false) => terms
+                .iter()
+                .rposition(|term| term.line_index < *scroll_offset + visible_height)
+                .unwrap_or(terms.len() - 1),
+        };
+
+        let line = terms[index].line_index;
+        if line < *scroll_offset + margin {
+            *scroll_offset = line.saturating_sub(margin);
+        } else if line + margin >= *scroll_offset + visible_height {
+            *scroll_offset = line.saturating_sub(visible_height / 2);
+        }
+        self.focused_term = Some(index);
+        true
+    }
+
+    /// The focused glossary term and its definition
+    pub fn get_focused_term(&self) -> Option<(&TermHit, &str)> {
+        let term = self.focused_pane_terms().get(self.focused_term?)?;
+        Some((term, self.glossary.definition(&term.term)?))
+    }
+
+    /// First code block visible in the focused pane
+    pub fn visible_code_block(&self, height: u16) -> Option<&CodeBlock> {
+        let (blocks, scroll) = if self.erwin_focused() {
+            (&self.erwin_code_blocks, self.erwin_scroll_offset)
+        } else {
+            (&self.code_blocks, self.scroll_offset)
+        };
+        let visible_end = scroll + self.visible_height(height);
+
+        blocks
+            .iter()
+            .find(|block| block.end_line > scroll && block.start_line < visible_end)
+    }
+
+    /// Answer whose section contains the reading position, `scrolloff` lines
+    /// below the top of the question pane
+    pub fn answer_at_scroll(&self) -> Option<i64> {
+        self.answer_positions
+            .iter()
+            .rev()
+            .find(|(_, pos)| *pos <= self.scroll_offset + self.scrolloff)
+            .map(|(id, _)| *id)
+    }
+
+    /// Banner link to the newer post of the answer in view (the Erwin pane's
+    /// when it's focused), if Erwin marked that answer superseded
+    pub fn superseded_link(&self) -> Option<&Link> {
+        let (answer_id, links) = if self.erwin_focused() {
+            (
+                self.get_current_erwin_answer()?.answer_id,
+                &self.erwin_links,
+            )
+        } else {
+            (self.answer_at_scroll()?, &self.content_links)
+        };
+        let url = self.superseded.get(&answer_id)?;
+        links.iter().find(|link| &link.url == url)
+    }
+
+    /// Sections of the thread outline: the question, then every answer in
+    /// display order (section `i + 1` is `answers[i]`)
+    pub fn outline_len(&self) -> usize {
+        self.answers.len() + 1
+    }
+
+    /// Outline section being read: the Erwin answer in its focused pane, else
+    /// the post at the top of the question pane
+    pub fn current_section(&self) -> usize {
+        let answer_id = if self.erwin_focused() {
+            self.get_current_erwin_answer().map(|a| a.answer_id)
+        } else {
+            self.answer_at_scroll()
+        };
+        answer_id
+            .and_then(|id| self.answers.iter().position(|a| a.answer_id == id))
+            .map_or(0, |i| i + 1)
+    }
+
+    /// First outline row shown in a sidebar of `height` rows, keeping the
+    /// selected (or current) section in view
+    pub fn outline_scroll(&self, height: usize) -> usize {
+        let section = self
+            .outline_selected
+            .unwrap_or_else(|| self.current_section());
+        (section + 1).saturating_sub(height)
+    }
+
+    /// Bring an outline section into view: Erwin's answers in their own pane
+    /// when it's beside the question, everything else in the question pane
+    pub fn jump_to_section(&mut self, section: usize, width: u16) {
+        self.focused_link_index = None;
+        self.focused_erwin_link_index = None;
+        let Some(answer) = section.checked_sub(1).and_then(|i| self.answers.get(i)) else {
+            self.left_pane_focused = true;
+            self.scroll_offset = 0;
+            return;
+        };
+
+        if is_erwin(&answer.author_name) && self.is_split(width) {
+            let answer_id = answer.answer_id;
+            self.erwin_answer_index = self
+                .answers
+                .iter()
+                .filter(|a| is_erwin(&a.author_name))
+                .position(|a| a.answer_id == answer_id)
+                .unwrap_or(0);
+            self.left_pane_focused = false;
+            self.erwin_scroll_offset = 0;
+            self.rebuild_erwin_content(width);
+        } else {
+            let answer_id = answer.answer_id;
+            self.left_pane_focused = true;
+            self.scroll_to_answer(answer_id);
+        }
+    }
+
+    /// The focused pane's position as a mark: the post at the top of the pane
+    /// and how many lines into it
+    pub fn scroll_mark(&self) -> ScrollMark {
+        if self.erwin_focused() {
+            return ScrollMark {
+                erwin_pane: true,
+                answer_id: self.get_current_erwin_answer().map(|a| a.answer_id),
+                offset: self.erwin_scroll_offset,
+            };
+        }
+        let section = self
+            .answer_positions
+            .iter()
+            .rev()
+            .find(|(_, pos)| *pos <= self.scroll_offset);
+        ScrollMark {
+            erwin_pane: false,
+            answer_id: section.map(|&(id, _)| id),
+            offset: self.scroll_offset - section.map_or(0, |&(_, pos)| pos),
+        }
+    }
+
+    /// Scroll to a mark: its answer brought into view as the outline does,
+    /// then the lines into it. False if the answer is gone from the thread.
+    pub fn go_to_mark(&mut self, mark: ScrollMark, width: u16) -> bool {
+        let Some(answer_id) = mark.answer_id else {
+            self.focused_link_index = None;
+            self.focused_erwin_link_index = None;
+            self.left_pane_focused = !(mark.erwin_pane && self.is_split(width));
+            *self.focused_scroll_mut() = mark.offset;
+            return true;
+        };
+        let Some(index) = self.answers.iter().position(|a| a.answer_id == answer_id) else {
+            return false;
+        };
+        self.jump_to_section(index + 1, width);
+        if self.erwin_focused() {
+            self.erwin_scroll_offset = mark.offset;
+        } else if let Some(&(_, pos)) = self
+            .answer_positions
+            .iter()
+            .find(|(id, _)| *id == answer_id)
+        {
+            self.scroll_offset = pos + mark.offset;
+        }
+        true
+    }
+
+    /// Bring an answer into view, in Erwin's pane when it's his and the pane
+    /// is beside the question (the question pane leaves his answers out then)
+    pub fn show_answer(&mut self, answer_id: i64, width: u16) {
+        if let Some(i) = self.answers.iter().position(|a| a.answer_id == answer_id) {
+            self.jump_to_section(i + 1, width);
+        }
+    }
+
+    /// HTML body of the post at the top of the focused pane (question or answer)
+    pub fn current_post_html(&self) -> Option<&str> {
+        if self.erwin_focused() {
+            return self
+                .get_current_erwin_answer()
+                .map(|a| a.answer_text.as_str());
+        }
+
+        match self.answer_at_scroll() {
+            Some(id) => self
+                .answers
+                .iter()
+                .find(|a| a.answer_id == id)
+                .map(|a| a.answer_text.as_str()),
+            None => self.question.as_ref().map(|q| q.body.as_str()),
+        }
+    }
+
+    /// HTML of the post a rendered line came from
+    pub fn source_html(&self, source: Source) -> Option<&str> {
+        let answer_index = |answer_id| self.answers.iter().position(|a| a.answer_id == answer_id);
+        match source {
+            Source::Question => self.question.as_ref().map(|q| q.body.as_str()),
+            Source::Answer(answer_id) => self
+                .answers
+                .get(answer_index(answer_id)?)
+                .map(|a| a.answer_text.as_str()),
+            Source::QuestionComment(n) => self.comments.get(n).map(|c| c.comment_text.as_str()),
+            Source::AnswerComment(answer_id, n) => self
+                .answer_comments
+                .get(answer_index(answer_id)?)?
+                .get(n)
+                .map(|c| c.comment_text.as_str()),
+        }
+    }
+
+    /// The focused pane's line to report (its focused link, or else the
+    /// reading position) and the post element drawn there, or first below it
+    pub fn source_at_cursor(&self, height: u16) -> Option<(usize, SourceSpan)> {
+        let visible_height = self.visible_height(height);
+        let (sources, scroll_offset) = if self.erwin_focused() {
+            (&self.erwin_sources, self.erwin_scroll_offset)
+        } else {
+            (&self.content_sources, self.scroll_offset)
+        };
+        let line = self
+            .get_focused_link()
+            .map_or(scroll_offset + self.scroll_margin(visible_height), |link| {
+                link.line_index
+            });
+        let span = sources.iter().find(|span| span.end_line > line)?;
+        Some((line.max(span.start_line), *span))
+    }
+
+    /// Labels for the links on screen: one letter each while there are few
+    /// enough, two otherwise, so no label starts another
+    pub fn link_hints(&self, width: u16, height: u16) -> Vec<LinkHint> {
+        let rows = self.visible_height(height);
+        let on_screen = |links: &[Link], scroll: usize| -> Vec<usize> {
+            let shown = scroll..scroll + rows;
+            links
+                .iter()
+                .enumerate()
+                .filter(|(_, link)| link.hitboxes.iter().any(|h| shown.contains(&h.line_index)))
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let mut visible: Vec<(Pane, usize)> = on_screen(&self.content_links, self.scroll_offset)
+            .into_iter()
+            .map(|i| (Pane::Question, i))
+            .collect();
+        if self.is_split(width) {
+            let erwin = on_screen(&self.erwin_links, self.erwin_scroll_offset);
+            visible.extend(erwin.into_iter().map(|i| (Pane::Erwin, i)));
+        }
+
+        let chars = HINT_CHARS.len();
+        visible.truncate(chars * chars);
+        let two_letters = visible.len() > chars;
+        visible
+            .into_iter()
+            .enumerate()
+            .map(|(n, (pane, link_index))| {
+                let label = if two_letters {
+                    [HINT_CHARS[n / chars], HINT_CHARS[n % chars]]
+                        .iter()
+                        .map(|&c| c as char)
+                        .collect()
+                } else {
+                    (HINT_CHARS[n] as char).to_string()
+                };
+                LinkHint {
+                    label,
+                    pane,
+                    link_index,
+                }
+            })
+            .collect()
+    }
+
+    /// Focus a link of either pane, moving the focus to its pane
+    pub fn focus_link(&mut self, pane: Pane, link_index: usize) {
+        match pane {
+            Pane::Question => {
+                self.left_pane_focused = true;
+                self.focused_link_index = Some(link_index);
+            }
+            Pane::Erwin => {
+                self.left_pane_focused = false;
+                self.focused_erwin_link_index = Some(link_index);
+            }
+        }
+    }
+
+    pub fn get_focused_link(&self) -> Option<&Link> {
+        let links = if self.erwin_focused() {
+            &self.erwin_links
+        } else {
+            &self.content_links
+        };
+
+        self.focused_pane_link_index()
+            .and_then(|idx| links.get(idx))
+    }
+
+    /// Link under the mouse pointer, with its number and the link count of its pane
+    pub fn get_hovered_link(&self) -> Option<(&Link, usize, usize)> {
+        let (links, idx) = match self.hovered_erwin_link_index {
+            Some(idx) => (&self.erwin_links, idx),
+            None => (&self.content_links, self.hovered_link_index?),
+        };
+        links.get(idx).map(|link| (link, idx + 1, links.len()))
+    }
+}
@@ -0,0 +1,139 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::{Action, App, Page};
+use crate::db::TagCount;
+use crate::search::fuzzy::fuzzy_filter;
+
+impl App {
+    pub(super) fn handle_tags_key(&mut self, key: KeyEvent) -> Option<Action> {
+        // Filter input mode
+        if self.tags.filter_active {
+            match key.code {
+                KeyCode::Esc => {
+                    self.tags.filter_active = false;
+                    self.tags.filter_input.clear();
+                    self.update_tag_matches();
+                }
+                KeyCode::Enter => {
+                    self.tags.filter_active = false;
+                }
+                KeyCode::Backspace => {
+                    self.tags.filter_input.pop();
+                    self.update_tag_matches();
+                }
+                KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.tags.filter_input.clear();
+                    self.update_tag_matches();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.tags.filter_input.push(c);
+                    self.update_tag_matches();
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        let visible_rows = self.height.saturating_sub(3) as usize;
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                if self.tags.matches.is_some() {
+                    self.tags.filter_input.clear();
+                    self.update_tag_matches();
+                } else {
+                    action = Some(Action::SwitchPage(Page::Index));
+                }
+            }
+            KeyCode::Char('/') => {
+                self.tags.filter_active = true;
+                self.tags.filter_input.clear();
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = self.visible_tags().len().saturating_sub(1);
+                self.tags.selected = (self.tags.selected + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.tags.selected = self.tags.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => {
+                self.tags.selected = 0;
+            }
+            KeyCode::Char('G') => {
+                self.tags.selected = self.visible_tags().len().saturating_sub(1);
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let max = self.visible_tags().len().saturating_sub(1);
+                self.tags.selected = (self.tags.selected + visible_rows / 2).min(max);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.tags.selected = self.tags.selected.saturating_sub(visible_rows / 2);
+            }
+            KeyCode::Enter => {
+                if let Some(tag) = self.visible_tags().get(self.tags.selected) {
+                    self.index.tag_filter = Some(tag.name.clone());
+                    self.index.reset_cursor();
+                    action = Some(Action::SwitchPage(Page::Index));
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(tag) = self.visible_tags().get(self.tags.selected) {
+                    let tag = tag.name.clone();
+                    action = self.explore_tag_neighbors(&tag);
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Some(tag) = self.visible_tags().get(self.tags.selected) {
+                    let tag = tag.name.clone();
+                    action = self.open_techniques(&tag);
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if self.tags.selected < self.tags.scroll {
+            self.tags.scroll = self.tags.selected;
+        } else if visible_rows > 0 && self.tags.selected >= self.tags.scroll + visible_rows {
+            self.tags.scroll = self.tags.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    pub(super) fn update_tag_matches(&mut self) {
+        self.tags.matches = if self.tags.filter_input.is_empty() {
+            None
+        } else {
+            let tags = self.db.get_tag_counts().unwrap_or_default();
+            Some(fuzzy_filter(tags, &self.tags.filter_input, |t| &t.name))
+        };
+        self.tags.selected = 0;
+        self.tags.scroll = 0;
+    }
+
+    /// Tags shown on the tags page, narrowed by the fuzzy filter if one is active
+    pub fn visible_tags(&self) -> Vec<&TagCount> {
+        let tags = self.db.get_tag_counts().unwrap_or_default();
+        match self.tags.matches {
+            Some(ref matches) => matches.iter().map(|m| &tags[m.index]).collect(),
+            None => tags.iter().collect(),
+        }
+    }
+
+    /// Show questions semantically close to a tag's centroid that don't carry the tag
+    fn explore_tag_neighbors(&mut self, tag: &str) -> Option<Action> {
+        let centroid = self.db.get_tag_centroid(tag).ok()??;
+        let results = self
+            .db
+            .semantic_search_excluding_tag(&centroid, tag, 20)
+            .ok()?;
+
+        self.search.clear_results();
+        self.search.semantic_results = Some(results.into_iter().map(|r| r.question_id).collect());
+        self.search.explore_tag = Some(tag.to_string());
+        self.index.tag_filter = None;
+        self.index.enter_search_order();
+        self.index.reset_cursor();
+        Some(Action::SwitchPage(Page::Index))
+    }
+}
@@ -0,0 +1,126 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::{Action, App, Page, TechniquesState};
+use crate::techniques::{find_techniques, MAX_QUESTIONS};
+
+impl App {
+    pub(super) fn handle_techniques_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let visible_rows = self.techniques_list_height();
+        let max = self.visible_technique_questions().len().saturating_sub(1);
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => action = Some(Action::SwitchPage(Page::Tags)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.techniques.selected = (self.techniques.selected + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.techniques.selected = self.techniques.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.techniques.selected = 0,
+            KeyCode::Char('G') => self.techniques.selected = max,
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.techniques.selected = (self.techniques.selected + visible_rows / 2).min(max);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.techniques.selected =
+                    self.techniques.selected.saturating_sub(visible_rows / 2);
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                let last = self.techniques.techniques.len().saturating_sub(1);
+                self.techniques.column = (self.techniques.column + 1).min(last);
+                self.reset_techniques_cursor();
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.techniques.column = self.techniques.column.saturating_sub(1);
+                self.reset_techniques_cursor();
+            }
+            KeyCode::Char('f') => {
+                self.techniques.only_column = !self.techniques.only_column;
+                self.techniques.selected = 0;
+                self.techniques.scroll = 0;
+            }
+            KeyCode::Enter => {
+                if let Some(&id) = self
+                    .visible_technique_questions()
+                    .get(self.techniques.selected)
+                {
+                    action = Some(Action::OpenQuestion(id));
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if self.techniques.selected < self.techniques.scroll {
+            self.techniques.scroll = self.techniques.selected;
+        } else if visible_rows > 0
+            && self.techniques.selected >= self.techniques.scroll + visible_rows
+        {
+            self.techniques.scroll = self.techniques.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    /// Find the techniques in Erwin's answers for the tag and switch to the
+    /// Techniques page
+    pub(super) fn open_techniques(&mut self, tag: &str) -> Option<Action> {
+        let answers = match self.db.erwin_answers_in_tag(tag, MAX_QUESTIONS) {
+            Ok(answers) => answers,
+            Err(err) => {
+                self.flash = Some(format!("{err:#}"));
+                return None;
+            }
+        };
+        let techniques = find_techniques(&answers);
+        if techniques.is_empty() {
+            self.flash = Some(format!(
+                "No recurring techniques in Erwin's code for [{tag}]"
+            ));
+            return None;
+        }
+
+        let mut questions: Vec<_> = self
+            .questions
+            .iter()
+            .filter(|q| techniques.iter().any(|t| t.questions.contains(&q.id)))
+            .filter(|q| !self.hidden.contains(&q.id))
+            .collect();
+        questions.sort_by_key(|q| std::cmp::Reverse(q.score));
+        let questions = questions.into_iter().map(|q| q.id).collect();
+
+        self.techniques = TechniquesState {
+            tag: tag.to_string(),
+            techniques,
+            questions,
+            ..TechniquesState::default()
+        };
+        Some(Action::SwitchPage(Page::Techniques))
+    }
+
+    /// Questions listed on the Techniques page, narrowed to the selected
+    /// technique if asked
+    pub fn visible_technique_questions(&self) -> Vec<i64> {
+        let state = &self.techniques;
+        let technique = state.techniques.get(state.column);
+        state
+            .questions
+            .iter()
+            .copied()
+            .filter(|id| !state.only_column || technique.is_some_and(|t| t.questions.contains(id)))
+            .collect()
+    }
+
+    /// Rows of the question list: the screen less the header, the legend, a
+    /// blank line, the column headings and the status bar
+    pub fn techniques_list_height(&self) -> usize {
+        (self.height as usize).saturating_sub(self.techniques.techniques.len() + 4)
+    }
+
+    /// A new column shows other questions when the list is narrowed to it
+    fn reset_techniques_cursor(&mut self) {
+        if self.techniques.only_column {
+            self.techniques.selected = 0;
+            self.techniques.scroll = 0;
+        }
+    }
+}
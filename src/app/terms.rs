@@ -0,0 +1,85 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::{Action, App, Page, TermFilter};
+use crate::terms::{TermCount, TermKind};
+
+impl App {
+    pub(super) fn handle_terms_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let visible_rows = self.height.saturating_sub(2) as usize;
+        let mut action = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => action = Some(Action::SwitchPage(Page::Index)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                let max = self.visible_terms().len().saturating_sub(1);
+                self.terms.selected = (self.terms.selected + 1).min(max);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.terms.selected = self.terms.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => {
+                self.terms.selected = 0;
+            }
+            KeyCode::Char('G') => {
+                self.terms.selected = self.visible_terms().len().saturating_sub(1);
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let max = self.visible_terms().len().saturating_sub(1);
+                self.terms.selected = (self.terms.selected + visible_rows / 2).min(max);
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.terms.selected = self.terms.selected.saturating_sub(visible_rows / 2);
+            }
+            // All terms -> functions -> keywords
+            KeyCode::Char('f') => {
+                self.terms.kind = match self.terms.kind {
+                    None => Some(TermKind::Function),
+                    Some(TermKind::Function) => Some(TermKind::Keyword),
+                    Some(TermKind::Keyword) => None,
+                };
+                self.terms.selected = 0;
+                self.terms.scroll = 0;
+            }
+            KeyCode::Enter => {
+                if let Some(term) = self.visible_terms().get(self.terms.selected) {
+                    let term = (*term).clone();
+                    action = self.filter_by_term(&term);
+                }
+            }
+            _ => {}
+        }
+
+        // Keep the selection on screen
+        if self.terms.selected < self.terms.scroll {
+            self.terms.scroll = self.terms.selected;
+        } else if visible_rows > 0 && self.terms.selected >= self.terms.scroll + visible_rows {
+            self.terms.scroll = self.terms.selected + 1 - visible_rows;
+        }
+        action
+    }
+
+    /// Terms shown on the terms page, narrowed to one kind if chosen
+    pub fn visible_terms(&self) -> Vec<&TermCount> {
+        let terms = self.db.get_term_counts().unwrap_or_default();
+        terms
+            .iter()
+            .filter(|t| self.terms.kind.is_none_or(|kind| t.kind == kind))
+            .collect()
+    }
+
+    /// List the questions whose Erwin answers use the term
+    fn filter_by_term(&mut self, term: &TermCount) -> Option<Action> {
+        let ids = match self.db.questions_using_term(&term.term, term.kind) {
+            Ok(ids) => ids,
+            Err(err) => {
+                self.flash = Some(format!("{err:#}"));
+                return None;
+            }
+        };
+        self.index.term_filter = Some(TermFilter {
+            label: term.label(),
+            question_ids: ids.into_iter().collect(),
+        });
+        self.index.reset_cursor();
+        Some(Action::SwitchPage(Page::Index))
+    }
+}
@@ -0,0 +1,1147 @@
+use anyhow::{bail, Context, Result};
+use chrono::{TimeZone, Utc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, ErrorKind, IsTerminal, Write};
+use std::path::PathBuf;
+
+use erwindb::ansi::{lines_to_ansi, lines_to_text};
+use erwindb::config::Config;
+use erwindb::content::{build_question_content, RelatedThreads, RenderOptions};
+use erwindb::db::{self, Answer, Comment, Database, QuestionSummary};
+use erwindb::document::Thread;
+use erwindb::export::{obsidian_vault, thread_html, thread_markdown, threads_org};
+use erwindb::external::{self, shell_quote, ExternalCommand};
+use erwindb::fixture;
+use erwindb::hooks::{HookEvent, Hooks};
+use erwindb::html::{
+    extract_site, extract_so_answer_id, extract_so_question_id, html_to_plain_text, is_erwin,
+};
+use erwindb::json::{QuestionJson, ThreadJson};
+use erwindb::license::license_for;
+use erwindb::se_api::{self, Client};
+use erwindb::search::fuzzy::fuzzy_filter;
+use erwindb::search::query::SearchQuery;
+use erwindb::search::semantic::SemanticSearch;
+use erwindb::site::Site;
+use erwindb::superseded;
+use erwindb::ui::styles::{self, Theme};
+use erwindb::update;
+use erwindb::userdb::{self, UserDb, UserState};
+
+/// Columns `erwindb list` prints when `--columns` isn't given
+const DEFAULT_COLUMNS: &str = "id,score,title";
+
+/// Browse Erwin Brandstetter's Stack Overflow answers. Without a subcommand
+/// the TUI opens, on the semantic search results for QUERY if one is given
+/// (put a QUERY that starts with a subcommand name after `--`).
+#[derive(Debug, Parser)]
+#[command(name = "erwindb", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub tui: TuiArgs,
+    /// Pin the clock so timestamps in names, exports and the user database are reproducible
+    #[arg(long, global = true)]
+    pub deterministic: bool,
+    /// Browse this SQLite file instead of the embedded corpus (also `ERWINDB_DB`)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub db: Option<PathBuf>,
+    /// Browse the database of a `[profiles.NAME]` config section
+    #[arg(long, global = true, value_name = "NAME", conflicts_with = "db")]
+    pub profile: Option<String>,
+}
+
+impl Cli {
+    /// Point `Database::open_default` at the database picked with `--db`,
+    /// `ERWINDB_DB` or `--profile`, if any, and a profile's `UserDb` at its
+    /// own file
+    pub fn select_database(&self) -> Result<()> {
+        let path = match (&self.db, &self.profile) {
+            (Some(path), _) => path.clone(),
+            (None, Some(name)) => {
+                let config = Config::load()?;
+                let profile = config
+                    .profiles
+                    .get(name)
+                    .with_context(|| format!("No [profiles.{name}] in the config"))?;
+                if name.contains(['/', '\\']) {
+                    bail!("Profile names can't contain path separators: {name}");
+                }
+                userdb::use_profile(name.clone());
+                profile.db.clone()
+            }
+            (None, None) => match std::env::var_os("ERWINDB_DB").filter(|v| !v.is_empty()) {
+                Some(path) => PathBuf::from(path),
+                None => return Ok(()),
+            },
+        };
+        db::use_database(path);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Open the TUI (the default)
+    Tui(TuiArgs),
+    /// Print questions as tab-separated lines, for external pickers
+    List(ListArgs),
+    /// Print a question and its answers as plain text, JSON or rendered blocks
+    Show(ShowArgs),
+    /// Print a thread styled as on the question page, for `less -R`
+    Cat(CatArgs),
+    /// Print the IDs and titles of questions matching a title search
+    Search(SearchArgs),
+    /// Print the IDs and titles of questions closest in meaning to a query
+    Semantic(SemanticArgs),
+    /// Convert a question, its answers and comments to Markdown
+    Export(ExportArgs),
+    /// Render a thread as a PDF through `[export] pdf_command`
+    ExportPdf(ExportPdfArgs),
+    /// Print a question, or a saved collection, as an Org-mode document
+    ExportOrg {
+        #[arg(value_name = "QUESTION_ID|COLLECTION")]
+        target: String,
+    },
+    /// Write a collection or tag as an Obsidian vault
+    ExportObsidian(ExportObsidianArgs),
+    /// Generate a small synthetic database for tests and benchmarks
+    MakeFixture(MakeFixtureArgs),
+    /// Back up or move bookmarks, collections, read history and marks
+    #[command(subcommand)]
+    State(StateCommand),
+    /// Query the Stack Exchange API through erwindb's client (key, cache, backoff)
+    #[command(subcommand)]
+    Api(ApiCommand),
+    /// Pull threads Erwin answered or edited since the last update from the API
+    Update(UpdateArgs),
+    /// Print the searches saved to the wishlist (`W` after a search in the TUI)
+    Wishlist,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StateCommand {
+    /// Write the user state as JSON
+    Export {
+        /// File to write (`-` for stdout)
+        #[arg(default_value = "-")]
+        file: PathBuf,
+    },
+    /// Merge user state exported elsewhere into this machine's
+    Import {
+        /// File to read (`-` for stdin)
+        file: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ApiCommand {
+    /// Print whether a key is set, the quota left today and the response cache
+    Quota,
+    /// Print the items of an API method as JSON, e.g. `questions/3800551 filter=withbody`
+    Get {
+        /// Method path after `/2.3/`
+        path: String,
+        /// Further query parameters as `name=value`
+        params: Vec<String>,
+        /// `stackoverflow` or `dba`
+        #[arg(long, default_value = "stackoverflow")]
+        site: String,
+        /// First page to fetch, to resume an earlier run
+        #[arg(long, default_value_t = 1)]
+        page: u32,
+        /// Pages of 100 items to fetch
+        #[arg(long, default_value_t = 1)]
+        pages: u32,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct UpdateArgs {
+    /// Only this site: `stackoverflow` or `dba`
+    #[arg(long)]
+    pub site: Option<String>,
+}
+
+#[derive(Debug, Default, Args)]
+pub struct TuiArgs {
+    /// Enter prints the chosen question and quits instead of opening it
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "id"
+    )]
+    pub pick: Option<PickFormat>,
+    /// Open on the semantic search results for this query
+    #[arg(value_name = "QUERY")]
+    pub query: Vec<String>,
+}
+
+/// What `--pick` prints for the chosen question
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PickFormat {
+    Id,
+    Url,
+    /// `id<TAB>site<TAB>url<TAB>title`
+    Tsv,
+}
+
+impl PickFormat {
+    pub fn format(self, question: &QuestionSummary) -> String {
+        let url = question.site.question_url(question.id);
+        match self {
+            Self::Id => question.id.to_string(),
+            Self::Url => url,
+            Self::Tsv => format!(
+                "{}\t{}\t{}\t{}",
+                question.id,
+                question.site.as_db(),
+                url,
+                question.title.replace('\t', " ")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// Output format (only tsv)
+    #[arg(long, default_value = "tsv", value_parser = ["tsv"])]
+    format: String,
+    /// Comma-separated: id, site, score, views, answers, date, tags, status, excerpt, url, title
+    #[arg(long, default_value = DEFAULT_COLUMNS)]
+    columns: String,
+    /// Only questions with this tag
+    #[arg(long)]
+    tag: Option<String>,
+    /// Fuzzy title search; best match first
+    #[arg(long)]
+    search: Option<String>,
+}
+
+/// Output of `show`, `search` and `semantic`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Plain text for `show`, `id<TAB>title` lines for searches
+    #[default]
+    Text,
+    /// Question metadata (and for `show`, bodies and comments) as JSON
+    Json,
+}
+
+/// Output of `show`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ShowFormat {
+    /// Plain text
+    #[default]
+    Text,
+    /// Metadata, HTML bodies and comments as JSON
+    Json,
+    /// Posts rendered to blocks of styled text, code and tables, as JSON
+    /// (the stable `document` format, for other front ends)
+    Document,
+}
+
+#[derive(Debug, Args)]
+pub struct ShowArgs {
+    question_id: i64,
+    #[arg(long, value_enum, default_value_t)]
+    format: ShowFormat,
+    /// Columns `--format document` wraps text to
+    #[arg(long, default_value_t = 80)]
+    width: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct CatArgs {
+    question_id: i64,
+    /// Columns to lay the thread out in (default: the terminal's width)
+    #[arg(long)]
+    width: Option<u16>,
+    /// When to print ANSI colors
+    #[arg(long, value_enum, default_value_t)]
+    color: ColorChoice,
+}
+
+/// When `cat` styles its output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// When stdout is a terminal and `NO_COLOR` isn't set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            Self::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && io::stdout().is_terminal()
+            }
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    /// Words to match against titles, and field operators like `tag:jsonb` or `score:>50`
+    #[arg(required = true)]
+    query: Vec<String>,
+    /// Print at most this many questions
+    #[arg(long, short = 'n')]
+    limit: Option<usize>,
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+#[derive(Debug, Args)]
+pub struct SemanticArgs {
+    /// A question in your own words
+    #[arg(required = true)]
+    query: Vec<String>,
+    /// Number of questions to print
+    #[arg(long, short = 'n', default_value_t = 20)]
+    limit: usize,
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    question_id: i64,
+    #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+    format: ExportFormat,
+    /// File to write instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// Document formats `erwindb export` writes
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Markdown,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportPdfArgs {
+    question_id: i64,
+    /// PDF to write (default: erwindb-<id>.pdf)
+    #[arg(long, short)]
+    output: Option<String>,
+    /// Print the print-ready HTML instead of converting it
+    #[arg(long)]
+    html: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportObsidianArgs {
+    /// Saved collection to export
+    #[arg(required_unless_present = "tag", conflicts_with = "tag")]
+    collection: Option<String>,
+    /// Export every question with this tag instead
+    #[arg(long)]
+    tag: Option<String>,
+    /// Vault directory
+    #[arg(long, short, default_value = "erwindb-vault")]
+    output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct MakeFixtureArgs {
+    /// Number of questions
+    #[arg(long, default_value_t = 50)]
+    questions: usize,
+    /// Database file to write
+    #[arg(long, short, default_value = "fixture.db")]
+    out: PathBuf,
+    /// Same seed, same database
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+    /// Replace an existing file
+    #[arg(long)]
+    force: bool,
+}
+
+/// A column of `erwindb list` output
+#[derive(Debug, Clone, Copy)]
+enum Column {
+    Id,
+    Site,
+    Score,
+    Views,
+    Answers,
+    Date,
+    Tags,
+    Status,
+    Excerpt,
+    Url,
+    Title,
+}
+
+impl Column {
+    fn parse(name: &str) -> Result<Self> {
+        Ok(match name {
+            "id" => Self::Id,
+            "site" => Self::Site,
+            "score" => Self::Score,
+            "views" => Self::Views,
+            "answers" => Self::Answers,
+            "date" => Self::Date,
+            "tags" => Self::Tags,
+            "status" => Self::Status,
+            "excerpt" => Self::Excerpt,
+            "url" => Self::Url,
+            "title" => Self::Title,
+            _ => bail!(
+                "Unknown column `{name}` (expected id, site, score, views, answers, date, tags, status, excerpt, url or title)"
+            ),
+        })
+    }
+
+    fn value(self, q: &QuestionSummary) -> String {
+        match self {
+            Self::Id => q.id.to_string(),
+            Self::Site => q.site.as_db().to_string(),
+            Self::Score => q.score.to_string(),
+            Self::Views => q.view_count.to_string(),
+            Self::Answers => q.answer_count.to_string(),
+            Self::Date => format_date(q.creation_date),
+            Self::Tags => q.tags.join(","),
+            Self::Status => q
+                .status
+                .badges()
+                .iter()
+                .map(|b| b.label())
+                .collect::<Vec<_>>()
+                .join(","),
+            Self::Excerpt => q.excerpt.clone(),
+            Self::Url => q.site.question_url(q.id),
+            // Keep one record per line for fzf/rofi
+            Self::Title => q.title.replace(['\t', '\n'], " "),
+        }
+    }
+}
+
+fn format_date(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Print to stdout; a closed pipe (`| head`) just means the reader has seen enough
+fn write_stdout(text: &str) -> Result<()> {
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        Err(err) if err.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// A question's answers, each with its comments
+fn answers_with_comments(db: &Database, id: i64) -> Result<Vec<(Answer, Vec<Comment>)>> {
+    db.get_answers(id)?
+        .into_iter()
+        .map(|answer| {
+            let comments = db.get_answer_comments(answer.id)?;
+            Ok((answer, comments))
+        })
+        .collect()
+}
+
+/// Print `value` as pretty-printed JSON
+fn write_json(value: &impl Serialize) -> Result<()> {
+    let mut json = serde_json::to_string_pretty(value)?;
+    json.push('\n');
+    write_stdout(&json)
+}
+
+/// `erwindb list [--format tsv] [--columns id,score,title] [--tag TAG] [--search TEXT]`
+///
+/// Prints one tab-separated line per question, highest score first (or best
+/// title match first with `--search`), for building external pickers.
+pub fn list(args: ListArgs) -> Result<()> {
+    let ListArgs {
+        columns,
+        tag,
+        search,
+        ..
+    } = args;
+    let columns = columns
+        .split(',')
+        .map(|name| Column::parse(name.trim()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let db = Database::open_default()?;
+    let questions = db.get_questions()?;
+    let mut selected: Vec<&QuestionSummary> = match search {
+        Some(pattern) => fuzzy_filter(&questions, &pattern, |q| &q.title)
+            .iter()
+            .map(|m| &questions[m.index])
+            .collect(),
+        None => {
+            let mut all: Vec<&QuestionSummary> = questions.iter().collect();
+            all.sort_by_key(|q| std::cmp::Reverse(q.score));
+            all
+        }
+    };
+    if let Some(tag) = tag {
+        selected.retain(|q| q.tags.contains(&tag));
+    }
+
+    let mut out = String::new();
+    for q in selected {
+        let values: Vec<String> = columns.iter().map(|c| c.value(q)).collect();
+        out.push_str(&values.join("\t"));
+        out.push('\n');
+    }
+    write_stdout(&out)
+}
+
+/// `erwindb search <query>... [--format json]`: `id<TAB>title` of every
+/// question the title search finds, as in the TUI (field operators included),
+/// best match first
+pub fn search(args: SearchArgs) -> Result<()> {
+    let query = SearchQuery::parse(&args.query.join(" "));
+    let db = Database::open_default()?;
+    let questions = db.get_questions()?;
+    let mut matches = query.search(&questions);
+    if query.text.is_empty() {
+        // Operators alone: highest score first, like `list`
+        matches.sort_by_key(|m| std::cmp::Reverse(questions[m.index].score));
+    }
+
+    let limit = args.limit.unwrap_or(usize::MAX);
+    if args.format == OutputFormat::Json {
+        let json: Vec<QuestionJson> = matches
+            .iter()
+            .take(limit)
+            .map(|m| QuestionJson::from(&questions[m.index]))
+            .collect();
+        return write_json(&json);
+    }
+    let out: String = matches
+        .iter()
+        .take(limit)
+        .map(|m| {
+            let q = &questions[m.index];
+            format!("{}\t{}\n", q.id, Column::Title.value(q))
+        })
+        .collect();
+    write_stdout(&out)
+}
+
+/// `erwindb semantic <query>... [--limit N] [--format json]`: `id<TAB>title`
+/// of the questions closest in meaning, closest first
+pub fn semantic(args: SemanticArgs) -> Result<()> {
+    let db = Database::open_default()?;
+    let semantic = SemanticSearch::new().context("Failed to load the embedding model")?;
+    let results = semantic.search(&db, &args.query.join(" "), None, args.limit)?;
+    let questions = db.get_questions()?;
+    let found = results.iter().filter_map(|result| {
+        let q = questions.iter().find(|q| q.id == result.question_id)?;
+        Some((q, result.distance))
+    });
+
+    if args.format == OutputFormat::Json {
+        let json: Vec<QuestionJson> = found
+            .map(|(q, distance)| QuestionJson {
+                distance: Some(distance),
+                ..QuestionJson::from(q)
+            })
+            .collect();
+        return write_json(&json);
+    }
+    let out: String = found
+        .map(|(q, _)| format!("{}\t{}\n", q.id, Column::Title.value(q)))
+        .collect();
+    write_stdout(&out)
+}
+
+/// `erwindb show <id> [--format json]`: the question and its answers as plain
+/// text, or as JSON with the stored HTML bodies and all comments
+pub fn show(args: ShowArgs) -> Result<()> {
+    let id = args.question_id;
+    let db = Database::open_default()?;
+    let question = db
+        .get_question(id)?
+        .with_context(|| format!("Question {id} not found"))?;
+    let summary = &question.summary;
+
+    match args.format {
+        ShowFormat::Text => {}
+        ShowFormat::Json => {
+            let comments = db.get_question_comments(id)?;
+            let answers = answers_with_comments(&db, id)?;
+            return write_json(&ThreadJson::new(&question, &comments, &answers));
+        }
+        ShowFormat::Document => {
+            let comments = db.get_question_comments(id)?;
+            let answers = answers_with_comments(&db, id)?;
+            let width = args.width.max(20);
+            return write_json(&Thread::new(&question, &comments, &answers, width));
+        }
+    }
+
+    let mut out = format!(
+        "{}\n{}\nAsked by {} on {} | {} votes | {} views | {} | {}\n\n{}\n",
+        summary.title,
+        summary.site.question_url(summary.id),
+        summary.author_name,
+        format_date(summary.creation_date),
+        summary.score,
+        summary.view_count,
+        summary.tags.join(", "),
+        license_for(summary.creation_date).name,
+        html_to_plain_text(&question.body).trim_end()
+    );
+
+    for answer in db.get_answers(id)? {
+        let mut marks = String::new();
+        if answer.is_accepted {
+            marks.push_str(" | accepted");
+        }
+        if is_erwin(&answer.author_name) {
+            marks.push_str(" | Erwin");
+        }
+        out.push_str(&format!(
+            "\n--- Answer by {} on {} | {} votes{} | {}\n{}\n\n{}\n",
+            answer.author_name,
+            format_date(answer.creation_date),
+            answer.score,
+            marks,
+            license_for(answer.creation_date).name,
+            summary.site.answer_url(answer.answer_id),
+            html_to_plain_text(&answer.answer_text).trim_end()
+        ));
+    }
+    out.push_str(&format!(
+        "\nContent by the authors above from {}, licensed under the CC BY-SA version noted \
+         for each post (https://stackoverflow.com/help/licensing).\n",
+        summary.site.host()
+    ));
+
+    write_stdout(&out)
+}
+
+/// `erwindb cat <id> [--width N] [--color WHEN]`
+///
+/// Renders the thread the way the question page does, in one pane, and prints
+/// it with ANSI colors instead of drawing it (plain text when piped, or with
+/// `NO_COLOR` set).
+pub fn cat(args: CatArgs) -> Result<()> {
+    let config = Config::load()?;
+    styles::set_theme(Theme::from_config(&config.theme)?);
+    let id = args.question_id;
+    let db = Database::open_default()?;
+    let question = db
+        .get_question(id)?
+        .with_context(|| format!("Question {id} not found"))?;
+    let comments = db.get_question_comments(id)?;
+    let (answers, answer_comments): (Vec<_>, Vec<_>) =
+        answers_with_comments(&db, id)?.into_iter().unzip();
+
+    let width = args
+        .width
+        .or_else(|| crossterm::terminal::size().ok().map(|(width, _)| width))
+        .unwrap_or(100);
+    let superseded = match db.get_superseded(id)? {
+        Some(found) => found,
+        None => superseded::detect(question.summary.site, &answers, &answer_comments),
+    };
+    let content = build_question_content(
+        &question,
+        &answers,
+        &comments,
+        &answer_comments,
+        RelatedThreads {
+            superseded: Some(&superseded),
+            ..RelatedThreads::default()
+        },
+        usize::from(width.max(20)),
+        RenderOptions::default(),
+    );
+    if args.color.enabled() {
+        write_stdout(&lines_to_ansi(&content.lines))
+    } else {
+        write_stdout(&lines_to_text(&content.lines))
+    }
+}
+
+/// `erwindb export <id> [--format markdown] [--output FILE]`
+///
+/// Converts the question, its answers and all comments to a Markdown document.
+pub fn export(args: ExportArgs) -> Result<()> {
+    let ExportArgs {
+        question_id: id,
+        format: ExportFormat::Markdown,
+        output,
+    } = args;
+
+    let hooks = Hooks::for_cli(Config::load()?.hooks);
+    let db = Database::open_default()?;
+    let question = db
+        .get_question(id)?
+        .with_context(|| format!("Question {id} not found"))?;
+    let answers = answers_with_comments(&db, id)?;
+    let markdown = thread_markdown(&question, &db.get_question_comments(id)?, &answers);
+
+    match output {
+        Some(ref path) => {
+            fs::write(path, markdown)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Wrote {}", path.display());
+        }
+        None => write_stdout(&markdown)?,
+    }
+    export_hook(
+        hooks,
+        "markdown",
+        output.map(|path| path.display().to_string()),
+        vec![&question.summary],
+    );
+    Ok(())
+}
+
+/// Run the `[hooks] export` command, if there is one, and wait for it. The
+/// export is written by then, so a failing hook is reported, not returned.
+fn export_hook(
+    mut hooks: Hooks,
+    format: &'static str,
+    output: Option<String>,
+    questions: Vec<&QuestionSummary>,
+) {
+    let event = HookEvent::Export {
+        format,
+        output,
+        questions: questions.into_iter().map(Into::into).collect(),
+    };
+    if let Err(err) = hooks.fire(&event).and_then(|()| hooks.wait()) {
+        eprintln!("{err:#}");
+    }
+}
+
+/// `erwindb export-pdf <id> [--output FILE] [--html]`
+///
+/// Renders the thread as print-ready HTML and pipes it through the configured
+/// converter (`[export] pdf_command`); `--html` prints the HTML instead.
+pub fn export_pdf(args: ExportPdfArgs) -> Result<()> {
+    let ExportPdfArgs {
+        question_id: id,
+        output,
+        html: html_only,
+    } = args;
+
+    let config = Config::load()?;
+    let hooks = Hooks::for_cli(config.hooks);
+    let db = Database::open_default()?;
+    let question = db
+        .get_question(id)?
+        .with_context(|| format!("Question {id} not found"))?;
+    let html = thread_html(&question, &db.get_answers(id)?);
+    if html_only {
+        return write_stdout(&html);
+    }
+
+    let output = output.unwrap_or_else(|| format!("erwindb-{id}.pdf"));
+    external::run(&ExternalCommand {
+        command: config
+            .export
+            .pdf_command
+            .replace("{output}", &shell_quote(&output)),
+        input: html,
+    })?;
+    eprintln!("Wrote {output}");
+    export_hook(hooks, "pdf", Some(output), vec![&question.summary]);
+    Ok(())
+}
+
+/// `erwindb export-org <id|collection>`
+///
+/// Writes a question, or every question of a saved collection, as an Org-mode
+/// document on stdout.
+pub fn export_org(target: &str) -> Result<()> {
+    let hooks = Hooks::for_cli(Config::load()?.hooks);
+    let db = Database::open_default()?;
+    let question = match target.parse::<i64>() {
+        Ok(id) => db.get_question(id)?,
+        Err(_) => None,
+    };
+    let (title, questions) = match question {
+        Some(question) => {
+            let summary = question.summary;
+            (summary.title, vec![(summary.site, summary.id)])
+        }
+        None => {
+            let questions = UserDb::open()?
+                .get_collection(target)?
+                .with_context(|| format!("No question or collection named `{target}`"))?;
+            (target.to_string(), questions)
+        }
+    };
+
+    let threads = corpus_threads(&db, questions)?;
+
+    write_stdout(&threads_org(&title, &threads))?;
+    export_hook(
+        hooks,
+        "org",
+        None,
+        threads
+            .iter()
+            .map(|(question, _)| &question.summary)
+            .collect(),
+    );
+    Ok(())
+}
+
+/// The threads of the questions, by site and ID, that the corpus has.
+/// Collections can outlive questions dropped from the corpus, and another
+/// site's question may have the ID.
+fn corpus_threads(
+    db: &Database,
+    questions: Vec<(Site, i64)>,
+) -> Result<Vec<(db::QuestionFull, Vec<Answer>)>> {
+    let mut threads = Vec::new();
+    for (site, id) in questions {
+        if let Some(question) = db.get_question(id)?.filter(|q| q.summary.site == site) {
+            threads.push((question, db.get_answers(id)?));
+        }
+    }
+    Ok(threads)
+}
+
+/// `erwindb export-obsidian <collection> | --tag TAG [--output DIR]`
+///
+/// Writes one Markdown note per question into an Obsidian vault directory,
+/// wikilinked wherever the questions reference each other.
+pub fn export_obsidian(args: ExportObsidianArgs) -> Result<()> {
+    let ExportObsidianArgs {
+        collection,
+        tag,
+        output: dir,
+    } = args;
+
+    let hooks = Hooks::for_cli(Config::load()?.hooks);
+    let db = Database::open_default()?;
+    let questions = match (collection, tag) {
+        (Some(name), None) => UserDb::open()?
+            .get_collection(&name)?
+            .with_context(|| format!("No collection named `{name}`"))?,
+        (None, Some(tag)) => {
+            let mut questions: Vec<QuestionSummary> = db
+                .get_questions()?
+                .into_iter()
+                .filter(|q| q.tags.contains(&tag))
+                .collect();
+            questions.sort_by_key(|q| std::cmp::Reverse(q.score));
+            questions.iter().map(|q| (q.site, q.id)).collect()
+        }
+        _ => bail!("Usage: erwindb export-obsidian <collection> | --tag TAG [--output DIR]"),
+    };
+    if questions.is_empty() {
+        bail!("Nothing to export");
+    }
+
+    let threads = corpus_threads(&db, questions)?;
+    let notes = obsidian_vault(&threads, |url| {
+        extract_so_question_id(url).or_else(|| {
+            let answer_id = extract_so_answer_id(url)?;
+            db.get_question_id_for_answer(extract_site(url)?, answer_id)
+                .ok()
+                .flatten()
+        })
+    });
+
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for note in &notes {
+        let path = dir.join(format!("{}.md", note.name));
+        fs::write(&path, &note.contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    eprintln!("Wrote {} notes to {}", notes.len(), dir.display());
+    export_hook(
+        hooks,
+        "obsidian",
+        Some(dir.display().to_string()),
+        threads
+            .iter()
+            .map(|(question, _)| &question.summary)
+            .collect(),
+    );
+    Ok(())
+}
+
+/// `erwindb make-fixture [--questions N] [--out FILE] [--seed N] [--force]`
+///
+/// Writes a synthetic database with the corpus schema, for tests and
+/// benchmarks that shouldn't depend on the embedded corpus.
+pub fn make_fixture(args: MakeFixtureArgs) -> Result<()> {
+    let MakeFixtureArgs {
+        questions,
+        out,
+        seed,
+        force,
+    } = args;
+    if out.exists() {
+        if !force {
+            bail!("{} already exists (--force replaces it)", out.display());
+        }
+        fs::remove_file(&out).with_context(|| format!("Failed to remove {}", out.display()))?;
+    }
+    if let Err(err) = fixture::make_fixture(&out, questions, seed) {
+        // Don't leave a half-written database behind
+        let _ = fs::remove_file(&out);
+        return Err(err);
+    }
+    eprintln!("Wrote {questions} questions to {}", out.display());
+    Ok(())
+}
+
+pub fn state(command: StateCommand) -> Result<()> {
+    let user_db = UserDb::open()?;
+    match command {
+        StateCommand::Export { file } => {
+            let mut json = serde_json::to_string_pretty(&user_db.export_state()?)?;
+            json.push('\n');
+            if file.as_os_str() == "-" {
+                io::stdout().write_all(json.as_bytes())?;
+            } else {
+                fs::write(&file, json)
+                    .with_context(|| format!("Failed to write {}", file.display()))?;
+                eprintln!("Wrote user state to {}", file.display());
+            }
+        }
+        StateCommand::Import { file } => {
+            let json = if file.as_os_str() == "-" {
+                io::read_to_string(io::stdin())?
+            } else {
+                fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?
+            };
+            let state: UserState = serde_json::from_str(&json)
+                .with_context(|| format!("Invalid user state in {}", file.display()))?;
+            let counts = user_db.import_state(&state)?;
+            eprintln!(
+                "Added {} bookmarks, {} collections, {} collection entries, {} views, {} marks, \
+                 {} hidden questions; updated reading time of {} questions",
+                counts.bookmarks,
+                counts.collections,
+                counts.collection_items,
+                counts.views,
+                counts.marks,
+                counts.hidden,
+                counts.reading_time
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `erwindb wishlist`: `date<TAB>kind<TAB>results<TAB>query<TAB>note` for
+/// each saved search, oldest first
+pub fn wishlist() -> Result<()> {
+    let mut out = String::new();
+    for entry in UserDb::open()?.get_wishlist()? {
+        let note = entry.note.unwrap_or_default();
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            format_date(entry.added_at),
+            entry.kind,
+            entry.results,
+            entry.query.replace(['\t', '\n'], " "),
+            note.replace(['\t', '\n'], " "),
+        ));
+    }
+    write_stdout(&out)
+}
+
+/// `erwindb api quota` and `erwindb api get <path> [name=value]... [--site S]
+/// [--page N] [--pages N]`
+pub fn api(command: ApiCommand) -> Result<()> {
+    let config = Config::load()?;
+    let mut client = Client::new(&config.api);
+    match command {
+        ApiCommand::Quota => {
+            let quota = client.refresh_quota()?;
+            if client.has_key() {
+                println!("Key:   set");
+            } else {
+                println!(
+                    "Key:   none ({} requests a day; set [api] key or {})",
+                    se_api::KEYLESS_QUOTA,
+                    se_api::KEY_ENV
+                );
+            }
+            println!("Quota: {quota}");
+            if let Some((dir, count)) = client.cache_usage() {
+                println!("Cache: {count} responses in {}", dir.display());
+            }
+        }
+        ApiCommand::Get {
+            path,
+            params,
+            site,
+            page,
+            pages,
+        } => {
+            let site = parse_site(&site)?;
+            let params = params
+                .iter()
+                .map(|param| {
+                    let (name, value) = param
+                        .split_once('=')
+                        .with_context(|| format!("Expected name=value, got {param}"))?;
+                    Ok((name, value.to_string()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let fetched =
+                client.get_pages::<serde_json::Value>(site, &path, &params, page, pages)?;
+            write_json(&fetched.items)?;
+            if let Some(next_page) = fetched.next_page {
+                eprintln!("More items from --page {next_page}");
+            }
+            if let Some(quota) = client.quota() {
+                eprintln!("Quota: {quota}");
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse_site(site: &str) -> Result<Site> {
+    match site {
+        "stackoverflow" => Ok(Site::StackOverflow),
+        "dba" => Ok(Site::Dba),
+        _ => bail!("Unknown site {site} (expected stackoverflow or dba)"),
+    }
+}
+
+/// `erwindb update [--site S]`
+pub fn update(args: UpdateArgs) -> Result<()> {
+    let config = Config::load()?;
+    let db = Database::open_default()?;
+    let mut client = Client::new(&config.api);
+    let sites = match args.site {
+        Some(site) => vec![parse_site(&site)?],
+        None => Site::ALL.to_vec(),
+    };
+
+    // Keeps the threads the corpus can't take
+    let user_db = UserDb::open().ok();
+    let mut titles = Vec::new();
+    for site in sites {
+        let update = update::update_site(&db, &mut client, site)?;
+        println!(
+            "{}: {} new, {} updated threads with activity since {}",
+            site.host(),
+            update.added,
+            update.updated,
+            format_date(update.since)
+        );
+        for (id, other, thread) in update.collisions {
+            // The corpus keys questions by ID alone; as a fetched thread it's
+            // still where links to it lead
+            let kept = user_db
+                .as_ref()
+                .is_some_and(|user_db| user_db.save_fetched_question(id, site, thread).is_ok());
+            let what = if kept {
+                "kept with the fetched threads"
+            } else {
+                "skipped"
+            };
+            println!(
+                "  Question {id} {what}: {} has a question with that ID",
+                other.host()
+            );
+        }
+        if update.more {
+            println!("  More are left; run erwindb update again");
+        }
+        titles.extend(update.titles);
+    }
+    if !titles.is_empty() {
+        println!("Embedding {} titles", titles.len());
+        let semantic = SemanticSearch::new()?;
+        update::embed_titles(&db, &semantic, &titles)?;
+    }
+    if let Some(quota) = client.quota() {
+        eprintln!("Quota: {quota}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("erwindb").chain(args.iter().copied()))
+            .expect("arguments should parse")
+    }
+
+    #[test]
+    fn global_flags_before_a_subcommand() {
+        let cli = parse(&["--db", "X", "list"]);
+        assert_eq!(cli.db, Some(PathBuf::from("X")));
+        assert!(matches!(cli.command, Some(Command::List(_))));
+        assert!(cli.tui.query.is_empty());
+
+        let cli = parse(&["--deterministic", "semantic", "q"]);
+        assert!(cli.deterministic);
+        match cli.command {
+            Some(Command::Semantic(args)) => assert_eq!(args.query, ["q"]),
+            other => panic!("expected semantic, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn readme_offline_example_runs_the_subcommand() {
+        // `ERWINDB_OFFLINE=1 erwindb --deterministic semantic upsert --format json`
+        let cli = parse(&["--deterministic", "semantic", "upsert", "--format", "json"]);
+        assert!(cli.deterministic);
+        match cli.command {
+            Some(Command::Semantic(args)) => {
+                assert_eq!(args.query, ["upsert"]);
+                assert_eq!(args.format, OutputFormat::Json);
+            }
+            other => panic!("expected semantic, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn free_text_opens_the_tui_search() {
+        let cli = parse(&["free text"]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.tui.query, ["free text"]);
+
+        // Later words that happen to be subcommand names stay in the query
+        let cli = parse(&["--pick=url", "how", "to", "update", "rows"]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.tui.query, ["how", "to", "update", "rows"]);
+
+        // A query that starts with one goes after `--`
+        let cli = parse(&["--", "update", "from", "another", "table"]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.tui.query, ["update", "from", "another", "table"]);
+    }
+
+    #[test]
+    fn list_columns_parse_by_name() {
+        assert!(matches!(Column::parse("id"), Ok(Column::Id)));
+        assert!(matches!(Column::parse("excerpt"), Ok(Column::Excerpt)));
+        assert!(matches!(Column::parse("title"), Ok(Column::Title)));
+
+        let err = Column::parse("Title").unwrap_err().to_string();
+        assert!(err.starts_with("Unknown column `Title`"), "{err}");
+        assert!(Column::parse("").is_err());
+    }
+}
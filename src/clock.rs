@@ -0,0 +1,31 @@
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Instant reported by `now()` in `--deterministic` mode (2025-01-01 00:00 UTC)
+const FIXED_NOW: i64 = 1_735_689_600;
+
+static DETERMINISTIC: AtomicBool = AtomicBool::new(false);
+
+/// Pin the clock so timestamps in names, exports and the user database are reproducible
+pub fn set_deterministic() {
+    DETERMINISTIC.store(true, Ordering::Relaxed);
+}
+
+/// Whole years from a Unix timestamp until now
+pub fn years_since(timestamp: i64) -> u32 {
+    const SECONDS_PER_YEAR: i64 = 31_557_600; // 365.25 days
+    ((now().timestamp() - timestamp).max(0) / SECONDS_PER_YEAR) as u32
+}
+
+/// Current local time, or the fixed instant (in UTC) in deterministic mode.
+/// Use this instead of `Local::now()` for anything that ends up on screen or on disk.
+pub fn now() -> DateTime<FixedOffset> {
+    if DETERMINISTIC.load(Ordering::Relaxed) {
+        Utc.timestamp_opt(FIXED_NOW, 0)
+            .single()
+            .expect("valid fixed timestamp")
+            .fixed_offset()
+    } else {
+        Local::now().fixed_offset()
+    }
+}
@@ -0,0 +1,447 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+#[cfg(feature = "tui")]
+use crate::hyperlink;
+use crate::memory::DEFAULT_BUDGET_MB;
+
+/// User configuration, read from `<config dir>/erwindb/config.toml`.
+/// Every section is optional; missing keys fall back to defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub previewer: PreviewerConfig,
+    pub tts: TtsConfig,
+    pub translate: TranslateConfig,
+    pub alerts: AlertsConfig,
+    pub export: ExportConfig,
+    pub playground: PlaygroundConfig,
+    pub layout: LayoutConfig,
+    pub navigation: NavigationConfig,
+    pub keys: KeysConfig,
+    pub stale: StaleConfig,
+    pub engaged: EngagedConfig,
+    pub theme: ThemeConfig,
+    pub memory: MemoryConfig,
+    pub api: ApiConfig,
+    pub terminal: TerminalConfig,
+    pub hooks: HooksConfig,
+    /// Other corpora, picked with `--profile NAME`
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// External commands used to preview content outside the TUI.
+/// Commands run through `sh -c` with the content on stdin; `{lang}` is replaced
+/// with the code block's language.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PreviewerConfig {
+    /// Command for a single code block
+    pub code: String,
+    /// Command for a whole post (question or answer body as Markdown-ish text)
+    pub post: String,
+    /// Per-language overrides for `code`, keyed by language (e.g. `sql`)
+    pub languages: HashMap<String, String>,
+}
+
+impl Default for PreviewerConfig {
+    fn default() -> Self {
+        Self {
+            code: "bat --paging=always --language {lang}".to_string(),
+            post: "glow -p -".to_string(),
+            languages: HashMap::new(),
+        }
+    }
+}
+
+impl PreviewerConfig {
+    /// Command for a code block, honoring per-language overrides
+    pub fn code_command(&self, lang: Option<&str>) -> String {
+        let lang = lang.unwrap_or("sql");
+        let command = self.languages.get(lang).unwrap_or(&self.code);
+        command.replace("{lang}", lang)
+    }
+}
+
+/// Text-to-speech command for reading answers aloud; it gets plain text on stdin
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TtsConfig {
+    pub command: String,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        let command = if cfg!(target_os = "macos") {
+            "say"
+        } else {
+            "espeak --stdin"
+        };
+        Self {
+            command: command.to_string(),
+        }
+    }
+}
+
+/// Translation command; it gets a post's plain text on stdin and prints the
+/// translation (e.g. `trans -brief :de`). Translation is off when unset.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranslateConfig {
+    pub command: Option<String>,
+}
+
+/// Alerts checked against content added by a corpus update
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// Case-insensitive phrases matched against titles and bodies
+    pub keywords: Vec<String>,
+    /// Tags matched against the (parent) question's tags
+    pub tags: Vec<String>,
+}
+
+/// Export settings
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    /// HTML-to-PDF converter for `erwindb export-pdf`; it gets the thread as HTML
+    /// on stdin, and `{output}` is replaced with the (quoted) PDF path
+    pub pdf_command: String,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            pdf_command: "weasyprint - {output}".to_string(),
+        }
+    }
+}
+
+/// SQL playground connection: a command that reads SQL on stdin and prints the
+/// result as CSV (e.g. `psql -X -q --csv postgres:///scratch`). The playground
+/// is off when unset.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlaygroundConfig {
+    pub command: Option<String>,
+}
+
+/// Screen layout
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutConfig {
+    /// Percent of the width given to the question pane when the Erwin pane is
+    /// beside it (25-75)
+    pub split: u16,
+    /// Show each thread's reading time as a column in the question list
+    pub reading_time: bool,
+    /// Lines of context kept above and below the list selection, and above
+    /// the reading position on the question page (Vim's `scrolloff`)
+    pub scrolloff: usize,
+    /// Scrollbars beside the question list and the question page's panes
+    pub scrollbars: bool,
+    /// Narrowest terminal that puts the Erwin pane beside the question
+    /// (160 when unset)
+    pub dual_pane_width: Option<u16>,
+    /// Widest the question page's text gets (90 when unset)
+    pub content_width: Option<usize>,
+    /// Rows of the question list: one line per question, or a second line
+    /// with its excerpt (as `D` toggles)
+    pub density: Density,
+    /// Preset in effect at startup
+    pub preset: Option<String>,
+    /// Named sets of the settings above (`[layout.presets.laptop]`), each
+    /// overriding the keys it sets; Ctrl-l switches between them
+    pub presets: BTreeMap<String, LayoutPreset>,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            split: 50,
+            reading_time: false,
+            scrolloff: 3,
+            scrollbars: true,
+            dual_pane_width: None,
+            content_width: None,
+            density: Density::Compact,
+            preset: None,
+            presets: BTreeMap::new(),
+        }
+    }
+}
+
+impl LayoutConfig {
+    /// The settings with a preset applied over them; `None` for `[layout]`
+    /// as written
+    pub fn resolve(&self, preset: Option<&str>) -> Result<Layout> {
+        let base = Layout {
+            split: self.split,
+            reading_time: self.reading_time,
+            scrolloff: self.scrolloff,
+            scrollbars: self.scrollbars,
+            dual_pane_width: self.dual_pane_width,
+            content_width: self.content_width,
+            density: self.density,
+        };
+        let Some(name) = preset else {
+            return Ok(base);
+        };
+        let preset = self
+            .presets
+            .get(name)
+            .with_context(|| format!("No layout preset named `{name}` in [layout.presets]"))?;
+        Ok(Layout {
+            split: preset.split.unwrap_or(base.split),
+            reading_time: preset.reading_time.unwrap_or(base.reading_time),
+            scrolloff: preset.scrolloff.unwrap_or(base.scrolloff),
+            scrollbars: preset.scrollbars.unwrap_or(base.scrollbars),
+            dual_pane_width: preset.dual_pane_width.or(base.dual_pane_width),
+            content_width: preset.content_width.or(base.content_width),
+            density: preset.density.unwrap_or(base.density),
+        })
+    }
+}
+
+/// A named layout: the `[layout]` keys it changes
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutPreset {
+    pub split: Option<u16>,
+    pub reading_time: Option<bool>,
+    pub scrolloff: Option<usize>,
+    pub scrollbars: Option<bool>,
+    pub dual_pane_width: Option<u16>,
+    pub content_width: Option<usize>,
+    pub density: Option<Density>,
+}
+
+/// The layout in effect: `[layout]` with the current preset applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub split: u16,
+    pub reading_time: bool,
+    pub scrolloff: usize,
+    pub scrollbars: bool,
+    pub dual_pane_width: Option<u16>,
+    pub content_width: Option<usize>,
+    pub density: Density,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    #[default]
+    Compact,
+    Detailed,
+}
+
+/// Moving through the question list
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NavigationConfig {
+    /// j/k past either end of the list wrap around to the other end
+    pub wrap: bool,
+    /// Space and Ctrl-d/u scroll the list along with the selection, so it
+    /// stays on the same screen row
+    pub keep_position: bool,
+}
+
+/// Actions whose keys `[keys]` can change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    Down,
+    Up,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+    Open,
+    Back,
+    Search,
+    SemanticSearch,
+    NextErwin,
+    PrevErwin,
+    CycleTheme,
+    CycleLayout,
+}
+
+/// Keys for the main actions (`down = ["n", "ctrl-n"]`), replacing their
+/// default keys. Typing into searches, filters and the playground isn't affected.
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct KeysConfig {
+    pub bindings: BTreeMap<KeyAction, Vec<String>>,
+}
+
+/// Colors: a built-in theme (`dark`, `light`, `high-contrast`) with single
+/// colors and the code highlighting theme optionally overridden
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub name: String,
+    /// syntect theme for code blocks, e.g. `Solarized (light)`
+    pub syntax: Option<String>,
+    /// Colors by role: `header_bg = "#005f87"`, `tag = "lightblue"`
+    pub colors: BTreeMap<String, String>,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: "dark".to_string(),
+            syntax: None,
+            colors: BTreeMap::new(),
+        }
+    }
+}
+
+/// A corpus other than the embedded one: a SQLite dump with the same schema,
+/// e.g. another answerer's posts
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileConfig {
+    pub db: PathBuf,
+}
+
+/// Memory kept for content that can be loaded again: once the open thread and
+/// the cached question bodies pass `budget_mb`, the least recently viewed
+/// bodies are dropped
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MemoryConfig {
+    pub budget_mb: usize,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            budget_mb: DEFAULT_BUDGET_MB,
+        }
+    }
+}
+
+/// Stack Exchange API access. Without a key the API allows 300 requests a day
+/// per IP (10,000 with one); responses are reused for `cache_minutes`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiConfig {
+    /// Falls back to `STACKOVERFLOW_API_KEY`, as the scraper does
+    pub key: Option<String>,
+    pub cache_minutes: u64,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            key: None,
+            cache_minutes: 60,
+        }
+    }
+}
+
+/// Commands run on events through `sh -c`, with the event as JSON on stdin
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// A question was opened on the question page
+    pub open: Option<String>,
+    /// A question was bookmarked or its bookmark removed
+    pub bookmark: Option<String>,
+    /// Questions were exported (`X`, `erwindb export*`)
+    pub export: Option<String>,
+}
+
+/// Terminal features used when available
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TerminalConfig {
+    /// Make links on the question page clickable with OSC 8; detected from the
+    /// terminal when unset
+    pub hyperlinks: Option<bool>,
+}
+
+#[cfg(feature = "tui")]
+impl TerminalConfig {
+    pub fn hyperlinks(&self) -> bool {
+        self.hyperlinks.unwrap_or_else(hyperlink::supported)
+    }
+}
+
+/// Reading-time tracking: questions read for at least `minutes` in total join
+/// the `engaged` smart collection and come up for review. Off when 0.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EngagedConfig {
+    pub minutes: u32,
+}
+
+/// Warning banner on threads whose advice may be out of date
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StaleConfig {
+    /// Warn on threads at least this many years old (0 turns the age rule off)
+    pub years: u32,
+    /// Banner for old threads; `{years}` is replaced with the thread's age
+    pub message: String,
+    /// Tags that always warn, with their own banner; a trailing `*` matches a
+    /// tag prefix (e.g. `postgresql-9.*`)
+    pub tags: BTreeMap<String, String>,
+}
+
+impl Default for StaleConfig {
+    fn default() -> Self {
+        Self {
+            years: 10,
+            message: "This thread is {years} years old \u{2014} its advice may predate newer \
+                      PostgreSQL features such as declarative partitioning"
+                .to_string(),
+            tags: BTreeMap::new(),
+        }
+    }
+}
+
+impl StaleConfig {
+    /// Banner for a thread with these tags and age, if any; tag rules win over age
+    pub fn warning(&self, tags: &[String], age_years: u32) -> Option<String> {
+        let tag_rule = tags.iter().find_map(|tag| {
+            self.tags.iter().find_map(|(pattern, message)| {
+                let matches = match pattern.strip_suffix('*') {
+                    Some(prefix) => tag.starts_with(prefix),
+                    None => tag == pattern,
+                };
+                matches.then(|| message.clone())
+            })
+        });
+        tag_rule.or_else(|| {
+            (self.years > 0 && age_years >= self.years)
+                .then(|| self.message.replace("{years}", &age_years.to_string()))
+        })
+    }
+}
+
+/// A file in erwindb's config directory
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("erwindb").join(name))
+}
+
+impl Config {
+    /// Load the config file, or defaults if it doesn't exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = config_file("config.toml") else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Invalid config in {}", path.display()))
+    }
+}
@@ -1,12 +1,23 @@
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-
-use crate::db::{Answer, Comment, Question};
-use crate::html::{decode_html_entities, html_to_content, is_erwin, strip_html_tags, Link};
+use std::collections::{HashMap, HashSet};
+use unicode_width::UnicodeWidthChar;
+
+use crate::db::{Answer, Comment, CrossSiteCopy, DuplicateTarget, QuestionFull};
+use crate::html::{
+    decode_html_entities, extract_site, extract_so_answer_id, extract_so_question_id,
+    html_to_content, html_to_plain_text, is_erwin, strip_html_tags, CodeBlock, ContentLine, Hitbox,
+    Link, PostNode,
+};
+pub use crate::links::LinkedQuestion;
+use crate::quality::best_explanation;
 use crate::ui::styles;
 
-/// Maximum content width for readability on wide screens
-const MAX_CONTENT_WIDTH: usize = 90;
+/// Maximum content width for readability on wide screens, unless
+/// `[layout] content_width` says otherwise
+pub const MAX_CONTENT_WIDTH: usize = 90;
+/// Blank lines on each side of a section rule in spacious layouts
+const SPACIOUS_GAP: usize = 3;
 
 /// Wrap text to a specified width at word boundaries
 fn wrap_text(text: &str, width: usize, indent: &str) -> Vec<String> {
@@ -37,61 +48,167 @@ fn wrap_text(text: &str, width: usize, indent: &str) -> Vec<String> {
     lines
 }
 
+/// The post a rendered line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Question,
+    /// An answer, by Stack Overflow answer ID
+    Answer(i64),
+    /// Comment `n` of the question
+    QuestionComment(usize),
+    /// Comment `n` of an answer
+    AnswerComment(i64, usize),
+}
+
+/// Rendered lines that came from one element of a post. Headers, separators
+/// and banners the app draws itself have none.
+#[derive(Debug, Clone, Copy)]
+pub struct SourceSpan {
+    pub source: Source,
+    pub node: PostNode,
+    pub start_line: usize,
+    pub end_line: usize, // Exclusive
+}
+
 /// Pre-rendered content for the show page
 pub struct RenderedContent {
     pub lines: Vec<Line<'static>>,
     pub erwin_positions: Vec<usize>,
+    /// (Stack Overflow answer ID, line index) for every answer in the pane
+    pub answer_positions: Vec<(i64, usize)>,
     pub links: Vec<Link>,
+    pub code_blocks: Vec<CodeBlock>,
+    pub sources: Vec<SourceSpan>,
+    /// Line of the "Linked questions" / "Linked from" section, if any
+    pub linked_position: Option<usize>,
 }
 
 /// Pre-rendered content for the Erwin pane
 pub struct RenderedErwinContent {
     pub lines: Vec<Line<'static>>,
     pub links: Vec<Link>,
+    pub code_blocks: Vec<CodeBlock>,
+    pub sources: Vec<SourceSpan>,
+}
+
+/// Display toggles for the question content
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions<'a> {
+    /// Leave Erwin's answers out (they're in the dedicated pane)
+    pub hide_erwin: bool,
+    /// Show how each answer author's reputation changed since ingest
+    pub rep_deltas: bool,
+    /// Banner warning that the thread's advice may be out of date
+    pub stale_warning: Option<&'a str>,
+    /// Answers folded to their header and a one-line excerpt
+    pub collapsed: Option<&'a HashSet<i64>>,
+    /// Leave comment sections out, keeping a line with their count
+    pub hide_comments: bool,
+    /// More room around the rules between the question and the answers, for
+    /// presenting
+    pub spacious: bool,
+    /// Widest the text gets; `MAX_CONTENT_WIDTH` when unset
+    pub max_width: Option<usize>,
+}
+
+/// Other threads the question points to, shown under its header
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RelatedThreads<'a> {
+    /// Migrated / cross-posted copy on the other site
+    pub copy: Option<&'a CrossSiteCopy>,
+    /// Duplicate original of a closed thread without local answers
+    pub duplicate: Option<&'a DuplicateTarget>,
+    /// Newer posts Erwin's superseded answers point to, by answer ID
+    pub superseded: Option<&'a HashMap<i64, String>>,
+    /// Corpus threads the posts link to, listed at the end
+    pub linked: &'a [LinkedQuestion],
+    /// Corpus threads linking to this one, listed at the end
+    pub linked_from: &'a [LinkedQuestion],
 }
 
 pub fn build_question_content(
-    question: &Question,
+    question: &QuestionFull,
     answers: &[Answer],
     question_comments: &[Comment],
     answer_comments: &[Vec<Comment>],
+    related: RelatedThreads,
     width: usize,
-    hide_erwin: bool,
+    options: RenderOptions,
 ) -> RenderedContent {
-    let content_width = width.saturating_sub(4).min(MAX_CONTENT_WIDTH);
+    let theme = styles::theme();
+    let content_width = width
+        .saturating_sub(3)
+        .min(options.max_width.unwrap_or(MAX_CONTENT_WIDTH));
     let mut lines: Vec<Line<'static>> = Vec::new();
     let mut erwin_positions: Vec<usize> = Vec::new();
+    let mut answer_positions: Vec<(i64, usize)> = Vec::new();
     let mut all_links: Vec<Link> = Vec::new();
+    let mut code_blocks: Vec<CodeBlock> = Vec::new();
+    let mut sources: Vec<SourceSpan> = Vec::new();
+
+    let summary = &question.summary;
 
     // Title
-    let title = decode_html_entities(&question.title);
+    let title = decode_html_entities(&summary.title);
     lines.push(Line::from(Span::styled(title, styles::title_style())));
 
     // URL
     lines.push(Line::from(Span::styled(
-        format!("stackoverflow.com/questions/{}", question.id),
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::DIM),
+        format!("{}/questions/{}", summary.site.host(), summary.id),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::DIM),
     )));
 
     // Meta info
-    let date = format_date(question.creation_date);
-    lines.push(Line::from(Span::styled(
+    let date = format_date(summary.creation_date);
+    let mut meta = vec![Span::styled(
         format!(
             "Asked by {} on {}  |  {} votes  |  {} views",
-            question.author_name,
+            summary.author_name,
             date,
-            question.score,
-            format_number(question.view_count)
+            summary.score,
+            format_number(summary.view_count)
         ),
         Style::default(),
-    )));
+    )];
+    meta.extend(bounty_span(summary.bounty));
+    lines.push(Line::from(meta));
 
-    lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "\u{2500}".repeat(content_width.min(60)),
-        styles::separator_style(),
-    )));
-    lines.push(Line::from(""));
+    if !summary.tags.is_empty() {
+        let tags: Vec<String> = summary.tags.iter().map(|tag| format!("[{tag}]")).collect();
+        lines.push(Line::from(Span::styled(
+            tags.join(" "),
+            styles::tag_style(),
+        )));
+    }
+
+    if let Some(warning) = options.stale_warning {
+        lines.push(Line::from(Span::styled(
+            format!("\u{23f3} {warning}"),
+            styles::stale_warning_style(),
+        )));
+    }
+
+    // Link to the same question on another site
+    if let Some(copy) = related.copy {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "\u{21aa} View {} copy on {} (#{}) \u{2014} press M",
+                copy.reason,
+                copy.site.host(),
+                copy.question_id
+            ),
+            Style::default().fg(theme.special),
+        )));
+    }
+
+    // Erwin's answer for these usually lives on the thread this one was closed against
+    if answers.is_empty() {
+        push_no_answers_banner(&mut lines, &mut all_links, related.duplicate);
+    }
+
+    push_separator(&mut lines, content_width, options.spacious);
 
     // Question body
     lines.push(Line::from(Span::styled(
@@ -102,6 +219,12 @@ pub fn build_question_content(
 
     let body_content = html_to_content(&question.body, content_width);
     let link_offset = lines.len();
+    push_sources(
+        &mut sources,
+        Source::Question,
+        &body_content.lines,
+        link_offset,
+    );
     for content_line in body_content.lines {
         lines.push(content_line.line);
     }
@@ -110,17 +233,21 @@ pub fn build_question_content(
         link.line_index += link_offset;
         all_links.push(link);
     }
+    code_blocks.extend(offset_code_blocks(body_content.code_blocks, link_offset));
 
     // Question comments
-    if !question_comments.is_empty() {
+    if options.hide_comments {
+        push_hidden_comments(&mut lines, question_comments.len());
+    } else if !question_comments.is_empty() {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             format!("Comments ({})", question_comments.len()),
             styles::comment_header_style(),
         )));
 
-        for comment in question_comments {
+        for (n, comment) in question_comments.iter().enumerate() {
             lines.push(Line::from(""));
+            let start_line = lines.len();
             let vote_str = if comment.score > 0 {
                 format!("[+{}] ", comment.score)
             } else {
@@ -137,28 +264,31 @@ pub fn build_question_content(
                     styles::comment_text_style(),
                 )));
             }
+            sources.push(SourceSpan {
+                source: Source::QuestionComment(n),
+                node: PostNode::Text,
+                start_line,
+                end_line: lines.len(),
+            });
         }
     }
 
     // Answers
+    let best = best_explanation(answers);
     for (i, answer) in answers.iter().enumerate() {
         let author_is_erwin = is_erwin(&answer.author_name);
 
         // Skip Erwin's answers when shown in dedicated pane
-        if author_is_erwin && hide_erwin {
+        if author_is_erwin && options.hide_erwin {
             continue;
         }
 
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "\u{2500}".repeat(content_width.min(60)),
-            styles::separator_style(),
-        )));
-        lines.push(Line::from(""));
-
-        // Track Erwin answer positions for scrolling in narrow mode
+        // Track answer positions for scrolling (Erwin's in narrow mode, any for link jumps)
+        let answer_position = lines.len();
+        push_separator(&mut lines, content_width, options.spacious);
+        answer_positions.push((answer.answer_id, answer_position));
         if author_is_erwin {
-            erwin_positions.push(lines.len().saturating_sub(3));
+            erwin_positions.push(answer_position);
         }
 
         // Answer header
@@ -174,18 +304,18 @@ pub fn build_question_content(
         };
         let erwin_mark = if author_is_erwin { " \u{25c6}" } else { "" };
 
-        if author_is_erwin {
-            lines.push(Line::from(vec![
+        let mut header = if author_is_erwin {
+            vec![
                 Span::styled(" \u{25c6} ", styles::erwin_header_style()),
                 Span::styled(
                     format!("ANSWER {}{}  ({} votes)", i + 1, accepted_mark, score_str),
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.highlight)
                         .add_modifier(Modifier::BOLD),
                 ),
-            ]));
+            ]
         } else {
-            lines.push(Line::from(Span::styled(
+            vec![Span::styled(
                 format!(
                     "ANSWER {}{}{}  ({} votes)",
                     i + 1,
@@ -194,8 +324,16 @@ pub fn build_question_content(
                     score_str
                 ),
                 styles::answer_header_style(),
-            )));
+            )]
+        };
+        header.extend(bounty_span(answer.awarded_bounty));
+        if best == Some(answer.answer_id) {
+            header.push(Span::styled(
+                "  \u{2726} best explanation",
+                styles::best_explanation_style(),
+            ));
         }
+        lines.push(Line::from(header));
 
         let author_style = if author_is_erwin {
             styles::erwin_text_style()
@@ -204,18 +342,33 @@ pub fn build_question_content(
         };
 
         lines.push(Line::from(Span::styled(
-            format!(
-                "by {} ({} rep)",
-                answer.author_name,
-                format_number(answer.author_reputation)
-            ),
+            author_line(answer, options.rep_deltas),
             author_style,
         )));
+        if let Some(url) = related
+            .superseded
+            .and_then(|superseded| superseded.get(&answer.answer_id))
+        {
+            push_superseded_banner(&mut lines, &mut all_links, url);
+        }
+        if options
+            .collapsed
+            .is_some_and(|collapsed| collapsed.contains(&answer.answer_id))
+        {
+            lines.push(collapsed_summary(answer, content_width));
+            continue;
+        }
         lines.push(Line::from(""));
 
         // Answer body
         let answer_content = html_to_content(&answer.answer_text, content_width);
         let answer_link_offset = lines.len();
+        push_sources(
+            &mut sources,
+            Source::Answer(answer.answer_id),
+            &answer_content.lines,
+            answer_link_offset,
+        );
         for content_line in answer_content.lines {
             if author_is_erwin {
                 let mut spans = vec![Span::styled("\u{2502} ", styles::erwin_accent_style())];
@@ -228,21 +381,33 @@ pub fn build_question_content(
         // Adjust link line indices and add to collection
         for mut link in answer_content.links {
             link.line_index += answer_link_offset;
+            if author_is_erwin {
+                // Past the accent bar
+                link.start_col += 2;
+                link.end_col += 2;
+            }
             all_links.push(link);
         }
+        code_blocks.extend(offset_code_blocks(
+            answer_content.code_blocks,
+            answer_link_offset,
+        ));
 
         // Answer comments
         let comments = answer_comments.get(i).map(|c| c.as_slice()).unwrap_or(&[]);
-        if !comments.is_empty() {
+        if options.hide_comments {
+            push_hidden_comments(&mut lines, comments.len());
+        } else if !comments.is_empty() {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 format!("Comments ({})", comments.len()),
                 styles::comment_header_style(),
             )));
 
-            for comment in comments {
+            for (n, comment) in comments.iter().enumerate() {
                 let comment_is_erwin = is_erwin(&comment.author_name);
                 lines.push(Line::from(""));
+                let start_line = lines.len();
                 let vote_str = if comment.score > 0 {
                     format!("[+{}] ", comment.score)
                 } else {
@@ -252,7 +417,7 @@ pub fn build_question_content(
                 let comment_text = strip_html_tags(&comment.comment_text);
 
                 let style = if comment_is_erwin {
-                    Style::default().fg(Color::Yellow)
+                    Style::default().fg(theme.highlight)
                 } else {
                     styles::comment_text_style()
                 };
@@ -264,31 +429,155 @@ pub fn build_question_content(
                 for wrapped_line in wrap_text(&full_text, content_width, "    ") {
                     lines.push(Line::from(Span::styled(wrapped_line, style)));
                 }
+                sources.push(SourceSpan {
+                    source: Source::AnswerComment(answer.answer_id, n),
+                    node: PostNode::Text,
+                    start_line,
+                    end_line: lines.len(),
+                });
             }
         }
     }
 
+    let mut linked_position = None;
+    for (heading, arrow, questions) in [
+        ("Linked questions", '\u{2192}', related.linked),
+        ("Linked from", '\u{2190}', related.linked_from),
+    ] {
+        if questions.is_empty() {
+            continue;
+        }
+        lines.push(Line::from(""));
+        linked_position.get_or_insert(lines.len());
+        push_linked_questions(&mut lines, &mut all_links, heading, arrow, questions);
+    }
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
         "\u{2500}".repeat(content_width.min(60)),
         styles::separator_style(),
     )));
 
+    let (lines, map) = wrap_lines(lines, width);
+    remap_links(&mut all_links, &map);
+    remap_code_blocks(&mut code_blocks, &map);
+    remap_sources(&mut sources, &map);
+    for pos in &mut erwin_positions {
+        *pos = map.line(*pos);
+    }
+    for (_, pos) in &mut answer_positions {
+        *pos = map.line(*pos);
+    }
+    let linked_position = linked_position.map(|pos| map.line(pos));
+
     RenderedContent {
         lines,
         erwin_positions,
+        answer_positions,
         links: all_links,
+        code_blocks,
+        sources,
+        linked_position,
+    }
+}
+
+/// A heading and one line per thread, each title a link into it
+fn push_linked_questions(
+    lines: &mut Vec<Line<'static>>,
+    links: &mut Vec<Link>,
+    heading: &str,
+    arrow: char,
+    questions: &[LinkedQuestion],
+) {
+    lines.push(Line::from(Span::styled(
+        format!("{heading} ({})", questions.len()),
+        styles::comment_header_style(),
+    )));
+    for question in questions {
+        let prefix = format!("  {arrow} ");
+        let title = decode_html_entities(&question.title);
+        let start_col = unicode_width::UnicodeWidthStr::width(prefix.as_str());
+        links.push(Link {
+            url: question.site.question_url(question.id),
+            site: Some(question.site),
+            line_index: lines.len(),
+            question_id: Some(question.id),
+            answer_id: None,
+            start_col,
+            end_col: start_col + unicode_width::UnicodeWidthStr::width(title.as_str()),
+            hitboxes: Vec::new(),
+        });
+        lines.push(Line::from(vec![
+            Span::styled(prefix, styles::dim_style()),
+            Span::styled(title, styles::link_style()),
+            Span::styled(format!("  {} votes", question.score), styles::dim_style()),
+        ]));
+    }
+}
+
+/// A rule between sections, with blank lines around it
+fn push_separator(lines: &mut Vec<Line<'static>>, content_width: usize, spacious: bool) {
+    let gap = if spacious { SPACIOUS_GAP } else { 1 };
+    lines.extend(std::iter::repeat_n(Line::from(""), gap));
+    lines.push(Line::from(Span::styled(
+        "\u{2500}".repeat(content_width.min(60)),
+        styles::separator_style(),
+    )));
+    lines.extend(std::iter::repeat_n(Line::from(""), gap));
+}
+
+/// The line standing in for a hidden comment section, if there are comments
+fn push_hidden_comments(lines: &mut Vec<Line<'static>>, count: usize) {
+    if count == 0 {
+        return;
     }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("Comments ({count}) hidden \u{2014} c shows them"),
+        styles::comment_header_style().add_modifier(Modifier::DIM),
+    )));
+}
+
+/// A folded answer's excerpt, cut to one line of `width`
+fn collapsed_summary(answer: &Answer, width: usize) -> Line<'static> {
+    let text = html_to_plain_text(&answer.answer_text);
+    let mut summary = "\u{25b8} ".to_string();
+    let mut used = 2;
+    // Heading markers aren't worth the room
+    let words = text
+        .split_whitespace()
+        .filter(|word| !word.chars().all(|c| c == '#'));
+    for (index, word) in words.enumerate() {
+        let word_width: usize = word.chars().filter_map(|c| c.width()).sum();
+        let space = usize::from(index > 0);
+        if used + space + word_width + 1 > width {
+            summary.push('\u{2026}');
+            break;
+        }
+        if space == 1 {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+        used += space + word_width;
+    }
+    Line::from(Span::styled(summary, styles::dim_style()))
 }
 
 pub fn build_erwin_content(
     answer: &Answer,
     comments: &[Comment],
+    superseded_by: Option<&str>,
     width: usize,
+    max_width: usize,
+    rep_deltas: bool,
+    hide_comments: bool,
 ) -> RenderedErwinContent {
-    let content_width = width.saturating_sub(6).min(MAX_CONTENT_WIDTH);
+    let theme = styles::theme();
+    let content_width = width.saturating_sub(5).min(max_width);
     let mut lines: Vec<Line<'static>> = Vec::new();
     let mut all_links: Vec<Link> = Vec::new();
+    let mut code_blocks: Vec<CodeBlock> = Vec::new();
+    let mut sources: Vec<SourceSpan> = Vec::new();
 
     // Answer header
     let accepted_mark = if answer.is_accepted {
@@ -302,26 +591,33 @@ pub fn build_erwin_content(
         answer.score.to_string()
     };
 
-    lines.push(Line::from(Span::styled(
+    let mut header = vec![Span::styled(
         format!("ANSWER{}  ({} votes)", accepted_mark, score_str),
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD),
-    )));
+    )];
+    header.extend(bounty_span(answer.awarded_bounty));
+    lines.push(Line::from(header));
 
     lines.push(Line::from(Span::styled(
-        format!(
-            "by {} ({} rep)",
-            answer.author_name,
-            format_number(answer.author_reputation)
-        ),
+        author_line(answer, rep_deltas),
         styles::erwin_text_style(),
     )));
+    if let Some(url) = superseded_by {
+        push_superseded_banner(&mut lines, &mut all_links, url);
+    }
     lines.push(Line::from(""));
 
     // Answer body
     let answer_content = html_to_content(&answer.answer_text, content_width);
     let link_offset = lines.len();
+    push_sources(
+        &mut sources,
+        Source::Answer(answer.answer_id),
+        &answer_content.lines,
+        link_offset,
+    );
     for content_line in answer_content.lines {
         lines.push(content_line.line);
     }
@@ -330,18 +626,22 @@ pub fn build_erwin_content(
         link.line_index += link_offset;
         all_links.push(link);
     }
+    code_blocks.extend(offset_code_blocks(answer_content.code_blocks, link_offset));
 
     // Answer comments
-    if !comments.is_empty() {
+    if hide_comments {
+        push_hidden_comments(&mut lines, comments.len());
+    } else if !comments.is_empty() {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             format!("Comments ({})", comments.len()),
             styles::comment_header_style(),
         )));
 
-        for comment in comments {
+        for (n, comment) in comments.iter().enumerate() {
             let comment_is_erwin = is_erwin(&comment.author_name);
             lines.push(Line::from(""));
+            let start_line = lines.len();
             let vote_str = if comment.score > 0 {
                 format!("[+{}] ", comment.score)
             } else {
@@ -351,7 +651,7 @@ pub fn build_erwin_content(
             let comment_text = strip_html_tags(&comment.comment_text);
 
             let style = if comment_is_erwin {
-                Style::default().fg(Color::Yellow)
+                Style::default().fg(theme.highlight)
             } else {
                 styles::comment_text_style()
             };
@@ -363,12 +663,191 @@ pub fn build_erwin_content(
             for wrapped_line in wrap_text(&full_text, content_width, "    ") {
                 lines.push(Line::from(Span::styled(wrapped_line, style)));
             }
+            sources.push(SourceSpan {
+                source: Source::AnswerComment(answer.answer_id, n),
+                node: PostNode::Text,
+                start_line,
+                end_line: lines.len(),
+            });
         }
     }
 
+    let (lines, map) = wrap_lines(lines, width);
+    remap_links(&mut all_links, &map);
+    remap_code_blocks(&mut code_blocks, &map);
+    remap_sources(&mut sources, &map);
+
     RenderedErwinContent {
         lines,
         links: all_links,
+        code_blocks,
+        sources,
+    }
+}
+
+/// Shift code block line ranges from post-relative to pane-relative
+fn offset_code_blocks(blocks: Vec<CodeBlock>, offset: usize) -> Vec<CodeBlock> {
+    blocks
+        .into_iter()
+        .map(|mut block| {
+            block.start_line += offset;
+            block.end_line += offset;
+            block
+        })
+        .collect()
+}
+
+/// Record which element of a post each run of its lines (starting at pane
+/// line `offset`) came from
+fn push_sources(
+    sources: &mut Vec<SourceSpan>,
+    source: Source,
+    lines: &[ContentLine],
+    offset: usize,
+) {
+    for (i, line) in lines.iter().enumerate() {
+        match sources.last_mut() {
+            Some(span)
+                if span.source == source
+                    && span.node == line.node
+                    && span.end_line == offset + i =>
+            {
+                span.end_line += 1;
+            }
+            _ => sources.push(SourceSpan {
+                source,
+                node: line.node,
+                start_line: offset + i,
+                end_line: offset + i + 1,
+            }),
+        }
+    }
+}
+
+/// Where logical lines ended up after `wrap_lines`: for each one, its first
+/// wrapped line and the logical columns at which its continuation rows start
+struct WrapMap {
+    rows: Vec<(usize, Vec<usize>)>,
+    total: usize,
+}
+
+impl WrapMap {
+    /// First wrapped line of a logical line (or the end, for exclusive bounds)
+    fn line(&self, logical: usize) -> usize {
+        self.rows
+            .get(logical)
+            .map_or(self.total, |(first, _)| *first)
+    }
+
+    /// A logical column range, split into one hitbox per wrapped row it covers
+    fn hitboxes(&self, logical: usize, start_col: usize, end_col: usize) -> Vec<Hitbox> {
+        let Some((first, breaks)) = self.rows.get(logical) else {
+            return Vec::new();
+        };
+        let mut bounds = vec![0];
+        bounds.extend(breaks);
+        bounds.push(usize::MAX);
+        bounds
+            .windows(2)
+            .enumerate()
+            .filter_map(|(row, row_cols)| {
+                let start = start_col.max(row_cols[0]);
+                let end = end_col.min(row_cols[1]);
+                (start < end).then(|| Hitbox {
+                    line_index: first + row,
+                    start_col: start - row_cols[0],
+                    end_col: end - row_cols[0],
+                })
+            })
+            .collect()
+    }
+}
+
+/// Hard-wrap lines wider than the pane into several lines, keeping span styles.
+/// Every line then fits, so screen rows map 1:1 to lines for scrolling and
+/// mouse hit-testing.
+fn wrap_lines(lines: Vec<Line<'static>>, width: usize) -> (Vec<Line<'static>>, WrapMap) {
+    let mut wrapped = Vec::with_capacity(lines.len());
+    let mut rows = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        let first = wrapped.len();
+        if width == 0 || line.width() <= width {
+            wrapped.push(line);
+            rows.push((first, Vec::new()));
+            continue;
+        }
+
+        let mut breaks = Vec::new();
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut row_width = 0;
+        let mut col = 0;
+        for span in &line.spans {
+            let mut text = String::new();
+            for c in span.content.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if row_width + char_width > width && row_width > 0 {
+                    if !text.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut text), span.style));
+                    }
+                    wrapped.push(Line::from(std::mem::take(&mut spans)).style(line.style));
+                    breaks.push(col);
+                    row_width = 0;
+                }
+                text.push(c);
+                row_width += char_width;
+                col += char_width;
+            }
+            if !text.is_empty() {
+                spans.push(Span::styled(text, span.style));
+            }
+        }
+        wrapped.push(Line::from(spans).style(line.style));
+        rows.push((first, breaks));
+    }
+
+    let total = wrapped.len();
+    (wrapped, WrapMap { rows, total })
+}
+
+/// Plain text laid out for a pane `width` columns wide: each line
+/// word-wrapped, and words too long for a row broken, so the line count is
+/// the height it takes on screen
+pub fn wrap_plain_text(text: &str, width: usize) -> Vec<Line<'static>> {
+    let lines = text
+        .lines()
+        .flat_map(|line| wrap_text(line, width, ""))
+        .map(Line::from)
+        .collect();
+    wrap_lines(lines, width).0
+}
+
+/// Move links from logical to wrapped lines, with a hitbox per row they cover
+fn remap_links(links: &mut [Link], map: &WrapMap) {
+    for link in links {
+        link.hitboxes = map.hitboxes(link.line_index, link.start_col, link.end_col);
+        match link.hitboxes.first() {
+            Some(first) => {
+                link.line_index = first.line_index;
+                link.start_col = first.start_col;
+                link.end_col = first.end_col;
+            }
+            None => link.line_index = map.line(link.line_index),
+        }
+    }
+}
+
+fn remap_code_blocks(blocks: &mut [CodeBlock], map: &WrapMap) {
+    for block in blocks {
+        block.start_line = map.line(block.start_line);
+        block.end_line = map.line(block.end_line);
+    }
+}
+
+fn remap_sources(sources: &mut [SourceSpan], map: &WrapMap) {
+    for span in sources {
+        span.start_line = map.line(span.start_line);
+        span.end_line = map.line(span.end_line);
     }
 }
 
@@ -383,6 +862,110 @@ fn format_date(timestamp: i64) -> String {
         .unwrap_or_else(|| "N/A".to_string())
 }
 
+/// Banner for a thread with no stored answers, linking the duplicate original if known
+fn push_no_answers_banner(
+    lines: &mut Vec<Line<'static>>,
+    links: &mut Vec<Link>,
+    duplicate: Option<&DuplicateTarget>,
+) {
+    let theme = styles::theme();
+    let warning = Style::default()
+        .fg(theme.highlight)
+        .add_modifier(Modifier::BOLD);
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "\u{26a0} No answers stored locally for this question",
+        warning,
+    )));
+
+    let Some(target) = duplicate else {
+        lines.push(Line::from(Span::styled(
+            "  Erwin's answer may be on another thread \u{2014} o opens this one in the browser",
+            styles::dim_style(),
+        )));
+        return;
+    };
+
+    let url = target.url();
+    let prefix = "  Closed as a duplicate of: ";
+    let text = format!(
+        "[{}]",
+        decode_html_entities(target.local_title.as_deref().unwrap_or(&url))
+    );
+    // Same [text][n] shape as body links so focus highlighting applies
+    let start_col = unicode_width::UnicodeWidthStr::width(prefix);
+    links.push(Link {
+        url: url.clone(),
+        site: Some(target.site),
+        line_index: lines.len(),
+        question_id: Some(target.question_id),
+        answer_id: None,
+        start_col,
+        end_col: start_col + unicode_width::UnicodeWidthStr::width(text.as_str()) + 3,
+        hitboxes: Vec::new(),
+    });
+    lines.push(Line::from(vec![
+        Span::raw(prefix),
+        Span::styled(text, styles::link_style()),
+        Span::styled("[1]", Style::default().fg(theme.dim)),
+    ]));
+
+    let hint = if target.local_title.is_some() {
+        "  Press Enter to open it"
+    } else {
+        "  Not in the local corpus \u{2014} press Enter to open it in the browser"
+    };
+    lines.push(Line::from(Span::styled(hint, styles::dim_style())));
+}
+
+/// "Superseded by → newer answer #123" under a superseded answer's author,
+/// the post being a link that Enter also follows
+fn push_superseded_banner(lines: &mut Vec<Line<'static>>, links: &mut Vec<Link>, url: &str) {
+    let answer_id = extract_so_answer_id(url);
+    let question_id = extract_so_question_id(url);
+    let text = match (answer_id, question_id) {
+        (Some(id), _) => format!("newer answer #{id}"),
+        (None, Some(id)) => format!("newer question #{id}"),
+        (None, None) => "newer post".to_string(),
+    };
+    let prefix = "\u{26a0} Superseded by \u{2192} ";
+    let start_col = unicode_width::UnicodeWidthStr::width(prefix);
+    links.push(Link {
+        url: url.to_string(),
+        site: extract_site(url),
+        line_index: lines.len(),
+        question_id,
+        answer_id,
+        start_col,
+        end_col: start_col + unicode_width::UnicodeWidthStr::width(text.as_str()),
+        hitboxes: Vec::new(),
+    });
+    lines.push(Line::from(vec![
+        Span::styled(prefix, styles::superseded_style()),
+        Span::styled(text, styles::link_style()),
+        Span::styled("  (Enter)", styles::dim_style()),
+    ]));
+}
+
+/// "  +150 bounty" marker, if any reputation was put up
+fn bounty_span(bounty: i32) -> Option<Span<'static>> {
+    (bounty > 0).then(|| Span::styled(format!("  +{bounty} bounty"), styles::bounty_style()))
+}
+
+/// "by Name (12.3K rep)", plus the change since ingest when `rep_deltas` is on
+fn author_line(answer: &Answer, rep_deltas: bool) -> String {
+    let mut rep = format!("{} rep", format_number(answer.author_reputation));
+    if rep_deltas {
+        let delta = answer.author_reputation - answer.ingest_reputation;
+        rep.push_str(&match delta.cmp(&0) {
+            std::cmp::Ordering::Greater => format!(", +{} since ingest", format_number(delta)),
+            std::cmp::Ordering::Less => format!(", -{} since ingest", format_number(-delta)),
+            std::cmp::Ordering::Equal => ", unchanged since ingest".to_string(),
+        });
+    }
+    format!("by {} ({})", answer.author_name, rep)
+}
+
 fn format_number(num: i32) -> String {
     if num >= 1_000_000 {
         format!("{:.1}M", num as f64 / 1_000_000.0)
@@ -1,36 +1,185 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use lru::LruCache;
 use rusqlite::ffi::sqlite3_auto_extension;
-use rusqlite::{params, Connection, OptionalExtension};
+use rusqlite::functions::FunctionFlags;
+use rusqlite::{params, Connection, OptionalExtension, Row};
 use sqlite_vec::sqlite3_vec_init;
+use std::cell::{OnceCell, RefCell};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+#[cfg(feature = "tui")]
+use crate::fetch::FetchedThread;
+use crate::html::{
+    duplicate_notice_url, extract_site, extract_so_answer_id, extract_so_question_id, index_text,
+    link_urls,
+};
+use crate::memory::{HeapSize, DEFAULT_BUDGET_MB, MB};
+use crate::site::Site;
+use crate::terms::{code_terms, count_terms};
+pub use crate::terms::{TermCount, TermKind};
 
 /// Embedded database (compiled into the binary)
+#[cfg(not(feature = "compressed-db"))]
 const EMBEDDED_DB: &[u8] = include_bytes!("../sqlite.db");
+/// Embedded database, zstd-compressed by `build.rs`
+#[cfg(feature = "compressed-db")]
+const EMBEDDED_DB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/sqlite.db.zst"));
+/// Uncompressed size of the embedded database, which tells releases apart
+const EMBEDDED_DB_SIZE: &str = env!("ERWINDB_EMBEDDED_DB_SIZE");
+
+/// Question bodies recently read, least recently used dropped first once
+/// they pass a byte budget
+struct BodyCache {
+    bodies: LruCache<i64, String>,
+    bytes: usize,
+    budget: usize,
+}
+
+impl BodyCache {
+    fn new(budget: usize) -> Self {
+        Self {
+            bodies: LruCache::unbounded(),
+            bytes: 0,
+            budget,
+        }
+    }
+
+    fn put(&mut self, id: i64, body: String) {
+        self.bytes += body.capacity();
+        if let Some(old) = self.bodies.put(id, body) {
+            self.bytes -= old.capacity();
+        }
+        self.trim();
+    }
+
+    fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.bytes > self.budget {
+            let Some((_, body)) = self.bodies.pop_lru() else {
+                break;
+            };
+            self.bytes -= body.capacity();
+        }
+    }
+}
 
+/// Question metadata for the list (no body, which dominates load time)
 #[derive(Debug, Clone)]
-pub struct Question {
+pub struct QuestionSummary {
     pub id: i64,
     pub title: String,
-    pub body: String,
     pub score: i32,
     pub view_count: i32,
     pub answer_count: i32,
     pub creation_date: i64,
     pub accepted_answer_id: Option<i64>,
     pub author_name: String,
+    pub site: Site,
+    pub tags: Vec<String>,
+    /// Answer scores, highest first
+    pub answer_scores: Vec<i32>,
+    pub status: QuestionStatus,
+    /// Reputation offered in bounties, open or awarded (0 if none)
+    pub bounty: i32,
+    /// First sentence of the body as plain text
+    pub excerpt: String,
+    /// Words in the question and all its answers
+    pub word_count: i32,
+    /// The accepted answer is Erwin's
+    pub erwin_accepted: bool,
+}
+
+/// Reading speed behind the reading-time estimates; slow, since the words
+/// include code
+const WORDS_PER_MINUTE: i32 = 200;
+
+impl QuestionSummary {
+    /// Minutes it takes to read the whole thread, rounded (at least one)
+    pub fn reading_minutes(&self) -> i32 {
+        ((self.word_count + WORDS_PER_MINUTE / 2) / WORDS_PER_MINUTE).max(1)
+    }
+}
+
+/// Moderation state of a question
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuestionStatus {
+    pub closed: bool,
+    /// Closed as a duplicate (implies `closed`)
+    pub duplicate: bool,
+    pub migrated: bool,
+    pub locked: bool,
+}
+
+/// Colored status marker in the index and the show header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusBadge {
+    Closed,
+    Duplicate,
+    Migrated,
+    Locked,
+}
+
+impl StatusBadge {
+    pub fn label(self) -> &'static str {
+        match self {
+            StatusBadge::Closed => "closed",
+            StatusBadge::Duplicate => "duplicate",
+            StatusBadge::Migrated => "migrated",
+            StatusBadge::Locked => "locked",
+        }
+    }
+}
+
+impl QuestionStatus {
+    /// Badges to show; a duplicate closure replaces the generic closed badge
+    pub fn badges(self) -> Vec<StatusBadge> {
+        let mut badges = Vec::new();
+        if self.duplicate {
+            badges.push(StatusBadge::Duplicate);
+        } else if self.closed {
+            badges.push(StatusBadge::Closed);
+        }
+        if self.migrated {
+            badges.push(StatusBadge::Migrated);
+        }
+        if self.locked {
+            badges.push(StatusBadge::Locked);
+        }
+        badges
+    }
+}
+
+/// Question with its HTML body, loaded when a question is opened
+#[derive(Debug, Clone)]
+pub struct QuestionFull {
+    pub summary: QuestionSummary,
+    pub body: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct Answer {
     pub id: i64,
-    #[allow(dead_code)]
     pub answer_id: i64,
     pub answer_text: String,
     pub score: i32,
     pub is_accepted: bool,
     pub author_name: String,
+    /// Reputation as of the latest sync
     pub author_reputation: i32,
+    /// Reputation when the answer was first scraped
+    pub ingest_reputation: i32,
+    /// Bounty reputation this answer was awarded (0 if none)
+    pub awarded_bounty: i32,
+    pub creation_date: i64,
+    /// Last edit (or other activity); `creation_date` if never touched
+    pub last_activity_date: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -40,15 +189,90 @@ pub struct Comment {
     pub author_name: String,
 }
 
+/// Copy of a question on another site (migrated or cross-posted)
+#[derive(Debug, Clone)]
+pub struct CrossSiteCopy {
+    pub question_id: i64,
+    pub site: Site,
+    pub reason: String,
+}
+
+/// What saving a thread did to the corpus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SavedThread {
+    /// The question wasn't in it before
+    Added,
+    /// The question was, under the same title
+    Updated,
+    /// The question was, under another title (so its embedding is stale)
+    Retitled,
+    /// Another site's question has the same ID and was left alone: the
+    /// corpus keys questions by ID
+    Collides(Site),
+}
+
+/// Original question of a thread closed as a duplicate
+#[derive(Debug, Clone)]
+pub struct DuplicateTarget {
+    pub question_id: i64,
+    pub site: Site,
+    /// Title of the target when it's in the local corpus
+    pub local_title: Option<String>,
+}
+
+impl DuplicateTarget {
+    pub fn url(&self) -> String {
+        self.site.question_url(self.question_id)
+    }
+}
+
+/// Identifies a question (`answer_id` is `None`) or one of its answers; post
+/// IDs are per site
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PostKey {
+    pub site: Site,
+    pub question_id: i64,
+    pub answer_id: Option<i64>,
+}
+
+/// Searchable text of a post; answers carry their question's title and tags
+#[derive(Debug, Clone)]
+pub struct PostText {
+    pub title: String,
+    pub body: String,
+    pub tags: Vec<String>,
+}
+
 #[derive(Debug)]
 pub struct SemanticResult {
     pub question_id: i64,
-    #[allow(dead_code)]
     pub distance: f32,
 }
 
+/// A tag and the number of questions carrying it
+#[derive(Debug, Clone)]
+pub struct TagCount {
+    pub name: String,
+    pub count: usize,
+}
+
 pub struct Database {
     conn: Connection,
+    tag_counts: OnceCell<Vec<TagCount>>,
+    term_counts: OnceCell<Vec<TermCount>>,
+    tag_centroids: RefCell<HashMap<String, Option<Vec<f32>>>>,
+    bodies: RefCell<BodyCache>,
+}
+
+/// Database picked with `--db`, `ERWINDB_DB` or a profile, opened instead of
+/// the embedded one
+static DATABASE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Open the database at `path` instead of the embedded one from now on. Any
+/// SQLite file with the corpus schema works; it is migrated like the
+/// embedded copy.
+pub fn use_database(path: PathBuf) {
+    let _ = DATABASE_PATH.set(path);
 }
 
 /// Get the path where the database should be stored
@@ -60,28 +284,328 @@ fn get_db_path() -> Result<PathBuf> {
     Ok(data_dir.join("sqlite.db"))
 }
 
-/// Extract the embedded database to the data directory if it doesn't exist or is outdated
+/// Extract the embedded database to the data directory if it doesn't exist or is outdated.
+///
+/// The extracted copy is migrated in place, so its size no longer matches the embedded
+/// one; a sidecar stamp file records which embedded database it was extracted from.
 fn ensure_db_exists() -> Result<PathBuf> {
     let db_path = get_db_path()?;
+    let stamp_path = db_path.with_extension("db.stamp");
+    let stamp = EMBEDDED_DB_SIZE;
 
-    let needs_update = if db_path.exists() {
-        let cached_size = fs::metadata(&db_path).map(|m| m.len()).unwrap_or(0);
-        cached_size != EMBEDDED_DB.len() as u64
-    } else {
-        true
-    };
+    let needs_update = !db_path.exists()
+        || fs::read_to_string(&stamp_path).map_or(true, |cached| cached.trim() != stamp);
 
     if needs_update {
         if let Some(parent) = db_path.parent() {
             fs::create_dir_all(parent).context("Failed to create data directory")?;
         }
-        fs::write(&db_path, EMBEDDED_DB).context("Failed to extract database")?;
+        write_embedded_db(&db_path).context("Failed to extract database")?;
+        fs::write(&stamp_path, stamp).context("Failed to write database stamp")?;
     }
 
     Ok(db_path)
 }
 
+#[cfg(not(feature = "compressed-db"))]
+fn write_embedded_db(path: &Path) -> Result<()> {
+    fs::write(path, EMBEDDED_DB)?;
+    Ok(())
+}
+
+#[cfg(feature = "compressed-db")]
+fn write_embedded_db(path: &Path) -> Result<()> {
+    let file = fs::File::create(path)?;
+    zstd::stream::copy_decode(EMBEDDED_DB, file)?;
+    Ok(())
+}
+
+/// Columns added to the corpus schema after the original scrape, applied to older databases.
+/// The optional statement backfills the new column from existing data.
+const COLUMN_MIGRATIONS: &[(&str, &str, &str, Option<&str>)] = &[
+    (
+        "questions",
+        "site",
+        "TEXT NOT NULL DEFAULT 'stackoverflow'",
+        None,
+    ),
+    (
+        "question_ids",
+        "site",
+        "TEXT NOT NULL DEFAULT 'stackoverflow'",
+        Some(
+            "UPDATE question_ids SET site = (SELECT site FROM questions WHERE questions.id = question_ids.id)
+             WHERE id IN (SELECT id FROM questions)",
+        ),
+    ),
+    (
+        "questions",
+        "answer_scores",
+        "TEXT",
+        Some(BACKFILL_ANSWER_SCORES),
+    ),
+    (
+        "answers",
+        "ingest_reputation",
+        "INTEGER",
+        Some("UPDATE answers SET ingest_reputation = author_reputation"),
+    ),
+    (
+        "questions",
+        "closed_reason",
+        "TEXT",
+        Some(BACKFILL_DUPLICATE_NOTICES),
+    ),
+    ("questions", "duplicate_of", "INTEGER", None),
+    ("questions", "locked_date", "INTEGER", None),
+    ("answers", "awarded_bounty", "INTEGER", None),
+    ("questions", "bounty", "INTEGER", None),
+    // Computed at ingest, by the scraper (which fills in older rows too) and
+    // `save_thread`, so opening never waits on the whole corpus's HTML
+    ("questions", "excerpt", "TEXT", None),
+    ("questions", "word_count", "INTEGER", None),
+];
+
+/// Cache each question's answer scores (highest first, space-separated) so the
+/// index doesn't need a per-row aggregate over the unindexed answers table
+const BACKFILL_ANSWER_SCORES: &str = "UPDATE questions SET answer_scores = s.scores
+    FROM (SELECT question_id, group_concat(score, ' ') AS scores
+          FROM (SELECT question_id, score FROM answers ORDER BY question_id, score DESC)
+          GROUP BY question_id) s
+    WHERE s.question_id = questions.id";
+
+/// Older duplicate closures only left a notice in the question body
+const BACKFILL_DUPLICATE_NOTICES: &str = "UPDATE questions SET closed_reason = 'duplicate'
+    WHERE body LIKE '%<strong>Possible Duplicate:</strong>%'";
+
+/// Tables added to the corpus schema after the original scrape
+const TABLE_MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS question_copies (
+        question_id INTEGER NOT NULL,
+        copy_id INTEGER NOT NULL,
+        reason TEXT NOT NULL,
+        PRIMARY KEY (question_id, copy_id)
+    )",
+    "CREATE TABLE IF NOT EXISTS tag_centroids (
+        tag TEXT PRIMARY KEY,
+        embedding BLOB NOT NULL,
+        question_count INTEGER NOT NULL
+    )",
+    // Plain text of each question's body and answers (rowid = question id),
+    // filled in the background by `ensure_body_index`
+    "CREATE VIRTUAL TABLE IF NOT EXISTS question_text
+        USING fts5(body, answers)",
+    // Erwin's answers he marked superseded, and the newer post he points to
+    // (`findSuperseded` scraper command)
+    "CREATE TABLE IF NOT EXISTS superseded_answers (
+        answer_id INTEGER PRIMARY KEY,
+        newer_url TEXT NOT NULL
+    )",
+    // Questions whose embedding changed since `sync_question_vectors` last
+    // ran, filled by `STALE_VECTOR_TRIGGERS` so writers without sqlite-vec
+    // (the scraper) still leave a trail
+    "CREATE TABLE IF NOT EXISTS stale_vectors (
+        question_id INTEGER PRIMARY KEY
+    )",
+    // Where `erwindb update` left off on each site: the latest activity on
+    // Erwin's answers it has seen
+    "CREATE TABLE IF NOT EXISTS sync_state (
+        site TEXT PRIMARY KEY,
+        synced_until INTEGER NOT NULL
+    )",
+    // Functions and keywords in the code of Erwin's answers, precomputed at
+    // ingest (`countTerms` scraper command)
+    "CREATE TABLE IF NOT EXISTS term_frequencies (
+        term TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        answer_count INTEGER NOT NULL,
+        PRIMARY KEY (term, kind)
+    )",
+];
+
+/// Erwin's answers, as `html::is_erwin` tells them apart
+const ERWIN_ANSWERS: &str = "SELECT question_id, answer_text FROM answers
+     WHERE lower(author_name) LIKE '%erwin%'";
+
+/// Terms used in fewer of Erwin's answers aren't worth listing
+const MIN_TERM_ANSWERS: usize = 3;
+
+fn migrate(conn: &Connection) -> Result<()> {
+    for statement in TABLE_MIGRATIONS {
+        conn.execute_batch(statement)
+            .context("Failed to migrate database")?;
+    }
+    for (table, column, definition, backfill) in COLUMN_MIGRATIONS {
+        let exists: bool = conn.query_row(
+            &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{table}') WHERE name = ?"),
+            params![column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            conn.execute_batch(&format!(
+                "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+            ))
+            .with_context(|| format!("Failed to add {table}.{column}"))?;
+            if let Some(backfill) = backfill {
+                conn.execute_batch(backfill)
+                    .with_context(|| format!("Failed to backfill {table}.{column}"))?;
+            }
+        }
+    }
+    sync_question_vectors(conn).context("Failed to build the vector index")?;
+    Ok(())
+}
+
+/// Dimensions of the embedding model's vectors
+pub const EMBEDDING_DIMENSIONS: usize = 384;
+
+/// Embeddings of the model's size, the only ones the vector index takes
+fn indexed_embeddings() -> String {
+    format!(
+        "FROM question_embeddings WHERE length(embedding) = {}",
+        4 * EMBEDDING_DIMENSIONS
+    )
+}
+
+/// Note each question whose embedding is added, replaced or removed in
+/// `stale_vectors`
+const STALE_VECTOR_TRIGGERS: [&str; 3] = [
+    "CREATE TRIGGER IF NOT EXISTS stale_vectors_insert AFTER INSERT ON question_embeddings
+     BEGIN INSERT OR IGNORE INTO stale_vectors VALUES (new.question_id); END",
+    "CREATE TRIGGER IF NOT EXISTS stale_vectors_update AFTER UPDATE ON question_embeddings
+     BEGIN
+         INSERT OR IGNORE INTO stale_vectors VALUES (old.question_id);
+         INSERT OR IGNORE INTO stale_vectors VALUES (new.question_id);
+     END",
+    "CREATE TRIGGER IF NOT EXISTS stale_vectors_delete AFTER DELETE ON question_embeddings
+     BEGIN INSERT OR IGNORE INTO stale_vectors VALUES (old.question_id); END",
+];
+
+/// Bring the kNN index over `question_embeddings` (rowid = question id) up
+/// to date: build it whole the first time, when changes weren't tracked
+/// yet, and after that re-index only the questions `stale_vectors` lists
+fn sync_question_vectors(conn: &Connection) -> Result<()> {
+    let tracked: bool = conn.query_row(
+        "SELECT COUNT(*) = 3 FROM sqlite_master
+         WHERE type = 'trigger' AND name LIKE 'stale_vectors_%'",
+        [],
+        |row| row.get(0),
+    )?;
+    let indexed = indexed_embeddings();
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(&format!(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS question_vectors
+             USING vec0(embedding float[{EMBEDDING_DIMENSIONS}] distance_metric=cosine)"
+    ))?;
+    if tracked {
+        let stale: bool =
+            tx.query_row("SELECT EXISTS (SELECT 1 FROM stale_vectors)", [], |row| {
+                row.get(0)
+            })?;
+        if !stale {
+            return Ok(());
+        }
+        tx.execute_batch(&format!(
+            "DELETE FROM question_vectors WHERE rowid IN (SELECT question_id FROM stale_vectors);
+             INSERT INTO question_vectors (rowid, embedding)
+                 SELECT question_id, embedding {indexed}
+                 AND question_id IN (SELECT question_id FROM stale_vectors);"
+        ))?;
+    } else {
+        for trigger in STALE_VECTOR_TRIGGERS {
+            tx.execute_batch(trigger)?;
+        }
+        tx.execute_batch(&format!(
+            "DELETE FROM question_vectors;
+             INSERT INTO question_vectors (rowid, embedding)
+                 SELECT question_id, embedding {indexed};"
+        ))?;
+    }
+    tx.execute("DELETE FROM stale_vectors", [])?;
+    tx.commit()?;
+    Ok(())
+}
+
+pub fn embedding_to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|f| f.to_le_bytes()).collect()
+}
+
+fn blob_to_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Split the space-separated tag list produced by the question queries
+fn split_tags(tags: Option<String>) -> Vec<String> {
+    tags.map(|t| t.split(' ').map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Columns read by `summary_from_row`
+const SUMMARY_COLUMNS: &str = "id, title, score, view_count, answer_count,
+    creation_date, accepted_answer_id, author_name, site,
+    (SELECT group_concat(value, ' ') FROM json_each(questions.tags)),
+    answer_scores, closed_reason, duplicate_of IS NOT NULL, locked_date IS NOT NULL,
+    EXISTS (SELECT 1 FROM question_copies c
+            WHERE c.question_id = questions.id AND c.reason = 'migrated'),
+    COALESCE(bounty, 0), COALESCE(excerpt, ''), COALESCE(word_count, 0),
+    COALESCE(accepted_answer_id IN (
+        SELECT answer_id FROM answers WHERE lower(author_name) LIKE '%erwin%'
+    ), FALSE)";
+
+fn summary_from_row(row: &Row) -> rusqlite::Result<QuestionSummary> {
+    Ok(QuestionSummary {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        score: row.get(2)?,
+        view_count: row.get(3)?,
+        answer_count: row.get(4)?,
+        creation_date: row.get(5)?,
+        accepted_answer_id: row.get(6)?,
+        author_name: row.get(7)?,
+        site: Site::from_db(&row.get::<_, String>(8)?),
+        tags: split_tags(row.get(9)?),
+        answer_scores: split_scores(row.get(10)?),
+        status: status_from_row(row)?,
+        bounty: row.get(15)?,
+        excerpt: row.get(16)?,
+        word_count: row.get(17)?,
+        erwin_accepted: row.get(18)?,
+    })
+}
+
+fn status_from_row(row: &Row) -> rusqlite::Result<QuestionStatus> {
+    let closed_reason: Option<String> = row.get(11)?;
+    let has_duplicate_target: bool = row.get(12)?;
+    Ok(QuestionStatus {
+        closed: closed_reason.is_some() || has_duplicate_target,
+        duplicate: has_duplicate_target
+            || closed_reason.is_some_and(|r| r.eq_ignore_ascii_case("duplicate")),
+        locked: row.get(13)?,
+        migrated: row.get(14)?,
+    })
+}
+
+/// Parse the cached space-separated answer scores
+fn split_scores(scores: Option<String>) -> Vec<i32> {
+    scores
+        .map(|s| s.split(' ').filter_map(|v| v.parse().ok()).collect())
+        .unwrap_or_default()
+}
+
 impl Database {
+    /// Open the database picked with `use_database`, falling back to the
+    /// embedded one
+    pub fn open_default() -> Result<Self> {
+        match DATABASE_PATH.get() {
+            // Opening a missing file would create an empty database
+            Some(path) if !path.exists() => bail!("No database at {}", path.display()),
+            Some(path) => Self::open(path),
+            None => Self::open_embedded(),
+        }
+    }
+
     /// Open the embedded database (extracts to data directory on first run)
     pub fn open_embedded() -> Result<Self> {
         let db_path = ensure_db_exists()?;
@@ -96,65 +620,103 @@ impl Database {
         }
 
         let conn = Connection::open(path).context("Failed to open database")?;
+        migrate(&conn)?;
 
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            tag_counts: OnceCell::new(),
+            term_counts: OnceCell::new(),
+            tag_centroids: RefCell::new(HashMap::new()),
+            bodies: RefCell::new(BodyCache::new(DEFAULT_BUDGET_MB * MB)),
+        })
     }
 
-    pub fn get_questions(&self) -> Result<Vec<Question>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, title, body, score, view_count, answer_count,
-                    creation_date, accepted_answer_id, author_name
-             FROM questions ORDER BY id DESC",
-        )?;
+    /// Bytes the body cache may hold; least recently read bodies past it go
+    pub fn set_body_cache_budget(&self, bytes: usize) {
+        self.bodies.borrow_mut().set_budget(bytes);
+    }
+
+    /// Bodies in the cache and the bytes they hold
+    pub fn body_cache_usage(&self) -> (usize, usize) {
+        let cache = self.bodies.borrow();
+        (cache.bodies.len(), cache.bytes)
+    }
+
+    /// Tag centroids loaded so far and the bytes they hold
+    pub fn centroid_cache_usage(&self) -> (usize, usize) {
+        let centroids = self.tag_centroids.borrow();
+        let bytes = centroids
+            .iter()
+            .map(|(tag, centroid)| {
+                tag.heap_size() + centroid.as_ref().map_or(0, |c| c.capacity() * 4)
+            })
+            .sum();
+        (centroids.len(), bytes)
+    }
+
+    /// File the database was opened from, for connections on other threads
+    pub fn path(&self) -> Option<PathBuf> {
+        self.conn.path().map(PathBuf::from)
+    }
+
+    /// All question summaries for the list, newest first
+    pub fn get_questions(&self) -> Result<Vec<QuestionSummary>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {SUMMARY_COLUMNS} FROM questions ORDER BY id DESC"
+        ))?;
 
         let questions = stmt
-            .query_map([], |row| {
-                Ok(Question {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    body: row.get(2)?,
-                    score: row.get(3)?,
-                    view_count: row.get(4)?,
-                    answer_count: row.get(5)?,
-                    creation_date: row.get(6)?,
-                    accepted_answer_id: row.get(7)?,
-                    author_name: row.get(8)?,
-                })
-            })?
+            .query_map([], summary_from_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(questions)
     }
 
-    pub fn get_question(&self, id: i64) -> Result<Option<Question>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, title, body, score, view_count, answer_count,
-                    creation_date, accepted_answer_id, author_name
-             FROM questions WHERE id = ?",
-        )?;
+    pub fn get_question(&self, id: i64) -> Result<Option<QuestionFull>> {
+        let summary = self
+            .conn
+            .query_row(
+                &format!("SELECT {SUMMARY_COLUMNS} FROM questions WHERE id = ?"),
+                params![id],
+                summary_from_row,
+            )
+            .optional()?;
 
-        let question = stmt
-            .query_row(params![id], |row| {
-                Ok(Question {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    body: row.get(2)?,
-                    score: row.get(3)?,
-                    view_count: row.get(4)?,
-                    answer_count: row.get(5)?,
-                    creation_date: row.get(6)?,
-                    accepted_answer_id: row.get(7)?,
-                    author_name: row.get(8)?,
-                })
-            })
+        let Some(summary) = summary else {
+            return Ok(None);
+        };
+        let body = self.get_question_body(id)?.unwrap_or_default();
+
+        Ok(Some(QuestionFull { summary, body }))
+    }
+
+    /// Question body HTML, served from the LRU cache when recently viewed
+    pub fn get_question_body(&self, id: i64) -> Result<Option<String>> {
+        if let Some(body) = self.bodies.borrow_mut().bodies.get(&id) {
+            return Ok(Some(body.clone()));
+        }
+
+        let body: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT body FROM questions WHERE id = ?",
+                params![id],
+                |row| row.get(0),
+            )
             .optional()?;
 
-        Ok(question)
+        if let Some(ref body) = body {
+            self.bodies.borrow_mut().put(id, body.clone());
+        }
+        Ok(body)
     }
 
     pub fn get_answers(&self, question_id: i64) -> Result<Vec<Answer>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, answer_id, answer_text, score, is_accepted, author_name, author_reputation
+            "SELECT id, answer_id, answer_text, score, is_accepted, author_name, author_reputation,
+                    creation_date, COALESCE(ingest_reputation, author_reputation),
+                    COALESCE(awarded_bounty, 0),
+                    MAX(COALESCE(last_activity_date, 0), creation_date)
              FROM answers WHERE question_id = ? ORDER BY answer_order",
         )?;
 
@@ -168,6 +730,10 @@ impl Database {
                     is_accepted: row.get::<_, i32>(4)? != 0,
                     author_name: row.get(5)?,
                     author_reputation: row.get(6)?,
+                    creation_date: row.get(7)?,
+                    ingest_reputation: row.get(8)?,
+                    awarded_bounty: row.get(9)?,
+                    last_activity_date: row.get(10)?,
                 })
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -213,6 +779,332 @@ impl Database {
         Ok(comments)
     }
 
+    /// Resolve a Stack Exchange answer ID to the local question it belongs to
+    pub fn get_question_id_for_answer(&self, site: Site, answer_id: i64) -> Result<Option<i64>> {
+        let question_id = self
+            .conn
+            .query_row(
+                "SELECT a.question_id FROM answers a
+                 JOIN questions q ON q.id = a.question_id
+                 WHERE a.answer_id = ? AND q.site = ? LIMIT 1",
+                params![answer_id, site.as_db()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(question_id)
+    }
+
+    /// All tags with their question counts, most frequent first (computed once)
+    pub fn get_tag_counts(&self) -> Result<&[TagCount]> {
+        if let Some(counts) = self.tag_counts.get() {
+            return Ok(counts);
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT j.value, COUNT(*) AS count
+             FROM questions, json_each(questions.tags) j
+             GROUP BY j.value
+             ORDER BY count DESC, j.value ASC",
+        )?;
+
+        let counts = stmt
+            .query_map([], |row| {
+                Ok(TagCount {
+                    name: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as usize,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(self.tag_counts.get_or_init(|| counts))
+    }
+
+    /// Functions and keywords in the code of Erwin's answers, most used first
+    /// (computed once). Uses the counts precomputed at ingest when present,
+    /// otherwise counts on the fly.
+    pub fn get_term_counts(&self) -> Result<&[TermCount]> {
+        if let Some(counts) = self.term_counts.get() {
+            return Ok(counts);
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT term, kind, answer_count FROM term_frequencies
+             ORDER BY answer_count DESC, term ASC",
+        )?;
+        let mut counts: Vec<TermCount> = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?
+            .filter_map(|row| match row {
+                Ok((term, kind, answers)) => TermKind::parse(&kind).map(|kind| {
+                    Ok(TermCount {
+                        term,
+                        kind,
+                        answers: answers as usize,
+                    })
+                }),
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<std::result::Result<_, _>>()?;
+
+        if counts.is_empty() {
+            let mut stmt = self.conn.prepare(ERWIN_ANSWERS)?;
+            let answers = stmt
+                .query_map([], |row| row.get::<_, String>(1))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            counts = count_terms(answers.iter().map(String::as_str), MIN_TERM_ANSWERS);
+        }
+
+        Ok(self.term_counts.get_or_init(|| counts))
+    }
+
+    /// Questions where one of Erwin's answers uses the term in its code
+    pub fn questions_using_term(&self, term: &str, kind: TermKind) -> Result<Vec<i64>> {
+        // Cheap substring prefilter, then the same tokenizing as the counts
+        let mut stmt = self.conn.prepare(&format!(
+            "{ERWIN_ANSWERS} AND instr(lower(answer_text), lower(?)) > 0"
+        ))?;
+        let mut ids = Vec::new();
+        let mut rows = stmt.query(params![term])?;
+        while let Some(row) = rows.next()? {
+            let id: i64 = row.get(0)?;
+            let html: String = row.get(1)?;
+            if !ids.contains(&id) && code_terms(&html).contains(&(term.to_string(), kind)) {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Erwin's answers to the best-scored `limit` questions with the tag
+    pub fn erwin_answers_in_tag(&self, tag: &str, limit: usize) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{ERWIN_ANSWERS} AND question_id IN (
+                 SELECT id FROM questions
+                 WHERE EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ?)
+                 ORDER BY score DESC, id ASC
+                 LIMIT ?
+             )
+             ORDER BY question_id, answer_order"
+        ))?;
+        let answers = stmt
+            .query_map(params![tag, limit as i64], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(answers)
+    }
+
+    /// Find a copy of this question on another site, if ingest linked one
+    pub fn get_cross_site_copy(&self, question_id: i64) -> Result<Option<CrossSiteCopy>> {
+        let copy = self
+            .conn
+            .query_row(
+                "SELECT c.copy_id, q.site, c.reason FROM question_copies c
+                 JOIN questions q ON q.id = c.copy_id
+                 WHERE c.question_id = ? LIMIT 1",
+                params![question_id],
+                |row| {
+                    Ok(CrossSiteCopy {
+                        question_id: row.get(0)?,
+                        site: Site::from_db(&row.get::<_, String>(1)?),
+                        reason: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(copy)
+    }
+
+    /// The newer post each superseded answer of the question points to, as
+    /// found at ingest; `None` if the scraper didn't look
+    pub fn get_superseded(&self, question_id: i64) -> Result<Option<HashMap<i64, String>>> {
+        let detected: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM superseded_answers)",
+            [],
+            |row| row.get(0),
+        )?;
+        if !detected {
+            return Ok(None);
+        }
+        let mut stmt = self.conn.prepare(
+            "SELECT s.answer_id, s.newer_url FROM superseded_answers s
+             JOIN answers a ON a.answer_id = s.answer_id
+             WHERE a.question_id = ?",
+        )?;
+        let superseded = stmt
+            .query_map(params![question_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Some(superseded))
+    }
+
+    /// Where a closed duplicate points: the scraped `duplicate_of`, or else the
+    /// first link of an old-style "Possible Duplicate" notice in the body
+    pub fn get_duplicate_target(&self, question_id: i64) -> Result<Option<DuplicateTarget>> {
+        let row: Option<(Option<i64>, String)> = self
+            .conn
+            .query_row(
+                "SELECT duplicate_of, site FROM questions WHERE id = ?",
+                params![question_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((duplicate_of, site)) = row else {
+            return Ok(None);
+        };
+
+        let mut site = Site::from_db(&site);
+        let target_id = match duplicate_of {
+            Some(id) => id,
+            None => {
+                let body = self.get_question_body(question_id)?.unwrap_or_default();
+                let Some(url) = duplicate_notice_url(&body) else {
+                    return Ok(None);
+                };
+                let Some(id) = extract_so_question_id(&url) else {
+                    return Ok(None);
+                };
+                site = extract_site(&url).unwrap_or(site);
+                id
+            }
+        };
+
+        let local_title = self
+            .conn
+            .query_row(
+                "SELECT title FROM questions WHERE id = ? AND site = ?",
+                params![target_id, site.as_db()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(Some(DuplicateTarget {
+            question_id: target_id,
+            site,
+            local_title,
+        }))
+    }
+
+    /// Links between the corpus's threads, as (linking, linked) question
+    /// pairs in post order: every question or answer link in a question or
+    /// answer that leads to another question on the same site. Links to an
+    /// answer count for its question.
+    pub fn get_question_links(&self) -> Result<Vec<(i64, i64)>> {
+        let mut sites = HashMap::new();
+        let mut stmt = self.conn.prepare("SELECT id, site FROM questions")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            sites.insert(
+                row.get::<_, i64>(0)?,
+                Site::from_db(row.get_ref(1)?.as_str()?),
+            );
+        }
+        let mut stmt = self
+            .conn
+            .prepare("SELECT answer_id, question_id FROM answers")?;
+        let answers = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<std::result::Result<HashMap<_, _>, _>>()?;
+
+        // Only posts naming one of the sites can link to a thread
+        let mut stmt = self.conn.prepare(
+            "SELECT id, body FROM questions
+             WHERE body LIKE '%stackoverflow.com/%' OR body LIKE '%dba.stackexchange.com/%'
+             UNION ALL
+             SELECT question_id, answer_text FROM answers
+             WHERE answer_text LIKE '%stackoverflow.com/%'
+                OR answer_text LIKE '%dba.stackexchange.com/%'
+             ORDER BY 1",
+        )?;
+        let mut links = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let source: i64 = row.get(0)?;
+            let Some(&site) = sites.get(&source) else {
+                continue;
+            };
+            for url in link_urls(row.get_ref(1)?.as_str()?) {
+                if extract_site(url) != Some(site) {
+                    continue;
+                }
+                let target = extract_so_question_id(url)
+                    .or_else(|| answers.get(&extract_so_answer_id(url)?).copied());
+                if let Some(target) = target.filter(|t| *t != source && sites.get(t) == Some(&site))
+                {
+                    links.push((source, target));
+                }
+            }
+        }
+        Ok(links)
+    }
+
+    /// Every question and answer in the corpus
+    pub fn get_post_keys(&self) -> Result<Vec<PostKey>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT site, id, NULL FROM questions
+             UNION ALL
+             SELECT q.site, a.question_id, a.answer_id FROM answers a
+             JOIN questions q ON q.id = a.question_id
+             ORDER BY 1, 2, 3",
+        )?;
+
+        let keys = stmt
+            .query_map([], |row| {
+                Ok(PostKey {
+                    site: Site::from_db(&row.get::<_, String>(0)?),
+                    question_id: row.get(1)?,
+                    answer_id: row.get(2)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(keys)
+    }
+
+    pub fn get_post_text(&self, key: PostKey) -> Result<Option<PostText>> {
+        let tags_sql = "(SELECT group_concat(value, ' ') FROM json_each(q.tags))";
+        let text = match key.answer_id {
+            None => self.conn.query_row(
+                &format!(
+                    "SELECT q.title, q.body, {tags_sql} FROM questions q
+                     WHERE q.site = ? AND q.id = ?"
+                ),
+                params![key.site.as_db(), key.question_id],
+                |row| {
+                    Ok(PostText {
+                        title: row.get(0)?,
+                        body: row.get(1)?,
+                        tags: split_tags(row.get(2)?),
+                    })
+                },
+            ),
+            Some(answer_id) => self.conn.query_row(
+                &format!(
+                    "SELECT q.title, a.answer_text, {tags_sql} FROM answers a
+                     JOIN questions q ON q.id = a.question_id
+                     WHERE q.site = ? AND a.question_id = ? AND a.answer_id = ?"
+                ),
+                params![key.site.as_db(), key.question_id, answer_id],
+                |row| {
+                    Ok(PostText {
+                        title: row.get(0)?,
+                        body: row.get(1)?,
+                        tags: split_tags(row.get(2)?),
+                    })
+                },
+            ),
+        }
+        .optional()?;
+
+        Ok(text)
+    }
+
     #[allow(dead_code)]
     pub fn question_exists(&self, question_id: i64) -> bool {
         self.conn
@@ -224,33 +1116,429 @@ impl Database {
             .is_ok()
     }
 
+    /// Questions closest to the embedding, closest first; only those in
+    /// `within` if given
     pub fn semantic_search(
         &self,
         query_embedding: &[f32],
+        within: Option<&[i64]>,
         limit: usize,
     ) -> Result<Vec<SemanticResult>> {
-        let blob: Vec<u8> = query_embedding
-            .iter()
-            .flat_map(|f| f.to_le_bytes())
-            .collect();
+        let within = within.map(serde_json::to_string).transpose()?;
+        self.nearest_questions(
+            query_embedding,
+            limit,
+            within
+                .as_deref()
+                .map(|ids| ("AND rowid IN (SELECT value FROM json_each(?3))", ids)),
+        )
+    }
 
-        let mut stmt = self.conn.prepare(
-            "SELECT qe.question_id,
-                    vec_distance_cosine(qe.embedding, ?) as distance
-             FROM question_embeddings qe
-             ORDER BY distance ASC
-             LIMIT ?",
+    /// kNN over the vector index, narrowed by an `AND ...` clause on `rowid`
+    /// and the string it binds as `?3`
+    fn nearest_questions(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+        filter: Option<(&str, &str)>,
+    ) -> Result<Vec<SemanticResult>> {
+        let (clause, value) = filter.unwrap_or_default();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT rowid, distance FROM question_vectors
+             WHERE embedding MATCH ?1 AND k = ?2 {clause}
+             ORDER BY distance"
+        ))?;
+        let blob = embedding_to_blob(query_embedding);
+        let row_to_result = |row: &Row| {
+            Ok(SemanticResult {
+                question_id: row.get(0)?,
+                distance: row.get(1)?,
+            })
+        };
+        let mut results = if filter.is_some() {
+            stmt.query_map(params![blob, limit as i64, value], row_to_result)?
+                .collect::<std::result::Result<Vec<_>, _>>()?
+        } else {
+            stmt.query_map(params![blob, limit as i64], row_to_result)?
+                .collect::<std::result::Result<Vec<_>, _>>()?
+        };
+        // vec0 orders by distance alone; ties go by id for stable results
+        results.sort_by(|a, b| {
+            a.distance
+                .total_cmp(&b.distance)
+                .then(a.question_id.cmp(&b.question_id))
+        });
+        Ok(results)
+    }
+
+    /// Normalized mean title embedding of a tag's questions. Uses the centroid
+    /// precomputed at ingest when present, otherwise averages on the fly.
+    pub fn get_tag_centroid(&self, tag: &str) -> Result<Option<Vec<f32>>> {
+        if let Some(centroid) = self.tag_centroids.borrow().get(tag) {
+            return Ok(centroid.clone());
+        }
+
+        let precomputed: Option<Vec<u8>> = self
+            .conn
+            .query_row(
+                "SELECT embedding FROM tag_centroids WHERE tag = ?",
+                params![tag],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let centroid = match precomputed {
+            Some(blob) => Some(blob_to_embedding(&blob)),
+            None => {
+                let mut stmt = self.conn.prepare(
+                    "SELECT qe.embedding FROM question_embeddings qe
+                     JOIN questions q ON q.id = qe.question_id
+                     WHERE EXISTS (SELECT 1 FROM json_each(q.tags) WHERE value = ?)",
+                )?;
+                let mut sum: Vec<f32> = Vec::new();
+                let mut rows = stmt.query(params![tag])?;
+                while let Some(row) = rows.next()? {
+                    let embedding = blob_to_embedding(&row.get::<_, Vec<u8>>(0)?);
+                    if sum.is_empty() {
+                        sum = vec![0.0; embedding.len()];
+                    }
+                    for (acc, value) in sum.iter_mut().zip(embedding) {
+                        *acc += value;
+                    }
+                }
+                let norm = sum.iter().map(|v| v * v).sum::<f32>().sqrt();
+                (norm > 0.0).then(|| sum.iter().map(|v| v / norm).collect())
+            }
+        };
+
+        self.tag_centroids
+            .borrow_mut()
+            .insert(tag.to_string(), centroid.clone());
+        Ok(centroid)
+    }
+
+    /// Semantic search restricted to questions that do not carry `tag`
+    pub fn semantic_search_excluding_tag(
+        &self,
+        query_embedding: &[f32],
+        tag: &str,
+        limit: usize,
+    ) -> Result<Vec<SemanticResult>> {
+        self.nearest_questions(
+            query_embedding,
+            limit,
+            Some((
+                "AND rowid IN (SELECT id FROM questions
+                     WHERE NOT EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ?3))",
+                tag,
+            )),
+        )
+    }
+
+    /// Fill the full-text index of question and answer bodies if it doesn't
+    /// cover the current corpus. Slow on first run; call it off the UI thread.
+    pub fn ensure_body_index(&self) -> Result<()> {
+        let up_to_date: bool = self.conn.query_row(
+            "SELECT (SELECT COUNT(*) FROM question_text) = (SELECT COUNT(*) FROM questions)",
+            [],
+            |row| row.get(0),
         )?;
+        if up_to_date {
+            return Ok(());
+        }
 
-        let results = stmt
-            .query_map(params![blob, limit as i64], |row| {
-                Ok(SemanticResult {
-                    question_id: row.get(0)?,
-                    distance: row.get(1)?,
-                })
-            })?
+        self.conn.create_scalar_function(
+            "index_text",
+            1,
+            FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+            |ctx| Ok(index_text(&ctx.get::<String>(0)?)),
+        )?;
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute_batch(
+            "DELETE FROM question_text;
+             INSERT INTO question_text (rowid, body, answers)
+             SELECT q.id, index_text(q.body), a.text
+             FROM questions q
+             LEFT JOIN (SELECT question_id, group_concat(index_text(answer_text), ' ') AS text
+                        FROM answers GROUP BY question_id) a ON a.question_id = q.id;",
+        )
+        .context("Failed to build the body index")?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Questions whose body or answers contain every word of the query (as word
+    /// prefixes), best match first
+    pub fn body_matches(&self, query: &str) -> Result<Vec<i64>> {
+        // Quote each word so FTS5 syntax in the input is taken literally
+        let terms: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("\"{word}\"*"))
+            .collect();
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT rowid FROM question_text WHERE question_text MATCH ? ORDER BY rank")?;
+        let ids = stmt
+            .query_map(params![terms.join(" ")], |row| row.get(0))?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        Ok(results)
+        Ok(ids)
+    }
+
+    /// Activity time on Erwin's answers up to which `site` is in the corpus:
+    /// where the last `erwindb update` stopped, or else the latest activity on
+    /// any of his answers there
+    pub fn synced_until(&self, site: Site) -> Result<i64> {
+        let synced: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT synced_until FROM sync_state WHERE site = ?",
+                params![site.as_db()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(synced) = synced {
+            return Ok(synced);
+        }
+        let latest = self.conn.query_row(
+            "SELECT COALESCE(MAX(a.last_activity_date), 0) FROM answers a
+             JOIN questions q ON q.id = a.question_id
+             WHERE q.site = ? AND lower(a.author_name) LIKE '%erwin%'",
+            params![site.as_db()],
+            |row| row.get(0),
+        )?;
+        Ok(latest)
+    }
+
+    pub fn set_synced_until(&self, site: Site, until: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO sync_state (site, synced_until) VALUES (?, ?)",
+            params![site.as_db(), until],
+        )?;
+        Ok(())
+    }
+
+    /// Insert a thread fetched from the API, or replace the stored one, in
+    /// the scraper's shape. Answer authors keep the reputation they had when
+    /// first stored; `superseded` (from `superseded::detect`) is only written
+    /// when the `findSuperseded` table is in use. A stored question of the
+    /// same ID from another site is never replaced.
+    #[cfg(feature = "tui")]
+    pub fn save_thread(
+        &self,
+        thread: &FetchedThread,
+        superseded: &HashMap<i64, String>,
+    ) -> Result<SavedThread> {
+        let summary = &thread.question.summary;
+        let tx = self.conn.unchecked_transaction()?;
+
+        let old: Option<(String, String)> = tx
+            .query_row(
+                "SELECT title, site FROM questions WHERE id = ?",
+                params![summary.id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let old_title = match old {
+            Some((_, site)) if Site::from_db(&site) != summary.site => {
+                return Ok(SavedThread::Collides(Site::from_db(&site)));
+            }
+            old => old.map(|(title, _)| title),
+        };
+        let ingest_reputation: HashMap<i64, i32> = tx
+            .prepare(
+                "SELECT answer_id, COALESCE(ingest_reputation, author_reputation)
+                 FROM answers WHERE question_id = ?",
+            )?
+            .query_map(params![summary.id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<_, _>>()?;
+
+        let answer_scores = summary
+            .answer_scores
+            .iter()
+            .map(i32::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        // Total ever offered: the open bounty plus those awarded to answers
+        let bounty = summary.bounty + thread.answers.iter().map(|a| a.awarded_bounty).sum::<i32>();
+        // The scraper keys `question_ids` by (site, id) and older corpora by
+        // id alone, so this avoids naming a conflict target
+        tx.execute(
+            "INSERT OR IGNORE INTO question_ids (id, site) VALUES (?, ?)",
+            params![summary.id, summary.site.as_db()],
+        )?;
+        tx.execute(
+            "UPDATE question_ids SET scraped = TRUE, scraped_at = CURRENT_TIMESTAMP
+             WHERE id = ? AND site = ?",
+            params![summary.id, summary.site.as_db()],
+        )?;
+        tx.execute(
+            "INSERT OR REPLACE INTO questions (
+                id, title, body, score, view_count, answer_count, creation_date,
+                last_activity_date, tags, is_answered, accepted_answer_id, author_name,
+                site, answer_scores, closed_reason, duplicate_of, locked_date, bounty,
+                excerpt, word_count
+             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                summary.id,
+                summary.title,
+                thread.question.body,
+                summary.score,
+                summary.view_count,
+                summary.answer_count,
+                summary.creation_date,
+                thread.last_activity_date,
+                serde_json::to_string(&summary.tags)?,
+                !thread.answers.is_empty(),
+                summary.accepted_answer_id,
+                summary.author_name,
+                summary.site.as_db(),
+                (!answer_scores.is_empty()).then_some(answer_scores),
+                thread.closed_reason,
+                thread.duplicate_of,
+                thread.locked_date,
+                (bounty > 0).then_some(bounty),
+                summary.excerpt,
+                summary.word_count,
+            ],
+        )?;
+
+        tx.execute(
+            "DELETE FROM question_comments WHERE question_id = ?",
+            params![summary.id],
+        )?;
+        tx.execute(
+            "DELETE FROM answer_comments
+             WHERE answer_id IN (SELECT id FROM answers WHERE question_id = ?)",
+            params![summary.id],
+        )?;
+        tx.execute(
+            "DELETE FROM answers WHERE question_id = ?",
+            params![summary.id],
+        )?;
+        for comment in &thread.comments {
+            tx.execute(
+                "INSERT INTO question_comments (question_id, comment_text, score, author_name)
+                 VALUES (?, ?, ?, ?)",
+                params![
+                    summary.id,
+                    comment.comment_text,
+                    comment.score,
+                    comment.author_name
+                ],
+            )?;
+        }
+        for (index, (answer, comments)) in thread
+            .answers
+            .iter()
+            .zip(&thread.answer_comments)
+            .enumerate()
+        {
+            tx.execute(
+                "INSERT INTO answers (
+                    question_id, answer_id, answer_text, answer_order, score, is_accepted,
+                    creation_date, last_activity_date, author_name, author_reputation,
+                    ingest_reputation, awarded_bounty
+                 ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    summary.id,
+                    answer.answer_id,
+                    answer.answer_text,
+                    index as i64 + 1,
+                    answer.score,
+                    answer.is_accepted,
+                    answer.creation_date,
+                    answer.last_activity_date,
+                    answer.author_name,
+                    answer.author_reputation,
+                    ingest_reputation
+                        .get(&answer.answer_id)
+                        .copied()
+                        .unwrap_or(answer.author_reputation),
+                    (answer.awarded_bounty > 0).then_some(answer.awarded_bounty),
+                ],
+            )?;
+            let answer_row = tx.last_insert_rowid();
+            for comment in comments {
+                tx.execute(
+                    "INSERT INTO answer_comments (answer_id, comment_text, score, author_name)
+                     VALUES (?, ?, ?, ?)",
+                    params![
+                        answer_row,
+                        comment.comment_text,
+                        comment.score,
+                        comment.author_name
+                    ],
+                )?;
+            }
+        }
+
+        let precomputed: bool = tx.query_row(
+            "SELECT EXISTS (SELECT 1 FROM superseded_answers)",
+            [],
+            |row| row.get(0),
+        )?;
+        if precomputed {
+            for answer in &thread.answers {
+                tx.execute(
+                    "DELETE FROM superseded_answers WHERE answer_id = ?",
+                    params![answer.answer_id],
+                )?;
+            }
+            for (answer_id, url) in superseded {
+                tx.execute(
+                    "INSERT INTO superseded_answers (answer_id, newer_url) VALUES (?, ?)",
+                    params![answer_id, url],
+                )?;
+            }
+        }
+        // Leaves the body index a row short, so `ensure_body_index` rebuilds it
+        tx.execute(
+            "DELETE FROM question_text WHERE rowid = ?",
+            params![summary.id],
+        )?;
+        tx.commit()?;
+
+        self.bodies
+            .borrow_mut()
+            .put(summary.id, thread.question.body.clone());
+        Ok(match old_title {
+            None => SavedThread::Added,
+            Some(title) if title == summary.title => SavedThread::Updated,
+            Some(_) => SavedThread::Retitled,
+        })
+    }
+
+    /// Store a question's title embedding and index it for kNN search
+    pub fn save_embedding(&self, question_id: i64, embedding: &[f32]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO question_embeddings (question_id, embedding) VALUES (?, ?)",
+            params![question_id, embedding_to_blob(embedding)],
+        )?;
+        tx.execute(
+            "DELETE FROM question_vectors WHERE rowid = ?",
+            params![question_id],
+        )?;
+        tx.execute(
+            &format!(
+                "INSERT INTO question_vectors (rowid, embedding)
+                 SELECT question_id, embedding {} AND question_id = ?",
+                indexed_embeddings()
+            ),
+            params![question_id],
+        )?;
+        // Indexed already, so the trigger's note is moot
+        tx.execute(
+            "DELETE FROM stale_vectors WHERE question_id = ?",
+            params![question_id],
+        )?;
+        tx.commit()?;
+        Ok(())
     }
 }
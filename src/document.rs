@@ -0,0 +1,397 @@
+//! Typed, renderer-neutral view of a thread for front ends other than the
+//! TUI (`erwindb show --format document`): posts laid out as blocks of styled
+//! text, code and tables, with their links resolved, and no ratatui types.
+//!
+//! These types are a stable interface. Within a `DOCUMENT_VERSION`, fields
+//! and variants are only ever added, never renamed, removed or changed in
+//! meaning; structs and enums are `#[non_exhaustive]` so adding one isn't a
+//! breaking change, and readers should ignore what they don't know.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::{Answer, Comment, QuestionFull};
+use crate::html::{
+    extract_site, extract_so_answer_id, extract_so_question_id, is_erwin, strip_html_tags,
+    LINK_REF_REGEX,
+};
+use crate::license::license_for;
+use crate::sanitize::{prepare, split_list_marker, Inline, PreparedLine};
+
+/// Bumped only for changes that could break existing readers
+pub const DOCUMENT_VERSION: u32 = 1;
+
+/// A question with its answers, each post rendered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Thread {
+    pub version: u32,
+    pub id: i64,
+    /// `stackoverflow` or `dba`
+    pub site: String,
+    pub url: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub view_count: i32,
+    pub question: Post,
+    /// Accepted first, then by score, as the app shows them
+    pub answers: Vec<Post>,
+}
+
+/// The question or an answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Post {
+    pub id: i64,
+    pub url: String,
+    pub author: String,
+    pub score: i32,
+    /// Always false for the question
+    pub accepted: bool,
+    /// Written by Erwin Brandstetter
+    pub erwin: bool,
+    /// Unix seconds
+    pub created: i64,
+    /// CC BY-SA version the post is under
+    pub license: String,
+    pub document: RenderedDocument,
+    pub comments: Vec<PostComment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PostComment {
+    pub author: String,
+    pub score: i32,
+    /// Plain text
+    pub text: String,
+}
+
+/// A post's body laid out for a given width
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RenderedDocument {
+    /// Columns the text was wrapped to
+    pub width: usize,
+    pub blocks: Vec<Block>,
+    /// Referenced by `Span::link`
+    pub links: Vec<DocumentLink>,
+}
+
+/// A run of a post. `quote_depth` is the number of blockquotes it's in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Block {
+    /// Running text (paragraphs, list items, headings) up to a blank line,
+    /// one entry per wrapped line
+    Text {
+        quote_depth: usize,
+        lines: Vec<Vec<Span>>,
+    },
+    /// A `<pre>` block as written; `lang` is its highlighting hint
+    Code {
+        quote_depth: usize,
+        code: String,
+        lang: Option<String>,
+    },
+    /// Cell text, row by row; the first `header_rows` rows are headers
+    Table {
+        quote_depth: usize,
+        header_rows: usize,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+/// Text with one formatting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Span {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub strong: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub emphasis: bool,
+    /// Inline code
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub code: bool,
+    /// Index into `RenderedDocument::links`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DocumentLink {
+    pub text: String,
+    pub url: String,
+    pub target: LinkTarget,
+}
+
+/// Where a link leads, as the app would follow it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum LinkTarget {
+    /// A question on a supported site (maybe not in the corpus)
+    Question { site: String, question_id: i64 },
+    /// An answer on a supported site; short `/a/` links don't name the
+    /// question
+    Answer {
+        site: String,
+        question_id: Option<i64>,
+        answer_id: i64,
+    },
+    /// Anything else, for the browser
+    External,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl Thread {
+    /// The thread with every post rendered at `width`
+    pub fn new(
+        question: &QuestionFull,
+        comments: &[Comment],
+        answers: &[(Answer, Vec<Comment>)],
+        width: usize,
+    ) -> Self {
+        let summary = &question.summary;
+        let site = summary.site;
+        Self {
+            version: DOCUMENT_VERSION,
+            id: summary.id,
+            site: site.as_db().to_string(),
+            url: site.question_url(summary.id),
+            title: summary.title.clone(),
+            tags: summary.tags.clone(),
+            view_count: summary.view_count,
+            question: Post {
+                id: summary.id,
+                url: site.question_url(summary.id),
+                author: summary.author_name.clone(),
+                score: summary.score,
+                accepted: false,
+                erwin: is_erwin(&summary.author_name),
+                created: summary.creation_date,
+                license: license_for(summary.creation_date).name.to_string(),
+                document: RenderedDocument::render(&question.body, width),
+                comments: post_comments(comments),
+            },
+            answers: answers
+                .iter()
+                .map(|(answer, comments)| Post {
+                    id: answer.answer_id,
+                    url: site.answer_url(answer.answer_id),
+                    author: answer.author_name.clone(),
+                    score: answer.score,
+                    accepted: answer.is_accepted,
+                    erwin: is_erwin(&answer.author_name),
+                    created: answer.creation_date,
+                    license: license_for(answer.creation_date).name.to_string(),
+                    document: RenderedDocument::render(&answer.answer_text, width),
+                    comments: post_comments(comments),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn post_comments(comments: &[Comment]) -> Vec<PostComment> {
+    comments
+        .iter()
+        .map(|comment| PostComment {
+            author: comment.author_name.clone(),
+            score: comment.score,
+            text: strip_html_tags(&comment.comment_text),
+        })
+        .collect()
+}
+
+impl RenderedDocument {
+    /// A post's HTML as blocks, text wrapped to `width` columns the way the
+    /// question page wraps it
+    pub fn render(html: &str, width: usize) -> Self {
+        let prepared = prepare(html, width);
+        let links = prepared
+            .links
+            .iter()
+            .map(|(text, url)| DocumentLink {
+                text: text.clone(),
+                url: url.clone(),
+                target: link_target(url),
+            })
+            .collect::<Vec<_>>();
+
+        let mut blocks = Vec::new();
+        let mut paragraph: Option<(usize, Vec<&PreparedLine>)> = None;
+        let flush = |paragraph: &mut Option<(usize, Vec<&PreparedLine>)>,
+                     blocks: &mut Vec<Block>| {
+            if let Some((quote_depth, lines)) = paragraph.take() {
+                let lines = paragraph_lines(&lines, links.len());
+                blocks.push(Block::Text { quote_depth, lines });
+            }
+        };
+        for line in &prepared.lines {
+            let quote_depth = line.quote_depth;
+            if let Some(index) = prepared.code_block_index(&line.text) {
+                flush(&mut paragraph, &mut blocks);
+                push_list_marker(&mut blocks, line, links.len());
+                let (code, lang) = &prepared.code_blocks[index];
+                blocks.push(Block::Code {
+                    quote_depth,
+                    code: code.clone(),
+                    lang: lang.clone(),
+                });
+            } else if let Some(index) = prepared.table_index(&line.text) {
+                flush(&mut paragraph, &mut blocks);
+                push_list_marker(&mut blocks, line, links.len());
+                let table = &prepared.tables[index];
+                blocks.push(Block::Table {
+                    quote_depth,
+                    header_rows: table.header_rows,
+                    rows: table.rows.clone(),
+                });
+            } else if line.text.trim().is_empty() {
+                flush(&mut paragraph, &mut blocks);
+            } else {
+                if paragraph
+                    .as_ref()
+                    .is_some_and(|(depth, _)| *depth != quote_depth)
+                {
+                    flush(&mut paragraph, &mut blocks);
+                }
+                paragraph
+                    .get_or_insert_with(|| (quote_depth, Vec::new()))
+                    .1
+                    .push(line);
+            }
+        }
+        flush(&mut paragraph, &mut blocks);
+
+        Self {
+            width,
+            blocks,
+            links,
+        }
+    }
+}
+
+fn link_target(url: &str) -> LinkTarget {
+    let Some(site) = extract_site(url) else {
+        return LinkTarget::External;
+    };
+    let site = site.as_db().to_string();
+    let question_id = extract_so_question_id(url);
+    match (question_id, extract_so_answer_id(url)) {
+        (question_id, Some(answer_id)) => LinkTarget::Answer {
+            site,
+            question_id,
+            answer_id,
+        },
+        (Some(question_id), None) => LinkTarget::Question { site, question_id },
+        (None, None) => LinkTarget::External,
+    }
+}
+
+/// The list item marker a code block or table placeholder line starts with,
+/// as a paragraph of its own before the block
+fn push_list_marker(blocks: &mut Vec<Block>, line: &PreparedLine, link_count: usize) {
+    let marker = split_list_marker(&line.text).0.trim();
+    if marker.is_empty() {
+        return;
+    }
+    let marker_line = PreparedLine {
+        quote_depth: line.quote_depth,
+        text: marker.to_string(),
+        runs: vec![(marker.len(), Inline::default())],
+    };
+    blocks.push(Block::Text {
+        quote_depth: line.quote_depth,
+        lines: paragraph_lines(&[&marker_line], link_count),
+    });
+}
+
+/// A paragraph's lines as spans, each `[text][n]` link reference reduced to
+/// its text and pointing at link `n - 1`. References are found across the
+/// paragraph, since wrapping can break one over lines.
+fn paragraph_lines(lines: &[&PreparedLine], link_count: usize) -> Vec<Vec<Span>> {
+    let mut text = String::new();
+    let mut runs: Vec<(usize, usize, Inline)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            runs.push((text.len(), text.len() + 1, Inline::default()));
+            text.push('\n');
+        }
+        let mut run_start = text.len();
+        for &(len, inline) in &line.runs {
+            runs.push((run_start, run_start + len, inline));
+            run_start += len;
+        }
+        text.push_str(&line.text);
+        if run_start < text.len() {
+            runs.push((run_start, text.len(), Inline::default()));
+        }
+    }
+    let text = text.as_str();
+
+    // Byte ranges kept, with the link they belong to; reference brackets
+    // and numbers fall between them
+    let mut kept: Vec<(usize, usize, Option<usize>)> = Vec::new();
+    let mut pos = 0;
+    for cap in LINK_REF_REGEX.captures_iter(text) {
+        let (Some(full_match), Some(label), Some(num)) = (cap.get(0), cap.get(1), cap.get(2))
+        else {
+            continue;
+        };
+        let Some(link) = num
+            .as_str()
+            .parse::<usize>()
+            .ok()
+            .filter(|&n| n > 0 && n <= link_count)
+        else {
+            continue;
+        };
+        kept.push((pos, full_match.start(), None));
+        kept.push((label.start(), label.end(), Some(link - 1)));
+        pos = full_match.end();
+    }
+    kept.push((pos, text.len(), None));
+
+    let mut lines = vec![Vec::new()];
+    for &(start, end, link) in &kept {
+        for &(run_start, run_end, inline) in &runs {
+            let (from, to) = (start.max(run_start), end.min(run_end));
+            if from >= to {
+                continue;
+            }
+            for (i, piece) in text[from..to].split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(Vec::new());
+                }
+                if piece.is_empty() {
+                    continue;
+                }
+                let spans: &mut Vec<Span> = lines.last_mut().unwrap();
+                match spans.last_mut() {
+                    Some(last)
+                        if last.link == link
+                            && (last.strong, last.emphasis, last.code)
+                                == (inline.strong, inline.emphasis, inline.code) =>
+                    {
+                        last.text.push_str(piece);
+                    }
+                    _ => spans.push(Span {
+                        text: piece.to_string(),
+                        strong: inline.strong,
+                        emphasis: inline.emphasis,
+                        code: inline.code,
+                        link,
+                    }),
+                }
+            }
+        }
+    }
+    lines
+}
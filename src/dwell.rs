@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use crate::site::Site;
+
+/// Longest gap between events that still counts as reading; anything longer
+/// means the reader stepped away
+const IDLE_CAP: Duration = Duration::from_secs(120);
+
+/// Days until an engaged question is due for review, by how often it has been
+/// reviewed (the last interval repeats)
+const REVIEW_INTERVAL_DAYS: &[i64] = &[1, 3, 7, 21, 60];
+
+/// Time spent on the open question, counted from event to event
+#[derive(Debug, Default)]
+pub struct DwellTracker {
+    current: Option<Dwell>,
+}
+
+#[derive(Debug)]
+struct Dwell {
+    /// Site and ID of the question
+    question: (Site, i64),
+    last_event: Instant,
+    elapsed: Duration,
+}
+
+impl DwellTracker {
+    /// Note the question open after an event, by site and ID (`None` if none
+    /// is). Returns the question just left and the time spent on it.
+    pub fn observe(&mut self, open: Option<(Site, i64)>) -> Option<((Site, i64), Duration)> {
+        let now = Instant::now();
+        if let Some(ref mut dwell) = self.current {
+            dwell.elapsed += now.duration_since(dwell.last_event).min(IDLE_CAP);
+            dwell.last_event = now;
+            if open == Some(dwell.question) {
+                return None;
+            }
+        }
+
+        let left = self.current.take().map(|d| (d.question, d.elapsed));
+        self.current = open.map(|question| Dwell {
+            question,
+            last_event: now,
+            elapsed: Duration::ZERO,
+        });
+        left
+    }
+}
+
+/// A question read long enough to join the engaged collection
+#[derive(Debug, Clone)]
+pub struct EngagedQuestion {
+    pub site: Site,
+    pub question_id: i64,
+    /// Reading time over all visits
+    pub seconds: i64,
+    /// When the reading time crossed the threshold
+    pub engaged_at: i64,
+    pub reviews: i64,
+    pub reviewed_at: Option<i64>,
+}
+
+impl EngagedQuestion {
+    /// When the question is next due for review (Unix seconds): a day after it
+    /// became engaged, then at growing intervals after each review
+    pub fn due_at(&self) -> i64 {
+        let step = (self.reviews as usize).min(REVIEW_INTERVAL_DAYS.len() - 1);
+        self.reviewed_at.unwrap_or(self.engaged_at) + REVIEW_INTERVAL_DAYS[step] * 86_400
+    }
+}
@@ -1,48 +1,112 @@
 use anyhow::Result;
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent};
+use crossterm::event::{
+    self, Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind,
+};
+use std::cell::Cell;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
 use std::time::Duration;
 
 pub enum Event {
     Tick,
+    /// The title search's workers have new matches
+    Wake,
     Key(KeyEvent),
     Mouse(MouseEvent),
     Resize(u16, u16),
+    /// Bracketed paste
+    Paste(String),
 }
 
 pub struct EventHandler {
     tick_rate: Duration,
+    /// Drop all but the last pending event of each kind. Off in `--deterministic`
+    /// mode, where every input must apply regardless of timing.
+    coalesce: bool,
+    /// Event read while coalescing that ended the burst, returned next
+    pending: Cell<Option<Event>>,
+    /// Sent by `waker`s; any number of wake-ups make one `Event::Wake`
+    wake_sender: Sender<()>,
+    wakes: Receiver<()>,
 }
 
 impl EventHandler {
-    pub fn new(tick_rate_ms: u64) -> Self {
+    pub fn new(tick_rate_ms: u64, coalesce: bool) -> Self {
+        let (wake_sender, wakes) = mpsc::channel();
         Self {
             tick_rate: Duration::from_millis(tick_rate_ms),
+            coalesce,
+            pending: Cell::new(None),
+            wake_sender,
+            wakes,
         }
     }
 
+    /// A callback for background threads that queues an `Event::Wake`. It's
+    /// seen before the next wait for input, within one tick.
+    pub fn waker(&self) -> Arc<dyn Fn() + Send + Sync> {
+        let sender = self.wake_sender.clone();
+        Arc::new(move || {
+            let _ = sender.send(());
+        })
+    }
+
     /// Poll for the next event, coalescing repeated key events to prevent lag
     pub fn next(&self) -> Result<Event> {
+        if let Some(event) = self.pending.take() {
+            return Ok(event);
+        }
+        if self.wakes.try_iter().count() > 0 {
+            return Ok(Event::Wake);
+        }
+
         // Wait for at least one event
         if !event::poll(self.tick_rate)? {
             return Ok(Event::Tick);
         }
 
+        if !self.coalesce {
+            return Ok(match event::read()? {
+                CrosstermEvent::Key(key) if key.kind != KeyEventKind::Release => Event::Key(key),
+                CrosstermEvent::Mouse(mouse) => Event::Mouse(mouse),
+                CrosstermEvent::Resize(w, h) => Event::Resize(w, h),
+                CrosstermEvent::Paste(text) => Event::Paste(text),
+                _ => Event::Tick,
+            });
+        }
+
         let mut last_key: Option<KeyEvent> = None;
         let mut last_mouse: Option<MouseEvent> = None;
         let mut last_resize: Option<(u16, u16)> = None;
 
-        // Read all pending events, keeping only the last of each type
+        // Read all pending events, keeping only the last of each type. Only runs
+        // of the same key (held j/k) collapse; typed text needs every key, and
+        // only pointer moves and drags collapse, not clicks or wheel notches.
         loop {
             match event::read()? {
-                CrosstermEvent::Key(key) => {
-                    // Skip key release events
-                    if key.kind != KeyEventKind::Release {
-                        last_key = Some(key);
+                // Skip key release events
+                CrosstermEvent::Key(key) if key.kind != KeyEventKind::Release => {
+                    if last_key.is_some_and(|last| {
+                        (last.code, last.modifiers) != (key.code, key.modifiers)
+                    }) {
+                        self.pending.set(Some(Event::Key(key)));
+                        break;
                     }
+                    last_key = Some(key);
                 }
-                CrosstermEvent::Mouse(mouse) => {
+                CrosstermEvent::Paste(text) => {
+                    self.pending.set(Some(Event::Paste(text)));
+                    break;
+                }
+                CrosstermEvent::Mouse(mouse)
+                    if matches!(mouse.kind, MouseEventKind::Moved | MouseEventKind::Drag(_)) =>
+                {
                     last_mouse = Some(mouse);
                 }
+                CrosstermEvent::Mouse(mouse) => {
+                    self.pending.set(Some(Event::Mouse(mouse)));
+                    break;
+                }
                 CrosstermEvent::Resize(w, h) => {
                     last_resize = Some((w, h));
                 }
@@ -0,0 +1,569 @@
+use chrono::{TimeZone, Utc};
+use regex::{Captures, Regex};
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use crate::db::{Answer, Comment, QuestionFull, QuestionSummary};
+use crate::html::{decode_html_entities, html_to_plain_text, is_erwin};
+use crate::license::{attribution_html, attribution_markdown, license_for, Attribution};
+use crate::markdown::{demote_headings, html_to_markdown};
+use crate::sanitize::{extract_lang_from_class, sanitize_html};
+
+static ANCHOR_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)<a\s[^>]*?href="([^"]*)"[^>]*>(.*?)</a>"#).unwrap());
+static PRE_BLOCK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<pre(?:\s[^>]*?class="([^"]*)")?[^>]*>(.*?)</pre>"#).unwrap()
+});
+static CODE_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"</?code[^>]*>").unwrap());
+
+/// Code block language for code without a `lang-*` hint; the corpus is SQL
+const DEFAULT_LANG: &str = "sql";
+
+/// Longest note file name taken from a question title, in characters
+const NOTE_NAME_MAX_CHARS: usize = 100;
+/// Characters Obsidian doesn't allow in note names or that break wikilinks
+const NOTE_NAME_FORBIDDEN: &str = "\\/:*?\"<>|#^[]";
+
+/// Print stylesheet for `thread_html`: monospace code boxes that don't split
+/// across pages, and small footnote lists under each post
+const THREAD_CSS: &str = "
+@page { size: A4; margin: 2cm; }
+body { font-family: Georgia, serif; font-size: 11pt; line-height: 1.45; }
+h1 { font-size: 18pt; margin-bottom: 0.2em; }
+h2 { font-size: 13pt; margin: 0; }
+.meta { color: #555; font-size: 9pt; margin: 0.2em 0 1em; }
+.post { border-top: 1px solid #bbb; margin-top: 1.5em; padding-top: 0.8em; }
+.erwin h2 { color: #8a6d00; }
+pre { font-family: monospace; font-size: 8.5pt; background: #f6f8fa; border: 1px solid #d0d7de;
+      border-radius: 4px; padding: 0.6em 0.8em; white-space: pre-wrap; page-break-inside: avoid; }
+code { font-family: monospace; font-size: 0.9em; }
+img { max-width: 100%; }
+sup.fn { font-size: 7pt; color: #555; }
+ol.footnotes { font-size: 8pt; color: #555; word-break: break-all; }
+.attribution { font-size: 8pt; color: #555; margin-top: 2em; }
+";
+
+fn format_day(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Markdown reading list: one entry per question with its link and stats,
+/// followed by the attribution the question titles require
+pub fn reading_list_markdown(title: &str, questions: &[&QuestionSummary]) -> String {
+    let mut out = format!("# {title}\n\n");
+    for q in questions {
+        let asked = format_day(q.creation_date);
+        out.push_str(&format!(
+            "- [{}]({}) \u{2014} {} votes, {} answers, asked {}",
+            q.title,
+            q.site.question_url(q.id),
+            q.score,
+            q.answer_count,
+            asked
+        ));
+        if !q.tags.is_empty() {
+            let tags: Vec<String> = q.tags.iter().map(|t| format!("`{t}`")).collect();
+            out.push_str(&format!(" \u{2014} {}", tags.join(" ")));
+        }
+        out.push('\n');
+    }
+
+    let attributions: Vec<Attribution> = questions
+        .iter()
+        .map(|q| Attribution {
+            kind: "Question",
+            title: q.title.clone(),
+            author: q.author_name.clone(),
+            url: q.site.question_url(q.id),
+            license: license_for(q.creation_date),
+        })
+        .collect();
+    out.push('\n');
+    out.push_str(&attribution_markdown(&attributions));
+    out
+}
+
+/// Standalone, print-ready HTML document of a question and its answers, for
+/// HTML-to-PDF converters. Links become numbered footnotes under each post.
+pub fn thread_html(question: &QuestionFull, answers: &[Answer]) -> String {
+    let summary = &question.summary;
+    let mut footnote = 0;
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        summary.title, THREAD_CSS, summary.title
+    );
+
+    out.push_str(&format!(
+        "<section class=\"post\">\n<h2>Question</h2>\n<p class=\"meta\">Asked by {} on {} \
+         \u{00b7} {} votes \u{00b7} {} \u{00b7} <a href=\"{url}\">{url}</a></p>\n",
+        summary.author_name,
+        format_day(summary.creation_date),
+        summary.score,
+        summary.tags.join(", "),
+        url = summary.site.question_url(summary.id)
+    ));
+    out.push_str(&with_footnotes(&question.body, &mut footnote));
+    out.push_str("</section>\n");
+
+    for (i, answer) in answers.iter().enumerate() {
+        let erwin = if is_erwin(&answer.author_name) {
+            " erwin"
+        } else {
+            ""
+        };
+        let accepted = if answer.is_accepted {
+            " \u{2713} Accepted"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "<section class=\"post{erwin}\">\n<h2>Answer {}{accepted}</h2>\n<p class=\"meta\">\
+             By {} on {} \u{00b7} {} votes \u{00b7} <a href=\"{url}\">{url}</a></p>\n",
+            i + 1,
+            answer.author_name,
+            format_day(answer.creation_date),
+            answer.score,
+            url = summary.site.answer_url(answer.answer_id)
+        ));
+        out.push_str(&with_footnotes(&answer.answer_text, &mut footnote));
+        out.push_str("</section>\n");
+    }
+
+    let mut attributions = vec![Attribution {
+        kind: "Question",
+        title: summary.title.clone(),
+        author: summary.author_name.clone(),
+        url: summary.site.question_url(summary.id),
+        license: license_for(summary.creation_date),
+    }];
+    attributions.extend(answers.iter().map(|a| Attribution {
+        kind: "Answer",
+        title: summary.title.clone(),
+        author: a.author_name.clone(),
+        url: summary.site.answer_url(a.answer_id),
+        license: license_for(a.creation_date),
+    }));
+    out.push_str(&attribution_html(&attributions));
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Markdown document of a question, its answers and all comments. Posts'
+/// own headings are pushed below the `## Answer` headings.
+pub fn thread_markdown(
+    question: &QuestionFull,
+    question_comments: &[Comment],
+    answers: &[(Answer, Vec<Comment>)],
+) -> String {
+    let summary = &question.summary;
+    let mut out = format!(
+        "# {}\n\n<{}>\n\n*Asked by {} on {} \u{00b7} {} votes \u{00b7} {} views",
+        summary.title,
+        summary.site.question_url(summary.id),
+        summary.author_name,
+        format_day(summary.creation_date),
+        summary.score,
+        summary.view_count
+    );
+    if !summary.tags.is_empty() {
+        out.push_str(&format!(" \u{00b7} {}", summary.tags.join(", ")));
+    }
+    out.push_str("*\n");
+    out.push_str(&markdown_post(&question.body, question_comments));
+
+    for (answer, comments) in answers {
+        let accepted = if answer.is_accepted { " \u{2713}" } else { "" };
+        out.push_str(&format!(
+            "\n## Answer by {}{accepted}\n\n*{} votes \u{00b7} {} \u{00b7} <{}>*\n",
+            answer.author_name,
+            answer.score,
+            format_day(answer.creation_date),
+            summary.site.answer_url(answer.answer_id)
+        ));
+        out.push_str(&markdown_post(&answer.answer_text, comments));
+    }
+
+    let mut attributions = vec![Attribution {
+        kind: "Question",
+        title: summary.title.clone(),
+        author: summary.author_name.clone(),
+        url: summary.site.question_url(summary.id),
+        license: license_for(summary.creation_date),
+    }];
+    attributions.extend(answers.iter().map(|(a, _)| Attribution {
+        kind: "Answer",
+        title: summary.title.clone(),
+        author: a.author_name.clone(),
+        url: summary.site.answer_url(a.answer_id),
+        license: license_for(a.creation_date),
+    }));
+    out.push('\n');
+    out.push_str(&attribution_markdown(&attributions));
+    out
+}
+
+/// A post body followed by its comments as a list
+fn markdown_post(html: &str, comments: &[Comment]) -> String {
+    let mut out = format!(
+        "\n{}\n",
+        demote_headings(&html_to_markdown(html), 2).trim_end()
+    );
+    if !comments.is_empty() {
+        out.push('\n');
+        for comment in comments {
+            out.push_str(&format!(
+                "- {} \u{2014} *{}*\n",
+                html_to_markdown(&comment.comment_text).replace('\n', " "),
+                comment.author_name
+            ));
+        }
+    }
+    out
+}
+
+/// Replace a post's links with footnote markers, listing the URLs after the post.
+/// Numbering continues across posts through `counter`.
+fn with_footnotes(body: &str, counter: &mut usize) -> String {
+    let mut urls = Vec::new();
+    let mut body = ANCHOR_REGEX
+        .replace_all(&sanitize_html(body), |cap: &Captures| {
+            *counter += 1;
+            urls.push((*counter, cap[1].to_string()));
+            format!("{}<sup class=\"fn\">[{}]</sup>", &cap[2], counter)
+        })
+        .into_owned();
+    if !urls.is_empty() {
+        body.push_str("\n<ol class=\"footnotes\">\n");
+        for (n, url) in urls {
+            body.push_str(&format!("<li value=\"{n}\">{url}</li>\n"));
+        }
+        body.push_str("</ol>\n");
+    }
+    body
+}
+
+/// Org-mode document with one top-level heading per question and a
+/// subheading per answer. Score, author, date, URL and license go in each
+/// heading's PROPERTIES drawer; code blocks become `#+BEGIN_SRC` blocks.
+pub fn threads_org(title: &str, threads: &[(QuestionFull, Vec<Answer>)]) -> String {
+    let mut out = format!(
+        "#+TITLE: {title}\n#+STARTUP: overview\n\nContent from Stack Exchange, used under the \
+         Creative Commons Attribution-ShareAlike license version noted in each post's LICENSE \
+         property.\n"
+    );
+    for (question, answers) in threads {
+        let summary = &question.summary;
+        let tags: String = summary
+            .tags
+            .iter()
+            .map(|t| t.replace(|c: char| !c.is_alphanumeric(), "_"))
+            .collect::<Vec<_>>()
+            .join(":");
+        out.push_str(&format!("\n* {}", summary.title));
+        if !tags.is_empty() {
+            out.push_str(&format!(" :{tags}:"));
+        }
+        out.push('\n');
+        out.push_str(&org_properties(&[
+            ("ID", summary.id.to_string()),
+            ("SCORE", summary.score.to_string()),
+            ("AUTHOR", summary.author_name.clone()),
+            ("DATE", org_date(summary.creation_date)),
+            ("URL", summary.site.question_url(summary.id)),
+            (
+                "LICENSE",
+                license_for(summary.creation_date).name.to_string(),
+            ),
+        ]));
+        out.push_str(&org_body(&question.body));
+
+        for (i, answer) in answers.iter().enumerate() {
+            let mut tags = Vec::new();
+            if answer.is_accepted {
+                tags.push("accepted");
+            }
+            if is_erwin(&answer.author_name) {
+                tags.push("erwin");
+            }
+            out.push_str(&format!("\n** Answer {} by {}", i + 1, answer.author_name));
+            if !tags.is_empty() {
+                out.push_str(&format!(" :{}:", tags.join(":")));
+            }
+            out.push('\n');
+            out.push_str(&org_properties(&[
+                ("ID", answer.answer_id.to_string()),
+                ("SCORE", answer.score.to_string()),
+                ("AUTHOR", answer.author_name.clone()),
+                ("DATE", org_date(answer.creation_date)),
+                ("URL", summary.site.answer_url(answer.answer_id)),
+                (
+                    "LICENSE",
+                    license_for(answer.creation_date).name.to_string(),
+                ),
+            ]));
+            out.push_str(&org_body(&answer.answer_text));
+        }
+    }
+    out
+}
+
+fn org_properties(properties: &[(&str, String)]) -> String {
+    let mut out = String::from(":PROPERTIES:\n");
+    for (name, value) in properties {
+        out.push_str(&format!(":{name}: {value}\n"));
+    }
+    out.push_str(":END:\n");
+    out
+}
+
+/// Inactive Org timestamp, so exported posts don't show up in the agenda
+fn org_date(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("[%Y-%m-%d %a]").to_string())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// A post as Org text: prose as plain paragraphs, `<pre>` blocks as source blocks
+fn org_body(html: &str) -> String {
+    render_post(html, org_prose, |lang, code| {
+        let mut out = format!("\n#+BEGIN_SRC {}\n", lang.unwrap_or(DEFAULT_LANG));
+        for line in code.lines() {
+            // Org's escape for lines that would otherwise read as headings or keywords
+            if line.starts_with('*') || line.starts_with("#+") {
+                out.push(',');
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push_str("#+END_SRC\n");
+        out
+    })
+}
+
+/// Split a post into prose and `<pre>` blocks and render each with its own
+/// function. Code comes decoded, with its `lang-*` hint if it has one.
+fn render_post(
+    html: &str,
+    mut prose: impl FnMut(&str) -> String,
+    mut code: impl FnMut(Option<&str>, &str) -> String,
+) -> String {
+    let html = &sanitize_html(html);
+    let mut out = String::new();
+    let mut rest = 0;
+    for cap in PRE_BLOCK_REGEX.captures_iter(html) {
+        let block = cap.get(0).unwrap();
+        out.push_str(&prose(&html[rest..block.start()]));
+        let lang = extract_lang_from_class(cap.get(1).map(|m| m.as_str()));
+        let text = decode_html_entities(&CODE_TAG_REGEX.replace_all(&cap[2], ""));
+        out.push_str(&code(lang.as_deref(), text.trim_end()));
+        rest = block.end();
+    }
+    out.push_str(&prose(&html[rest..]));
+    out
+}
+
+fn org_prose(html: &str) -> String {
+    let html = ANCHOR_REGEX.replace_all(html, |cap: &Captures| {
+        format!("[[{}][{}]]", &cap[1], &cap[2])
+    });
+    let text = html_to_plain_text(&html);
+    let mut out = String::new();
+    for line in text.trim().lines() {
+        // Leading stars start headings and `# ` starts a comment, so html2text's
+        // list items and section titles need rewriting
+        if let Some(item) = line.strip_prefix("* ") {
+            out.push_str(&format!("- {item}"));
+        } else if line.starts_with('*') {
+            out.push_str(&format!(" {line}"));
+        } else if let Some(title) = line.trim_start_matches('#').strip_prefix(' ') {
+            out.push_str(&format!("*{}*", title.trim()));
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    if out.is_empty() {
+        out
+    } else {
+        format!("\n{out}")
+    }
+}
+
+/// One Markdown file of an Obsidian vault
+pub struct VaultNote {
+    /// File name without the `.md` extension, which is also the wikilink target
+    pub name: String,
+    pub contents: String,
+}
+
+/// Obsidian vault of the given threads: one note per question with YAML
+/// frontmatter, wikilinks wherever a post links to another exported question,
+/// and a "Linked from" section listing the notes that link to it.
+/// `resolve` maps a Stack Exchange URL to the local question it points at.
+pub fn obsidian_vault(
+    threads: &[(QuestionFull, Vec<Answer>)],
+    resolve: impl Fn(&str) -> Option<i64>,
+) -> Vec<VaultNote> {
+    let mut names: HashMap<i64, String> = HashMap::new();
+    let mut taken = HashSet::new();
+    for (question, _) in threads {
+        let id = question.summary.id;
+        let mut name = note_name(&question.summary.title);
+        if name.is_empty() || !taken.insert(name.to_lowercase()) {
+            name = format!("{name} ({id})").trim_start().to_string();
+            taken.insert(name.to_lowercase());
+        }
+        names.insert(id, name);
+    }
+
+    // The link graph between exported questions, as target -> sources
+    let mut backlinks: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (question, answers) in threads {
+        let source = question.summary.id;
+        let bodies = std::iter::once(&question.body).chain(answers.iter().map(|a| &a.answer_text));
+        for body in bodies {
+            for cap in ANCHOR_REGEX.captures_iter(body) {
+                let Some(target) = resolve(&cap[1]).filter(|t| *t != source) else {
+                    continue;
+                };
+                let sources = backlinks.entry(target).or_default();
+                if names.contains_key(&target) && !sources.contains(&source) {
+                    sources.push(source);
+                }
+            }
+        }
+    }
+
+    threads
+        .iter()
+        .map(|(question, answers)| {
+            let summary = &question.summary;
+            let name = names[&summary.id].clone();
+            let prose = |html: &str| markdown_prose(html, &names, &resolve);
+            let mut out = format!(
+                "---\ntitle: \"{}\"\nid: {}\nurl: {}\nauthor: \"{}\"\nscore: {}\nanswers: {}\n\
+                 date: {}\nlicense: {}\n",
+                summary.title.replace('"', "\\\""),
+                summary.id,
+                summary.site.question_url(summary.id),
+                summary.author_name.replace('"', "\\\""),
+                summary.score,
+                summary.answer_count,
+                format_day(summary.creation_date),
+                license_for(summary.creation_date).name
+            );
+            if !summary.tags.is_empty() {
+                out.push_str("tags:\n");
+                for tag in &summary.tags {
+                    out.push_str(&format!("  - {tag}\n"));
+                }
+            }
+            out.push_str(&format!("---\n\n# {}\n", summary.title));
+            out.push_str(&render_post(&question.body, prose, markdown_code));
+
+            for (i, answer) in answers.iter().enumerate() {
+                let accepted = if answer.is_accepted { " \u{2713}" } else { "" };
+                out.push_str(&format!(
+                    "\n## Answer {} by {}{accepted}\n\n*{} votes \u{00b7} {} \u{00b7} {}*\n",
+                    i + 1,
+                    answer.author_name,
+                    answer.score,
+                    format_day(answer.creation_date),
+                    summary.site.answer_url(answer.answer_id)
+                ));
+                out.push_str(&render_post(&answer.answer_text, prose, markdown_code));
+            }
+
+            if let Some(sources) = backlinks.get(&summary.id).filter(|s| !s.is_empty()) {
+                out.push_str("\n## Linked from\n\n");
+                for source in sources {
+                    out.push_str(&format!("- [[{}]]\n", names[source]));
+                }
+            }
+
+            let mut attributions = vec![Attribution {
+                kind: "Question",
+                title: summary.title.clone(),
+                author: summary.author_name.clone(),
+                url: summary.site.question_url(summary.id),
+                license: license_for(summary.creation_date),
+            }];
+            attributions.extend(answers.iter().map(|a| Attribution {
+                kind: "Answer",
+                title: summary.title.clone(),
+                author: a.author_name.clone(),
+                url: summary.site.answer_url(a.answer_id),
+                license: license_for(a.creation_date),
+            }));
+            out.push('\n');
+            out.push_str(&attribution_markdown(&attributions));
+
+            VaultNote {
+                name,
+                contents: out,
+            }
+        })
+        .collect()
+}
+
+/// A question title as a note file name
+fn note_name(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .filter(|c| !NOTE_NAME_FORBIDDEN.contains(*c))
+        .collect();
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(NOTE_NAME_MAX_CHARS)
+        .collect::<String>()
+        .trim_end_matches(['.', ' '])
+        .to_string()
+}
+
+/// Prose as Markdown: links to exported questions become wikilinks, other
+/// links Markdown links, and the post's own headings sit below the answer headings
+fn markdown_prose(
+    html: &str,
+    names: &HashMap<i64, String>,
+    resolve: &impl Fn(&str) -> Option<i64>,
+) -> String {
+    let html = CODE_TAG_REGEX.replace_all(html, "`");
+    let html = ANCHOR_REGEX.replace_all(&html, |cap: &Captures| {
+        match resolve(&cap[1]).and_then(|id| names.get(&id)) {
+            Some(name) => {
+                let text = cap[2].replace(['|', '[', ']'], "");
+                if text == *name {
+                    format!("[[{name}]]")
+                } else {
+                    format!("[[{name}|{text}]]")
+                }
+            }
+            None => format!("[{}]({})", &cap[2], &cap[1]),
+        }
+    });
+    let text = html_to_plain_text(&html);
+    let mut out = String::new();
+    for line in text.trim().lines() {
+        if line.starts_with('#') {
+            out.push_str("##");
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    if out.is_empty() {
+        out
+    } else {
+        format!("\n{out}")
+    }
+}
+
+fn markdown_code(lang: Option<&str>, code: &str) -> String {
+    format!("\n```{}\n{code}\n```\n", lang.unwrap_or(DEFAULT_LANG))
+}
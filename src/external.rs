@@ -0,0 +1,54 @@
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Child, Command, ExitStatus, Stdio};
+
+/// A shell command to run outside the TUI with `input` piped to its stdin
+#[derive(Debug, Clone)]
+pub struct ExternalCommand {
+    pub command: String,
+    pub input: String,
+}
+
+/// Run the command with the current terminal, waiting for it to finish
+pub fn run(external: &ExternalCommand) -> Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&external.command)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run `{}`", external.command))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The previewer may exit without reading everything (e.g. quitting a pager)
+        let _ = stdin.write_all(external.input.as_bytes());
+    }
+
+    let status = child.wait()?;
+    check_status(&external.command, status)
+}
+
+/// Quote a value for interpolation into an `sh -c` command
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Turn a shell command's exit status into a user-facing error
+pub fn check_status(command: &str, status: ExitStatus) -> Result<()> {
+    match status.code() {
+        Some(0) | None => Ok(()),
+        Some(127) => bail!("Command not found: `{}`", command),
+        Some(code) => bail!("`{}` exited with status {}", command, code),
+    }
+}
+
+/// Stop a background command spawned with `process_group(0)`, including any
+/// pipeline children it started
+pub fn kill_group(child: &mut Child) {
+    let group = format!("-{}", child.id());
+    let _ = Command::new("kill")
+        .args(["-TERM", "--", &group])
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
@@ -0,0 +1,278 @@
+//! Threads fetched from the Stack Exchange API when a link leads outside the
+//! corpus. The API's question item is stored as is (`UserDb::save_fetched_question`)
+//! and mapped onto the corpus types when the thread is opened.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::config::ApiConfig;
+use crate::db::{Answer, Comment, QuestionFull, QuestionStatus, QuestionSummary};
+use crate::html::{decode_html_entities, excerpt, is_erwin, word_count};
+use crate::se_api::Client;
+use crate::site::Site;
+
+/// The scraper's filter: the question with its body, answers and all comments
+/// in one request
+pub const THREAD_FILTER: &str = "tvdphAWpCmQIK6q*qU.H5TfzEg0n5h2ww)5jDh3E(TT";
+
+#[derive(Deserialize)]
+struct ApiQuestion {
+    question_id: i64,
+    title: String,
+    body: String,
+    score: i32,
+    view_count: i32,
+    creation_date: i64,
+    last_activity_date: Option<i64>,
+    #[serde(default)]
+    tags: Vec<String>,
+    accepted_answer_id: Option<i64>,
+    owner: Option<ApiOwner>,
+    closed_reason: Option<String>,
+    closed_details: Option<ApiClosedDetails>,
+    locked_date: Option<i64>,
+    bounty_amount: Option<i32>,
+    // The API leaves out empty lists
+    #[serde(default)]
+    comments: Vec<ApiComment>,
+    #[serde(default)]
+    answers: Vec<ApiAnswer>,
+}
+
+#[derive(Deserialize)]
+struct ApiAnswer {
+    answer_id: i64,
+    body: String,
+    score: i32,
+    is_accepted: bool,
+    creation_date: i64,
+    last_activity_date: Option<i64>,
+    owner: Option<ApiOwner>,
+    awarded_bounty_amount: Option<i32>,
+    #[serde(default)]
+    comments: Vec<ApiComment>,
+}
+
+#[derive(Deserialize)]
+struct ApiClosedDetails {
+    #[serde(default)]
+    original_questions: Vec<ApiOriginal>,
+}
+
+#[derive(Deserialize)]
+struct ApiOriginal {
+    question_id: i64,
+}
+
+#[derive(Deserialize)]
+struct ApiComment {
+    body: String,
+    score: i32,
+    owner: Option<ApiOwner>,
+}
+
+/// Deleted users have no owner object
+#[derive(Deserialize)]
+struct ApiOwner {
+    display_name: Option<String>,
+    reputation: Option<i32>,
+}
+
+fn owner_name(owner: Option<&ApiOwner>) -> String {
+    owner
+        .and_then(|owner| owner.display_name.as_deref())
+        .map_or_else(|| "Unknown".to_string(), decode_html_entities)
+}
+
+fn into_comments(comments: Vec<ApiComment>) -> Vec<Comment> {
+    comments
+        .into_iter()
+        .map(|comment| Comment {
+            author_name: owner_name(comment.owner.as_ref()),
+            comment_text: comment.body,
+            score: comment.score,
+        })
+        .collect()
+}
+
+/// A fetched thread in the shape `ShowState` takes from the corpus
+pub struct FetchedThread {
+    pub question: QuestionFull,
+    pub answers: Vec<Answer>,
+    pub comments: Vec<Comment>,
+    /// Comments of each answer, parallel to `answers`
+    pub answer_comments: Vec<Vec<Comment>>,
+    /// The API's close reason (`Duplicate`, `Needs more focus`, ...)
+    pub closed_reason: Option<String>,
+    /// Question a duplicate was closed in favor of
+    pub duplicate_of: Option<i64>,
+    pub locked_date: Option<i64>,
+    /// Latest activity on the question or any of its answers
+    pub last_activity_date: i64,
+}
+
+impl FetchedThread {
+    /// Map a stored API question item onto the corpus types
+    pub fn from_json(site: Site, json: &str) -> Result<Self> {
+        let question: ApiQuestion =
+            serde_json::from_str(json).context("Unreadable fetched question")?;
+
+        // Scrape order: accepted first, then by votes
+        let mut api_answers = question.answers;
+        api_answers.sort_by_key(|a| (!a.is_accepted, -a.score));
+        let mut answers = Vec::with_capacity(api_answers.len());
+        let mut answer_comments = Vec::with_capacity(api_answers.len());
+        for answer in api_answers {
+            let reputation = answer.owner.as_ref().and_then(|o| o.reputation);
+            answers.push(Answer {
+                id: answer.answer_id,
+                answer_id: answer.answer_id,
+                score: answer.score,
+                is_accepted: answer.is_accepted,
+                author_name: owner_name(answer.owner.as_ref()),
+                author_reputation: reputation.unwrap_or(0),
+                ingest_reputation: reputation.unwrap_or(0),
+                awarded_bounty: answer.awarded_bounty_amount.unwrap_or(0),
+                creation_date: answer.creation_date,
+                last_activity_date: answer.last_activity_date.unwrap_or(answer.creation_date),
+                answer_text: answer.body,
+            });
+            answer_comments.push(into_comments(answer.comments));
+        }
+
+        let mut answer_scores: Vec<i32> = answers.iter().map(|a| a.score).collect();
+        answer_scores.sort_unstable_by(|a, b| b.cmp(a));
+        let erwin_accepted = answers
+            .iter()
+            .any(|a| a.is_accepted && is_erwin(&a.author_name));
+        let words = word_count(&question.body)
+            + answers
+                .iter()
+                .map(|a| word_count(&a.answer_text))
+                .sum::<usize>();
+        let summary = QuestionSummary {
+            id: question.question_id,
+            title: question.title,
+            score: question.score,
+            view_count: question.view_count,
+            answer_count: answers.len() as i32,
+            creation_date: question.creation_date,
+            accepted_answer_id: question.accepted_answer_id,
+            author_name: owner_name(question.owner.as_ref()),
+            site,
+            tags: question.tags,
+            answer_scores,
+            status: QuestionStatus {
+                closed: question.closed_reason.is_some(),
+                duplicate: question
+                    .closed_reason
+                    .as_deref()
+                    .is_some_and(|reason| reason.eq_ignore_ascii_case("duplicate")),
+                locked: question.locked_date.is_some(),
+                migrated: false,
+            },
+            bounty: question.bounty_amount.unwrap_or(0),
+            excerpt: excerpt(&question.body),
+            word_count: words as i32,
+            erwin_accepted,
+        };
+        let last_activity_date = answers
+            .iter()
+            .map(|a| a.last_activity_date)
+            .chain(question.last_activity_date)
+            .fold(question.creation_date, i64::max);
+
+        Ok(Self {
+            question: QuestionFull {
+                summary,
+                body: question.body,
+            },
+            answers,
+            comments: into_comments(question.comments),
+            answer_comments,
+            closed_reason: question.closed_reason,
+            duplicate_of: question
+                .closed_details
+                .and_then(|details| details.original_questions.first().map(|q| q.question_id)),
+            locked_date: question.locked_date,
+            last_activity_date,
+        })
+    }
+}
+
+/// A question (or the question of an answer) to fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchTarget {
+    pub site: Site,
+    pub question_id: Option<i64>,
+    /// Answer the link pointed to, scrolled to once the thread is open
+    pub answer_id: Option<i64>,
+}
+
+/// A fetched thread's question item, ready to store
+pub struct Fetched {
+    pub question_id: i64,
+    pub json: String,
+}
+
+/// A fetch running on its own thread, so the API's waits don't block the UI
+pub struct QuestionFetch {
+    pub target: FetchTarget,
+    result: Receiver<Result<Fetched>>,
+}
+
+impl QuestionFetch {
+    pub fn start(config: ApiConfig, target: FetchTarget) -> Self {
+        let (tx, result) = mpsc::channel();
+        thread::spawn(move || {
+            let mut client = Client::new(&config);
+            let _ = tx.send(fetch_thread(&mut client, target));
+        });
+        Self { target, result }
+    }
+
+    /// `None` while still fetching
+    pub fn poll(&mut self) -> Option<Result<Fetched>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(Err(anyhow::anyhow!("The fetch stopped unexpectedly")))
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiAnswerRef {
+    question_id: i64,
+}
+
+fn fetch_thread(client: &mut Client, target: FetchTarget) -> Result<Fetched> {
+    let question_id = match (target.question_id, target.answer_id) {
+        (Some(question_id), _) => question_id,
+        (None, Some(answer_id)) => client
+            .get::<ApiAnswerRef>(target.site, &format!("answers/{answer_id}"), &[])?
+            .items
+            .first()
+            .map(|answer| answer.question_id)
+            .with_context(|| format!("Answer #{answer_id} was deleted or doesn't exist"))?,
+        (None, None) => anyhow::bail!("Nothing to fetch"),
+    };
+    let item = client
+        .get::<serde_json::Value>(
+            target.site,
+            &format!("questions/{question_id}"),
+            &[("filter", THREAD_FILTER.to_string())],
+        )?
+        .items
+        .into_iter()
+        .next()
+        .with_context(|| format!("Question #{question_id} was deleted or doesn't exist"))?;
+    let json = item.to_string();
+    // Fail here rather than when the thread is opened
+    FetchedThread::from_json(target.site, &json)?;
+    Ok(Fetched { question_id, json })
+}
@@ -0,0 +1,391 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+use crate::db::{embedding_to_blob, Database};
+use crate::html::{excerpt, word_count};
+use crate::search::semantic::stub_embedding;
+
+/// The corpus tables as the original scrape created them. Columns and tables
+/// added since come from `Database::open`'s migrations, which also backfill
+/// the derived ones (answer scores, closures); `make_fixture` then stores
+/// what the scraper computes at ingest (excerpts, word counts).
+const SCRAPE_SCHEMA: &str = "
+CREATE TABLE question_ids (
+    id INTEGER PRIMARY KEY,
+    discovered_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    scraped BOOLEAN DEFAULT FALSE,
+    scraped_at DATETIME NULL
+);
+CREATE TABLE questions (
+    id INTEGER PRIMARY KEY,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    score INTEGER DEFAULT 0,
+    view_count INTEGER DEFAULT 0,
+    answer_count INTEGER DEFAULT 0,
+    creation_date INTEGER DEFAULT 0,
+    last_activity_date INTEGER DEFAULT 0,
+    tags TEXT DEFAULT '[]',
+    is_answered BOOLEAN DEFAULT FALSE,
+    accepted_answer_id INTEGER,
+    author_name TEXT DEFAULT 'Unknown',
+    author_reputation INTEGER DEFAULT 0,
+    author_user_id INTEGER DEFAULT 0,
+    scraped_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    site TEXT NOT NULL DEFAULT 'stackoverflow',
+    FOREIGN KEY (id) REFERENCES question_ids (id)
+);
+CREATE TABLE question_comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    question_id INTEGER,
+    comment_text TEXT,
+    score INTEGER DEFAULT 0,
+    creation_date INTEGER DEFAULT 0,
+    author_name TEXT DEFAULT 'Unknown',
+    author_reputation INTEGER DEFAULT 0,
+    author_user_id INTEGER DEFAULT 0,
+    FOREIGN KEY (question_id) REFERENCES questions (id)
+);
+CREATE TABLE answers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    question_id INTEGER,
+    answer_id INTEGER,
+    answer_text TEXT,
+    answer_order INTEGER,
+    score INTEGER DEFAULT 0,
+    is_accepted BOOLEAN DEFAULT FALSE,
+    creation_date INTEGER DEFAULT 0,
+    last_activity_date INTEGER DEFAULT 0,
+    author_name TEXT DEFAULT 'Unknown',
+    author_reputation INTEGER DEFAULT 0,
+    author_user_id INTEGER DEFAULT 0,
+    FOREIGN KEY (question_id) REFERENCES questions (id)
+);
+CREATE TABLE answer_comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    answer_id INTEGER,
+    comment_text TEXT,
+    score INTEGER DEFAULT 0,
+    creation_date INTEGER DEFAULT 0,
+    author_name TEXT DEFAULT 'Unknown',
+    author_reputation INTEGER DEFAULT 0,
+    author_user_id INTEGER DEFAULT 0,
+    FOREIGN KEY (answer_id) REFERENCES answers (id)
+);
+CREATE TABLE question_embeddings (
+    question_id INTEGER PRIMARY KEY,
+    embedding BLOB NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (question_id) REFERENCES questions (id)
+);
+";
+
+/// What a synthetic question asks about
+struct Topic {
+    task: &'static str,
+    tags: &'static [&'static str],
+    /// SQL the posts show for it
+    code: &'static str,
+}
+
+const TOPICS: &[Topic] = &[
+    Topic {
+        task: "select the first row in each group",
+        tags: &["sql", "postgresql", "greatest-n-per-group"],
+        code: "SELECT DISTINCT ON (customer) *\nFROM   purchases\nORDER  BY customer, total DESC;",
+    },
+    Topic {
+        task: "upsert a row",
+        tags: &["postgresql", "upsert"],
+        code: "INSERT INTO tbl (id, val) VALUES (1, 'x')\nON CONFLICT (id) DO UPDATE SET val = EXCLUDED.val;",
+    },
+    Topic {
+        task: "query elements of a jsonb array",
+        tags: &["postgresql", "json", "jsonb"],
+        code: "SELECT *\nFROM   tbl\nWHERE  data @> '[{\"id\": 1}]';",
+    },
+    Topic {
+        task: "generate a series of dates",
+        tags: &["postgresql", "date", "generate-series"],
+        code: "SELECT d::date\nFROM   generate_series(date '2024-01-01', date '2024-12-31', interval '1 day') d;",
+    },
+    Topic {
+        task: "reset a sequence after a bulk load",
+        tags: &["postgresql", "sequence"],
+        code: "SELECT setval('tbl_id_seq', max(id)) FROM tbl;",
+    },
+    Topic {
+        task: "speed up a LIKE search with an index",
+        tags: &["postgresql", "indexing", "pattern-matching"],
+        code: "CREATE INDEX tbl_name_trgm_idx ON tbl USING gin (name gin_trgm_ops);",
+    },
+    Topic {
+        task: "aggregate rows into an array",
+        tags: &["postgresql", "aggregate", "arrays"],
+        code: "SELECT customer, array_agg(id ORDER BY id)\nFROM   purchases\nGROUP  BY 1;",
+    },
+    Topic {
+        task: "get the top rows per category with a LATERAL join",
+        tags: &["sql", "postgresql", "lateral-join"],
+        code: "SELECT c.*, p.*\nFROM   customer c\nCROSS  JOIN LATERAL (\n   SELECT *\n   FROM   purchases p\n   WHERE  p.customer_id = c.id\n   ORDER  BY total DESC\n   LIMIT  3\n   ) p;",
+    },
+    Topic {
+        task: "delete duplicate rows",
+        tags: &["sql", "postgresql", "duplicates"],
+        code: "DELETE FROM tbl t\nUSING  tbl t1\nWHERE  t.key = t1.key\nAND    t.ctid > t1.ctid;",
+    },
+    Topic {
+        task: "pivot rows into columns",
+        tags: &["postgresql", "pivot", "crosstab"],
+        code: "SELECT *\nFROM   crosstab('SELECT row_name, cat, val FROM tbl ORDER BY 1, 2')\n       AS ct (row_name text, a int, b int);",
+    },
+];
+
+const TITLE_PATTERNS: &[&str] = &[
+    "How to {}?",
+    "Best way to {} in PostgreSQL",
+    "Fast way to {} on a big table",
+    "Why is it so slow to {}?",
+    "Can I {} without a subquery?",
+];
+
+const AUTHORS: &[&str] = &[
+    "Ada Example",
+    "Bob Sample",
+    "Chen Placeholder",
+    "Dana Fixture",
+    "Eli Testcase",
+];
+
+/// Stands in for the corpus' expert: `html::is_erwin` recognizes the name
+const ERWIN: &str = "Erwin Brandstetter";
+
+/// First synthetic question ID; far above Stack Overflow's, so fixture IDs
+/// never look like real questions
+const FIRST_ID: i64 = 900_000_000;
+
+/// 2009-01-01 .. 2025-01-01, spanning every CC BY-SA version
+const DATE_RANGE: (i64, i64) = (1_230_768_000, 1_735_689_600);
+
+/// Small deterministic PRNG (xorshift64*), so a seed always yields the same file
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Uniform in `0..n`
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n.max(1)
+    }
+
+    fn range(&mut self, (low, high): (i64, i64)) -> i64 {
+        low + self.below((high - low) as u64) as i64
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// Write a synthetic, schema-complete corpus of `questions` threads to `path`:
+/// questions with code blocks, tags and comments, answers (some by Erwin,
+/// some accepted), links between the threads, a duplicate closure, DBA-site
+/// questions, and title embeddings from the offline stand-in model
+pub fn make_fixture(path: &Path, questions: usize, seed: u64) -> Result<()> {
+    let conn =
+        Connection::open(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    conn.execute_batch(SCRAPE_SCHEMA)
+        .context("Failed to create the corpus tables")?;
+
+    let mut rng = Rng(seed.max(1));
+    let threads: Vec<(&Topic, String)> = (0..questions)
+        .map(|_| {
+            let topic = rng.pick(TOPICS);
+            let title = rng.pick(TITLE_PATTERNS).replacen("{}", topic.task, 1);
+            let mut chars = title.chars();
+            let title = chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            });
+            (topic, title)
+        })
+        .collect();
+
+    conn.execute_batch("BEGIN")?;
+    // Excerpt and word count of each thread
+    let mut stats = Vec::with_capacity(questions);
+    let mut next_answer_id = FIRST_ID + questions as i64;
+    for (i, (topic, title)) in threads.iter().enumerate() {
+        let id = FIRST_ID + i as i64;
+        let Topic { task, tags, code } = topic;
+        let asked = rng.range(DATE_RANGE);
+        let site = if rng.chance(15) {
+            "dba"
+        } else {
+            "stackoverflow"
+        };
+        let score = rng.range((-2, 500));
+
+        let mut body = format!(
+            "<p>I'd like to {task}. This is what I have:</p>\n{}\n<p>Is there a better way?</p>",
+            code_block(code)
+        );
+        // An older-style duplicate closure, left as a notice in the body
+        if i > 0 && rng.chance(5) {
+            let original = FIRST_ID + rng.below(i as u64) as i64;
+            body = format!(
+                "<blockquote>\n<p><strong>Possible Duplicate:</strong><br>\n\
+                 <a href=\"https://stackoverflow.com/questions/{original}\">{}</a></p>\n\
+                 </blockquote>\n{body}",
+                escape(&threads[(original - FIRST_ID) as usize].1)
+            );
+        }
+
+        let excerpt = excerpt(&body);
+        let mut words = word_count(&body);
+
+        let answer_count = rng.below(5) as i64;
+        let accepted = (answer_count > 0 && rng.chance(70)).then(|| rng.below(answer_count as u64));
+        let accepted_answer_id = accepted.map(|order| next_answer_id + order as i64);
+        conn.execute(
+            "INSERT INTO question_ids (id, scraped) VALUES (?, TRUE)",
+            params![id],
+        )?;
+        conn.execute(
+            "INSERT INTO questions (id, title, body, score, view_count, answer_count,
+                 creation_date, last_activity_date, tags, is_answered, accepted_answer_id,
+                 author_name, author_reputation, site)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                id,
+                title,
+                body,
+                score,
+                score.max(1) * 100 + rng.range((0, 5_000)),
+                answer_count,
+                asked,
+                asked,
+                serde_json::to_string(tags)?,
+                answer_count > 0,
+                accepted_answer_id,
+                rng.pick(AUTHORS),
+                rng.range((1, 50_000)),
+                site,
+            ],
+        )?;
+        for _ in 0..rng.below(3) {
+            conn.execute(
+                "INSERT INTO question_comments (question_id, comment_text, score, creation_date,
+                     author_name)
+                 VALUES (?, ?, ?, ?, ?)",
+                params![
+                    id,
+                    "What's your Postgres version? And please show the table definition.",
+                    rng.range((0, 10)),
+                    asked,
+                    rng.pick(AUTHORS),
+                ],
+            )?;
+        }
+        conn.execute(
+            "INSERT INTO question_embeddings (question_id, embedding) VALUES (?, ?)",
+            params![id, embedding_to_blob(&stub_embedding(title))],
+        )?;
+        for order in 0..answer_count {
+            let answer_id = next_answer_id;
+            next_answer_id += 1;
+            let is_accepted = accepted == Some(order as u64);
+            let author = if order == 0 && rng.chance(60) {
+                ERWIN
+            } else {
+                rng.pick(AUTHORS)
+            };
+            let mut text = format!(
+                "<p>To {task}, use <code>{}</code>:</p>\n{}\n",
+                escape(code.split_whitespace().next().unwrap_or_default()),
+                code_block(code)
+            );
+            if i > 0 && rng.chance(40) {
+                let other = rng.below(i as u64) as usize;
+                text.push_str(&format!(
+                    "<p>Related:</p>\n<ul>\n<li><a href=\"https://stackoverflow.com/questions/{}\">{}</a></li>\n</ul>\n",
+                    FIRST_ID + other as i64,
+                    escape(&threads[other].1)
+                ));
+            }
+            words += word_count(&text);
+            let answered = rng.range((asked, DATE_RANGE.1));
+            conn.execute(
+                "INSERT INTO answers (question_id, answer_id, answer_text, answer_order, score,
+                     is_accepted, creation_date, last_activity_date, author_name,
+                     author_reputation)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    id,
+                    answer_id,
+                    text,
+                    order,
+                    rng.range((-1, score.max(0) + 50)),
+                    is_accepted,
+                    answered,
+                    answered,
+                    author,
+                    rng.range((1, 700_000)),
+                ],
+            )?;
+            let answer_row = conn.last_insert_rowid();
+            for _ in 0..rng.below(3) {
+                conn.execute(
+                    "INSERT INTO answer_comments (answer_id, comment_text, score, creation_date,
+                         author_name)
+                     VALUES (?, ?, ?, ?, ?)",
+                    params![
+                        answer_row,
+                        "Thanks, that works. Does it also need an index on <code>customer</code>?",
+                        rng.range((0, 20)),
+                        answered,
+                        rng.pick(AUTHORS),
+                    ],
+                )?;
+            }
+        }
+        stats.push((id, excerpt, words));
+    }
+    conn.execute_batch("COMMIT")?;
+    drop(conn);
+
+    // Add the columns and tables of later schema versions, with their backfills
+    Database::open(path)?;
+    let conn = Connection::open(path)?;
+    conn.execute_batch("BEGIN")?;
+    for (id, excerpt, words) in stats {
+        conn.execute(
+            "UPDATE questions SET excerpt = ?, word_count = ? WHERE id = ?",
+            params![excerpt, words as i64, id],
+        )?;
+    }
+    conn.execute_batch("COMMIT")?;
+    Ok(())
+}
+
+fn code_block(code: &str) -> String {
+    format!(
+        "<pre class=\"lang-sql prettyprint-override\"><code>{}\n</code></pre>",
+        escape(code)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use ratatui::text::{Line, Span};
+use std::collections::BTreeMap;
+use std::fs;
+use unicode_width::UnicodeWidthChar;
+
+use crate::config::config_file;
+use crate::html::CodeBlock;
+use crate::ui::styles;
+
+/// PostgreSQL terms that turn up in answers without explanation
+const BUILTIN: &[(&str, &str)] = &[
+    ("MVCC", "Multi-Version Concurrency Control: writers create new row versions instead of blocking readers"),
+    ("HOT", "Heap-Only Tuple: an update that stays on the same page and touches no indexed column, so no index entries are added"),
+    ("TOAST", "The Oversized-Attribute Storage Technique: large values are compressed and/or moved out of line into a side table"),
+    ("WAL", "Write-Ahead Log: changes are logged before data files are written, for crash recovery and replication"),
+    ("LSN", "Log Sequence Number: a position in the write-ahead log"),
+    ("XID", "Transaction ID: 32-bit counter stamped on row versions; wraparound is prevented by freezing"),
+    ("OID", "Object Identifier: the internal ID of catalog objects such as tables, types and functions"),
+    ("TID", "Tuple Identifier: a row version's physical location (block, offset), exposed as the ctid column"),
+    ("ctid", "System column holding a row version's physical location (block, offset); changes on update"),
+    ("B-tree", "The default index type: sorted, supports equality, ranges, ORDER BY and uniqueness"),
+    ("GIN", "Generalized Inverted Index: maps elements (array items, jsonb keys, trigrams, lexemes) to the rows containing them"),
+    ("GiST", "Generalized Search Tree: index framework for overlapping data such as ranges, geometry and nearest-neighbour search"),
+    ("SP-GiST", "Space-Partitioned GiST: index framework for non-balanced structures like quad-trees, k-d trees and radix trees"),
+    ("BRIN", "Block Range Index: tiny index storing min/max per range of table blocks; good for naturally ordered data"),
+    ("CTE", "Common Table Expression: a named subquery in a WITH clause"),
+    ("FSM", "Free Space Map: per-table record of free space in each page, used to place new rows"),
+    ("PITR", "Point-In-Time Recovery: restoring a base backup and replaying WAL up to a chosen moment"),
+    ("RLS", "Row-Level Security: policies that filter which rows a role can see or modify"),
+    ("SSI", "Serializable Snapshot Isolation: how PostgreSQL implements the SERIALIZABLE isolation level"),
+    ("fillfactor", "Percentage of each table or index page filled on insert; the rest is kept free for (HOT) updates"),
+    ("SARGable", "Search ARGument-able: a predicate written so an index can be used, e.g. no function wrapped around the column"),
+];
+
+/// A glossary term's place in rendered lines (display column of its first cell)
+#[derive(Debug, Clone)]
+pub struct TermHit {
+    pub term: String,
+    pub line_index: usize,
+    pub start_col: usize,
+}
+
+/// Definitions of terms underlined in post text: the built-in list plus
+/// `<config dir>/erwindb/glossary.toml`, a flat table of `term = "definition"`.
+/// User entries override built-ins, and an empty definition removes one.
+/// Terms match case-sensitively as whole words.
+#[derive(Debug)]
+pub struct Glossary {
+    terms: BTreeMap<String, String>,
+}
+
+impl Default for Glossary {
+    fn default() -> Self {
+        Self {
+            terms: BUILTIN
+                .iter()
+                .map(|(term, definition)| (term.to_string(), definition.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Glossary {
+    /// Built-in terms merged with the user's glossary file, if any
+    pub fn load() -> Result<Self> {
+        let mut glossary = Self::default();
+        let Some(path) = config_file("glossary.toml").filter(|path| path.exists()) else {
+            return Ok(glossary);
+        };
+
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let user: BTreeMap<String, String> = toml::from_str(&text)
+            .with_context(|| format!("Invalid glossary in {}", path.display()))?;
+        for (term, definition) in user {
+            if definition.is_empty() {
+                glossary.terms.remove(&term);
+            } else {
+                glossary.terms.insert(term, definition);
+            }
+        }
+        Ok(glossary)
+    }
+
+    pub fn definition(&self, term: &str) -> Option<&str> {
+        self.terms.get(term).map(String::as_str)
+    }
+
+    /// Underline glossary terms in `lines`, leaving code blocks alone; returns
+    /// where they are, in reading order
+    pub fn mark(&self, lines: &mut [Line<'static>], code_blocks: &[CodeBlock]) -> Vec<TermHit> {
+        // Longest first, so "SP-GiST" wins over the "GiST" inside it
+        let mut terms: Vec<&str> = self.terms.keys().map(String::as_str).collect();
+        terms.sort_by_key(|term| std::cmp::Reverse(term.len()));
+
+        let mut hits = Vec::new();
+        for (line_index, line) in lines.iter_mut().enumerate() {
+            if code_blocks
+                .iter()
+                .any(|block| (block.start_line..block.end_line).contains(&line_index))
+            {
+                continue;
+            }
+
+            let text: String = line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect();
+            let mut ranges: Vec<(usize, usize, &str)> = Vec::new();
+            for term in &terms {
+                for (start, _) in text.match_indices(term) {
+                    let end = start + term.len();
+                    let overlaps = ranges.iter().any(|&(s, e, _)| start < e && end > s);
+                    if !overlaps && is_word_boundary(&text, start, end) {
+                        ranges.push((start, end, term));
+                    }
+                }
+            }
+            if ranges.is_empty() {
+                continue;
+            }
+            ranges.sort_by_key(|&(start, _, _)| start);
+
+            let width = |s: &str| -> usize { s.chars().map(|c| c.width().unwrap_or(0)).sum() };
+            let columns: Vec<(usize, usize)> = ranges
+                .iter()
+                .map(|&(start, end, _)| (width(&text[..start]), width(&text[..end])))
+                .collect();
+            *line = underline_columns(line, &columns);
+            hits.extend(
+                ranges
+                    .iter()
+                    .zip(&columns)
+                    .map(|(&(_, _, term), &(start_col, _))| TermHit {
+                        term: term.to_string(),
+                        line_index,
+                        start_col,
+                    }),
+            );
+        }
+        hits
+    }
+}
+
+/// No letter, digit or underscore right before or after the match
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..start].chars().next_back().is_some_and(is_word)
+        && !text[end..].chars().next().is_some_and(is_word)
+}
+
+/// Restyle the cells within the given column ranges with the glossary underline
+fn underline_columns(line: &Line, ranges: &[(usize, usize)]) -> Line<'static> {
+    let in_range = |col: usize| ranges.iter().any(|&(start, end)| col >= start && col < end);
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut col = 0;
+
+    for span in &line.spans {
+        let mut run = String::new();
+        let mut run_marked = false;
+        for c in span.content.chars() {
+            let marked = in_range(col);
+            if marked != run_marked && !run.is_empty() {
+                spans.push(styled_run(std::mem::take(&mut run), span, run_marked));
+            }
+            run_marked = marked;
+            run.push(c);
+            col += c.width().unwrap_or(0);
+        }
+        if !run.is_empty() {
+            spans.push(styled_run(run, span, run_marked));
+        }
+    }
+
+    Line::from(spans).style(line.style)
+}
+
+fn styled_run(text: String, span: &Span, marked: bool) -> Span<'static> {
+    let style = if marked {
+        styles::glossary_term_style(span.style)
+    } else {
+        span.style
+    };
+    Span::styled(text, style)
+}
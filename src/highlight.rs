@@ -5,6 +5,8 @@ use syntect::easy::HighlightLines;
 use syntect::highlighting::{Style as SyntectStyle, ThemeSet};
 use syntect::parsing::SyntaxSet;
 
+use crate::ui::styles;
+
 static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
 static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
 
@@ -14,7 +16,7 @@ pub fn highlight_code(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
         .or_else(|| SYNTAX_SET.find_syntax_by_token("sql"))
         .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text());
 
-    let theme = &THEME_SET.themes["base16-ocean.dark"];
+    let theme = &THEME_SET.themes[styles::theme().syntax];
     let mut highlighter = HighlightLines::new(syntax, theme);
 
     code.lines()
@@ -35,6 +37,19 @@ pub fn highlight_code(code: &str, lang: Option<&str>) -> Vec<Line<'static>> {
         .collect()
 }
 
+/// A syntect theme by name, for `[theme] syntax`
+pub fn syntax_theme(name: &str) -> Option<&'static str> {
+    THEME_SET
+        .themes
+        .get_key_value(name)
+        .map(|(name, _)| name.as_str())
+}
+
+/// Names of the syntect themes `syntax_theme` knows
+pub fn syntax_theme_names() -> impl Iterator<Item = &'static str> {
+    THEME_SET.themes.keys().map(String::as_str)
+}
+
 fn syntect_to_ratatui_style(style: SyntectStyle) -> Style {
     let fg = Color::Rgb(style.foreground.r, style.foreground.g, style.foreground.b);
     Style::default().fg(fg)
@@ -0,0 +1,138 @@
+//! `[hooks]`: user commands run when a question is opened, bookmarked or
+//! exported, each getting the event as JSON on stdin. They run in the
+//! background with their output discarded; a failing hook is reported but
+//! never stops what triggered it.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+
+use crate::clock;
+use crate::config::HooksConfig;
+use crate::external::check_status;
+use crate::json::QuestionJson;
+
+/// What happened, as the hook sees it: `{"event": "open", "at": ..., "question": {...}}`
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum HookEvent<'a> {
+    Open {
+        question: QuestionJson<'a>,
+    },
+    Bookmark {
+        question: QuestionJson<'a>,
+        /// False when the bookmark was removed
+        bookmarked: bool,
+    },
+    Export {
+        /// `markdown`, `pdf`, `org` or `obsidian`
+        format: &'static str,
+        /// File or directory written, if not stdout
+        output: Option<String>,
+        questions: Vec<QuestionJson<'a>>,
+    },
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    #[serde(flatten)]
+    event: &'a HookEvent<'a>,
+    at: String,
+}
+
+/// Runs the configured hooks and keeps track of them until they finish
+pub struct Hooks {
+    config: HooksConfig,
+    /// Running hooks and their commands
+    running: Vec<(String, Child)>,
+    /// Let hooks write errors to the terminal (not while the TUI is up)
+    stderr: bool,
+}
+
+impl Hooks {
+    /// Hooks for the TUI, silenced so they can't draw over it
+    pub fn new(config: HooksConfig) -> Self {
+        Self {
+            config,
+            running: Vec::new(),
+            stderr: false,
+        }
+    }
+
+    /// Hooks for a one-off command, whose errors show in the terminal
+    pub fn for_cli(config: HooksConfig) -> Self {
+        Self {
+            stderr: true,
+            ..Self::new(config)
+        }
+    }
+
+    /// Start the event's hook, if one is configured
+    pub fn fire(&mut self, event: &HookEvent) -> Result<()> {
+        let command = match event {
+            HookEvent::Open { .. } => &self.config.open,
+            HookEvent::Bookmark { .. } => &self.config.bookmark,
+            HookEvent::Export { .. } => &self.config.export,
+        };
+        let Some(command) = command.clone() else {
+            return Ok(());
+        };
+        let json = serde_json::to_string(&Payload {
+            event,
+            at: clock::now().to_rfc3339(),
+        })?;
+
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(if self.stderr {
+                Stdio::inherit()
+            } else {
+                Stdio::null()
+            })
+            .spawn()
+            .with_context(|| format!("Failed to run hook `{command}`"))?;
+        // Off the UI thread, in case the hook is slow to read
+        if let Some(mut stdin) = child.stdin.take() {
+            thread::spawn(move || {
+                let _ = stdin.write_all(json.as_bytes());
+            });
+        }
+        self.running.push((command, child));
+        Ok(())
+    }
+
+    /// The failures of hooks that finished since the last call
+    pub fn poll(&mut self) -> Vec<anyhow::Error> {
+        let mut failures = Vec::new();
+        self.running
+            .retain_mut(|(command, child)| match child.try_wait() {
+                Ok(Some(status)) => {
+                    failures.extend(check_status(command, status).err());
+                    false
+                }
+                Ok(None) => true,
+                Err(err) => {
+                    failures.push(err.into());
+                    false
+                }
+            });
+        failures
+    }
+
+    /// Wait for every running hook, failing with the first that failed
+    pub fn wait(mut self) -> Result<()> {
+        let mut result = Ok(());
+        for (command, mut child) in self.running.drain(..) {
+            let status = child.wait()?;
+            if result.is_ok() {
+                result = check_status(&command, status);
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,271 @@
+use crate::sanitize::sanitize_html;
+use crate::site::Site;
+use regex::Regex;
+use std::sync::LazyLock;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+#[cfg(feature = "tui")]
+mod render;
+
+#[cfg(feature = "tui")]
+pub use render::{html_to_content, ContentLine, ParsedContent};
+
+static SO_QUESTION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:stackoverflow|dba\.stackexchange)\.com/(?:questions|q)/(\d+)").unwrap()
+});
+static SO_ANSWER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:stackoverflow|dba\.stackexchange)\.com/(?:a/(\d+)|questions/\d+/[^/#?]+/(\d+))")
+        .unwrap()
+});
+static SITE_HOST_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:https?:)?//(?:www\.)?([^/:?#]+)").unwrap());
+/// Pre-2013 duplicate closures were edited into the body as a blockquote notice
+static DUPLICATE_NOTICE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<strong>Possible Duplicate:</strong>\s*(?:<br\s*/?>)?\s*<a href="([^"]+)""#)
+        .unwrap()
+});
+/// Code blocks and duplicate notices, which make poor excerpts
+static EXCERPT_SKIP_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<pre\b.*?</pre>|<blockquote>\s*<p><strong>Possible Duplicate:</strong>.*?</blockquote>")
+        .unwrap()
+});
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+static HREF_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"href="([^"]+)""#).unwrap());
+static SPACE_BEFORE_PUNCT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s+([.,;:?!)])").unwrap());
+static SENTENCE_END_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[.?!](?:\s|$)").unwrap());
+
+/// Longest excerpt kept, in characters
+const EXCERPT_MAX_CHARS: usize = 160;
+/// Sentences shorter than this ("Hi all.", "Why?") run on into the next
+const EXCERPT_MIN_CHARS: usize = 25;
+
+pub static LINK_REF_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\[(\d+)\]").unwrap());
+
+#[derive(Debug, Clone)]
+pub struct Link {
+    pub url: String,
+    pub site: Option<Site>,
+    pub line_index: usize,
+    pub question_id: Option<i64>,
+    pub answer_id: Option<i64>,
+    pub start_col: usize, // Column where link starts
+    pub end_col: usize,   // Column where link ends
+    /// Screen cells the link covers once its pane's lines are wrapped, one per
+    /// physical line (filled in by the content wrapping pass)
+    pub hitboxes: Vec<Hitbox>,
+}
+
+/// A link's column range on one physical (wrapped) line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hitbox {
+    pub line_index: usize,
+    pub start_col: usize,
+    pub end_col: usize, // Exclusive
+}
+
+impl Link {
+    pub fn contains(&self, line_index: usize, col: usize) -> bool {
+        self.hitboxes.iter().any(|hitbox| {
+            hitbox.line_index == line_index && col >= hitbox.start_col && col < hitbox.end_col
+        })
+    }
+}
+
+/// A code block's source and the rendered lines it occupies
+#[derive(Debug, Clone)]
+pub struct CodeBlock {
+    pub code: String,
+    pub lang: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize, // Exclusive
+}
+
+/// The element of a post a rendered line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostNode {
+    /// Running text: paragraphs, lists, quotes, headings
+    Text,
+    /// The post's `n`th `<pre>`, numbered as `sanitize::element_html` counts them
+    CodeBlock(usize),
+    /// The post's `n`th `<table>`
+    Table(usize),
+}
+
+/// `text` cut to `width` columns, ending in `…` if anything was cut
+pub fn fit_cell(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut fitted = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let c_width = c.width().unwrap_or(0);
+        if used + c_width + 1 > width {
+            break;
+        }
+        fitted.push(c);
+        used += c_width;
+    }
+    fitted.push('…');
+    fitted
+}
+
+pub fn decode_html_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&#x27;", "'")
+        .replace("&#x2F;", "/")
+}
+
+/// Undecorated text for a whole post, one paragraph per line (for text-to-speech)
+pub fn html_to_plain_text(html: &str) -> String {
+    html2text::config::plain_no_decorate()
+        .string_from_read(sanitize_html(html).as_bytes(), 10000)
+        .unwrap_or_default()
+}
+
+/// Text of an HTML fragment with the tags dropped, for full-text indexing
+pub fn index_text(html: &str) -> String {
+    decode_html_entities(&TAG_REGEX.replace_all(html, " "))
+}
+
+/// Words in a post, code included, for reading-time estimates
+pub fn word_count(html: &str) -> usize {
+    TAG_REGEX.replace_all(html, " ").split_whitespace().count()
+}
+
+/// Strip HTML tags from text (for comments and other inline content)
+pub fn strip_html_tags(html: &str) -> String {
+    // Use html2text with a large width to avoid wrapping
+    html2text::from_read(sanitize_html(html).as_bytes(), 10000)
+        .unwrap_or_default()
+        .lines()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One-line summary of a post: its first sentence, without code or markup
+pub fn excerpt(html: &str) -> String {
+    let text = EXCERPT_SKIP_REGEX.replace_all(html, " ");
+    let text = TAG_REGEX.replace_all(&text, " ");
+    let text = decode_html_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    // Inline tags were replaced by spaces too: "a <code>x</code>." -> "a x ."
+    let text = SPACE_BEFORE_PUNCT_REGEX.replace_all(&text, "$1");
+    let sentence = SENTENCE_END_REGEX
+        .find_iter(&text)
+        .map(|end| &text[..end.start() + 1])
+        .find(|s| s.len() >= EXCERPT_MIN_CHARS)
+        .unwrap_or(&text);
+    if sentence.chars().count() <= EXCERPT_MAX_CHARS {
+        return sentence.to_string();
+    }
+    let cut: String = sentence.chars().take(EXCERPT_MAX_CHARS - 1).collect();
+    format!("{}\u{2026}", cut.trim_end())
+}
+
+/// Targets of the links in a post's HTML, as written
+pub fn link_urls(html: &str) -> impl Iterator<Item = &str> {
+    HREF_REGEX
+        .captures_iter(html)
+        .filter_map(|cap| cap.get(1))
+        .map(|m| m.as_str())
+}
+
+pub fn extract_so_question_id(url: &str) -> Option<i64> {
+    SO_QUESTION_REGEX
+        .captures(url)
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// Identify the Stack Exchange site a URL points to
+pub fn extract_site(url: &str) -> Option<Site> {
+    SITE_HOST_REGEX
+        .captures(url)
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| Site::from_host(m.as_str()))
+}
+
+/// Extract the answer ID from `/a/<id>` short links and
+/// `/questions/<qid>/<slug>/<id>` answer permalinks
+pub fn extract_so_answer_id(url: &str) -> Option<i64> {
+    SO_ANSWER_REGEX
+        .captures(url)
+        .and_then(|cap| cap.get(1).or_else(|| cap.get(2)))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+/// First link of a "Possible Duplicate" notice in a question body
+pub fn duplicate_notice_url(html: &str) -> Option<String> {
+    DUPLICATE_NOTICE_REGEX
+        .captures(html)
+        .and_then(|cap| cap.get(1))
+        .map(|m| decode_html_entities(m.as_str()))
+}
+
+pub fn is_erwin(author_name: &str) -> bool {
+    author_name.to_lowercase().contains("erwin")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_count_ignores_markup() {
+        assert_eq!(word_count(""), 0);
+        assert_eq!(word_count("<p>Use <code>DISTINCT ON</code>.</p>"), 4);
+        // Tags between words separate them
+        assert_eq!(word_count("one<br>two<br/>three"), 3);
+        assert_eq!(
+            word_count("<pre><code>SELECT 1;\nSELECT 2;</code></pre>"),
+            4
+        );
+    }
+
+    #[test]
+    fn excerpt_is_the_first_long_enough_sentence() {
+        assert_eq!(
+            excerpt("<p>Hi. I have a table with many rows per group. How do I pick one?</p>"),
+            "Hi. I have a table with many rows per group."
+        );
+        assert_eq!(
+            excerpt("<p>Call <code>now()</code> inside a transaction, then compare.</p>"),
+            "Call now() inside a transaction, then compare."
+        );
+        assert_eq!(
+            excerpt("<p>No sentence end here</p>"),
+            "No sentence end here"
+        );
+        assert_eq!(excerpt("<p>Fish &amp; chips &lt;3</p>"), "Fish & chips <3");
+    }
+
+    #[test]
+    fn excerpt_skips_code_blocks_and_duplicate_notices() {
+        let html = "<blockquote>\n<p><strong>Possible Duplicate:</strong><br>\n\
+                    <a href=\"https://stackoverflow.com/q/1\">Other</a></p>\n</blockquote>\n\
+                    <pre><code>SELECT * FROM t;</code></pre>\n\
+                    <p>This query returns every row twice.</p>";
+        assert_eq!(excerpt(html), "This query returns every row twice.");
+    }
+
+    #[test]
+    fn long_excerpts_are_cut() {
+        let excerpt = excerpt(&format!("<p>{}.</p>", "word ".repeat(100)));
+        assert_eq!(excerpt.chars().count(), EXCERPT_MAX_CHARS);
+        assert!(excerpt.ends_with("word\u{2026}"));
+    }
+}
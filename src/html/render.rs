@@ -0,0 +1,356 @@
+//! Posts rendered as styled ratatui lines, for the question page and `erwindb cat`
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::{
+    extract_site, extract_so_answer_id, extract_so_question_id, fit_cell, CodeBlock, Link,
+    PostNode, LINK_REF_REGEX,
+};
+use crate::highlight::highlight_code;
+use crate::sanitize::{prepare, split_list_marker, Inline, PreparedLine, Table};
+use crate::ui::styles;
+
+/// Drawn in front of quoted lines, once per level of nesting (as wide as the
+/// mark html2text laid them out with)
+const QUOTE_BAR: &str = "▎ ";
+
+/// Narrowest a table column is squeezed to before columns are left out
+const MIN_COLUMN_WIDTH: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct ContentLine {
+    pub line: Line<'static>,
+    pub node: PostNode,
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedContent {
+    pub lines: Vec<ContentLine>,
+    pub links: Vec<Link>,
+    pub code_blocks: Vec<CodeBlock>,
+}
+
+pub fn html_to_content(html: &str, width: usize) -> ParsedContent {
+    let prepared = prepare(html, width);
+    let link_map = &prepared.links;
+    let mut lines = Vec::new();
+    let mut all_links: Vec<Link> = Vec::new();
+    let mut rendered_blocks: Vec<CodeBlock> = Vec::new();
+
+    // Process each line, tracking where inline link references appear
+    for prepared_line in &prepared.lines {
+        let line = prepared_line.text.as_str();
+        let quote_bar = QUOTE_BAR.repeat(prepared_line.quote_depth);
+        // Check for code block placeholder
+        if let Some(code_idx) = prepared.code_block_index(line) {
+            push_list_marker(&mut lines, line, &quote_bar);
+            let (code, lang) = &prepared.code_blocks[code_idx];
+            let highlighted = highlight_code(code, lang.as_deref());
+            let start_line = lines.len();
+
+            for code_line in highlighted {
+                let mut indented_spans = vec![Span::raw("    ".to_string())];
+                for span in code_line.spans {
+                    indented_spans.push(Span::styled(span.content.to_string(), span.style));
+                }
+                lines.push(ContentLine {
+                    line: quoted(&quote_bar, Line::from(indented_spans)),
+                    node: PostNode::CodeBlock(code_idx),
+                });
+            }
+            rendered_blocks.push(CodeBlock {
+                code: code.clone(),
+                lang: lang.clone(),
+                start_line,
+                end_line: lines.len(),
+            });
+        } else if let Some(table_idx) = prepared.table_index(line) {
+            push_list_marker(&mut lines, line, &quote_bar);
+            let table = render_table(&prepared.tables[table_idx], width);
+            lines.extend(table.into_iter().map(|line| ContentLine {
+                line: quoted(&quote_bar, line),
+                node: PostNode::Table(table_idx),
+            }));
+        } else {
+            // Check if this line contains link references and track them
+            let line_index = lines.len();
+            for cap in LINK_REF_REGEX.captures_iter(line) {
+                let (Some(full_match), Some(num_match)) = (cap.get(0), cap.get(2)) else {
+                    continue;
+                };
+                if let Ok(link_num) = num_match.as_str().parse::<usize>() {
+                    if link_num > 0 && link_num <= link_map.len() {
+                        let (_, url) = &link_map[link_num - 1];
+                        // Calculate column positions using unicode width
+                        let start_col = quote_bar.width() + line[..full_match.start()].width();
+                        let end_col = start_col + full_match.as_str().width();
+                        all_links.push(Link {
+                            url: url.clone(),
+                            site: extract_site(url),
+                            line_index,
+                            question_id: extract_so_question_id(url),
+                            answer_id: extract_so_answer_id(url),
+                            start_col,
+                            end_col,
+                            hitboxes: Vec::new(),
+                        });
+                    }
+                }
+            }
+
+            // Style the line's formatting and link references
+            let styled_line = style_line(prepared_line, link_map);
+            lines.push(ContentLine {
+                line: quoted(&quote_bar, styled_line),
+                node: PostNode::Text,
+            });
+        }
+    }
+
+    ParsedContent {
+        lines,
+        links: all_links,
+        code_blocks: rendered_blocks,
+    }
+}
+
+/// The list item marker a code block or table placeholder line starts with,
+/// on a line of its own above the block
+fn push_list_marker(lines: &mut Vec<ContentLine>, line: &str, quote_bar: &str) {
+    let marker = split_list_marker(line).0.trim_end();
+    if !marker.is_empty() {
+        lines.push(ContentLine {
+            line: quoted(quote_bar, Line::from(marker.to_string())),
+            node: PostNode::Text,
+        });
+    }
+}
+
+/// A table as box-drawn lines at most `width` wide (unless even one squeezed
+/// column is wider). Columns shrink, widest first, cutting their cells with
+/// `…`; columns that still don't fit are left out behind a `…` column.
+/// Columns of numbers are right-aligned.
+fn render_table(table: &Table, width: usize) -> Vec<Line<'static>> {
+    let theme = styles::theme();
+    let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return Vec::new();
+    }
+    fn cell(row: &[String], col: usize) -> &str {
+        row.get(col).map_or("", String::as_str)
+    }
+    let body = &table.rows[table.header_rows..];
+
+    let mut widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            let widest = table.rows.iter().map(|row| cell(row, col).width());
+            widest.max().unwrap_or(0).max(1)
+        })
+        .collect();
+    let numeric: Vec<bool> = (0..columns)
+        .map(|col| {
+            let mut cells = body
+                .iter()
+                .map(|row| cell(row, col))
+                .filter(|c| !c.is_empty());
+            let first = cells.next();
+            first.is_some_and(|c| c.parse::<f64>().is_ok())
+                && cells.all(|c| c.parse::<f64>().is_ok())
+        })
+        .collect();
+
+    // Each column takes "│ " + cell + " ", the table a closing "│"; the `…`
+    // column for left-out ones takes 4 more
+    let table_width = |widths: &[usize]| {
+        let hidden = if widths.len() < columns { 4 } else { 0 };
+        widths.iter().map(|w| w + 3).sum::<usize>() + 1 + hidden
+    };
+    let mut shown = columns;
+    while table_width(&widths[..shown]) > width {
+        let widest = widths[..shown]
+            .iter_mut()
+            .filter(|w| **w > MIN_COLUMN_WIDTH)
+            .max_by_key(|w| **w);
+        match widest {
+            Some(widest) => *widest -= 1,
+            None if shown > 1 => shown -= 1,
+            None => break,
+        }
+    }
+    let widths = &widths[..shown];
+    let hidden = shown < columns;
+
+    let border = Style::default().fg(theme.dim);
+    let rule = |left: char, middle: char, right: char| {
+        let mut rule = String::from(left);
+        for (col, width) in widths.iter().enumerate() {
+            if col > 0 {
+                rule.push(middle);
+            }
+            rule.push_str(&"─".repeat(width + 2));
+        }
+        if hidden {
+            rule.push(middle);
+            rule.push_str("───");
+        }
+        rule.push(right);
+        Line::from(Span::styled(rule, border))
+    };
+
+    let mut lines = vec![rule('┌', '┬', '┐')];
+    for (index, row) in table.rows.iter().enumerate() {
+        if index == table.header_rows && index > 0 {
+            lines.push(rule('├', '┼', '┤'));
+        }
+        let header = index < table.header_rows;
+        let style = if header {
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        // Headers wrap so they stay readable; other cells are cut
+        let cells: Vec<Vec<String>> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, &width)| {
+                if header {
+                    wrap_cell(cell(row, col), width)
+                } else {
+                    vec![fit_cell(cell(row, col), width)]
+                }
+            })
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+        for line in 0..height {
+            let mut spans = Vec::new();
+            for (col, &width) in widths.iter().enumerate() {
+                let text = cells[col].get(line).map_or("", String::as_str);
+                let pad = " ".repeat(width.saturating_sub(text.width()));
+                let text = if numeric[col] && !header {
+                    format!("{pad}{text}")
+                } else {
+                    format!("{text}{pad}")
+                };
+                spans.push(Span::styled("│ ", border));
+                spans.push(Span::styled(text, style));
+                spans.push(Span::raw(" "));
+            }
+            if hidden {
+                let more = if line == 0 { "…" } else { " " };
+                spans.push(Span::styled("│ ", border));
+                spans.push(Span::styled(more, Style::default().fg(theme.dim)));
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled("│", border));
+            lines.push(Line::from(spans));
+        }
+    }
+    lines.push(rule('└', '┴', '┘'));
+    lines
+}
+
+/// `text` wrapped at spaces to lines of at most `width` columns; longer words
+/// are broken
+fn wrap_cell(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for word in text.split_whitespace() {
+        let line = lines.last_mut().unwrap();
+        if !line.is_empty() && line.width() + 1 + word.width() <= width {
+            line.push(' ');
+            line.push_str(word);
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(String::new());
+        }
+        for c in word.chars() {
+            let line = lines.last_mut().unwrap();
+            if !line.is_empty() && line.width() + c.width().unwrap_or(0) > width {
+                lines.push(String::new());
+            }
+            lines.last_mut().unwrap().push(c);
+        }
+    }
+    lines
+}
+
+/// A line's runs styled by their formatting, with link references laid over
+/// them
+fn style_line(line: &PreparedLine, link_map: &[(String, String)]) -> Line<'static> {
+    let text = line.text.as_str();
+
+    // Styled byte ranges: the runs, then what's laid over them
+    let mut ranges: Vec<(usize, usize, Style)> = Vec::new();
+    let mut run_start = 0;
+    for &(len, inline) in &line.runs {
+        ranges.push((run_start, run_start + len, inline_style(inline)));
+        run_start += len;
+    }
+    for cap in LINK_REF_REGEX.captures_iter(text) {
+        let (Some(full_match), Some(num_match)) = (cap.get(0), cap.get(2)) else {
+            continue;
+        };
+        let valid = num_match
+            .as_str()
+            .parse::<usize>()
+            .is_ok_and(|idx| idx > 0 && idx <= link_map.len());
+        if valid {
+            // `[text]` as a link, `[n]` dimmed
+            let num_start = num_match.start() - 1;
+            ranges.push((full_match.start(), num_start, styles::link_style()));
+            ranges.push((num_start, full_match.end(), styles::dim_style()));
+        }
+    }
+
+    let mut cuts: Vec<usize> = ranges
+        .iter()
+        .flat_map(|&(start, end, _)| [start, end])
+        .collect();
+    cuts.push(0);
+    cuts.push(text.len());
+    cuts.sort_unstable();
+    cuts.dedup();
+    let spans: Vec<Span<'static>> = cuts
+        .windows(2)
+        .map(|cut| {
+            let style = ranges
+                .iter()
+                .filter(|&&(start, end, _)| start <= cut[0] && cut[0] < end)
+                .fold(Style::default(), |style, &(_, _, over)| style.patch(over));
+            Span::styled(text[cut[0]..cut[1]].to_string(), style)
+        })
+        .collect();
+
+    if spans.is_empty() {
+        Line::from(text.to_string())
+    } else {
+        Line::from(spans)
+    }
+}
+
+/// `line` behind the bars of the quotes it's in
+fn quoted(bar: &str, mut line: Line<'static>) -> Line<'static> {
+    if !bar.is_empty() {
+        line.spans
+            .insert(0, Span::styled(bar.to_string(), styles::dim_style()));
+    }
+    line
+}
+
+fn inline_style(inline: Inline) -> Style {
+    let mut style = Style::default();
+    if inline.strong {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if inline.emphasis {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if inline.code {
+        style = style.patch(styles::inline_code_style());
+    }
+    style
+}
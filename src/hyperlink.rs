@@ -0,0 +1,126 @@
+//! OSC 8 hyperlinks for the links on the question page. ratatui measures a
+//! cell's symbol with unicode-width, so escape sequences can't go into the
+//! buffer; instead the show page records where each link is drawn, and after
+//! the frame the main loop prints those cells again between OSC 8 sequences.
+
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::buffer::{Buffer, Cell};
+use std::io::{self, Write};
+use unicode_width::UnicodeWidthStr;
+
+/// Ends the current hyperlink
+const CLOSE: &str = "\x1b]8;;\x1b\\";
+
+/// A link's text on one screen row
+#[derive(Debug, Clone)]
+pub struct Hyperlink {
+    pub x: u16,
+    pub y: u16,
+    /// The text drawn there, to tell whether a popup covers it since
+    pub text: String,
+    pub url: String,
+}
+
+/// Whether the terminal is known to handle OSC 8. Most others ignore it, but
+/// some print it, so unknown terminals get plain links.
+pub fn supported() -> bool {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    let term = var("TERM");
+    // Multiplexers only pass hyperlinks on when configured to
+    if !var("TMUX").is_empty() || term.starts_with("screen") {
+        return false;
+    }
+    if matches!(
+        var("TERM_PROGRAM").as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper" | "Tabby" | "rio"
+    ) {
+        return true;
+    }
+    // Windows Terminal, kitty, and Konsole since 21.04
+    if !var("WT_SESSION").is_empty() || !var("KITTY_WINDOW_ID").is_empty() {
+        return true;
+    }
+    if var("KONSOLE_VERSION")
+        .parse::<u32>()
+        .is_ok_and(|v| v >= 210_400)
+    {
+        return true;
+    }
+    // GNOME Terminal, Tilix and other VTE terminals since 0.50
+    if var("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5000) {
+        return true;
+    }
+    matches!(
+        term.as_str(),
+        "xterm-kitty" | "xterm-ghostty" | "alacritty" | "foot" | "foot-extra" | "wezterm"
+    )
+}
+
+/// Cells of one hyperlink as drawn in a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    url: String,
+    cells: Vec<(u16, u16, Cell)>,
+}
+
+/// The frame's cells under each link, leaving out links something was drawn
+/// over (a popup, the glossary)
+pub fn runs(links: &[Hyperlink], buffer: &Buffer) -> Vec<Run> {
+    let area = buffer.area;
+    links
+        .iter()
+        .filter_map(|link| {
+            let mut cells = Vec::new();
+            let mut text = String::new();
+            let mut x = link.x;
+            while text.width() < link.text.width() && x < area.right() && link.y < area.bottom() {
+                let cell = &buffer[(x, link.y)];
+                text.push_str(cell.symbol());
+                cells.push((x, link.y, cell.clone()));
+                // The cells a wide character covers aren't printed
+                x += cell.symbol().width().max(1) as u16;
+            }
+            (text == link.text).then(|| Run {
+                url: link.url.clone(),
+                cells,
+            })
+        })
+        .collect()
+}
+
+/// Prints link cells as hyperlinks, only when they changed since the last
+/// frame (the terminal keeps them until they're drawn over)
+#[derive(Default)]
+pub struct Printer {
+    printed: Vec<Run>,
+}
+
+impl Printer {
+    /// Print everything again next time, after the screen was cleared
+    pub fn invalidate(&mut self) {
+        self.printed.clear();
+    }
+
+    pub fn print<W: Write>(
+        &mut self,
+        backend: &mut CrosstermBackend<W>,
+        runs: Vec<Run>,
+    ) -> io::Result<()> {
+        if runs == self.printed {
+            return Ok(());
+        }
+        for run in &runs {
+            write!(backend, "\x1b]8;;{}\x1b\\", clean_url(&run.url))?;
+            backend.draw(run.cells.iter().map(|(x, y, cell)| (*x, *y, cell)))?;
+            backend.write_all(CLOSE.as_bytes())?;
+        }
+        Write::flush(backend)?;
+        self.printed = runs;
+        Ok(())
+    }
+}
+
+/// A URL without control characters, which could end the sequence early
+fn clean_url(url: &str) -> String {
+    url.chars().filter(|c| !c.is_control()).collect()
+}
@@ -0,0 +1,236 @@
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+
+use crate::db::{Answer, Comment, QuestionFull, QuestionSummary};
+use crate::html::is_erwin;
+use crate::license::license_for;
+
+/// Question metadata in `--format json` output
+#[derive(Serialize)]
+pub struct QuestionJson<'a> {
+    pub id: i64,
+    pub site: &'static str,
+    pub url: String,
+    pub title: &'a str,
+    pub author: &'a str,
+    pub score: i32,
+    pub view_count: i32,
+    pub answer_count: i32,
+    pub accepted_answer_id: Option<i64>,
+    pub created: String,
+    pub tags: &'a [String],
+    pub license: &'static str,
+    /// Semantic search distance, lower is closer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<f32>,
+}
+
+impl<'a> From<&'a QuestionSummary> for QuestionJson<'a> {
+    fn from(q: &'a QuestionSummary) -> Self {
+        Self {
+            id: q.id,
+            site: q.site.as_db(),
+            url: q.site.question_url(q.id),
+            title: &q.title,
+            author: &q.author_name,
+            score: q.score,
+            view_count: q.view_count,
+            answer_count: q.answer_count,
+            accepted_answer_id: q.accepted_answer_id,
+            created: timestamp(q.creation_date),
+            tags: &q.tags,
+            license: license_for(q.creation_date).name,
+            distance: None,
+        }
+    }
+}
+
+/// A question with its body, comments and answers; bodies are the stored HTML
+#[derive(Serialize)]
+pub struct ThreadJson<'a> {
+    #[serde(flatten)]
+    pub question: QuestionJson<'a>,
+    pub body: &'a str,
+    pub comments: Vec<CommentJson<'a>>,
+    pub answers: Vec<AnswerJson<'a>>,
+}
+
+impl<'a> ThreadJson<'a> {
+    pub fn new(
+        question: &'a QuestionFull,
+        comments: &'a [Comment],
+        answers: &'a [(Answer, Vec<Comment>)],
+    ) -> Self {
+        let summary = &question.summary;
+        Self {
+            question: QuestionJson::from(summary),
+            body: &question.body,
+            comments: comments.iter().map(CommentJson::from).collect(),
+            answers: answers
+                .iter()
+                .map(|(answer, comments)| AnswerJson {
+                    id: answer.answer_id,
+                    url: summary.site.answer_url(answer.answer_id),
+                    author: &answer.author_name,
+                    erwin: is_erwin(&answer.author_name),
+                    score: answer.score,
+                    accepted: answer.is_accepted,
+                    created: timestamp(answer.creation_date),
+                    license: license_for(answer.creation_date).name,
+                    body: &answer.answer_text,
+                    comments: comments.iter().map(CommentJson::from).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AnswerJson<'a> {
+    pub id: i64,
+    pub url: String,
+    pub author: &'a str,
+    pub erwin: bool,
+    pub score: i32,
+    pub accepted: bool,
+    pub created: String,
+    pub license: &'static str,
+    pub body: &'a str,
+    pub comments: Vec<CommentJson<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct CommentJson<'a> {
+    pub author: &'a str,
+    pub score: i32,
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a Comment> for CommentJson<'a> {
+    fn from(c: &'a Comment) -> Self {
+        Self {
+            author: &c.author_name,
+            score: c.score,
+            text: &c.comment_text,
+        }
+    }
+}
+
+/// RFC 3339 UTC timestamp, e.g. `2010-09-27T03:27:30Z`
+fn timestamp(secs: i64) -> String {
+    Utc.timestamp_opt(secs, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::QuestionStatus;
+    use crate::site::Site;
+
+    fn summary() -> QuestionSummary {
+        QuestionSummary {
+            id: 3800551,
+            title: "Select first row in each GROUP BY group?".to_string(),
+            score: 1800,
+            view_count: 2_000_000,
+            answer_count: 1,
+            creation_date: 1285558050,
+            accepted_answer_id: Some(7630564),
+            author_name: "kender".to_string(),
+            site: Site::Dba,
+            tags: vec!["sql".to_string(), "postgresql".to_string()],
+            answer_scores: vec![1500],
+            status: QuestionStatus::default(),
+            bounty: 0,
+            excerpt: String::new(),
+            word_count: 900,
+            erwin_accepted: true,
+        }
+    }
+
+    #[test]
+    fn question_json_shape() {
+        let summary = summary();
+        let mut question = QuestionJson::from(&summary);
+        assert_eq!(
+            serde_json::to_value(&question).unwrap(),
+            json!({
+                "id": 3800551,
+                "site": "dba",
+                "url": "https://dba.stackexchange.com/questions/3800551",
+                "title": "Select first row in each GROUP BY group?",
+                "author": "kender",
+                "score": 1800,
+                "view_count": 2_000_000,
+                "answer_count": 1,
+                "accepted_answer_id": 7630564,
+                "created": "2010-09-27T03:27:30Z",
+                "tags": ["sql", "postgresql"],
+                "license": "CC BY-SA 2.5",
+            })
+        );
+
+        question.distance = Some(0.25);
+        assert_eq!(serde_json::to_value(&question).unwrap()["distance"], 0.25);
+    }
+
+    #[test]
+    fn thread_json_shape() {
+        let question = QuestionFull {
+            summary: summary(),
+            body: "<p>Question</p>".to_string(),
+        };
+        let comment = |author: &str, text: &str| Comment {
+            comment_text: text.to_string(),
+            score: 2,
+            author_name: author.to_string(),
+        };
+        let comments = [comment("kender", "Which version?")];
+        let answers = [(
+            Answer {
+                id: 1,
+                answer_id: 7630564,
+                answer_text: "<p>Use DISTINCT ON.</p>".to_string(),
+                score: 1500,
+                is_accepted: true,
+                author_name: "Erwin Brandstetter".to_string(),
+                author_reputation: 600_000,
+                ingest_reputation: 500_000,
+                awarded_bounty: 0,
+                creation_date: 1559390400,
+                last_activity_date: 1559390400,
+            },
+            vec![comment("kender", "Thanks!")],
+        )];
+
+        let thread = ThreadJson::new(&question, &comments, &answers);
+        let value = serde_json::to_value(&thread).unwrap();
+        // The question's fields sit at the top level, next to its body
+        assert_eq!(value["id"], 3800551);
+        assert_eq!(value["body"], "<p>Question</p>");
+        assert_eq!(
+            value["comments"],
+            json!([{ "author": "kender", "score": 2, "text": "Which version?" }])
+        );
+        assert_eq!(
+            value["answers"],
+            json!([{
+                "id": 7630564,
+                "url": "https://dba.stackexchange.com/a/7630564",
+                "author": "Erwin Brandstetter",
+                "erwin": true,
+                "score": 1500,
+                "accepted": true,
+                "created": "2019-06-01T12:00:00Z",
+                "license": "CC BY-SA 4.0",
+                "body": "<p>Use DISTINCT ON.</p>",
+                "comments": [{ "author": "kender", "score": 2, "text": "Thanks!" }],
+            }])
+        );
+    }
+}
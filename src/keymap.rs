@@ -0,0 +1,154 @@
+use anyhow::{bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+use crate::app::Page;
+use crate::config::{KeyAction, KeysConfig};
+
+impl KeyAction {
+    /// Whether the action's default key means the action on `page`; elsewhere
+    /// the key does something else (`q` quits from the list) and is left alone
+    fn applies_on(self, page: Page) -> bool {
+        match self {
+            Self::Back => page != Page::Index,
+            Self::Search => matches!(page, Page::Index | Page::Bookmarks | Page::Tags),
+            Self::SemanticSearch => matches!(page, Page::Index | Page::Bookmarks),
+            Self::NextErwin | Self::PrevErwin => page == Page::Show,
+            _ => true,
+        }
+    }
+
+    /// The key the page handlers match for this action
+    fn default_key(self) -> Key {
+        let (code, modifiers) = match self {
+            Self::Down => (KeyCode::Char('j'), KeyModifiers::NONE),
+            Self::Up => (KeyCode::Char('k'), KeyModifiers::NONE),
+            Self::PageDown => (KeyCode::Char('d'), KeyModifiers::CONTROL),
+            Self::PageUp => (KeyCode::Char('u'), KeyModifiers::CONTROL),
+            Self::Top => (KeyCode::Char('g'), KeyModifiers::NONE),
+            Self::Bottom => (KeyCode::Char('G'), KeyModifiers::NONE),
+            Self::Open => (KeyCode::Enter, KeyModifiers::NONE),
+            Self::Back => (KeyCode::Char('q'), KeyModifiers::NONE),
+            Self::Search => (KeyCode::Char('/'), KeyModifiers::NONE),
+            Self::SemanticSearch => (KeyCode::Char('?'), KeyModifiers::NONE),
+            Self::NextErwin => (KeyCode::Char('e'), KeyModifiers::NONE),
+            Self::PrevErwin => (KeyCode::Char('E'), KeyModifiers::NONE),
+            Self::CycleTheme => (KeyCode::Char('t'), KeyModifiers::CONTROL),
+            Self::CycleLayout => (KeyCode::Char('l'), KeyModifiers::CONTROL),
+        };
+        Key { code, modifiers }
+    }
+}
+
+/// A key press as bindings compare it. Shift is part of the character for
+/// character keys (`G`, `?`), so it's dropped there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for Key {
+    fn from(key: KeyEvent) -> Self {
+        let mut modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !matches!(key.code, KeyCode::Char(_)) {
+            modifiers |= key.modifiers & KeyModifiers::SHIFT;
+        }
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+}
+
+/// The layer between key events and the page handlers: keys bound in `[keys]`
+/// reach the handlers as their action's default key, and the default keys of
+/// rebound actions do nothing (unless bound to something else), on the pages
+/// where the action applies.
+#[derive(Default)]
+pub struct Keymap {
+    /// Configured key -> its action
+    remap: HashMap<Key, KeyAction>,
+    /// Default keys of rebound actions
+    unbound: HashMap<Key, KeyAction>,
+}
+
+impl Keymap {
+    pub fn new(config: &KeysConfig) -> Result<Self> {
+        let mut keymap = Self::default();
+        for (&action, specs) in &config.bindings {
+            keymap.unbound.insert(action.default_key(), action);
+            for spec in specs {
+                let key =
+                    parse_key(spec).with_context(|| format!("Invalid key `{spec}` in [keys]"))?;
+                if let Some(previous) = keymap.remap.insert(key, action) {
+                    if previous != action {
+                        bail!("Key `{spec}` is bound to more than one action in [keys]");
+                    }
+                }
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// The key `page`'s handler should see for `key`; `None` if it was
+    /// unbound
+    pub fn translate(&self, key: KeyEvent, page: Page) -> Option<KeyEvent> {
+        let pressed = Key::from(key);
+        if let Some(action) = self.remap.get(&pressed).filter(|a| a.applies_on(page)) {
+            let target = action.default_key();
+            return Some(KeyEvent::new(target.code, target.modifiers));
+        }
+        if self
+            .unbound
+            .get(&pressed)
+            .is_some_and(|a| a.applies_on(page))
+        {
+            return None;
+        }
+        Some(key)
+    }
+}
+
+/// `j`, `G`, `enter`, `space`, `pagedown`, `f5`, `ctrl-d`, `alt-j`...
+fn parse_key(spec: &str) -> Result<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = spec;
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        if let Some(key) = lower.strip_prefix("ctrl-").filter(|k| !k.is_empty()) {
+            modifiers |= KeyModifiers::CONTROL;
+            rest = &rest[rest.len() - key.len()..];
+        } else if let Some(key) = lower.strip_prefix("alt-").filter(|k| !k.is_empty()) {
+            modifiers |= KeyModifiers::ALT;
+            rest = &rest[rest.len() - key.len()..];
+        } else {
+            break;
+        }
+    }
+
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => bail!("unknown key name"),
+            },
+        },
+    };
+    Ok(Key { code, modifiers })
+}
@@ -0,0 +1,100 @@
+//! The database, rendering and search layers behind the `erwindb` binary, for
+//! tools of your own: [`db::Database`] over the corpus, [`html`] and
+//! [`document`] for posts, [`search`] for fuzzy, full-text and semantic
+//! queries (`search::semantic::SemanticSearch`), and, with the default `tui`
+//! feature, the ratatui content builders ([`content`], `html::html_to_content`).
+//!
+//! Without `tui` the library builds without ratatui, crossterm, Lua or an
+//! HTTP client.
+
+#[cfg(feature = "tui")]
+pub mod content;
+pub mod db;
+pub mod document;
+pub mod html;
+pub mod search;
+pub mod site;
+
+// Shared with the binary's `main.rs` and `cli.rs`; not a stable API
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod ansi;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod app;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod clock;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod config;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod export;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod external;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod fixture;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod hooks;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod hyperlink;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod json;
+#[doc(hidden)]
+pub mod license;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod report;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod se_api;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod superseded;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod ui;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod update;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod userdb;
+
+#[cfg(feature = "tui")]
+mod alerts;
+#[cfg(feature = "tui")]
+mod dwell;
+#[cfg(feature = "tui")]
+mod fetch;
+#[cfg(feature = "tui")]
+mod glossary;
+#[cfg(feature = "tui")]
+mod highlight;
+#[cfg(feature = "tui")]
+mod keymap;
+#[cfg(feature = "tui")]
+mod links;
+#[cfg(feature = "tui")]
+mod markdown;
+mod memory;
+#[cfg(feature = "tui")]
+mod playground;
+#[cfg(feature = "tui")]
+mod plugins;
+#[cfg(feature = "tui")]
+mod quality;
+mod sanitize;
+#[cfg(feature = "tui")]
+mod techniques;
+mod terms;
+#[cfg(feature = "tui")]
+mod translate;
+#[cfg(feature = "tui")]
+mod tts;
@@ -0,0 +1,84 @@
+use chrono::{TimeZone, Utc};
+
+/// Creative Commons license a Stack Exchange post is published under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct License {
+    pub name: &'static str,
+    pub url: &'static str,
+}
+
+const CC_BY_SA_2_5: License = License {
+    name: "CC BY-SA 2.5",
+    url: "https://creativecommons.org/licenses/by-sa/2.5/",
+};
+const CC_BY_SA_3_0: License = License {
+    name: "CC BY-SA 3.0",
+    url: "https://creativecommons.org/licenses/by-sa/3.0/",
+};
+const CC_BY_SA_4_0: License = License {
+    name: "CC BY-SA 4.0",
+    url: "https://creativecommons.org/licenses/by-sa/4.0/",
+};
+
+/// License for a post created at `timestamp`. Stack Exchange moved new
+/// contributions to 3.0 on 2011-04-08 and to 4.0 on 2018-05-02
+/// (see https://stackoverflow.com/help/licensing).
+pub fn license_for(timestamp: i64) -> License {
+    let cutoff = |y, m, d| Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap().timestamp();
+    if timestamp < cutoff(2011, 4, 8) {
+        CC_BY_SA_2_5
+    } else if timestamp < cutoff(2018, 5, 2) {
+        CC_BY_SA_3_0
+    } else {
+        CC_BY_SA_4_0
+    }
+}
+
+/// Who wrote a post and where it came from, for attribution blocks
+#[derive(Debug, Clone)]
+pub struct Attribution {
+    /// "Question" or "Answer"
+    pub kind: &'static str,
+    pub title: String,
+    pub author: String,
+    pub url: String,
+    pub license: License,
+}
+
+/// HTML version of `attribution_markdown`, for printable exports
+pub fn attribution_html(posts: &[Attribution]) -> String {
+    let mut out = String::from(
+        "<section class=\"attribution\">\n<h2>Attribution</h2>\n<p>Content from Stack Exchange, \
+         used under the Creative Commons Attribution-ShareAlike license version noted for each \
+         post.</p>\n<ul>\n",
+    );
+    for post in posts {
+        out.push_str(&format!(
+            "<li>{} \u{201c}{}\u{201d} by {}, <a href=\"{}\">{}</a>, <a href=\"{}\">{}</a></li>\n",
+            post.kind,
+            post.title,
+            post.author,
+            post.url,
+            post.url,
+            post.license.url,
+            post.license.name
+        ));
+    }
+    out.push_str("</ul>\n</section>\n");
+    out
+}
+
+/// Markdown section crediting each post's author, source and license
+pub fn attribution_markdown(posts: &[Attribution]) -> String {
+    let mut out = String::from(
+        "## Attribution\n\nContent from Stack Exchange, used under the Creative Commons \
+         Attribution-ShareAlike license version noted for each post.\n\n",
+    );
+    for post in posts {
+        out.push_str(&format!(
+            "- {} \"{}\" by {}, <{}>, [{}]({})\n",
+            post.kind, post.title, post.author, post.url, post.license.name, post.license.url
+        ));
+    }
+    out
+}
@@ -0,0 +1,83 @@
+//! The corpus's link graph: which threads link to which, from the question
+//! and answer links in their posts. Built once per run on a background
+//! thread, for the show page's "Linked questions" and "Linked from" lists.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::db::Database;
+use crate::site::Site;
+
+/// Threads linking to each other, by question ID
+#[derive(Debug, Default)]
+pub struct LinkGraph {
+    /// Questions each thread links to, in the order its posts first do
+    linked: HashMap<i64, Vec<i64>>,
+    /// Threads linking to each question
+    linked_from: HashMap<i64, Vec<i64>>,
+}
+
+impl LinkGraph {
+    pub fn build(db: &Database) -> Result<Self> {
+        let mut graph = Self::default();
+        for (source, target) in db.get_question_links()? {
+            let linked = graph.linked.entry(source).or_default();
+            if !linked.contains(&target) {
+                linked.push(target);
+                graph.linked_from.entry(target).or_default().push(source);
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Questions the thread links to
+    pub fn linked(&self, question_id: i64) -> &[i64] {
+        self.linked.get(&question_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Threads linking to the question ("what links here")
+    pub fn linked_from(&self, question_id: i64) -> &[i64] {
+        self.linked_from
+            .get(&question_id)
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// A thread in a linked-questions list
+#[derive(Debug, Clone)]
+pub struct LinkedQuestion {
+    pub id: i64,
+    pub site: Site,
+    pub title: String,
+    pub score: i32,
+}
+
+/// `LinkGraph` building on a background thread with its own connection,
+/// since it reads every post
+pub struct LinkGraphLoad {
+    result: Receiver<Result<LinkGraph>>,
+}
+
+impl LinkGraphLoad {
+    pub fn start(db_path: PathBuf) -> Self {
+        let (tx, result) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(Database::open(&db_path).and_then(|db| LinkGraph::build(&db)));
+        });
+        Self { result }
+    }
+
+    /// `None` while still building
+    pub fn poll(&self) -> Option<Result<LinkGraph>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(anyhow::anyhow!(
+                "Building the link graph stopped unexpectedly"
+            ))),
+        }
+    }
+}
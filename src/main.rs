@@ -1,39 +1,78 @@
-mod app;
-mod content;
-mod db;
+mod cli;
 mod event;
-mod highlight;
-mod html;
-mod search;
-mod ui;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use clap::Parser;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
 
-use app::App;
+use cli::{Cli, Command};
+use erwindb::app::App;
+use erwindb::external::{self, ExternalCommand};
+use erwindb::{clock, hyperlink, report, ui};
 use event::EventHandler;
 
 fn main() -> Result<()> {
-    if std::env::args().any(|a| a == "--version" || a == "-V") {
-        println!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
-        return Ok(());
+    let cli = Cli::parse();
+    let deterministic = cli.deterministic;
+    if deterministic {
+        clock::set_deterministic();
     }
+    cli.select_database()?;
+
+    let tui = match cli.command {
+        None => cli.tui,
+        Some(Command::Tui(args)) => args,
+        Some(Command::List(args)) => return cli::list(args),
+        Some(Command::Show(args)) => return cli::show(args),
+        Some(Command::Cat(args)) => return cli::cat(args),
+        Some(Command::Search(args)) => return cli::search(args),
+        Some(Command::Semantic(args)) => return cli::semantic(args),
+        Some(Command::Export(args)) => return cli::export(args),
+        Some(Command::ExportPdf(args)) => return cli::export_pdf(args),
+        Some(Command::ExportOrg { target }) => return cli::export_org(&target),
+        Some(Command::ExportObsidian(args)) => return cli::export_obsidian(args),
+        Some(Command::MakeFixture(args)) => return cli::make_fixture(args),
+        Some(Command::State(command)) => return cli::state(command),
+        Some(Command::Api(command)) => return cli::api(command),
+        Some(Command::Update(args)) => return cli::update(args),
+        Some(Command::Wishlist) => return cli::wishlist(),
+    };
 
     // Create app first (downloads models with progress bars visible)
-    let mut app = App::new()?;
-    let events = EventHandler::new(16); // ~60fps for responsive scrolling
+    let pick = tui.pick;
+    let events = EventHandler::new(16, !deterministic); // ~60fps for responsive scrolling
+    let mut app = App::new(pick.is_some(), events.waker())?;
+    if !tui.query.is_empty() {
+        app.search_on_start(&tui.query.join(" "));
+    }
+
+    // In pick mode stdout carries the result, so draw on the terminal itself
+    let mut output: Box<dyn Write> = if pick.is_some() {
+        let tty = OpenOptions::new()
+            .write(true)
+            .open("/dev/tty")
+            .context("--pick needs a terminal")?;
+        Box::new(tty)
+    } else {
+        Box::new(io::stdout())
+    };
 
     // Set up terminal after models are loaded
     enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    execute!(
+        output,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    let backend = CrosstermBackend::new(output);
     let mut terminal = Terminal::new(backend)?;
 
     // Main loop
@@ -44,7 +83,8 @@ fn main() -> Result<()> {
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
@@ -52,27 +92,67 @@ fn main() -> Result<()> {
         eprintln!("Error: {err:?}");
     }
 
+    if let Some(format) = pick {
+        let picked = app
+            .picked
+            .and_then(|id| app.questions.iter().find(|q| q.id == id))
+            .map(|question| format.format(question));
+        // Dropping the app finishes its queued user database writes
+        drop(app);
+        match picked {
+            Some(line) => println!("{line}"),
+            // Like other pickers, signal "nothing chosen" through the exit status
+            None => std::process::exit(1),
+        }
+    }
+
     Ok(())
 }
 
-fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+fn run_app<W: Write>(
+    terminal: &mut Terminal<CrosstermBackend<W>>,
     app: &mut App,
     events: &EventHandler,
 ) -> Result<()> {
+    let mut hyperlinks = hyperlink::Printer::default();
     loop {
-        terminal.draw(|frame| ui::draw(frame, app))?;
+        let frame = terminal.draw(|frame| ui::draw(frame, app))?;
+        let runs = hyperlink::runs(&app.hyperlinks, frame.buffer);
+        hyperlinks.print(terminal.backend_mut(), runs)?;
 
         match events.next()? {
-            event::Event::Tick => {}
+            event::Event::Tick => app.tick(),
+            event::Event::Wake => app.poll_title_matches(),
             event::Event::Key(key) => {
                 app.handle_key(key);
+                app.track_dwell();
             }
             event::Event::Mouse(mouse) => {
                 app.handle_mouse(mouse);
+                app.track_dwell();
             }
             event::Event::Resize(width, height) => {
                 app.handle_resize(width, height);
+                hyperlinks.invalidate();
+            }
+            event::Event::Paste(text) => app.handle_paste(&text),
+        }
+
+        if let Some(command) = app.pending_external.take() {
+            if let Err(err) = run_suspended(terminal, &command) {
+                app.flash = Some(err.to_string());
+            }
+            hyperlinks.invalidate();
+        }
+
+        if let Some(text) = app.pending_clipboard.take() {
+            let backend = terminal.backend_mut();
+            let sequence = report::clipboard_sequence(&text);
+            if let Err(err) = backend
+                .write_all(sequence.as_bytes())
+                .and_then(|()| backend.flush())
+            {
+                app.flash = Some(format!("Failed to copy: {err}"));
             }
         }
 
@@ -81,3 +161,28 @@ fn run_app(
         }
     }
 }
+
+/// Suspend the TUI, run the command in the normal terminal, then restore the TUI
+fn run_suspended<W: Write>(
+    terminal: &mut Terminal<CrosstermBackend<W>>,
+    command: &ExternalCommand,
+) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+
+    let result = external::run(command);
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture
+    )?;
+    terminal.clear()?;
+
+    result
+}
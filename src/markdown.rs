@@ -0,0 +1,296 @@
+use scraper::{ElementRef, Html, Node};
+
+use crate::sanitize::{extract_lang_from_class, sanitize_html};
+
+/// Code block language for code without a `lang-*` hint; the corpus is SQL
+const DEFAULT_LANG: &str = "sql";
+
+/// A post's HTML as Markdown: paragraphs, headings, emphasis, links, images,
+/// lists, quotes, tables, and fenced code blocks with the post's language hint
+pub fn html_to_markdown(html: &str) -> String {
+    let fragment = Html::parse_fragment(&sanitize_html(html));
+    tidy(&children(fragment.root_element()))
+}
+
+/// Push every heading `levels` deeper (capped at `######`), so a post's own
+/// headings sit below the headings of the document it's embedded in
+pub fn demote_headings(markdown: &str, levels: usize) -> String {
+    let mut out = String::new();
+    let mut fence: Option<String> = None;
+    for line in markdown.lines() {
+        match fence {
+            Some(ref open) if closes_fence(open, line) => fence = None,
+            Some(_) => {}
+            None if line.starts_with("```") => fence = Some(fence_of(line)),
+            None if line.starts_with('#') => {
+                let level = line.chars().take_while(|&c| c == '#').count();
+                out.push_str(&"#".repeat((level + levels).min(6)));
+                out.push_str(&line[level..]);
+                out.push('\n');
+                continue;
+            }
+            None => {}
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+fn children(element: ElementRef) -> String {
+    element
+        .children()
+        .map(|child| match (ElementRef::wrap(child), child.value()) {
+            (Some(element), _) => render(element),
+            (None, Node::Text(text)) => escape(&collapse_whitespace(text)),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+fn render(node: ElementRef) -> String {
+    let element = node.value();
+    match element.name() {
+        "p" | "div" => block(&children(node)),
+        name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+            let level = usize::from(name.as_bytes()[1] - b'0');
+            block(&format!("{} {}", "#".repeat(level), children(node).trim()))
+        }
+        "br" => "  \n".to_string(),
+        "hr" => block("---"),
+        "pre" => {
+            let lang = extract_lang_from_class(element.attr("class"));
+            code_block(lang.as_deref().unwrap_or(DEFAULT_LANG), &text_of(node))
+        }
+        "code" | "kbd" => inline_code(&text_of(node)),
+        "strong" | "b" => emphasis("**", &children(node)),
+        "em" | "i" => emphasis("*", &children(node)),
+        "del" | "s" | "strike" => emphasis("~~", &children(node)),
+        "a" => {
+            let text = children(node).trim().to_string();
+            match element.attr("href") {
+                Some(href) if text.is_empty() || text == href => format!("<{href}>"),
+                Some(href) => format!("[{text}]({href})"),
+                None => text,
+            }
+        }
+        "img" => format!(
+            "![{}]({})",
+            element.attr("alt").unwrap_or_default(),
+            element.attr("src").unwrap_or_default()
+        ),
+        "ul" => list(node, None),
+        "ol" => {
+            let start = element.attr("start").and_then(|s| s.parse().ok());
+            list(node, Some(start.unwrap_or(1)))
+        }
+        "blockquote" => {
+            let quoted: String = tidy(&children(node))
+                .lines()
+                .map(|line| match line {
+                    "" => ">\n".to_string(),
+                    _ => format!("> {line}\n"),
+                })
+                .collect();
+            block(&quoted)
+        }
+        "table" => table(node),
+        _ => children(node),
+    }
+}
+
+/// A paragraph-level element, set off by blank lines
+fn block(content: &str) -> String {
+    format!("\n\n{}\n\n", content.trim())
+}
+
+/// `**bold**` and the like; markers go inside any surrounding spaces, which
+/// would otherwise stop them from counting
+fn emphasis(marker: &str, content: &str) -> String {
+    let text = content.trim();
+    if text.is_empty() {
+        return content.to_string();
+    }
+    let before = if content.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let after = if content.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!("{before}{marker}{text}{marker}{after}")
+}
+
+fn code_block(lang: &str, code: &str) -> String {
+    let fence = "`".repeat((longest_backtick_run(code) + 1).max(3));
+    format!("\n\n{fence}{lang}\n{}\n{fence}\n\n", code.trim_end())
+}
+
+fn inline_code(code: &str) -> String {
+    let code = code.replace('\n', " ");
+    let fence = "`".repeat(longest_backtick_run(&code) + 1);
+    // A space keeps a backtick at either end from reading as part of the fence
+    let pad = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{fence}{pad}{code}{pad}{fence}")
+}
+
+fn list(node: ElementRef, mut number: Option<usize>) -> String {
+    let mut out = String::new();
+    for item in node.children().filter_map(ElementRef::wrap) {
+        if item.value().name() != "li" {
+            continue;
+        }
+        let marker = match number {
+            Some(n) => format!("{n}. "),
+            None => "- ".to_string(),
+        };
+        let indent = " ".repeat(marker.len());
+        for (i, line) in tidy(&children(item)).lines().enumerate() {
+            match (i, line) {
+                (0, _) => out.push_str(&format!("{marker}{line}")),
+                (_, "") => {}
+                _ => out.push_str(&format!("{indent}{line}")),
+            }
+            out.push('\n');
+        }
+        number = number.map(|n| n + 1);
+    }
+    block(&out)
+}
+
+/// A GitHub-style table; the first row becomes the header
+fn table(node: ElementRef) -> String {
+    let rows: Vec<Vec<String>> = node
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|el| el.value().name() == "tr")
+        .map(|row| {
+            row.children()
+                .filter_map(ElementRef::wrap)
+                .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+                .map(|cell| {
+                    tidy(&children(cell))
+                        .lines()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .replace('|', "\\|")
+                })
+                .collect()
+        })
+        .filter(|cells: &Vec<String>| !cells.is_empty())
+        .collect();
+    let Some(columns) = rows.iter().map(Vec::len).max() else {
+        return String::new();
+    };
+
+    let line = |cells: &[String]| {
+        let mut line = String::from("|");
+        for i in 0..columns {
+            line.push_str(&format!(" {} |", cells.get(i).map_or("", String::as_str)));
+        }
+        line.push('\n');
+        line
+    };
+    let mut out = line(&rows[0]);
+    out.push_str(&format!("|{}\n", " --- |".repeat(columns)));
+    for row in &rows[1..] {
+        out.push_str(&line(row));
+    }
+    block(&out)
+}
+
+/// Text of an element and everything in it, as written (for code)
+fn text_of(node: ElementRef) -> String {
+    node.text().collect()
+}
+
+/// HTML's whitespace rules: runs of spaces and line breaks become one space
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                out.push(' ');
+            }
+            in_space = true;
+        } else {
+            out.push(c);
+            in_space = false;
+        }
+    }
+    out
+}
+
+/// Backslash-escape characters Markdown would read as formatting. An
+/// underscore inside a word (`table_name`) can't start emphasis and stays.
+fn escape(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let escaped = match c {
+            '\\' | '`' | '*' | '[' | ']' => true,
+            '_' => {
+                let word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric());
+                !(word(i.checked_sub(1).and_then(|p| chars.get(p))) && word(chars.get(i + 1)))
+            }
+            _ => false,
+        };
+        if escaped {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+/// The backtick fence a code block opens with
+fn fence_of(line: &str) -> String {
+    line.chars().take_while(|&c| c == '`').collect()
+}
+
+/// Whether `line` ends the code block `open` started: a run of at least as
+/// many backticks and nothing else
+fn closes_fence(open: &str, line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with(open) && line.trim_matches('`').is_empty()
+}
+
+/// Collapse the blank lines blocks leave between each other (code blocks
+/// keep theirs) and trim the ends
+fn tidy(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut fence: Option<String> = None;
+    let mut blank = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(ref open) if closes_fence(open, line) => fence = None,
+            Some(_) => {}
+            None if line.trim().is_empty() => {
+                blank = !out.is_empty();
+                continue;
+            }
+            None if trimmed.starts_with("```") => fence = Some(fence_of(trimmed)),
+            None => {}
+        }
+        if blank {
+            out.push('\n');
+            blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
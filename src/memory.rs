@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+#[cfg(feature = "tui")]
+use std::fs;
+use std::mem::size_of;
+
+use crate::db::{Answer, Comment, QuestionFull, QuestionSummary};
+use crate::html::{CodeBlock, Link};
+
+pub const MB: usize = 1024 * 1024;
+
+/// `[memory] budget_mb` unless configured
+pub const DEFAULT_BUDGET_MB: usize = 64;
+
+/// Heap bytes a value owns, estimated from allocated capacities. Allocator
+/// overhead isn't counted, so real use runs somewhat higher.
+pub trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl HeapSize for Cow<'_, str> {
+    fn heap_size(&self) -> usize {
+        match self {
+            Cow::Borrowed(_) => 0,
+            Cow::Owned(text) => text.heap_size(),
+        }
+    }
+}
+
+impl HeapSize for QuestionSummary {
+    fn heap_size(&self) -> usize {
+        self.title.heap_size()
+            + self.author_name.heap_size()
+            + self.tags.heap_size()
+            + self.answer_scores.capacity() * size_of::<i32>()
+            + self.excerpt.heap_size()
+    }
+}
+
+impl HeapSize for QuestionFull {
+    fn heap_size(&self) -> usize {
+        self.summary.heap_size() + self.body.heap_size()
+    }
+}
+
+impl HeapSize for Answer {
+    fn heap_size(&self) -> usize {
+        self.answer_text.heap_size() + self.author_name.heap_size()
+    }
+}
+
+impl HeapSize for Comment {
+    fn heap_size(&self) -> usize {
+        self.comment_text.heap_size() + self.author_name.heap_size()
+    }
+}
+
+impl HeapSize for Link {
+    fn heap_size(&self) -> usize {
+        self.url.heap_size() + self.hitboxes.capacity() * size_of::<crate::html::Hitbox>()
+    }
+}
+
+impl HeapSize for CodeBlock {
+    fn heap_size(&self) -> usize {
+        self.code.heap_size() + self.lang.heap_size()
+    }
+}
+
+/// Sizes of the TUI's rendered lines and open thread
+#[cfg(feature = "tui")]
+mod tui {
+    use ratatui::text::{Line, Span};
+
+    use super::HeapSize;
+    use crate::app::ShowState;
+    use crate::content::SourceSpan;
+    use crate::glossary::TermHit;
+
+    impl HeapSize for Span<'_> {
+        fn heap_size(&self) -> usize {
+            self.content.heap_size()
+        }
+    }
+
+    impl HeapSize for Line<'_> {
+        fn heap_size(&self) -> usize {
+            self.spans.heap_size()
+        }
+    }
+
+    impl HeapSize for SourceSpan {
+        fn heap_size(&self) -> usize {
+            0
+        }
+    }
+
+    impl HeapSize for TermHit {
+        fn heap_size(&self) -> usize {
+            self.term.heap_size()
+        }
+    }
+
+    impl HeapSize for ShowState {
+        /// The open thread: its posts and everything rendered from them
+        fn heap_size(&self) -> usize {
+            self.question.heap_size()
+                + self.answers.heap_size()
+                + self.comments.heap_size()
+                + self.answer_comments.heap_size()
+                + self.rendered_content.heap_size()
+                + self.rendered_erwin_content.heap_size()
+                + self.content_links.heap_size()
+                + self.erwin_links.heap_size()
+                + self.code_blocks.heap_size()
+                + self.erwin_code_blocks.heap_size()
+                + self.content_sources.heap_size()
+                + self.erwin_sources.heap_size()
+                + self.content_terms.heap_size()
+                + self.erwin_terms.heap_size()
+        }
+    }
+}
+
+/// What `:memory` shows
+#[cfg(feature = "tui")]
+pub struct MemoryReport {
+    /// Label, bytes and a detail (e.g. an entry count) for each tracked part
+    pub parts: Vec<(&'static str, usize, String)>,
+    /// Bytes held by the parts the budget covers (the thread and the bodies)
+    pub budgeted: usize,
+    /// `[memory] budget_mb` in bytes
+    pub budget: usize,
+    /// Resident size of the whole process, where the platform reports it
+    pub resident: Option<usize>,
+}
+
+#[cfg(feature = "tui")]
+impl MemoryReport {
+    /// Bytes held by the tracked parts
+    pub fn tracked(&self) -> usize {
+        self.parts.iter().map(|(_, bytes, _)| bytes).sum()
+    }
+}
+
+/// Resident set size from `/proc` (Linux only)
+#[cfg(feature = "tui")]
+pub fn resident_size() -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kib: usize = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// `1.4 MB`, `812 KB`, `96 B`
+#[cfg(feature = "tui")]
+pub fn format_bytes(bytes: usize) -> String {
+    const KB: usize = 1024;
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{} KB", bytes / KB)
+    } else {
+        format!("{bytes} B")
+    }
+}
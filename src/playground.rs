@@ -0,0 +1,136 @@
+use anyhow::{anyhow, Context, Result};
+use std::io::{Read, Write};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::external::kill_group;
+
+/// What a playground query printed: CSV rows on stdout, notices and errors on stderr
+#[derive(Debug, Clone, Default)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub messages: String,
+    /// Exit code, when the command failed
+    pub failed: Option<i32>,
+}
+
+/// A playground query running in the background; the command gets the SQL on
+/// stdin and prints the result as CSV (e.g. `psql --csv`)
+pub struct QueryRun {
+    command: String,
+    child: Child,
+    output: Receiver<(String, String)>,
+}
+
+impl QueryRun {
+    pub fn start(command: &str, sql: String) -> Result<Self> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .spawn()
+            .with_context(|| format!("Failed to run `{command}`"))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            thread::spawn(move || {
+                let _ = stdin.write_all(sql.as_bytes());
+            });
+        }
+
+        // Both pipes are drained concurrently so neither fills up and blocks the query
+        let (tx, output) = mpsc::channel();
+        if let (Some(mut stdout), Some(mut stderr)) = (child.stdout.take(), child.stderr.take()) {
+            thread::spawn(move || {
+                let errors = thread::spawn(move || {
+                    let mut text = String::new();
+                    let _ = stderr.read_to_string(&mut text);
+                    text
+                });
+                let mut text = String::new();
+                let _ = stdout.read_to_string(&mut text);
+                let _ = tx.send((text, errors.join().unwrap_or_default()));
+            });
+        }
+
+        Ok(Self {
+            command: command.to_string(),
+            child,
+            output,
+        })
+    }
+
+    /// `None` while the query is still running
+    pub fn poll(&mut self) -> Option<Result<QueryResult>> {
+        let (stdout, stderr) = match self.output.try_recv() {
+            Ok(output) => output,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => {
+                return Some(Err(anyhow!("`{}` produced no output", self.command)))
+            }
+        };
+        let status = match self.child.wait() {
+            Ok(status) => status,
+            Err(err) => return Some(Err(err.into())),
+        };
+        if status.code() == Some(127) {
+            return Some(Err(anyhow!("Command not found: `{}`", self.command)));
+        }
+
+        let mut rows = parse_csv(&stdout);
+        let columns = if rows.is_empty() {
+            Vec::new()
+        } else {
+            rows.remove(0)
+        };
+        Some(Ok(QueryResult {
+            columns,
+            rows,
+            messages: stderr.trim_end().to_string(),
+            failed: status.code().filter(|code| *code != 0),
+        }))
+    }
+}
+
+impl Drop for QueryRun {
+    fn drop(&mut self) {
+        kill_group(&mut self.child);
+    }
+}
+
+/// RFC 4180 CSV as printed by `psql --csv`: quoted fields may hold commas,
+/// doubled quotes and newlines
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
@@ -0,0 +1,529 @@
+//! Lua plugins. Every `*.lua` file in `<config dir>/erwindb/plugins` runs once
+//! at startup, in file name order, with an `erwindb` table to register `:`
+//! commands, status bar segments and pages, and to query the corpus (read
+//! only). The table is the plugin API, versioned by `erwindb.api_version`.
+//! Callbacks run on the UI thread, so they should return quickly; one that
+//! runs past `INSTRUCTION_BUDGET` Lua instructions is stopped with an error.
+
+use anyhow::{anyhow, bail, Context as _, Result};
+use mlua::{Function, HookTriggers, Lua, RegistryKey, Table, Value, Variadic};
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{Connection, OpenFlags};
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::app::BUILTIN_COMMANDS;
+use crate::db::QuestionSummary;
+use crate::json::QuestionJson;
+
+/// Bumped when a change to the `erwindb` table could break existing plugins
+pub const API_VERSION: u32 = 1;
+
+/// Lua instructions a plugin file or callback may run before it's stopped,
+/// so a runaway loop can't hang the UI
+const INSTRUCTION_BUDGET: u32 = 100_000_000;
+/// Instructions between budget checks
+const INSTRUCTIONS_PER_CHECK: u32 = 10_000;
+
+/// A command or page, under the name `:` opens it by
+struct Registration {
+    plugin: String,
+    name: String,
+    /// The command's description, or the page's title
+    label: String,
+    function: RegistryKey,
+}
+
+/// Something a callback asked of the app, done once it returns
+#[derive(Debug)]
+pub enum Request {
+    Open(i64),
+    Flash(String),
+}
+
+#[derive(Default)]
+struct Registry {
+    /// Plugin whose file is running, to attribute registrations to
+    loading: String,
+    commands: Vec<Registration>,
+    pages: Vec<Registration>,
+    segments: Vec<(String, RegistryKey)>,
+    requests: Vec<Request>,
+}
+
+impl Registry {
+    fn check_name(&self, name: &str) -> mlua::Result<()> {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(mlua::Error::runtime(format!(
+                "`{name}` isn't a valid command name"
+            )));
+        }
+        if BUILTIN_COMMANDS.contains(&name) || name.parse::<i64>().is_ok() {
+            return Err(mlua::Error::runtime(format!(":{name} is taken by erwindb")));
+        }
+        match self
+            .commands
+            .iter()
+            .chain(&self.pages)
+            .find(|r| r.name == name)
+        {
+            Some(taken) => Err(mlua::Error::runtime(format!(
+                ":{name} is taken by plugin {}",
+                taken.plugin
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A line of a plugin page; Enter opens its question, if it has one
+#[derive(Debug, Clone)]
+pub struct PageLine {
+    pub text: String,
+    pub question: Option<i64>,
+}
+
+/// What callbacks get as their `ctx` argument
+pub struct Context<'a> {
+    /// `index`, `show`, `tags`, ... (`Page::name`)
+    pub page: &'static str,
+    /// The question open, or selected in a list
+    pub question: Option<&'a QuestionSummary>,
+}
+
+pub struct Plugins {
+    lua: Lua,
+    registry: Rc<RefCell<Registry>>,
+    /// Plugin file names, loaded or not
+    names: Vec<String>,
+    /// Status segments run while drawing, so their errors wait here for
+    /// `take_errors`; a segment that failed isn't run again
+    errors: RefCell<Vec<anyhow::Error>>,
+    failed_segments: RefCell<HashSet<usize>>,
+    /// Budget checks the running file or callback has passed
+    checks: Rc<Cell<u32>>,
+}
+
+impl Plugins {
+    /// Run the plugins in the plugin directory, with read-only access to the
+    /// corpus at `db_path`. A plugin that fails to load is skipped, with the
+    /// error returned.
+    pub fn load(db_path: Option<&Path>) -> (Self, Vec<anyhow::Error>) {
+        let mut plugins = Self {
+            lua: Lua::new(),
+            registry: Rc::default(),
+            names: Vec::new(),
+            errors: RefCell::default(),
+            failed_segments: RefCell::default(),
+            checks: Rc::default(),
+        };
+        plugins.install_budget();
+        let mut errors = Vec::new();
+        let files = match plugin_files() {
+            Ok(files) => files,
+            Err(err) => return (plugins, vec![err]),
+        };
+        if files.is_empty() {
+            return (plugins, errors);
+        }
+        if let Err(err) = plugins.install_api(db_path) {
+            return (plugins, vec![err]);
+        }
+        for file in files {
+            let name = file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            plugins.registry.borrow_mut().loading = name.clone();
+            if let Err(err) = plugins.run_file(&file) {
+                errors.push(err.context(format!("Plugin {name} failed to load")));
+            }
+            plugins.names.push(name);
+        }
+        (plugins, errors)
+    }
+
+    /// Stop whatever Lua is running once it's used up `INSTRUCTION_BUDGET`;
+    /// `start_budget` hands each file and callback a full one
+    fn install_budget(&self) {
+        let checks = Rc::clone(&self.checks);
+        let triggers = HookTriggers::new().every_nth_instruction(INSTRUCTIONS_PER_CHECK);
+        self.lua.set_hook(triggers, move |_, _| {
+            checks.set(checks.get() + 1);
+            if checks.get() > INSTRUCTION_BUDGET / INSTRUCTIONS_PER_CHECK {
+                return Err(mlua::Error::runtime(format!(
+                    "stopped after {INSTRUCTION_BUDGET} instructions"
+                )));
+            }
+            Ok(())
+        });
+    }
+
+    fn start_budget(&self) {
+        self.checks.set(0);
+    }
+
+    fn run_file(&self, file: &Path) -> Result<()> {
+        let source = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        self.start_budget();
+        self.lua
+            .load(&source)
+            .set_name(format!("@{}", file.display()))
+            .exec()
+            .map_err(lua_error)
+    }
+
+    /// The `erwindb` table
+    fn install_api(&self, db_path: Option<&Path>) -> Result<()> {
+        let lua = &self.lua;
+        let api = lua.create_table().map_err(lua_error)?;
+        api.set("api_version", API_VERSION).map_err(lua_error)?;
+
+        // erwindb.command(name, description, function(args, ctx))
+        let registry = Rc::clone(&self.registry);
+        let command = lua.create_function(
+            move |lua, (name, description, function): (String, String, Function)| {
+                let mut registry = registry.borrow_mut();
+                registry.check_name(&name)?;
+                let registration = Registration {
+                    plugin: registry.loading.clone(),
+                    name,
+                    label: description,
+                    function: lua.create_registry_value(function)?,
+                };
+                registry.commands.push(registration);
+                Ok(())
+            },
+        );
+        // erwindb.page(name, title, function(ctx)), the function returning lines
+        let registry = Rc::clone(&self.registry);
+        let page = lua.create_function(
+            move |lua, (name, title, function): (String, String, Function)| {
+                let mut registry = registry.borrow_mut();
+                registry.check_name(&name)?;
+                let registration = Registration {
+                    plugin: registry.loading.clone(),
+                    name,
+                    label: title,
+                    function: lua.create_registry_value(function)?,
+                };
+                registry.pages.push(registration);
+                Ok(())
+            },
+        );
+        // erwindb.status(function(ctx)), returning text or nil
+        let registry = Rc::clone(&self.registry);
+        let status = lua.create_function(move |lua, function: Function| {
+            let mut registry = registry.borrow_mut();
+            let plugin = registry.loading.clone();
+            registry
+                .segments
+                .push((plugin, lua.create_registry_value(function)?));
+            Ok(())
+        });
+        let registry = Rc::clone(&self.registry);
+        let open = lua.create_function(move |_, id: i64| {
+            registry.borrow_mut().requests.push(Request::Open(id));
+            Ok(())
+        });
+        let registry = Rc::clone(&self.registry);
+        let flash = lua.create_function(move |_, text: String| {
+            registry.borrow_mut().requests.push(Request::Flash(text));
+            Ok(())
+        });
+        // erwindb.query(sql, ...), rows as tables keyed by column name
+        let conn = db_path
+            .map(|path| {
+                Connection::open_with_flags(
+                    path,
+                    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                )
+            })
+            .transpose()
+            .context("Failed to open the corpus for plugins")?;
+        let query = lua.create_function(move |lua, (sql, params): (String, Variadic<Value>)| {
+            let conn = conn
+                .as_ref()
+                .ok_or_else(|| mlua::Error::runtime("The corpus has no file to query"))?;
+            query_rows(lua, conn, &sql, &params).map_err(mlua::Error::external)
+        });
+
+        for (name, function) in [
+            ("command", command),
+            ("page", page),
+            ("status", status),
+            ("open", open),
+            ("flash", flash),
+            ("query", query),
+        ] {
+            api.set(name, function.map_err(lua_error)?)
+                .map_err(lua_error)?;
+        }
+        lua.globals().set("erwindb", api).map_err(lua_error)
+    }
+
+    /// Whether any plugin file was found
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// `:` commands and pages with their plugin and description or title
+    pub fn commands(&self) -> Vec<(String, String, String)> {
+        let registry = self.registry.borrow();
+        registry
+            .commands
+            .iter()
+            .chain(&registry.pages)
+            .map(|r| (r.name.clone(), r.plugin.clone(), r.label.clone()))
+            .collect()
+    }
+
+    pub fn has_command(&self, name: &str) -> bool {
+        self.registry
+            .borrow()
+            .commands
+            .iter()
+            .any(|c| c.name == name)
+    }
+
+    pub fn has_page(&self, name: &str) -> bool {
+        self.registry.borrow().pages.iter().any(|p| p.name == name)
+    }
+
+    /// Run `:name args`, returning the text to flash, if any
+    pub fn run_command(&self, name: &str, args: &str, ctx: &Context) -> Result<Option<String>> {
+        let (plugin, function) = self.function(|r| &r.commands, name)?;
+        let ctx = self.context_table(ctx)?;
+        self.start_budget();
+        function
+            .call::<_, Option<String>>((args, ctx))
+            .map_err(lua_error)
+            .with_context(|| format!("Plugin {plugin} failed on :{name}"))
+    }
+
+    /// A page's title and its lines, from a fresh call of its function
+    pub fn render_page(&self, name: &str, ctx: &Context) -> Result<(String, Vec<PageLine>)> {
+        let title = self
+            .registry
+            .borrow()
+            .pages
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.label.clone())
+            .unwrap_or_default();
+        let (plugin, function) = self.function(|r| &r.pages, name)?;
+        let ctx = self.context_table(ctx)?;
+        self.start_budget();
+        let lines = function
+            .call::<_, Table>(ctx)
+            .and_then(page_lines)
+            .map_err(lua_error)
+            .with_context(|| format!("Plugin {plugin} failed to render :{name}"))?;
+        Ok((title, lines))
+    }
+
+    /// Texts of the status segments for the status bar
+    pub fn status(&self, ctx: &Context) -> Vec<String> {
+        let segments: Vec<(usize, String, Function)> = {
+            let registry = self.registry.borrow();
+            let failed = self.failed_segments.borrow();
+            registry
+                .segments
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !failed.contains(i))
+                .filter_map(|(i, (plugin, key))| {
+                    let function = self.lua.registry_value(key).ok()?;
+                    Some((i, plugin.clone(), function))
+                })
+                .collect()
+        };
+        if segments.is_empty() {
+            return Vec::new();
+        }
+        let ctx = match self.context_table(ctx) {
+            Ok(ctx) => ctx,
+            Err(err) => {
+                self.errors.borrow_mut().push(err);
+                return Vec::new();
+            }
+        };
+        segments
+            .into_iter()
+            .filter_map(|(i, plugin, function)| {
+                self.start_budget();
+                match function.call::<_, Option<String>>(ctx.clone()) {
+                    Ok(text) => text.filter(|text| !text.is_empty()),
+                    Err(err) => {
+                        self.failed_segments.borrow_mut().insert(i);
+                        let err = lua_error(err)
+                            .context(format!("Plugin {plugin}'s status segment failed"));
+                        self.errors.borrow_mut().push(err);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// What the last callbacks asked of the app
+    pub fn take_requests(&self) -> Vec<Request> {
+        std::mem::take(&mut self.registry.borrow_mut().requests)
+    }
+
+    /// Failures of status segments since the last call
+    pub fn take_errors(&self) -> Vec<anyhow::Error> {
+        std::mem::take(&mut self.errors.borrow_mut())
+    }
+
+    /// The registered function, outside the registry's borrow so the call
+    /// can use the API
+    fn function(
+        &self,
+        list: impl Fn(&Registry) -> &Vec<Registration>,
+        name: &str,
+    ) -> Result<(String, Function<'_>)> {
+        let registry = self.registry.borrow();
+        let registration = list(&registry)
+            .iter()
+            .find(|r| r.name == name)
+            .ok_or_else(|| anyhow!("No plugin command :{name}"))?;
+        let function = self
+            .lua
+            .registry_value(&registration.function)
+            .map_err(lua_error)?;
+        Ok((registration.plugin.clone(), function))
+    }
+
+    fn context_table(&self, ctx: &Context) -> Result<Table<'_>> {
+        let table = self.lua.create_table().map_err(lua_error)?;
+        table.set("page", ctx.page).map_err(lua_error)?;
+        if let Some(question) = ctx.question {
+            let json = serde_json::to_value(QuestionJson::from(question))?;
+            table
+                .set(
+                    "question",
+                    json_to_lua(&self.lua, &json).map_err(lua_error)?,
+                )
+                .map_err(lua_error)?;
+        }
+        Ok(table)
+    }
+}
+
+/// `*.lua` files in the plugin directory, by name; none if it doesn't exist
+fn plugin_files() -> Result<Vec<PathBuf>> {
+    let Some(dir) = dirs::config_dir().map(|dir| dir.join("erwindb").join("plugins")) else {
+        return Ok(Vec::new());
+    };
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lua"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Lines returned by a page function: strings, or `{text = ..., question = id}`
+fn page_lines(table: Table) -> mlua::Result<Vec<PageLine>> {
+    table
+        .sequence_values::<Value>()
+        .map(|value| match value? {
+            Value::Table(line) => Ok(PageLine {
+                text: line.get::<_, Option<String>>("text")?.unwrap_or_default(),
+                question: line.get("question")?,
+            }),
+            Value::String(text) => Ok(PageLine {
+                text: text.to_str()?.to_string(),
+                question: None,
+            }),
+            other => Err(mlua::Error::runtime(format!(
+                "Page lines are strings or tables, not {}",
+                other.type_name()
+            ))),
+        })
+        .collect()
+}
+
+fn query_rows<'lua>(
+    lua: &'lua Lua,
+    conn: &Connection,
+    sql: &str,
+    params: &[Value],
+) -> Result<Table<'lua>> {
+    let params = params
+        .iter()
+        .map(|value| {
+            Ok(match value {
+                Value::Nil => SqlValue::Null,
+                Value::Boolean(b) => SqlValue::Integer(i64::from(*b)),
+                Value::Integer(i) => SqlValue::Integer(*i),
+                Value::Number(n) => SqlValue::Real(*n),
+                Value::String(s) => SqlValue::Text(s.to_str().map_err(lua_error)?.to_string()),
+                other => bail!("Can't bind a {} as a query parameter", other.type_name()),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare(sql)?;
+    let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let mut rows = stmt.query(rusqlite::params_from_iter(params))?;
+    let table = lua.create_table().map_err(lua_error)?;
+    while let Some(row) = rows.next()? {
+        let item = lua.create_table().map_err(lua_error)?;
+        for (i, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(i)? {
+                ValueRef::Null => Value::Nil,
+                ValueRef::Integer(i) => Value::Integer(i),
+                ValueRef::Real(n) => Value::Number(n),
+                ValueRef::Text(bytes) | ValueRef::Blob(bytes) => {
+                    Value::String(lua.create_string(bytes).map_err(lua_error)?)
+                }
+            };
+            item.set(column.as_str(), value).map_err(lua_error)?;
+        }
+        table.push(item).map_err(lua_error)?;
+    }
+    Ok(table)
+}
+
+fn json_to_lua<'lua>(lua: &'lua Lua, value: &serde_json::Value) -> mlua::Result<Value<'lua>> {
+    use serde_json::Value as Json;
+    Ok(match value {
+        Json::Null => Value::Nil,
+        Json::Bool(b) => Value::Boolean(*b),
+        Json::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Number(n.as_f64().unwrap_or_default()),
+        },
+        Json::String(s) => Value::String(lua.create_string(s)?),
+        Json::Array(items) => {
+            let table = lua.create_table()?;
+            for item in items {
+                table.push(json_to_lua(lua, item)?)?;
+            }
+            Value::Table(table)
+        }
+        Json::Object(fields) => {
+            let table = lua.create_table()?;
+            for (key, field) in fields {
+                table.set(key.as_str(), json_to_lua(lua, field)?)?;
+            }
+            Value::Table(table)
+        }
+    })
+}
+
+/// Lua errors as plain messages (their tracebacks included)
+fn lua_error(err: mlua::Error) -> anyhow::Error {
+    anyhow!("{err}")
+}
@@ -0,0 +1,121 @@
+//! Heuristic answer quality, for the "best explanation" badge and the
+//! by-quality answer order. Votes pile up on old answers, so the score here
+//! leans on what the text itself shows: how much it explains, how that's
+//! balanced against code, whether it cites the manuals, whether it was
+//! revised, and how fast it earned its votes.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::clock;
+use crate::db::Answer;
+use crate::html::word_count;
+
+static PRE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?is)<pre\b.*?</pre>").unwrap());
+static HREF_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"href="(?:https?:)?//([^"/]+)([^"]*)""#).unwrap());
+
+/// Official manuals, by host and path prefix
+const DOC_SITES: &[(&str, &str)] = &[
+    ("www.postgresql.org", "/docs/"),
+    ("postgresql.org", "/docs/"),
+    ("wiki.postgresql.org", ""),
+    ("dev.mysql.com", "/doc/"),
+    ("mariadb.com", "/kb/"),
+    ("learn.microsoft.com", "/"),
+    ("docs.microsoft.com", "/"),
+    ("docs.oracle.com", "/"),
+    ("www.sqlite.org", "/"),
+    ("sqlite.org", "/"),
+];
+
+/// Prose words at which an answer counts as thoroughly explained
+const FULL_PROSE_WORDS: f32 = 250.0;
+/// Share of words in code blocks that reads best: worked examples with the
+/// reasoning around them
+const IDEAL_CODE_SHARE: f32 = 0.4;
+/// Votes a year that count as fully convincing
+const FULL_VOTES_PER_YEAR: f32 = 30.0;
+/// An answer counts as revised once it changed this long after posting
+const REVISION_GRACE_SECONDS: i64 = 24 * 60 * 60;
+
+/// Signals of one answer, each from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnswerQuality {
+    /// Words outside code blocks
+    pub prose: f32,
+    /// How close the code/prose mix is to `IDEAL_CODE_SHARE`
+    pub balance: f32,
+    /// Links to official documentation, up to three
+    pub doc_links: f32,
+    /// Active after posting (the corpus has no edit counts, so this stands
+    /// in for them)
+    pub revised: f32,
+    /// Votes per year since posting
+    pub velocity: f32,
+}
+
+impl AnswerQuality {
+    pub fn of(answer: &Answer) -> Self {
+        let html = &answer.answer_text;
+        let code_words: usize = PRE_REGEX
+            .find_iter(html)
+            .map(|m| word_count(m.as_str()))
+            .sum();
+        let prose_words = word_count(&PRE_REGEX.replace_all(html, " "));
+        let total = (prose_words + code_words).max(1) as f32;
+        let code_share = code_words as f32 / total;
+
+        let doc_links = HREF_REGEX
+            .captures_iter(html)
+            .filter(|cap| {
+                let (host, path) = (&cap[1], &cap[2]);
+                DOC_SITES.iter().any(|(site, prefix)| {
+                    host.eq_ignore_ascii_case(site) && path.starts_with(prefix)
+                })
+            })
+            .count();
+
+        let age_years = (clock::now().timestamp() - answer.creation_date) as f32
+            / (365.25 * 24.0 * 60.0 * 60.0);
+        let votes_per_year = answer.score.max(0) as f32 / age_years.max(1.0);
+
+        Self {
+            prose: (prose_words as f32 / FULL_PROSE_WORDS).min(1.0),
+            balance: if code_words == 0 {
+                // Prose alone can explain, but shows nothing to run
+                0.3
+            } else {
+                1.0 - ((code_share - IDEAL_CODE_SHARE).abs() / (1.0 - IDEAL_CODE_SHARE)).min(1.0)
+            },
+            doc_links: doc_links.min(3) as f32 / 3.0,
+            revised: if answer.last_activity_date > answer.creation_date + REVISION_GRACE_SECONDS {
+                1.0
+            } else {
+                0.0
+            },
+            velocity: ((1.0 + votes_per_year).ln() / (1.0 + FULL_VOTES_PER_YEAR).ln()).min(1.0),
+        }
+    }
+
+    /// The signals weighted into one score from 0 to 1
+    pub fn score(&self) -> f32 {
+        0.25 * self.prose
+            + 0.2 * self.balance
+            + 0.2 * self.doc_links
+            + 0.1 * self.revised
+            + 0.25 * self.velocity
+    }
+}
+
+/// The answer that explains best, if the thread has more than one to pick from
+pub fn best_explanation(answers: &[Answer]) -> Option<i64> {
+    if answers.len() < 2 {
+        return None;
+    }
+    answers
+        .iter()
+        .map(|answer| (answer.answer_id, AnswerQuality::of(answer).score()))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(answer_id, _)| answer_id)
+}
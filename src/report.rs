@@ -0,0 +1,191 @@
+//! Failures of background jobs (semantic preview, body search, question fetches,
+//! hooks, translation, read-aloud, user database writes), kept for the error details
+//! screen and the issue report it copies; and rendering reports (`R`), which
+//! pair a post element's raw HTML with the lines it rendered to
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::{DateTime, FixedOffset};
+use regex::Regex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use crate::clock;
+use crate::content::{Source, SourceSpan};
+use crate::html::PostNode;
+use crate::site::Site;
+
+static EMAIL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\w.+-]+@[\w-]+(?:\.[\w-]+)+").unwrap());
+
+/// Failures kept per session, oldest dropped first
+pub const MAX_ERRORS: usize = 20;
+/// Where the copied report is meant to be filed
+pub const ISSUES_URL: &str = "https://github.com/ahacop/erwindb/issues/new";
+
+pub struct ErrorReport {
+    /// The job that failed: `Body search`
+    pub job: &'static str,
+    /// The error and its causes, outermost first
+    pub chain: Vec<String>,
+    pub at: DateTime<FixedOffset>,
+}
+
+impl ErrorReport {
+    pub fn new(job: &'static str, err: &anyhow::Error) -> Self {
+        Self {
+            job,
+            chain: err.chain().map(ToString::to_string).collect(),
+            at: clock::now(),
+        }
+    }
+
+    /// A failure that only left a message
+    pub fn message(job: &'static str, message: String) -> Self {
+        Self {
+            job,
+            chain: vec![message],
+            at: clock::now(),
+        }
+    }
+
+    pub fn time(&self) -> String {
+        self.at.format("%H:%M:%S").to_string()
+    }
+}
+
+/// A GitHub issue body for `errors[index]`, with the session's other failures
+/// up to it as the log
+pub fn issue_body(errors: &[ErrorReport], index: usize) -> String {
+    let error = &errors[index];
+    let mut body = format!(
+        "### What happened\n\n{} failed at {}.\n\n### Error\n\n```\n{}\n",
+        error.job,
+        error.at.format("%Y-%m-%d %H:%M:%S %:z"),
+        error.chain[0]
+    );
+    if error.chain.len() > 1 {
+        body.push_str("\nCaused by:\n");
+        for (depth, cause) in error.chain[1..].iter().enumerate() {
+            body.push_str(&format!("    {depth}: {cause}\n"));
+        }
+    }
+    body.push_str("```\n");
+
+    if index > 0 {
+        body.push_str("\n### Earlier failures\n\n");
+        for earlier in &errors[..index] {
+            body.push_str(&format!(
+                "- {} {}: {}\n",
+                earlier.time(),
+                earlier.job,
+                earlier.chain.join(": ")
+            ));
+        }
+    }
+
+    body.push_str(&environment());
+    body
+}
+
+fn environment() -> String {
+    format!(
+        "\n### Environment\n\n- erwindb {}\n- {} {}\n- TERM={}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::var("TERM").unwrap_or_default()
+    )
+}
+
+/// What `R` captured on the question page
+pub struct RenderReport<'a> {
+    pub question_id: i64,
+    pub site: Site,
+    /// `Question pane` / `Erwin pane`
+    pub pane: &'static str,
+    pub width: u16,
+    /// Pane line the report was taken at
+    pub line: usize,
+    pub span: SourceSpan,
+    /// Raw HTML of the element the span came from
+    pub html: &'a str,
+    /// The span's rendered lines as text
+    pub rendered: Vec<String>,
+}
+
+impl RenderReport<'_> {
+    /// The report as Markdown, ready to attach to an issue
+    pub fn to_markdown(&self) -> String {
+        let post = match self.span.source {
+            Source::Question => format!("Question #{}", self.question_id),
+            Source::Answer(answer_id) => format!("Answer #{answer_id}"),
+            Source::QuestionComment(n) => format!("Comment {} on the question", n + 1),
+            Source::AnswerComment(answer_id, n) => {
+                format!("Comment {} on answer #{answer_id}", n + 1)
+            }
+        };
+        let element = match self.span.node {
+            PostNode::Text => "text".to_string(),
+            PostNode::CodeBlock(n) => format!("code block {}", n + 1),
+            PostNode::Table(n) => format!("table {}", n + 1),
+        };
+        let mut report = format!(
+            "### Rendering report\n\n- {}\n- {post}, {element}\n- {}, {} columns, line {} (lines {}-{} of the element)\n\n### Raw HTML\n\n```html\n{}\n```\n\n### Rendered\n\n```\n",
+            self.site.question_url(self.question_id),
+            self.pane,
+            self.width,
+            self.line,
+            self.span.start_line,
+            self.span.end_line.saturating_sub(1),
+            self.html.trim()
+        );
+        for (i, line) in self.rendered.iter().enumerate() {
+            // The line the report was taken at
+            let mark = if self.span.start_line + i == self.line {
+                "> "
+            } else {
+                "  "
+            };
+            report.push_str(&format!("{mark}{}\n", line.trim_end()));
+        }
+        report.push_str("```\n");
+        report.push_str(&environment());
+        redact(&report)
+    }
+
+    /// Write the report under `<data dir>/erwindb/reports`
+    pub fn save(&self) -> Result<PathBuf> {
+        let dir = dirs::data_dir()
+            .context("Could not find data directory")?
+            .join("erwindb")
+            .join("reports");
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let file = dir.join(format!(
+            "render-{}-{}.md",
+            self.question_id,
+            clock::now().format("%Y%m%d-%H%M%S")
+        ));
+        fs::write(&file, self.to_markdown())
+            .with_context(|| format!("Failed to write {}", file.display()))?;
+        Ok(file)
+    }
+}
+
+/// Mask what could identify the reporter: email addresses and the home
+/// directory (which names the user)
+fn redact(text: &str) -> String {
+    let text = EMAIL_REGEX.replace_all(text, "<email>");
+    match dirs::home_dir().and_then(|home| home.to_str().map(str::to_string)) {
+        Some(home) if home.len() > 1 => text.replace(&home, "~"),
+        _ => text.into_owned(),
+    }
+}
+
+/// OSC 52 sequence that puts `text` on the clipboard of the terminal (and of
+/// tmux with `set-clipboard on`), also over SSH
+pub fn clipboard_sequence(text: &str) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    format!("\x1b]52;c;{encoded}\x07")
+}
@@ -0,0 +1,611 @@
+//! Scraped post HTML comes as the sites served it, and some of it is broken:
+//! unclosed or misnested tags, stray control characters, absurd nesting. Every
+//! renderer goes through this module first, which turns any input into small,
+//! well-formed HTML in one linear pass. It depends on nothing else in the
+//! crate, so the fuzz harness (`fuzz/`) can build it on its own.
+
+use html2text::render::TextDecorator;
+use regex::Regex;
+use scraper::{ElementRef, Html, Node};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+/// Longest post accepted, eight times the longest in the corpus; anything
+/// after it is cut before parsing
+const MAX_HTML_BYTES: usize = 256 * 1024;
+/// Start tags left open at once before parsing. The parser's scope checks walk
+/// the open elements, so unbounded nesting makes parsing quadratic (100k
+/// nested `<div>`s take minutes). Real posts nest less than ten deep.
+const MAX_OPEN_TAGS: usize = 256;
+/// Elements nested deeper than this lose their tags (their content stays), so
+/// the recursive renderers behind this pass have a bounded stack
+const MAX_DEPTH: usize = 32;
+/// Cap on table `colspan` / `rowspan`
+const MAX_SPAN: u32 = 32;
+
+/// Characters html2text can mark quoted lines with (followed by a space, once
+/// per level of nesting). The first one a post doesn't contain is used, so no
+/// line of text can pass for a quoted one.
+const QUOTE_MARKS: &[char] = &['>', '|', '^', '~'];
+
+/// Elements dropped with their content
+const DROPPED: &[&str] = &[
+    "script", "style", "template", "iframe", "object", "embed", "noscript",
+];
+/// Elements without content or an end tag
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+/// Attributes any of the renderers read; the rest are dropped
+const KEPT_ATTRS: &[&str] = &[
+    "href", "src", "alt", "title", "class", "start", "colspan", "rowspan",
+];
+
+static LANG_CLASS_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"lang-(\w+)").unwrap());
+
+/// Extract language hint from a <pre> tag's class attribute (e.g., "lang-sql prettyprint-override")
+pub fn extract_lang_from_class(class: Option<&str>) -> Option<String> {
+    class
+        .and_then(|c| LANG_CLASS_REGEX.captures(c))
+        .and_then(|cap| cap.get(1))
+        .map(|m| m.as_str().to_string())
+        .filter(|l| l != "none") // lang-none means no highlighting
+}
+
+/// A post as well-formed HTML: balanced tags, quoted and escaped attributes,
+/// no comments, scripts or control characters, nesting and size capped
+pub fn sanitize_html(html: &str) -> String {
+    write_html(&parse(html), |_| None)
+}
+
+/// A post laid out as text for the TUI, before highlighting and styling
+pub struct PreparedHtml {
+    /// The post wrapped to width by html2text, each link as `[text][n]` and
+    /// each code block as a placeholder line
+    pub lines: Vec<PreparedLine>,
+    /// Text and URL of link `n`, at index `n - 1`
+    pub links: Vec<(String, String)>,
+    /// Code and language hint of each `<pre>` block
+    pub code_blocks: Vec<(String, Option<String>)>,
+    /// Each `<table>`, laid out by the caller
+    pub tables: Vec<Table>,
+    marker: String,
+}
+
+/// One line of a prepared post
+pub struct PreparedLine {
+    /// `<blockquote>`s the line is in; their marks are cut from `text`
+    pub quote_depth: usize,
+    pub text: String,
+    /// Byte length and formatting of each run of `text`, in order
+    pub runs: Vec<(usize, Inline)>,
+}
+
+/// Inline formatting of a run of text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Inline {
+    /// `<strong>` / `<b>`
+    pub strong: bool,
+    /// `<em>` / `<i>`
+    pub emphasis: bool,
+    /// `<code>` outside a `<pre>`
+    pub code: bool,
+}
+
+/// A table's cell text, row by row (a cell spanning columns is followed by
+/// empty ones, so rows line up)
+pub struct Table {
+    pub rows: Vec<Vec<String>>,
+    /// Leading rows that are headers (`<th>` cells or `<thead>`)
+    pub header_rows: usize,
+}
+
+impl PreparedHtml {
+    /// Index into `code_blocks` if `line` is a code block placeholder (after
+    /// any list item marker)
+    pub fn code_block_index(&self, line: &str) -> Option<usize> {
+        split_list_marker(line)
+            .1
+            .strip_prefix(self.marker.as_str())?
+            .strip_suffix("__")?
+            .parse()
+            .ok()
+            .filter(|&index| index < self.code_blocks.len())
+    }
+
+    /// Index into `tables` if `line` is a table placeholder (after any list
+    /// item marker)
+    pub fn table_index(&self, line: &str) -> Option<usize> {
+        split_list_marker(line)
+            .1
+            .strip_prefix(self.marker.as_str())?
+            .strip_prefix("TABLE_")?
+            .strip_suffix("__")?
+            .parse()
+            .ok()
+            .filter(|&index| index < self.tables.len())
+    }
+}
+
+/// Split a line into its indentation and list item marker (`* `, `- `,
+/// `1. `), which html2text puts before a block that opens a list item, and
+/// the rest
+pub fn split_list_marker(line: &str) -> (&str, &str) {
+    let rest = line.trim_start();
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let item = if digits > 0 {
+        rest[digits..].strip_prefix(". ")
+    } else {
+        rest.strip_prefix("* ").or_else(|| rest.strip_prefix("- "))
+    };
+    let rest = item.unwrap_or(rest);
+    line.split_at(line.len() - rest.len())
+}
+
+pub fn prepare(html: &str, width: usize) -> PreparedHtml {
+    let fragment = parse(html);
+    // A placeholder prefix the post's text doesn't contain, so no line of it
+    // can pass for a code block
+    let text: String = fragment.root_element().text().collect();
+    let mut marker = String::from("__CODE_BLOCK_");
+    while text.contains(&marker) {
+        marker.insert(0, '_');
+    }
+    let quote_mark = QUOTE_MARKS
+        .iter()
+        .find(|&&mark| !text.contains(mark))
+        .unwrap_or(&QUOTE_MARKS[0]);
+    let quote_prefix = format!("{quote_mark} ");
+
+    let mut links = Vec::new();
+    let mut code_blocks = Vec::new();
+    let mut tables = Vec::new();
+    let processed = write_html(&fragment, |element| match element.value().name() {
+        "a" => {
+            let href = element
+                .value()
+                .attr("href")
+                .filter(|href| !href.is_empty())?;
+            let text: String = element.text().collect();
+            if text.is_empty() {
+                return None;
+            }
+            links.push((text.clone(), href.to_string()));
+            let mut reference = String::new();
+            push_text(&mut reference, &format!("[{}][{}]", text, links.len()));
+            Some(reference)
+        }
+        "pre" => {
+            let lang = extract_lang_from_class(element.value().attr("class"));
+            code_blocks.push((element.text().collect(), lang));
+            Some(format!("{marker}{}__", code_blocks.len() - 1))
+        }
+        "table" => {
+            tables.push(read_table(element));
+            Some(format!("<p>{marker}TABLE_{}__</p>", tables.len() - 1))
+        }
+        _ => None,
+    });
+
+    let decorator = PostDecorator {
+        quote_prefix: quote_prefix.clone(),
+    };
+    // Without `do_decorate`, which would also mark them up like Markdown
+    let lines = html2text::config::with_decorator(decorator)
+        .link_footnotes(true)
+        .lines_from_read(processed.as_bytes(), width)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|line| {
+            let mut text = String::new();
+            let mut runs = Vec::new();
+            for run in line.tagged_strings() {
+                text.push_str(&run.s);
+                runs.push((run.s.len(), Inline::from(run.tag.as_slice())));
+            }
+            let quote_len = text.len() - text.trim_start_matches(quote_prefix.as_str()).len();
+            text.drain(..quote_len);
+            cut_runs(&mut runs, quote_len);
+            PreparedLine {
+                quote_depth: quote_len / quote_prefix.len(),
+                text,
+                runs,
+            }
+        })
+        .collect();
+
+    PreparedHtml {
+        lines,
+        links,
+        code_blocks,
+        tables,
+        marker,
+    }
+}
+
+/// Outer HTML of the post's `index`th `<pre>` or `<table>` (`name`), counted
+/// the way `prepare` numbers its code blocks and tables: elements inside a
+/// link, code block or table it replaces whole don't count
+#[cfg(feature = "tui")]
+pub fn element_html(html: &str, name: &str, index: usize) -> Option<String> {
+    let fragment = parse(html);
+    let mut seen = 0;
+    let mut found = None;
+    write_html(&fragment, |element| match element.value().name() {
+        "a" => {
+            let href = element.value().attr("href").filter(|href| !href.is_empty());
+            let text: String = element.text().collect();
+            (href.is_some() && !text.is_empty()).then(String::new)
+        }
+        tag @ ("pre" | "table") => {
+            if tag == name {
+                if seen == index {
+                    found = Some(element.html());
+                }
+                seen += 1;
+            }
+            Some(String::new())
+        }
+        _ => None,
+    });
+    found
+}
+
+/// Formatting html2text tags a run of text with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum Format {
+    #[default]
+    Plain,
+    Strong,
+    Emphasis,
+    Code,
+}
+
+impl From<&[Format]> for Inline {
+    fn from(formats: &[Format]) -> Self {
+        Self {
+            strong: formats.contains(&Format::Strong),
+            emphasis: formats.contains(&Format::Emphasis),
+            code: formats.contains(&Format::Code),
+        }
+    }
+}
+
+/// Drop the first `len` bytes from `runs`
+fn cut_runs(runs: &mut Vec<(usize, Inline)>, mut len: usize) {
+    let mut cut = 0;
+    for run in runs.iter_mut() {
+        if len < run.0 {
+            run.0 -= len;
+            break;
+        }
+        len -= run.0;
+        cut += 1;
+    }
+    runs.drain(..cut);
+}
+
+/// html2text's plain text layout, with bold, italic and inline code tagged
+/// instead of dropped
+#[derive(Clone)]
+struct PostDecorator {
+    quote_prefix: String,
+}
+
+impl TextDecorator for PostDecorator {
+    type Annotation = Format;
+
+    fn decorate_link_start(&mut self, _url: &str) -> (String, Format) {
+        ("[".to_string(), Format::Plain)
+    }
+
+    fn decorate_link_end(&mut self) -> String {
+        "]".to_string()
+    }
+
+    fn decorate_em_start(&self) -> (String, Format) {
+        (String::new(), Format::Emphasis)
+    }
+
+    fn decorate_em_end(&self) -> String {
+        String::new()
+    }
+
+    fn decorate_strong_start(&self) -> (String, Format) {
+        (String::new(), Format::Strong)
+    }
+
+    fn decorate_strong_end(&self) -> String {
+        String::new()
+    }
+
+    fn decorate_strikeout_start(&self) -> (String, Format) {
+        (String::new(), Format::Plain)
+    }
+
+    fn decorate_strikeout_end(&self) -> String {
+        String::new()
+    }
+
+    fn decorate_code_start(&self) -> (String, Format) {
+        (String::new(), Format::Code)
+    }
+
+    fn decorate_code_end(&self) -> String {
+        String::new()
+    }
+
+    fn decorate_preformat_first(&self) -> Format {
+        Format::Plain
+    }
+
+    fn decorate_preformat_cont(&self) -> Format {
+        Format::Plain
+    }
+
+    fn decorate_image(&mut self, _src: &str, title: &str) -> (String, Format) {
+        (format!("[{title}]"), Format::Plain)
+    }
+
+    fn header_prefix(&self, level: usize) -> String {
+        "#".repeat(level) + " "
+    }
+
+    fn quote_prefix(&self) -> String {
+        self.quote_prefix.clone()
+    }
+
+    fn unordered_item_prefix(&self) -> String {
+        "* ".to_string()
+    }
+
+    fn ordered_item_prefix(&self, i: i64) -> String {
+        format!("{i}. ")
+    }
+
+    fn make_subblock_decorator(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// Rows of a table, leaving out those of tables nested in it
+fn read_table(table: ElementRef) -> Table {
+    let mut rows = Vec::new();
+    let mut header_rows = 0;
+    for row in table.descendants().filter_map(ElementRef::wrap) {
+        if row.value().name() != "tr" || !row_of(row, table) {
+            continue;
+        }
+        let mut cells = Vec::new();
+        let mut all_headers = true;
+        for cell in row.children().filter_map(ElementRef::wrap) {
+            let name = cell.value().name();
+            if name != "td" && name != "th" {
+                continue;
+            }
+            all_headers &= name == "th";
+            let text: String = cell.text().collect();
+            cells.push(text.split_whitespace().collect::<Vec<_>>().join(" "));
+            let span = cell
+                .value()
+                .attr("colspan")
+                .and_then(|span| span.trim().parse::<u32>().ok())
+                .unwrap_or(1)
+                .clamp(1, MAX_SPAN);
+            cells.extend((1..span).map(|_| String::new()));
+        }
+        if cells.is_empty() {
+            continue;
+        }
+        let in_head = row
+            .parent()
+            .and_then(ElementRef::wrap)
+            .is_some_and(|parent| parent.value().name() == "thead");
+        if header_rows == rows.len() && (all_headers || in_head) {
+            header_rows += 1;
+        }
+        rows.push(cells);
+    }
+    Table { rows, header_rows }
+}
+
+/// Whether `table` is the nearest table around `row`
+fn row_of(row: ElementRef, table: ElementRef) -> bool {
+    row.ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|ancestor| ancestor.value().name() == "table")
+        .is_some_and(|ancestor| ancestor.id() == table.id())
+}
+
+fn parse(html: &str) -> Html {
+    let mut end = html.len().min(MAX_HTML_BYTES);
+    while !html.is_char_boundary(end) {
+        end -= 1;
+    }
+    Html::parse_fragment(&limit_open_tags(&html[..end]))
+}
+
+/// Drop start tags beyond `MAX_OPEN_TAGS` still open, by a lexical count: end
+/// tags close the latest open one, void and self-closing tags don't count.
+/// Unclosed `<p>`s and `<li>`s make the count run high, never low.
+fn limit_open_tags(html: &str) -> Cow<'_, str> {
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut open = 0usize;
+    let mut rest = 0;
+    while let Some(found) = html[rest..].find('<') {
+        let start = rest + found;
+        let tag = &html[start + 1..];
+        let Some(len) = tag.find('>') else {
+            break;
+        };
+        rest = start + 1 + len + 1;
+
+        let (closing, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name_len = tag
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(tag.len());
+        let name = tag[..name_len].to_ascii_lowercase();
+        if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            continue;
+        }
+        if closing {
+            open = open.saturating_sub(1);
+        } else if !VOID.contains(&name.as_str()) && !html[..rest].ends_with("/>") {
+            if open == MAX_OPEN_TAGS {
+                out.push_str(&html[copied..start]);
+                copied = rest;
+            } else {
+                open += 1;
+            }
+        }
+    }
+    if copied == 0 {
+        return Cow::Borrowed(html);
+    }
+    out.push_str(&html[copied..]);
+    Cow::Owned(out)
+}
+
+/// Serialize a parsed post, writing whatever `replace` returns (already HTML)
+/// in place of an element and its content. The walk keeps its own stack
+/// instead of recursing, since the parsed tree can be arbitrarily deep.
+fn write_html(fragment: &Html, mut replace: impl FnMut(ElementRef) -> Option<String>) -> String {
+    let mut out = String::new();
+    let root = fragment.root_element();
+    // Open elements: the element, its next child, and whether its tags are written
+    let mut stack = vec![(root, root.first_child(), false)];
+    while let Some(frame) = stack.last_mut() {
+        let Some(node) = frame.1 else {
+            let (element, _, written) = frame;
+            if *written {
+                out.push_str(&format!("</{}>", element.value().name()));
+            }
+            stack.pop();
+            continue;
+        };
+        frame.1 = node.next_sibling();
+
+        match node.value() {
+            Node::Text(text) => push_text(&mut out, text),
+            Node::Element(element) => {
+                let name = element.name();
+                let Some(child) = ElementRef::wrap(node) else {
+                    continue;
+                };
+                if DROPPED.contains(&name) {
+                    continue;
+                }
+                if let Some(replacement) = replace(child) {
+                    out.push_str(&replacement);
+                    continue;
+                }
+                let written = stack.len() <= MAX_DEPTH;
+                if written {
+                    push_start_tag(&mut out, child);
+                }
+                if !VOID.contains(&name) {
+                    stack.push((child, child.first_child(), written));
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn push_start_tag(out: &mut String, element: ElementRef) {
+    let name = element.value().name();
+    out.push('<');
+    out.push_str(name);
+    for (attr, value) in element.value().attrs() {
+        if !KEPT_ATTRS.contains(&attr) {
+            continue;
+        }
+        let value = match attr {
+            "colspan" | "rowspan" => value
+                .trim()
+                .parse::<u32>()
+                .unwrap_or(1)
+                .clamp(1, MAX_SPAN)
+                .to_string(),
+            _ => value.to_string(),
+        };
+        out.push_str(&format!(" {attr}=\""));
+        for c in value.chars().filter(|&c| !is_stray_control(c)) {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '"' => out.push_str("&quot;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                _ => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+    out.push('>');
+    // The parser drops a newline right after `<pre>`, so one that belongs to
+    // the code needs another in front of it to survive the next parse
+    let leading_newline = element
+        .first_child()
+        .and_then(|child| child.value().as_text().map(|text| text.starts_with('\n')));
+    if name == "pre" && leading_newline == Some(true) {
+        out.push('\n');
+    }
+}
+
+fn push_text(out: &mut String, text: &str) {
+    for c in text.chars().filter(|&c| !is_stray_control(c)) {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+/// Control characters other than line breaks and tabs, which a terminal
+/// would act on instead of showing
+fn is_stray_control(c: char) -> bool {
+    c.is_control() && c != '\n' && c != '\t'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_after_list_markers() {
+        let html = "<ol><li><pre><code>SELECT 1;</code></pre><p>First</p></li>\
+                    <li><table><tr><td>a</td></tr></table></li></ol>\
+                    <ul><li><pre><code>SELECT 2;</code></pre></li></ul>";
+        let prepared = prepare(html, 50);
+        let code_blocks: Vec<_> = prepared
+            .lines
+            .iter()
+            .filter_map(|line| prepared.code_block_index(&line.text))
+            .collect();
+        let tables: Vec<_> = prepared
+            .lines
+            .iter()
+            .filter_map(|line| prepared.table_index(&line.text))
+            .collect();
+        assert_eq!(code_blocks, [0, 1]);
+        assert_eq!(tables, [0]);
+        assert!(prepared.lines.iter().all(|line| !line.text.contains("__")
+            || prepared.code_block_index(&line.text).is_some()
+            || prepared.table_index(&line.text).is_some()));
+    }
+
+    #[test]
+    fn list_marker_split() {
+        assert_eq!(split_list_marker("  12. rest"), ("  12. ", "rest"));
+        assert_eq!(split_list_marker("* rest"), ("* ", "rest"));
+        assert_eq!(split_list_marker("  rest"), ("  ", "rest"));
+        assert_eq!(split_list_marker("2021 rest"), ("", "2021 rest"));
+    }
+}
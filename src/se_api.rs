@@ -0,0 +1,294 @@
+//! Stack Exchange API client shared by everything that goes online. Honors the
+//! API's `backoff` and throttling (with retries), keeps the quota the last
+//! response reported, takes the key from `[api]`, and caches responses on disk
+//! so repeated and resumed fetches don't spend quota.
+
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::ApiConfig;
+use crate::search::semantic::offline;
+use crate::site::Site;
+
+const API_ROOT: &str = "https://api.stackexchange.com/2.3";
+/// Environment variable the scraper reads the key from, used when `[api] key`
+/// is unset
+pub const KEY_ENV: &str = "STACKOVERFLOW_API_KEY";
+/// Largest `pagesize` the API allows
+pub const MAX_PAGE_SIZE: u32 = 100;
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// Attempts per request when throttled or the API is unavailable
+const MAX_ATTEMPTS: u32 = 4;
+/// Wait before the first retry, doubled for each further one
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+/// Daily requests per IP without a key (10,000 with one)
+pub const KEYLESS_QUOTA: u32 = 300;
+
+/// `error_id`s worth retrying: throttle_violation, temporarily_unavailable
+const RETRY_ERRORS: [u32; 2] = [502, 503];
+
+/// Requests left today, as the last response reported them
+#[derive(Debug, Clone, Copy)]
+pub struct Quota {
+    pub remaining: u32,
+    pub max: u32,
+}
+
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} requests left today", self.remaining, self.max)
+    }
+}
+
+/// The wrapper object every API response comes in
+#[derive(Deserialize)]
+struct Wrapper<T> {
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+    #[serde(default)]
+    has_more: bool,
+    quota_remaining: Option<u32>,
+    quota_max: Option<u32>,
+    /// Seconds to wait before calling the API again
+    backoff: Option<u64>,
+    error_id: Option<u32>,
+    error_name: Option<String>,
+    error_message: Option<String>,
+}
+
+/// Items of one or more pages; `next_page` resumes where they stopped
+pub struct Pages<T> {
+    pub items: Vec<T>,
+    pub next_page: Option<u32>,
+}
+
+/// `/info` of a site, fetched to learn the quota
+#[derive(Deserialize)]
+struct SiteInfo {}
+
+pub struct Client {
+    agent: ureq::Agent,
+    key: Option<String>,
+    cache_dir: Option<PathBuf>,
+    cache_ttl: Duration,
+    /// No requests before this, as asked by a `backoff`
+    not_before: Option<Instant>,
+    quota: Option<Quota>,
+}
+
+impl Client {
+    pub fn new(config: &ApiConfig) -> Self {
+        let key = config
+            .key
+            .clone()
+            .or_else(|| std::env::var(KEY_ENV).ok())
+            .filter(|key| !key.is_empty());
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(TIMEOUT).build(),
+            key,
+            cache_dir: cache_dir(),
+            cache_ttl: Duration::from_secs(config.cache_minutes * 60),
+            not_before: None,
+            quota: None,
+        }
+    }
+
+    pub fn has_key(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Quota after the last request that reached the API
+    pub fn quota(&self) -> Option<Quota> {
+        self.quota
+    }
+
+    /// Where responses are cached, and how many there are
+    pub fn cache_usage(&self) -> Option<(PathBuf, usize)> {
+        let dir = self.cache_dir.clone()?;
+        let count = fs::read_dir(&dir).map_or(0, |entries| entries.count());
+        Some((dir, count))
+    }
+
+    /// Ask the API for the quota without using the cache
+    pub fn refresh_quota(&mut self) -> Result<Quota> {
+        let url = self.url(Site::StackOverflow, "info", &[]);
+        self.request::<SiteInfo>(&url, false)?;
+        self.quota.context("The API didn't report a quota")
+    }
+
+    /// One page of `path` (`questions/{ids}`, `users/{id}/answers`, ...) with
+    /// `params` such as `filter`, `page` and `pagesize`
+    pub fn get<T: DeserializeOwned>(
+        &mut self,
+        site: Site,
+        path: &str,
+        params: &[(&str, String)],
+    ) -> Result<Pages<T>> {
+        let page = params
+            .iter()
+            .find(|(name, _)| *name == "page")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(1);
+        let url = self.url(site, path, params);
+        let wrapper = self.request::<T>(&url, true)?;
+        Ok(Pages {
+            items: wrapper.items,
+            next_page: wrapper.has_more.then_some(page + 1),
+        })
+    }
+
+    /// Up to `max_pages` full pages of `path` from `start_page` on. An
+    /// interrupted fetch resumes from `next_page`, or starts over cheaply since
+    /// the pages it got are cached.
+    pub fn get_pages<T: DeserializeOwned>(
+        &mut self,
+        site: Site,
+        path: &str,
+        params: &[(&str, String)],
+        start_page: u32,
+        max_pages: u32,
+    ) -> Result<Pages<T>> {
+        let mut items = Vec::new();
+        let mut next_page = Some(start_page);
+        for _ in 0..max_pages {
+            let Some(page) = next_page else {
+                break;
+            };
+            let mut page_params = params.to_vec();
+            page_params.push(("page", page.to_string()));
+            page_params.push(("pagesize", MAX_PAGE_SIZE.to_string()));
+            let mut fetched = self.get(site, path, &page_params)?;
+            items.append(&mut fetched.items);
+            next_page = fetched.next_page;
+        }
+        Ok(Pages { items, next_page })
+    }
+
+    fn url(&self, site: Site, path: &str, params: &[(&str, String)]) -> String {
+        let mut url = format!("{API_ROOT}/{path}?site={}", site.as_db());
+        for (name, value) in params {
+            url.push_str(&format!("&{name}={}", encode(value)));
+        }
+        url
+    }
+
+    fn request<T: DeserializeOwned>(&mut self, url: &str, cached: bool) -> Result<Wrapper<T>> {
+        let cache_file = match self.cache_dir {
+            Some(ref dir) if cached => Some(dir.join(cache_key(url))),
+            _ => None,
+        };
+        if let Some(body) = cache_file
+            .as_ref()
+            .and_then(|file| read_fresh(file, self.cache_ttl))
+        {
+            if let Ok(wrapper) = serde_json::from_str(&body) {
+                return Ok(wrapper);
+            }
+        }
+
+        if offline() {
+            bail!("Offline (ERWINDB_OFFLINE is set)");
+        }
+        if self.quota.is_some_and(|quota| quota.remaining == 0) {
+            bail!("Stack Exchange API quota used up; it resets at midnight UTC");
+        }
+
+        let mut delay = RETRY_DELAY;
+        let mut attempt = 1;
+        loop {
+            if let Some(not_before) = self.not_before.take() {
+                thread::sleep(not_before.saturating_duration_since(Instant::now()));
+            }
+
+            let body = self.call(url)?;
+            let wrapper: Wrapper<T> =
+                serde_json::from_str(&body).context("Unexpected Stack Exchange API response")?;
+            if let Some(backoff) = wrapper.backoff {
+                self.not_before = Some(Instant::now() + Duration::from_secs(backoff));
+            }
+            if let (Some(remaining), Some(max)) = (wrapper.quota_remaining, wrapper.quota_max) {
+                self.quota = Some(Quota { remaining, max });
+            }
+
+            let Some(error_id) = wrapper.error_id else {
+                if let Some(ref file) = cache_file {
+                    // A failed write only costs quota next time
+                    let _ = fs::create_dir_all(file.parent().unwrap_or(file));
+                    let _ = fs::write(file, &body);
+                }
+                return Ok(wrapper);
+            };
+            let message = wrapper.error_message.unwrap_or_default();
+            if !RETRY_ERRORS.contains(&error_id) || attempt == MAX_ATTEMPTS {
+                bail!(
+                    "Stack Exchange API error {error_id} ({}): {message}",
+                    wrapper.error_name.unwrap_or_default()
+                );
+            }
+            // "too many requests from this IP, more requests available in 42 seconds"
+            let wait = message
+                .split_whitespace()
+                .filter_map(|word| word.parse().ok())
+                .next_back()
+                .map_or(delay, Duration::from_secs);
+            self.not_before = Some(Instant::now() + wait);
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+
+    /// The response body, also for the error statuses the API answers with
+    /// its error wrapper
+    fn call(&self, url: &str) -> Result<String> {
+        let mut request = self.agent.get(url);
+        if let Some(ref key) = self.key {
+            request = request.query("key", key);
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(err) => return Err(err).context("Failed to reach the Stack Exchange API"),
+        };
+        response
+            .into_string()
+            .context("Failed to read the Stack Exchange API response")
+    }
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("erwindb").join("api"))
+}
+
+/// File name for a URL's cached response (FNV-1a, stable across builds)
+fn cache_key(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}.json")
+}
+
+/// A cached body no older than `ttl`
+fn read_fresh(file: &Path, ttl: Duration) -> Option<String> {
+    let modified = fs::metadata(file).ok()?.modified().ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    (age < ttl).then(|| fs::read_to_string(file).ok()).flatten()
+}
+
+/// Percent-encode a query parameter value
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b';' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
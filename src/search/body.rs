@@ -0,0 +1,73 @@
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::db::Database;
+
+/// Typing pause before a body search runs; queries sent meanwhile replace it
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Questions whose bodies or answers matched a query
+pub struct BodyMatches {
+    pub query: String,
+    /// Best match first
+    pub ids: Vec<i64>,
+}
+
+/// Full-text search over question and answer bodies on a background thread with
+/// its own connection, so typing never waits on it. The thread builds the index
+/// on first use.
+pub struct BodySearch {
+    queries: Sender<String>,
+    results: Receiver<Result<BodyMatches>>,
+}
+
+impl BodySearch {
+    pub fn start(db_path: PathBuf) -> Self {
+        let (queries, query_rx) = mpsc::channel::<String>();
+        let (result_tx, results) = mpsc::channel();
+
+        thread::spawn(move || {
+            let db = match Database::open(&db_path).and_then(|db| {
+                db.ensure_body_index()?;
+                Ok(db)
+            }) {
+                Ok(db) => db,
+                Err(err) => {
+                    let _ = result_tx.send(Err(err));
+                    return;
+                }
+            };
+
+            while let Ok(mut query) = query_rx.recv() {
+                thread::sleep(DEBOUNCE);
+                while let Ok(newer) = query_rx.try_recv() {
+                    query = newer;
+                }
+                let result = db
+                    .body_matches(&query)
+                    .map(|ids| BodyMatches { query, ids });
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { queries, results }
+    }
+
+    pub fn search(&self, query: &str) {
+        let _ = self.queries.send(query.to_string());
+    }
+
+    /// Latest finished search, if any; an `Err` means the thread has given up
+    pub fn poll(&self) -> Option<Result<BodyMatches>> {
+        let mut latest = None;
+        while let Ok(result) = self.results.try_recv() {
+            latest = Some(result);
+        }
+        latest
+    }
+}
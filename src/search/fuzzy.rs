@@ -1,7 +1,16 @@
+//! Fuzzy matching with nucleo. Patterns use fzf's syntax: words match in
+//! any order, and `'exact`, `^prefix`, `suffix$` and `!not` narrow them.
+//! `fuzzy_filter` matches on the calling thread; `BackgroundMatcher` runs the
+//! question list's title search on nucleo's worker pool, so typing never
+//! waits for it.
+
 use nucleo::{
-    pattern::{Atom, AtomKind, CaseMatching, Normalization},
-    Config, Matcher, Utf32Str,
+    pattern::{CaseMatching, Normalization, Pattern},
+    Config, Matcher, Nucleo, Utf32Str,
 };
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::sync::Arc;
 
 pub struct FuzzyMatcher {
     matcher: Matcher,
@@ -20,38 +29,31 @@ impl FuzzyMatcher {
             return Some(0);
         }
 
-        let atom = Atom::new(
-            pattern,
-            CaseMatching::Ignore,
-            Normalization::Smart,
-            AtomKind::Fuzzy,
-            false,
-        );
+        let pattern = Pattern::parse(pattern, CaseMatching::Ignore, Normalization::Smart);
         let mut buf = Vec::new();
         let haystack = Utf32Str::new(text, &mut buf);
 
-        atom.score(haystack, &mut self.matcher).map(|s| s as u32)
+        pattern.score(haystack, &mut self.matcher)
     }
 
-    pub fn match_indices(&mut self, pattern: &str, text: &str) -> Option<(u32, Vec<u32>)> {
-        if pattern.is_empty() {
-            return None;
-        }
+    /// Score of `pattern`'s match in `text`, cheaper than `pattern_indices`
+    fn pattern_score(&mut self, pattern: &Pattern, text: &str) -> Option<u32> {
+        let mut buf = Vec::new();
+        pattern.score(Utf32Str::new(text, &mut buf), &mut self.matcher)
+    }
 
-        let atom = Atom::new(
-            pattern,
-            CaseMatching::Ignore,
-            Normalization::Smart,
-            AtomKind::Fuzzy,
-            false,
-        );
+    /// Score and sorted character indices of `pattern`'s match in `text`
+    fn pattern_indices(&mut self, pattern: &Pattern, text: &str) -> Option<(u32, Vec<u32>)> {
         let mut buf = Vec::new();
         let haystack = Utf32Str::new(text, &mut buf);
 
         let mut indices = Vec::new();
-        let score = atom.indices(haystack, &mut self.matcher, &mut indices)?;
+        let score = pattern.indices(haystack, &mut self.matcher, &mut indices)?;
+        // Each word's indices are appended separately
+        indices.sort_unstable();
+        indices.dedup();
 
-        Some((score as u32, indices))
+        Some((score, indices))
     }
 }
 
@@ -79,30 +81,149 @@ where
         return Vec::new();
     }
 
+    let pattern = Pattern::parse(pattern, CaseMatching::Ignore, Normalization::Smart);
     let mut matcher = FuzzyMatcher::new();
-    let mut matches: Vec<FuzzyMatch> = items
+    let mut matches: Vec<(usize, FuzzyMatch)> = items
         .iter()
         .enumerate()
         .filter_map(|(index, item)| {
             let text = get_text(item);
             matcher
-                .match_indices(pattern, text)
-                .map(|(score, indices)| FuzzyMatch {
-                    index,
-                    score,
-                    match_indices: indices,
+                .pattern_indices(&pattern, text)
+                .map(|(score, indices)| {
+                    let fuzzy_match = FuzzyMatch {
+                        index,
+                        score,
+                        match_indices: indices,
+                    };
+                    (text.chars().count(), fuzzy_match)
                 })
         })
         .collect();
 
-    // Sort by score descending
-    matches.sort_by(|a, b| b.score.cmp(&a.score));
+    // Best score first, then shorter texts, as nucleo's workers order them
+    matches.sort_by_key(|(len, m)| (Reverse(m.score), *len, m.index));
+    let mut matches: Vec<FuzzyMatch> = matches.into_iter().map(|(_, m)| m).collect();
+    drop_weak_matches(&mut matches);
+    matches
+}
 
-    // Filter to keep only results within threshold of best score
+/// Keep only results within `RELATIVE_SCORE_THRESHOLD` of the best score
+fn drop_weak_matches(matches: &mut Vec<FuzzyMatch>) {
     if let Some(best) = matches.first() {
         let min_score = (best.score as f32 * RELATIVE_SCORE_THRESHOLD) as u32;
         matches.retain(|m| m.score >= min_score);
     }
+}
 
-    matches
+/// Matches of the latest pattern given to a `BackgroundMatcher`
+pub struct BackgroundMatches {
+    pub pattern: String,
+    /// Best first; while the workers are still running, the best so far.
+    /// `match_indices` are left empty: `BackgroundMatcher::highlight` finds
+    /// them for the rows that are drawn.
+    pub matches: Vec<FuzzyMatch>,
+}
+
+/// Fuzzy matching on nucleo's worker pool over a fixed list of texts. A new
+/// pattern cancels the run for the old one, and typing more of a pattern only
+/// rescans what matched before.
+pub struct BackgroundMatcher {
+    nucleo: Nucleo<usize>,
+    pattern: String,
+    /// `poll` should hand out the matches even if its tick changes nothing:
+    /// `search` picked them up, or the same pattern was searched again
+    ready: bool,
+    /// Called from the workers when `poll` has something new
+    notify: Arc<dyn Fn() + Send + Sync>,
+    /// For the scores and highlighted characters, which the workers don't
+    /// report
+    matcher: RefCell<FuzzyMatcher>,
+}
+
+impl BackgroundMatcher {
+    /// `notify` is called, from any thread, whenever `poll` should be called
+    pub fn new<'a>(
+        texts: impl IntoIterator<Item = &'a str>,
+        notify: Arc<dyn Fn() + Send + Sync>,
+    ) -> Self {
+        let nucleo = Nucleo::new(Config::DEFAULT, Arc::clone(&notify), None, 1);
+        let injector = nucleo.injector();
+        for (index, text) in texts.into_iter().enumerate() {
+            injector.push(index, |_, columns| columns[0] = text.into());
+        }
+        Self {
+            nucleo,
+            pattern: String::new(),
+            ready: false,
+            notify,
+            matcher: RefCell::new(FuzzyMatcher::new()),
+        }
+    }
+
+    /// Start matching `pattern`; the matches arrive through `poll`
+    pub fn search(&mut self, pattern: &str) {
+        if pattern == self.pattern {
+            self.ready = true;
+            (self.notify)();
+            return;
+        }
+        let append = !self.pattern.is_empty() && pattern.starts_with(self.pattern.as_str());
+        self.nucleo.pattern.reparse(
+            0,
+            pattern,
+            CaseMatching::Ignore,
+            Normalization::Smart,
+            append,
+        );
+        self.pattern = pattern.to_string();
+        // Only a tick starts the workers on the new pattern, and asks them to
+        // call `notify` when they're done; a run that's over already won't
+        self.ready |= self.nucleo.tick(0).changed;
+        if self.ready {
+            (self.notify)();
+        }
+    }
+
+    /// The matches, whenever the workers have new ones
+    pub fn poll(&mut self) -> Option<BackgroundMatches> {
+        let changed = self.nucleo.tick(0).changed;
+        if self.pattern.is_empty() || !(changed || self.ready) {
+            return None;
+        }
+        self.ready = false;
+        let snapshot = self.nucleo.snapshot();
+        let pattern = snapshot.pattern().column_pattern(0);
+        let matcher = self.matcher.get_mut();
+        let mut matches: Vec<FuzzyMatch> = snapshot
+            .matched_items(..)
+            .filter_map(|item| {
+                let text = item.matcher_columns[0].to_string();
+                Some(FuzzyMatch {
+                    index: *item.data,
+                    score: matcher.pattern_score(pattern, &text)?,
+                    match_indices: Vec::new(),
+                })
+            })
+            .collect();
+        drop_weak_matches(&mut matches);
+        Some(BackgroundMatches {
+            pattern: self.pattern.clone(),
+            matches,
+        })
+    }
+
+    /// Sorted character indices of `pattern`'s match in `text`, to highlight
+    /// a drawn row of the matches `poll` handed out
+    pub fn highlight(&self, pattern: &str, text: &str) -> Vec<u32> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+        let pattern = Pattern::parse(pattern, CaseMatching::Ignore, Normalization::Smart);
+        self.matcher
+            .borrow_mut()
+            .pattern_indices(&pattern, text)
+            .map(|(_, indices)| indices)
+            .unwrap_or_default()
+    }
 }
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use super::fuzzy::FuzzyMatch;
+
+/// Damping constant of reciprocal-rank fusion; the usual 60 keeps one list's
+/// top hit from drowning out items ranked well in both
+const RRF_K: f64 = 60.0;
+
+/// A question found by the hybrid search, with its place in each ranking
+#[derive(Debug, Clone)]
+pub struct HybridMatch {
+    /// Index into the question list
+    pub index: usize,
+    /// The title match, for highlighting (`None` if only the meaning matched)
+    pub fuzzy: Option<FuzzyMatch>,
+    /// 1-based rank among title matches
+    pub fuzzy_rank: Option<usize>,
+    /// 1-based rank among semantic neighbours
+    pub semantic_rank: Option<usize>,
+    /// Fused score; higher is better
+    pub score: f64,
+}
+
+/// Merge title matches and semantic neighbours (both best first, as question
+/// list indices) with reciprocal-rank fusion: each list contributes
+/// `1 / (RRF_K + rank)`, so questions found by both rise to the top.
+pub fn reciprocal_rank_fusion(fuzzy: Vec<FuzzyMatch>, semantic: &[usize]) -> Vec<HybridMatch> {
+    let mut merged: HashMap<usize, HybridMatch> = HashMap::new();
+    for (rank, m) in fuzzy.into_iter().enumerate() {
+        let hybrid = merged
+            .entry(m.index)
+            .or_insert_with(|| empty_match(m.index));
+        hybrid.fuzzy_rank = Some(rank + 1);
+        hybrid.score += 1.0 / (RRF_K + (rank + 1) as f64);
+        hybrid.fuzzy = Some(m);
+    }
+    for (rank, &index) in semantic.iter().enumerate() {
+        let hybrid = merged.entry(index).or_insert_with(|| empty_match(index));
+        hybrid.semantic_rank = Some(rank + 1);
+        hybrid.score += 1.0 / (RRF_K + (rank + 1) as f64);
+    }
+
+    let mut matches: Vec<HybridMatch> = merged.into_values().collect();
+    // Ties (same ranks in swapped lists) fall back to question list order
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.index.cmp(&b.index)));
+    matches
+}
+
+fn empty_match(index: usize) -> HybridMatch {
+    HybridMatch {
+        index,
+        fuzzy: None,
+        fuzzy_rank: None,
+        semantic_rank: None,
+        score: 0.0,
+    }
+}
@@ -1,2 +1,5 @@
+pub mod body;
 pub mod fuzzy;
+pub mod hybrid;
+pub mod query;
 pub mod semantic;
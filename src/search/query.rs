@@ -0,0 +1,281 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::db::QuestionSummary;
+use crate::search::fuzzy::{fuzzy_filter, FuzzyMatch};
+
+/// A title search split into field filters and the words left for fuzzy
+/// matching, e.g. `tag:jsonb score:>50 lateral join`. Words that aren't a
+/// known `field:value` stay part of the text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub filters: Vec<Filter>,
+    pub text: String,
+}
+
+/// One `field:value` operator
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// `tag:jsonb`
+    Tag(String),
+    /// `author:name`: the asker's name contains it (case-insensitive)
+    Author(String),
+    /// `score:>50`
+    Score(Comparison),
+    /// `views:>=10000`
+    Views(Comparison),
+    /// `answers:<3`
+    Answers(Comparison),
+    /// `accepted:yes` / `accepted:no`
+    Accepted(bool),
+    /// `closed:yes` / `closed:no`
+    Closed(bool),
+}
+
+/// `>n`, `>=n`, `<n`, `<=n`, `=n` or plain `n`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub op: Ordering,
+    /// `>=` / `<=`: equality also matches
+    pub or_equal: bool,
+    pub value: i64,
+}
+
+impl Comparison {
+    fn parse(value: &str) -> Option<Self> {
+        let (op, or_equal, number) = if let Some(n) = value.strip_prefix(">=") {
+            (Ordering::Greater, true, n)
+        } else if let Some(n) = value.strip_prefix("<=") {
+            (Ordering::Less, true, n)
+        } else if let Some(n) = value.strip_prefix('>') {
+            (Ordering::Greater, false, n)
+        } else if let Some(n) = value.strip_prefix('<') {
+            (Ordering::Less, false, n)
+        } else {
+            (
+                Ordering::Equal,
+                false,
+                value.strip_prefix('=').unwrap_or(value),
+            )
+        };
+        Some(Self {
+            op,
+            or_equal,
+            value: number.parse().ok()?,
+        })
+    }
+
+    fn matches(self, actual: i64) -> bool {
+        let ord = actual.cmp(&self.value);
+        ord == self.op || (self.or_equal && ord == Ordering::Equal)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match (self.op, self.or_equal) {
+            (Ordering::Greater, false) => ">",
+            (Ordering::Greater, true) => ">=",
+            (Ordering::Less, false) => "<",
+            (Ordering::Less, true) => "<=",
+            (Ordering::Equal, _) => "",
+        };
+        write!(f, "{op}{}", self.value)
+    }
+}
+
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" | "y" | "true" => Some(true),
+        "no" | "n" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+impl Filter {
+    fn parse(word: &str) -> Option<Self> {
+        let (field, value) = word.split_once(':')?;
+        if value.is_empty() {
+            return None;
+        }
+        Some(match field.to_lowercase().as_str() {
+            "tag" => Self::Tag(value.to_lowercase()),
+            "author" => Self::Author(value.to_lowercase()),
+            "score" => Self::Score(Comparison::parse(value)?),
+            "views" => Self::Views(Comparison::parse(value)?),
+            "answers" => Self::Answers(Comparison::parse(value)?),
+            "accepted" => Self::Accepted(parse_flag(value)?),
+            "closed" => Self::Closed(parse_flag(value)?),
+            _ => return None,
+        })
+    }
+
+    pub fn matches(&self, question: &QuestionSummary) -> bool {
+        match self {
+            Self::Tag(tag) => question.tags.iter().any(|t| t == tag),
+            Self::Author(name) => question.author_name.to_lowercase().contains(name),
+            Self::Score(cmp) => cmp.matches(question.score.into()),
+            Self::Views(cmp) => cmp.matches(question.view_count.into()),
+            Self::Answers(cmp) => cmp.matches(question.answer_count.into()),
+            Self::Accepted(yes) => question.accepted_answer_id.is_some() == *yes,
+            Self::Closed(yes) => question.status.closed == *yes,
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let flag = |yes: bool| if yes { "yes" } else { "no" };
+        match self {
+            Self::Tag(tag) => write!(f, "tag:{tag}"),
+            Self::Author(name) => write!(f, "author:{name}"),
+            Self::Score(cmp) => write!(f, "score:{cmp}"),
+            Self::Views(cmp) => write!(f, "views:{cmp}"),
+            Self::Answers(cmp) => write!(f, "answers:{cmp}"),
+            Self::Accepted(yes) => write!(f, "accepted:{}", flag(*yes)),
+            Self::Closed(yes) => write!(f, "closed:{}", flag(*yes)),
+        }
+    }
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut query = Self::default();
+        let mut words = Vec::new();
+        for word in input.split_whitespace() {
+            match Filter::parse(word) {
+                Some(filter) => query.filters.push(filter),
+                None => words.push(word),
+            }
+        }
+        query.text = words.join(" ");
+        query
+    }
+
+    pub fn matches(&self, question: &QuestionSummary) -> bool {
+        self.filters.iter().all(|filter| filter.matches(question))
+    }
+
+    /// Questions passing the filters, best title match first; with operators
+    /// alone, every question that passes them in list order
+    pub fn search(&self, questions: &[QuestionSummary]) -> Vec<FuzzyMatch> {
+        if self.text.is_empty() {
+            return (0..questions.len())
+                .filter(|&index| self.matches(&questions[index]))
+                .map(|index| FuzzyMatch {
+                    index,
+                    score: 0,
+                    match_indices: Vec::new(),
+                })
+                .collect();
+        }
+        let mut matches = fuzzy_filter(questions, &self.text, |q| &q.title);
+        matches.retain(|m| self.matches(&questions[m.index]));
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::QuestionStatus;
+    use crate::site::Site;
+
+    fn question(title: &str, score: i32, tags: &[&str]) -> QuestionSummary {
+        QuestionSummary {
+            id: 1,
+            title: title.to_string(),
+            score,
+            view_count: 10_000,
+            answer_count: 2,
+            creation_date: 0,
+            accepted_answer_id: None,
+            author_name: "Kender".to_string(),
+            site: Site::StackOverflow,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            answer_scores: Vec::new(),
+            status: QuestionStatus::default(),
+            bounty: 0,
+            excerpt: String::new(),
+            word_count: 0,
+            erwin_accepted: false,
+        }
+    }
+
+    #[test]
+    fn parse_splits_filters_from_text() {
+        let query = SearchQuery::parse("tag:JSONB lateral score:>=50 join accepted:no");
+        assert_eq!(
+            query.filters,
+            vec![
+                Filter::Tag("jsonb".to_string()),
+                Filter::Score(Comparison {
+                    op: Ordering::Greater,
+                    or_equal: true,
+                    value: 50,
+                }),
+                Filter::Accepted(false),
+            ]
+        );
+        assert_eq!(query.text, "lateral join");
+    }
+
+    #[test]
+    fn unknown_or_malformed_operators_stay_text() {
+        let query = SearchQuery::parse("foo:bar score:lots tag: accepted:maybe 12:30");
+        assert!(query.filters.is_empty());
+        assert_eq!(query.text, "foo:bar score:lots tag: accepted:maybe 12:30");
+    }
+
+    #[test]
+    fn filters_display_as_parsed() {
+        for word in [
+            "tag:jsonb",
+            "author:erwin",
+            "score:>50",
+            "views:>=10000",
+            "answers:<3",
+            "score:<=0",
+            "score:7",
+            "accepted:yes",
+            "closed:no",
+        ] {
+            assert_eq!(Filter::parse(word).unwrap().to_string(), word);
+        }
+        assert_eq!(Filter::parse("score:=7").unwrap().to_string(), "score:7");
+    }
+
+    #[test]
+    fn comparisons_match() {
+        let question = question("DISTINCT ON", 50, &["postgresql"]);
+        let matches = |query: &str| SearchQuery::parse(query).matches(&question);
+        assert!(matches(
+            "score:50 score:>=50 score:<=50 score:>49 score:<51"
+        ));
+        assert!(!matches("score:>50"));
+        assert!(!matches("score:<50"));
+        assert!(matches("views:10000 answers:2"));
+        assert!(matches("tag:postgresql author:kend accepted:no closed:no"));
+        assert!(!matches("tag:postgres"));
+        assert!(!matches("accepted:yes"));
+    }
+
+    #[test]
+    fn search_with_filters_alone_keeps_list_order() {
+        let questions = [
+            question("a", 10, &["sql"]),
+            question("b", 100, &["sql"]),
+            question("c", 100, &["json"]),
+        ];
+        let indices = |query: &str| {
+            SearchQuery::parse(query)
+                .search(&questions)
+                .iter()
+                .map(|m| m.index)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(indices("score:>=10"), [0, 1, 2]);
+        assert_eq!(indices("tag:sql score:>50"), [1]);
+        assert_eq!(indices(""), [0, 1, 2]);
+    }
+}
@@ -1,27 +1,202 @@
 use anyhow::Result;
 use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::db::{Database, SemanticResult, EMBEDDING_DIMENSIONS};
+
+/// Typing pause before a preview search runs; queries sent meanwhile replace it
+const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(200);
+/// Closest questions shown in the search modal while typing
+pub const PREVIEW_LIMIT: usize = 3;
+
+/// Whether `ERWINDB_OFFLINE` is set (to anything but `0`): no model download,
+/// and semantic search runs on `stub_embedding` so sandboxed builds and
+/// integration tests still exercise it
+pub fn offline() -> bool {
+    std::env::var_os("ERWINDB_OFFLINE").is_some_and(|v| !v.is_empty() && v != "0")
+}
 
 pub struct SemanticSearch {
-    model: TextEmbedding,
+    /// `None` offline
+    model: Option<TextEmbedding>,
 }
 
 impl SemanticSearch {
     pub fn new() -> Result<Self> {
-        let cache_dir = dirs::cache_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join("erwindb");
+        Self::load(true)
+    }
+
+    /// Load the model, downloading it first if it isn't cached yet (with a
+    /// progress bar on stderr if `progress`)
+    fn load(progress: bool) -> Result<Self> {
+        if offline() {
+            return Ok(Self { model: None });
+        }
 
         let model = TextEmbedding::try_new(
             InitOptions::new(EmbeddingModel::AllMiniLML6V2)
-                .with_cache_dir(cache_dir)
-                .with_show_download_progress(true),
+                .with_cache_dir(model_cache_dir())
+                .with_show_download_progress(progress),
         )?;
 
-        Ok(Self { model })
+        Ok(Self { model: Some(model) })
+    }
+
+    /// Whether loading the model would download it first
+    pub fn needs_download() -> bool {
+        if offline() {
+            return false;
+        }
+        let Ok(entries) = std::fs::read_dir(model_cache_dir()) else {
+            return true;
+        };
+        !entries
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.file_name().to_string_lossy().starts_with("models--"))
     }
 
     pub fn embed(&self, text: &str) -> Result<Vec<f32>> {
-        let embeddings = self.model.embed(vec![text], None)?;
+        let Some(ref model) = self.model else {
+            return Ok(stub_embedding(text));
+        };
+        let embeddings = model.embed(vec![text], None)?;
         Ok(embeddings.into_iter().next().unwrap())
     }
+
+    /// Questions whose titles are closest in meaning to `query`, closest
+    /// first; only those in `within` if given
+    pub fn search(
+        &self,
+        db: &Database,
+        query: &str,
+        within: Option<&[i64]>,
+        limit: usize,
+    ) -> Result<Vec<SemanticResult>> {
+        db.semantic_search(&self.embed(query)?, within, limit)
+    }
+}
+
+fn model_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("erwindb")
+}
+
+/// `SemanticSearch` loading on a background thread, since the model takes
+/// seconds to load (and the first time, to download)
+pub struct ModelLoad {
+    result: Receiver<Result<SemanticSearch>>,
+}
+
+impl ModelLoad {
+    pub fn start() -> Self {
+        let (tx, result) = mpsc::channel();
+        // No progress bar, which would draw over the TUI
+        thread::spawn(move || {
+            let _ = tx.send(SemanticSearch::load(false));
+        });
+        Self { result }
+    }
+
+    /// `None` while still loading
+    pub fn poll(&self) -> Option<Result<SemanticSearch>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(anyhow::anyhow!(
+                "Loading the model stopped unexpectedly"
+            ))),
+        }
+    }
+}
+
+/// Stand-in embedding for offline mode: the text's words hashed into the
+/// model's dimensions and normalized. Deterministic, but it knows nothing
+/// about meaning, so the rankings it produces are arbitrary.
+pub fn stub_embedding(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0; EMBEDDING_DIMENSIONS];
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        // FNV-1a, stable across builds unlike the std hasher
+        let hash = word
+            .to_lowercase()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+                (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+            });
+        vector[(hash % EMBEDDING_DIMENSIONS as u64) as usize] += 1.0;
+    }
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+    vector
+}
+
+/// Closest questions to a query typed so far
+pub struct SemanticPreviewResults {
+    pub query: String,
+    /// Closest first
+    pub results: Vec<SemanticResult>,
+}
+
+/// Semantic search on a background thread while the query is being typed, so
+/// the search modal can preview the top results without keystrokes waiting on
+/// the model. Shares the model with the App; the thread has its own connection.
+pub struct SemanticPreview {
+    /// Query and the questions to search among (all if `None`)
+    queries: Sender<(String, Option<Arc<[i64]>>)>,
+    results: Receiver<Result<SemanticPreviewResults>>,
+}
+
+impl SemanticPreview {
+    pub fn start(semantic: Arc<SemanticSearch>, db_path: PathBuf) -> Self {
+        let (queries, query_rx) = mpsc::channel::<(String, Option<Arc<[i64]>>)>();
+        let (result_tx, results) = mpsc::channel();
+
+        thread::spawn(move || {
+            let db = match Database::open(&db_path) {
+                Ok(db) => db,
+                Err(err) => {
+                    let _ = result_tx.send(Err(err));
+                    return;
+                }
+            };
+
+            while let Ok(mut request) = query_rx.recv() {
+                thread::sleep(PREVIEW_DEBOUNCE);
+                while let Ok(newer) = query_rx.try_recv() {
+                    request = newer;
+                }
+                let (query, within) = request;
+                let result = semantic
+                    .search(&db, &query, within.as_deref(), PREVIEW_LIMIT)
+                    .map(|results| SemanticPreviewResults { query, results });
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { queries, results }
+    }
+
+    pub fn search(&self, query: &str, within: Option<Arc<[i64]>>) {
+        let _ = self.queries.send((query.to_string(), within));
+    }
+
+    /// Latest finished search, if any
+    pub fn poll(&self) -> Option<Result<SemanticPreviewResults>> {
+        let mut latest = None;
+        while let Ok(result) = self.results.try_recv() {
+            latest = Some(result);
+        }
+        latest
+    }
 }
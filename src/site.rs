@@ -0,0 +1,67 @@
+/// Stack Exchange site a question was scraped from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Site {
+    #[default]
+    StackOverflow,
+    Dba,
+}
+
+impl Site {
+    pub const ALL: [Site; 2] = [Site::StackOverflow, Site::Dba];
+
+    /// Parse the value stored in the `site` column (API site parameter)
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "dba" => Site::Dba,
+            _ => Site::StackOverflow,
+        }
+    }
+
+    /// Parse the host part of a URL
+    pub fn from_host(host: &str) -> Option<Self> {
+        match host {
+            "stackoverflow.com" => Some(Site::StackOverflow),
+            "dba.stackexchange.com" => Some(Site::Dba),
+            _ => None,
+        }
+    }
+
+    /// Value stored in the `site` column (matches the API `site=` parameter)
+    pub fn as_db(self) -> &'static str {
+        match self {
+            Site::StackOverflow => "stackoverflow",
+            Site::Dba => "dba",
+        }
+    }
+
+    /// Erwin Brandstetter's user ID on the site (the scraper's `USER_IDS`)
+    pub fn erwin_user_id(self) -> i64 {
+        match self {
+            Site::StackOverflow => 939860,
+            Site::Dba => 3684,
+        }
+    }
+
+    pub fn host(self) -> &'static str {
+        match self {
+            Site::StackOverflow => "stackoverflow.com",
+            Site::Dba => "dba.stackexchange.com",
+        }
+    }
+
+    /// Short label for badges
+    pub fn badge(self) -> &'static str {
+        match self {
+            Site::StackOverflow => "SO",
+            Site::Dba => "DBA",
+        }
+    }
+
+    pub fn question_url(self, question_id: i64) -> String {
+        format!("https://{}/questions/{}", self.host(), question_id)
+    }
+
+    pub fn answer_url(self, answer_id: i64) -> String {
+        format!("https://{}/a/{}", self.host(), answer_id)
+    }
+}
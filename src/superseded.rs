@@ -0,0 +1,73 @@
+//! Erwin's answers he has since pointed away from: a comment of his under
+//! the answer, or a paragraph of it, that calls it outdated (or names a
+//! newer answer, a better solution, …) and links a post newer than the
+//! answer. Post IDs grow over time on each site, so "newer" is a larger ID.
+//! The scraper's `findSuperseded` runs the same detection at ingest.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::db::{Answer, Comment};
+use crate::html::{extract_site, extract_so_answer_id, extract_so_question_id, is_erwin};
+use crate::site::Site;
+
+/// Wording that retires an answer
+static SUPERSEDED_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:outdated|obsolete|superseded|deprecated|no longer (?:needed|necessary|recommended|the best))\b|\b(?:newer|more recent|better|new|updated|improved) (?:answer|version|solution|variant|post)\b",
+    )
+    .unwrap()
+});
+/// Links, whose text is left out when looking for the wording (a linked
+/// title like "…see updated row" says nothing about the answer)
+static ANCHOR_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<a\b[^>]*>.*?</a>").unwrap());
+static HREF_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"href="([^"]+)""#).unwrap());
+/// Ends of the blocks an answer body is split into
+static BLOCK_END_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)</p>|</[uo]l>|<h\d|<hr").unwrap());
+
+/// URL of the newer post an answer of Erwin's defers to, if he marked it
+/// superseded
+pub fn newer_post(site: Site, answer: &Answer, comments: &[Comment]) -> Option<String> {
+    if !is_erwin(&answer.author_name) {
+        return None;
+    }
+    comments
+        .iter()
+        .filter(|comment| is_erwin(&comment.author_name))
+        .map(|comment| comment.comment_text.as_str())
+        .chain(BLOCK_END_REGEX.split(&answer.answer_text))
+        .find_map(|text| newer_link(site, answer.answer_id, text))
+}
+
+/// A link in `text` to a post on `site` newer than `answer_id`, if the text
+/// says the answer is superseded
+fn newer_link(site: Site, answer_id: i64, text: &str) -> Option<String> {
+    if !SUPERSEDED_REGEX.is_match(&ANCHOR_REGEX.replace_all(text, " ")) {
+        return None;
+    }
+    HREF_REGEX
+        .captures_iter(text)
+        .map(|cap| cap[1].to_string())
+        .find(|url| {
+            let post_id = extract_so_answer_id(url).or_else(|| extract_so_question_id(url));
+            extract_site(url) == Some(site) && post_id.is_some_and(|id| id > answer_id)
+        })
+}
+
+/// `newer_post` for each answer of a thread, by answer ID
+pub fn detect(
+    site: Site,
+    answers: &[Answer],
+    answer_comments: &[Vec<Comment>],
+) -> HashMap<i64, String> {
+    answers
+        .iter()
+        .zip(answer_comments)
+        .filter_map(|(answer, comments)| {
+            newer_post(site, answer, comments).map(|url| (answer.answer_id, url))
+        })
+        .collect()
+}
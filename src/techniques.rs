@@ -0,0 +1,147 @@
+//! Recurring solution patterns in Erwin's code for one tag, for the
+//! Techniques page. His code blocks are clustered by the functions and
+//! keywords they use; a cluster that turns up in several questions counts as
+//! a technique, named after its most telling terms.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::terms::{block_terms, term_label, TermKind};
+
+type Term = (String, TermKind);
+
+/// Questions of a tag looked at, best-scored first, so big tags stay quick
+pub const MAX_QUESTIONS: usize = 400;
+/// Columns of the matrix
+const MAX_TECHNIQUES: usize = 12;
+/// Questions a cluster has to turn up in to count as a technique
+const MIN_QUESTIONS: usize = 3;
+/// Weighted Jaccard similarity a block needs to join a cluster
+const MIN_SIMILARITY: f64 = 0.5;
+/// Terms in a larger share of the tag's blocks tell none of them apart
+const MAX_TERM_SHARE: f64 = 0.5;
+/// Terms a technique is named after
+const LABEL_TERMS: usize = 3;
+
+pub struct Technique {
+    /// Its most telling terms, as written in code
+    pub label: String,
+    /// Questions where one of Erwin's code blocks uses it
+    pub questions: HashSet<i64>,
+}
+
+/// Code blocks grouped so far
+#[derive(Default)]
+struct Cluster {
+    blocks: usize,
+    /// Blocks using each term
+    counts: HashMap<Term, usize>,
+    questions: HashSet<i64>,
+}
+
+impl Cluster {
+    /// Terms at least half of its blocks use
+    fn core(&self) -> impl Iterator<Item = &Term> {
+        self.counts
+            .iter()
+            .filter(|(_, &count)| count * 2 >= self.blocks)
+            .map(|(term, _)| term)
+    }
+}
+
+/// Techniques in the code of Erwin's answers (question id and HTML), used in
+/// the most questions first
+pub fn find_techniques(answers: &[(i64, String)]) -> Vec<Technique> {
+    let blocks: Vec<(i64, HashSet<Term>)> = answers
+        .iter()
+        .flat_map(|(question_id, html)| {
+            block_terms(html)
+                .into_iter()
+                .map(move |terms| (*question_id, terms))
+        })
+        .collect();
+
+    // Terms weighted by how rare they are among the tag's blocks. Those in a
+    // single block can't recur, those in most of them are the tag's staples.
+    let mut frequency: HashMap<&Term, usize> = HashMap::new();
+    for term in blocks.iter().flat_map(|(_, terms)| terms) {
+        *frequency.entry(term).or_default() += 1;
+    }
+    let max_blocks = ((blocks.len() as f64 * MAX_TERM_SHARE) as usize).max(2);
+    let weight: HashMap<Term, f64> = frequency
+        .into_iter()
+        .filter(|&(_, count)| (2..=max_blocks).contains(&count))
+        .map(|(term, count)| (term.clone(), (blocks.len() as f64 / count as f64).ln()))
+        .collect();
+
+    // Each block joins the most similar cluster, or starts one
+    let mut clusters: Vec<Cluster> = Vec::new();
+    for (question_id, terms) in &blocks {
+        let terms: Vec<&Term> = terms.iter().filter(|t| weight.contains_key(*t)).collect();
+        if terms.is_empty() {
+            continue;
+        }
+        let block_weight: f64 = terms.iter().map(|t| weight[*t]).sum();
+        let best = clusters
+            .iter()
+            .enumerate()
+            .map(|(index, cluster)| {
+                let core: HashSet<&Term> = cluster.core().collect();
+                let shared: f64 = terms
+                    .iter()
+                    .filter(|t| core.contains(*t))
+                    .map(|t| weight[*t])
+                    .sum();
+                let core_weight: f64 = core.iter().map(|t| weight[*t]).sum();
+                (index, shared / (block_weight + core_weight - shared))
+            })
+            .filter(|&(_, similarity)| similarity >= MIN_SIMILARITY)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index);
+        let cluster = match best {
+            Some(index) => &mut clusters[index],
+            None => {
+                clusters.push(Cluster::default());
+                clusters.last_mut().unwrap()
+            }
+        };
+        cluster.blocks += 1;
+        for &term in &terms {
+            *cluster.counts.entry(term.clone()).or_default() += 1;
+        }
+        cluster.questions.insert(*question_id);
+    }
+
+    // Name the recurring clusters; ones that end up with the same name merge
+    let mut techniques: Vec<Technique> = Vec::new();
+    for cluster in clusters {
+        if cluster.questions.len() < MIN_QUESTIONS {
+            continue;
+        }
+        let mut core: Vec<(&Term, f64)> = cluster
+            .core()
+            .map(|term| (term, cluster.counts[term] as f64 * weight[term]))
+            .collect();
+        core.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0 .0.cmp(&b.0 .0)));
+        let label = core
+            .iter()
+            .take(LABEL_TERMS)
+            .map(|((term, kind), _)| term_label(term, *kind))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match techniques.iter_mut().find(|t| t.label == label) {
+            Some(technique) => technique.questions.extend(cluster.questions),
+            None => techniques.push(Technique {
+                label,
+                questions: cluster.questions,
+            }),
+        }
+    }
+    techniques.sort_by(|a, b| {
+        b.questions
+            .len()
+            .cmp(&a.questions.len())
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    techniques.truncate(MAX_TECHNIQUES);
+    techniques
+}
@@ -0,0 +1,176 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use crate::html::decode_html_entities;
+
+/// Inline code and code blocks (`<pre><code>`) of a post
+static CODE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<code>(.*?)</code>").unwrap());
+/// Code blocks (`<pre><code>`) of a post
+#[cfg(feature = "tui")]
+static BLOCK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<pre[^>]*>\s*<code>(.*?)</code>").unwrap());
+/// An identifier, and whether a `(` follows it directly
+static TOKEN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*(\()?").unwrap());
+
+/// Keywords in nearly every query, which would bury the interesting ones
+const STOP_KEYWORDS: &[&str] = &[
+    "ALL",
+    "AND",
+    "ASC",
+    "BEGIN",
+    "BIGINT",
+    "BOOLEAN",
+    "CASE",
+    "COMMIT",
+    "CREATE",
+    "DATE",
+    "DECLARE",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "ELSE",
+    "END",
+    "EXISTS",
+    "FALSE",
+    "FROM",
+    "FUNCTION",
+    "GROUP",
+    "HAVING",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INT",
+    "INTEGER",
+    "INTO",
+    "JOIN",
+    "KEY",
+    "LANGUAGE",
+    "LEFT",
+    "LIMIT",
+    "NOT",
+    "NULL",
+    "ORDER",
+    "PRIMARY",
+    "REFERENCES",
+    "RETURN",
+    "RETURNS",
+    "SELECT",
+    "SERIAL",
+    "SET",
+    "SQL",
+    "TABLE",
+    "TEXT",
+    "THEN",
+    "TRUE",
+    "UPDATE",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TermKind {
+    /// Called like a function: `generate_series(`
+    Function,
+    /// Upper-case SQL keyword: `LATERAL`
+    Keyword,
+}
+
+impl TermKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "function" => Some(Self::Function),
+            "keyword" => Some(Self::Keyword),
+            _ => None,
+        }
+    }
+}
+
+/// A term and how many of Erwin's answers use it
+#[derive(Debug, Clone)]
+pub struct TermCount {
+    pub term: String,
+    pub kind: TermKind,
+    pub answers: usize,
+}
+
+impl TermCount {
+    /// As written in code: `generate_series()`, `LATERAL`
+    pub fn label(&self) -> String {
+        term_label(&self.term, self.kind)
+    }
+}
+
+pub fn term_label(term: &str, kind: TermKind) -> String {
+    match kind {
+        TermKind::Function => format!("{term}()"),
+        TermKind::Keyword => term.to_string(),
+    }
+}
+
+/// Functions and keywords in a post's code. Functions are lower-cased names
+/// directly followed by `(` (Erwin writes `count(*)` but `CREATE TABLE tbl (`),
+/// keywords are all-caps words of three letters or more. Mirrors `termsInCode`
+/// in the scraper, which precomputes the counts at ingest.
+pub fn code_terms(html: &str) -> HashSet<(String, TermKind)> {
+    let mut terms = HashSet::new();
+    for code in CODE_REGEX.captures_iter(html) {
+        add_terms(&decode_html_entities(&code[1]), &mut terms);
+    }
+    terms
+}
+
+/// Terms of each code block in a post, as `code_terms` finds them
+#[cfg(feature = "tui")]
+pub fn block_terms(html: &str) -> Vec<HashSet<(String, TermKind)>> {
+    BLOCK_REGEX
+        .captures_iter(html)
+        .map(|code| {
+            let mut terms = HashSet::new();
+            add_terms(&decode_html_entities(&code[1]), &mut terms);
+            terms
+        })
+        .collect()
+}
+
+fn add_terms(code: &str, terms: &mut HashSet<(String, TermKind)>) {
+    for token in TOKEN_REGEX.captures_iter(code) {
+        let word = token[0].trim_end_matches('(');
+        if token.get(1).is_some() && word.chars().any(|c| c.is_ascii_lowercase()) {
+            terms.insert((word.to_lowercase(), TermKind::Function));
+        } else if word.len() >= 3
+            && word.chars().all(|c| c.is_ascii_uppercase() || c == '_')
+            && !STOP_KEYWORDS.contains(&word)
+        {
+            terms.insert((word.to_string(), TermKind::Keyword));
+        }
+    }
+}
+
+/// Terms used in at least `min_answers` of the given posts, most used first
+pub fn count_terms<'a>(
+    posts: impl IntoIterator<Item = &'a str>,
+    min_answers: usize,
+) -> Vec<TermCount> {
+    let mut counts: HashMap<(String, TermKind), usize> = HashMap::new();
+    for html in posts {
+        for term in code_terms(html) {
+            *counts.entry(term).or_default() += 1;
+        }
+    }
+    let mut counts: Vec<TermCount> = counts
+        .into_iter()
+        .filter(|(_, answers)| *answers >= min_answers)
+        .map(|((term, kind), answers)| TermCount {
+            term,
+            kind,
+            answers,
+        })
+        .collect();
+    counts.sort_by(|a, b| b.answers.cmp(&a.answers).then(a.term.cmp(&b.term)));
+    counts
+}
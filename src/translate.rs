@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Context, Result};
+use std::io::{Read, Write};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::external::{check_status, kill_group};
+
+/// A translation command running in the background; it gets plain text on
+/// stdin and prints the translation to stdout
+pub struct Translator {
+    command: String,
+    child: Child,
+    output: Receiver<String>,
+}
+
+impl Translator {
+    pub fn start(command: &str, text: String) -> Result<Self> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .process_group(0)
+            .spawn()
+            .with_context(|| format!("Failed to run `{command}`"))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            thread::spawn(move || {
+                let _ = stdin.write_all(text.as_bytes());
+            });
+        }
+
+        // Read stdout off the UI thread; the channel yields once the command closes it
+        let (tx, output) = mpsc::channel();
+        if let Some(mut stdout) = child.stdout.take() {
+            thread::spawn(move || {
+                let mut translated = String::new();
+                let _ = stdout.read_to_string(&mut translated);
+                let _ = tx.send(translated);
+            });
+        }
+
+        Ok(Self {
+            command: command.to_string(),
+            child,
+            output,
+        })
+    }
+
+    /// `None` while still translating, otherwise the translated text
+    pub fn poll(&mut self) -> Option<Result<String>> {
+        let translated = match self.output.try_recv() {
+            Ok(translated) => translated,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => {
+                return Some(Err(anyhow!("`{}` produced no output", self.command)))
+            }
+        };
+        Some(
+            self.child
+                .wait()
+                .map_err(Into::into)
+                .and_then(|status| check_status(&self.command, status))
+                .map(|()| translated),
+        )
+    }
+}
+
+impl Drop for Translator {
+    fn drop(&mut self) {
+        kill_group(&mut self.child);
+    }
+}
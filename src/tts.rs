@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+
+use crate::external::{check_status, kill_group};
+
+/// A running text-to-speech command reading text from stdin in the background
+pub struct Speaker {
+    command: String,
+    child: Child,
+}
+
+impl Speaker {
+    pub fn start(command: &str, text: String) -> Result<Self> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            // Own process group so stopping also ends pipelines (`pandoc | espeak`)
+            .process_group(0)
+            .spawn()
+            .with_context(|| format!("Failed to run `{command}`"))?;
+
+        // Long answers can exceed the pipe buffer, so feed stdin off the UI thread
+        if let Some(mut stdin) = child.stdin.take() {
+            thread::spawn(move || {
+                let _ = stdin.write_all(text.as_bytes());
+            });
+        }
+
+        Ok(Self {
+            command: command.to_string(),
+            child,
+        })
+    }
+
+    /// `None` while still speaking, otherwise how the command finished
+    pub fn poll(&mut self) -> Option<Result<()>> {
+        match self.child.try_wait() {
+            Ok(Some(status)) => Some(check_status(&self.command, status)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+}
+
+impl Drop for Speaker {
+    fn drop(&mut self) {
+        kill_group(&mut self.child);
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::{TimeZone, Utc};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+
+/// Width of the alert rule column
+const RULE_WIDTH: usize = 18;
+
+pub fn draw_alerts(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(size);
+
+    let header = format!(
+        " Alerts ({} new, {} unread) ",
+        app.alerts.hits.len(),
+        app.alerts.unread
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(header)).style(styles::header_style()),
+        chunks[0],
+    );
+    draw_alert_list(frame, app, chunks[1]);
+    draw_status_bar(frame, app, chunks[2]);
+}
+
+fn draw_alert_list(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    if app.alerts.hits.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "  No alerts yet. Add keywords or tags under [alerts] in config.toml;",
+                styles::dim_style(),
+            )),
+            Line::from(Span::styled(
+                "  matching questions and answers show up here after a corpus update.",
+                styles::dim_style(),
+            )),
+        ]);
+        frame.render_widget(hint, area);
+        return;
+    }
+
+    let lines: Vec<Line> = app
+        .alerts
+        .hits
+        .iter()
+        .enumerate()
+        .skip(app.alerts.scroll)
+        .take(area.height as usize)
+        .map(|(idx, hit)| {
+            let is_selected = idx == app.alerts.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+
+            let unread = if hit.read { " " } else { "\u{25cf}" };
+            let found = Utc
+                .timestamp_opt(hit.found_at, 0)
+                .single()
+                .map(|dt| dt.format("%b %d, %Y").to_string())
+                .unwrap_or_default();
+            let kind = if hit.post.answer_id.is_some() {
+                "A"
+            } else {
+                "Q"
+            };
+
+            let mut title_style = Style::default();
+            if !hit.read {
+                title_style = title_style.add_modifier(Modifier::BOLD);
+            }
+            if hit.read && !is_selected {
+                title_style = title_style.fg(theme.dim);
+            }
+
+            Line::from(vec![
+                Span::styled(selector, selector_style),
+                Span::styled(format!("{unread} "), Style::default().fg(theme.special)),
+                Span::styled(format!("{found:<12} "), styles::dim_style()),
+                Span::styled(format!("{kind} "), Style::default().fg(theme.accent)),
+                Span::styled(
+                    format!("{:<width$} ", hit.rule, width = RULE_WIDTH),
+                    Style::default().fg(theme.highlight),
+                ),
+                Span::styled(hit.title.clone(), title_style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let help = " j/k:move  Enter:open  r:toggle read  R:mark all read  q:back";
+
+    let total = app.alerts.hits.len();
+    let position = if total > 0 {
+        format!("{}/{}", app.alerts.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
@@ -0,0 +1,55 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::styles;
+use crate::app::ConfirmState;
+
+/// Yes/no modal drawn on top of whichever page is active
+pub fn draw_confirm(frame: &mut Frame, confirm: &ConfirmState) {
+    let theme = styles::theme();
+    let area = frame.area();
+    let hint = "y:confirm  n/Esc:cancel";
+
+    let modal_width = (confirm.message.width().max(hint.len()) as u16 + 4)
+        .clamp(30, 70)
+        .min(area.width);
+    let inner_width = modal_width.saturating_sub(4).max(1) as usize;
+    let message_rows = confirm.message.width().div_ceil(inner_width).max(1) as u16;
+    // Border + message + blank + hint + border
+    let modal_height = (message_rows + 4).min(area.height);
+
+    let x = (area.width.saturating_sub(modal_width)) / 2;
+    let y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(x, y, modal_width, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Confirm ")
+        .title_style(
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.error))
+        .padding(ratatui::widgets::Padding::horizontal(1));
+
+    let lines = vec![
+        Line::from(Span::styled(
+            confirm.message.clone(),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(hint, Style::default().fg(theme.dim))),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(block).wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, modal_area);
+}
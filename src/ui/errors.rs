@@ -0,0 +1,121 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::styles;
+use crate::app::ErrorDetailsState;
+use crate::report::ErrorReport;
+
+/// Error details screen drawn on top of whichever page is active
+pub fn draw_error_details(
+    frame: &mut Frame,
+    errors: &[ErrorReport],
+    details: &mut ErrorDetailsState,
+) {
+    let theme = styles::theme();
+    let area = frame.area();
+    let Some(error) = errors.get(details.index) else {
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                error.job,
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" failed at {}", error.time()),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            error.chain[0].clone(),
+            Style::default().fg(theme.error),
+        )),
+    ];
+    if error.chain.len() > 1 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Caused by:",
+            Style::default().fg(theme.muted),
+        )));
+        for (depth, cause) in error.chain[1..].iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {depth}: "), styles::dim_style()),
+                Span::styled(cause.clone(), Style::default().fg(theme.text)),
+            ]));
+        }
+    }
+    if details.index > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Earlier failures",
+            Style::default().fg(theme.muted),
+        )));
+        for earlier in &errors[..details.index] {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", earlier.time()), styles::dim_style()),
+                Span::styled(
+                    format!("{}: ", earlier.job),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(earlier.chain.join(": "), Style::default().fg(theme.muted)),
+            ]));
+        }
+    }
+
+    let modal_width = 80.min(area.width);
+    let modal_height = area.height.saturating_sub(4).clamp(3, 24).min(area.height);
+    let x = (area.width.saturating_sub(modal_width)) / 2;
+    let y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(x, y, modal_width, modal_height);
+
+    // Wrapped content and the help line share the inside of the border
+    let inner_width = modal_width.saturating_sub(4);
+    let inner_height = modal_height.saturating_sub(3) as usize;
+    let content_height: usize = lines
+        .iter()
+        .map(|line| line.width().div_ceil(inner_width.max(1) as usize).max(1))
+        .sum();
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    details.scroll = details
+        .scroll
+        .min(content_height.saturating_sub(inner_height));
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Error {}/{} ", details.index + 1, errors.len()))
+        .title_style(
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.error))
+        .padding(ratatui::widgets::Padding::horizontal(1));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let content_area = Rect::new(inner.x, inner.y, inner.width, inner_height as u16);
+    frame.render_widget(
+        paragraph.scroll((details.scroll.min(u16::MAX as usize) as u16, 0)),
+        content_area,
+    );
+    let help_area = Rect::new(inner.x, inner.bottom().saturating_sub(1), inner.width, 1);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "j/k:scroll  h/l:older/newer  y:copy issue report  q:close",
+            styles::dim_style(),
+        )),
+        help_area,
+    );
+}
@@ -0,0 +1,124 @@
+use chrono::{TimeZone, Utc};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+use crate::clock;
+
+pub fn draw_hidden(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(size);
+
+    let header = format!(" Hidden questions ({}) ", app.hidden_page.questions.len());
+    frame.render_widget(
+        Paragraph::new(Line::from(header)).style(styles::header_style()),
+        chunks[0],
+    );
+    draw_hidden_list(frame, app, chunks[1]);
+    draw_status_bar(frame, app, chunks[2]);
+}
+
+fn draw_hidden_list(frame: &mut Frame, app: &App, area: Rect) {
+    if app.hidden_page.questions.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "  Nothing hidden. x on the list hides a question; it shows up here to unhide.",
+                styles::dim_style(),
+            )),
+        ]);
+        frame.render_widget(hint, area);
+        return;
+    }
+
+    let offset = *clock::now().offset();
+    let lines: Vec<Line> = app
+        .hidden_page
+        .questions
+        .iter()
+        .enumerate()
+        .skip(app.hidden_page.scroll)
+        .take(area.height as usize)
+        .map(|(idx, hidden)| {
+            let is_selected = idx == app.hidden_page.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+
+            let hidden_at = Utc
+                .timestamp_opt(hidden.hidden_at, 0)
+                .single()
+                .map(|dt| {
+                    dt.with_timezone(&offset)
+                        .format("%b %d, %Y %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let title = app
+                .questions
+                .iter()
+                .find(|q| q.id == hidden.question_id && q.site == hidden.site)
+                .map_or_else(
+                    || format!("#{} (not in this corpus)", hidden.question_id),
+                    |q| q.title.clone(),
+                );
+
+            Line::from(vec![
+                Span::styled(selector, selector_style),
+                Span::styled(format!("{hidden_at:<18} "), styles::dim_style()),
+                Span::styled(
+                    format!("{:<4}", hidden.site.badge()),
+                    styles::site_badge_style(),
+                ),
+                Span::raw(title),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    if let Some(ref message) = app.flash {
+        let status = Line::from(Span::styled(format!(" {}", message), styles::flash_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    let help = " j/k:move  g/G:first/last  Enter:open  u:unhide  q:back";
+
+    let total = app.hidden_page.questions.len();
+    let position = if total > 0 {
+        format!("{}/{}", app.hidden_page.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
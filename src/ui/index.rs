@@ -1,14 +1,25 @@
 use chrono::{TimeZone, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use super::styles;
-use crate::app::{App, SearchMode, SortColumn, SortDirection};
+use super::{draw_scrollbar, split_scrollbar, status_badges, styles};
+use crate::app::{App, Page, SearchMode, SortColumn, SortDirection};
+use crate::clock;
+use crate::html::fit_cell;
+use crate::search::query::SearchQuery;
+use crate::search::semantic::PREVIEW_LIMIT;
+use crate::site::Site;
+
+/// Width of the answer score sparkline column
+const SPARKLINE_WIDTH: usize = 6;
+/// Width of the optional reading-time column ("14m")
+const READING_TIME_WIDTH: usize = 4;
 
 pub fn draw_index(frame: &mut Frame, app: &App) {
     let size = frame.area();
@@ -16,61 +27,219 @@ pub fn draw_index(frame: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Header
-            Constraint::Length(1), // Column headers
-            Constraint::Min(1),    // Content
-            Constraint::Length(1), // Status bar
+            Constraint::Length(1),                          // Header
+            Constraint::Length(1),                          // Column headers
+            Constraint::Min(1),                             // Content
+            Constraint::Length(u16::from(app.index.stats)), // Stats footer
+            Constraint::Length(1),                          // Status bar
         ])
         .split(size);
 
     draw_header(frame, app, chunks[0]);
     draw_column_headers(frame, app, chunks[1]);
     draw_question_list(frame, app, chunks[2]);
-    draw_status_bar(frame, app, chunks[3]);
+    if app.index.stats {
+        draw_stats_footer(frame, app, chunks[3]);
+    }
+    draw_status_bar(frame, app, chunks[4]);
 
     // Draw semantic search modal on top if active
-    if app.search_mode == SearchMode::Semantic {
+    if matches!(app.search.mode, SearchMode::Semantic | SearchMode::Hybrid) {
         draw_semantic_modal(frame, app, size);
     }
 }
 
 fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
-    let (style, text) = match app.search_mode {
-        SearchMode::Title => (
-            styles::search_title_style(),
-            format!(" /{}\u{2588}", app.search_input),
-        ),
-        // Semantic search uses a modal, so show normal header
-        SearchMode::Semantic | SearchMode::None => {
-            let count_text = if let Some(ref matches) = app.fuzzy_matches {
+    let (style, text) = match app.search.mode {
+        SearchMode::Title => {
+            let mut spans = vec![Span::raw(format!(" /{}\u{2588}", app.search.input))];
+            if !app.search.filters.is_empty() {
+                let filters: Vec<String> = app
+                    .search
+                    .filters
+                    .iter()
+                    .map(|f| format!("[{f}]"))
+                    .collect();
+                spans.push(Span::styled(
+                    format!("   {}", filters.join(" ")),
+                    styles::search_filter_style(),
+                ));
+            }
+            if let Some(hint) = body_match_hint(app) {
+                spans.push(Span::styled(format!("   {hint}"), styles::dim_style()));
+            }
+            (styles::search_title_style(), Line::from(spans))
+        }
+        // Semantic and hybrid search use a modal, so show normal header
+        SearchMode::Semantic | SearchMode::Hybrid | SearchMode::None => {
+            let bookmarks_page = app.page == Page::Bookmarks;
+            let count_text = if let Some(ref matches) = app.search.fuzzy_matches {
+                let bodies = app.body_only_matches().map_or(0, |m| m.len());
+                let (count, bodies) = match bodies {
+                    0 => (matches.len(), String::new()),
+                    n if app.search.include_bodies => {
+                        (matches.len() + n, format!(", {n} in bodies"))
+                    }
+                    n => (matches.len(), format!(" [+{n} in bodies: Ctrl-F]")),
+                };
                 format!(
-                    " ErwinDB ({}/{} matching \"{}\") ",
-                    matches.len(),
+                    " ErwinDB ({}/{} matching \"{}\"{}) ",
+                    count,
                     app.questions.len(),
-                    app.search_input
+                    app.search.input,
+                    bodies
                 )
-            } else if app.semantic_loading {
-                " Searching... ".to_string()
-            } else if app.semantic_results.is_some() {
+            } else if app.search.waiting_for_model.is_some() {
+                " Loading the embedding model... ".to_string()
+            } else if let Some(ref tag) = app.search.explore_tag {
+                format!(
+                    " ErwinDB ({} near [{}] without the tag) ",
+                    app.search
+                        .semantic_results
+                        .as_ref()
+                        .map(|r| r.len())
+                        .unwrap_or(0),
+                    tag
+                )
+            } else if let Some(ref matches) = app.search.hybrid_results {
+                format!(
+                    " ErwinDB ({} hybrid results for \"{}\") ",
+                    matches.len(),
+                    app.search.input
+                )
+            } else if app.search.semantic_results.is_some() {
                 format!(
                     " ErwinDB ({} semantic results) ",
-                    app.semantic_results.as_ref().map(|r| r.len()).unwrap_or(0)
+                    app.search
+                        .semantic_results
+                        .as_ref()
+                        .map(|r| r.len())
+                        .unwrap_or(0)
                 )
+            } else if bookmarks_page {
+                format!(" Bookmarks ({}) ", app.bookmarks.len())
             } else {
                 format!(" ErwinDB ({} questions) ", app.questions.len())
             };
-            (styles::header_style(), count_text)
+            let count_text = if bookmarks_page && app.search.has_results() {
+                format!(
+                    "{}[bookmarked: {}] ",
+                    count_text,
+                    app.visible_questions_count()
+                )
+            } else {
+                count_text
+            };
+            let count_text = match app.index.tag_filter {
+                Some(ref tag) => format!(
+                    "{}[{}: {}] ",
+                    count_text,
+                    tag,
+                    app.visible_questions_count()
+                ),
+                None => count_text,
+            };
+            let count_text = match app.index.term_filter {
+                Some(ref filter) => format!(
+                    "{}[uses {}: {}] ",
+                    count_text,
+                    filter.label,
+                    app.visible_questions_count()
+                ),
+                None => count_text,
+            };
+            let count_text = if app.pick_mode {
+                format!("{}[pick: Enter selects] ", count_text)
+            } else {
+                count_text
+            };
+            let count_text = if app.index.hide_closed {
+                format!("{}[closed hidden] ", count_text)
+            } else {
+                count_text
+            };
+            let count_text = match app.hidden.len() {
+                n if n > 0 && app.index.show_hidden => {
+                    format!("{}[{} hidden shown] ", count_text, n)
+                }
+                _ => count_text,
+            };
+            let count_text = match app.index.pins.len() {
+                0 => count_text,
+                n => format!("{}[{} pinned] ", count_text, n),
+            };
+            let count_text = match app.alerts.unread {
+                0 => count_text,
+                n => format!("{}[{} unread alerts] ", count_text, n),
+            };
+            let count_text = match app.review.due {
+                0 => count_text,
+                n => format!("{}[{} to review: R] ", count_text, n),
+            };
+            (styles::header_style(), Line::from(count_text))
         }
     };
 
-    let header = Paragraph::new(Line::from(text)).style(style);
+    let header = Paragraph::new(text).style(style);
     frame.render_widget(header, area);
 }
 
+/// How many more questions match the title search in their bodies, while typing
+fn body_match_hint(app: &App) -> Option<String> {
+    let titles = app.search.fuzzy_matches.as_ref()?.len();
+    // Operators alone leave no words to look for in bodies
+    if SearchQuery::parse(&app.search.input).text.is_empty() {
+        return Some(format!("{titles} matches"));
+    }
+    Some(match app.body_only_matches() {
+        None if app.body_search.is_some() => {
+            format!("{titles} title matches, counting body matches\u{2026}")
+        }
+        None => return None,
+        Some(bodies) if app.search.include_bodies => format!(
+            "{titles} title + {} body matches \u{2014} Ctrl-F for titles only",
+            bodies.len()
+        ),
+        Some(bodies) => format!(
+            "{titles} title matches, +{} in bodies \u{2014} Ctrl-F to include",
+            bodies.len()
+        ),
+    })
+}
+
+/// Where the selected hybrid result placed in each ranking
+fn hybrid_ranks(app: &App) -> String {
+    let Some(id) = app.get_selected_question().map(|q| q.id) else {
+        return String::new();
+    };
+    let Some(m) = app
+        .search
+        .hybrid_results
+        .iter()
+        .flatten()
+        .find(|m| app.questions[m.index].id == id)
+    else {
+        return String::new();
+    };
+    let rank = |rank: Option<usize>| rank.map_or("-".to_string(), |r| format!("#{r}"));
+    format!(
+        "  [title {} \u{00b7} meaning {}]",
+        rank(m.fuzzy_rank),
+        rank(m.semantic_rank)
+    )
+}
+
 fn draw_semantic_modal(frame: &mut Frame, app: &App, area: Rect) {
-    // Modal dimensions: border + input + hint + border = 4 lines minimum
+    let theme = styles::theme();
+    // Modal dimensions: border + input + hint + border = 4 lines minimum; the
+    // semantic modal adds the preview rows and a gap below them
+    let preview_rows = if app.search.mode == SearchMode::Semantic {
+        PREVIEW_LIMIT as u16
+    } else {
+        0
+    };
     let modal_width = 60.min(area.width.saturating_sub(4));
-    let modal_height = 5;
+    let modal_height = 5 + preview_rows + preview_rows.min(1);
 
     // Center the modal
     let x = (area.width.saturating_sub(modal_width)) / 2;
@@ -82,15 +251,24 @@ fn draw_semantic_modal(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(Clear, modal_area);
 
     // Draw modal border
+    let kind = if app.search.mode == SearchMode::Hybrid {
+        "Hybrid"
+    } else {
+        "Semantic"
+    };
+    let title = match app.search.scope {
+        Some(ref scope) => format!(" {kind} Search in {} ", scope.label),
+        None => format!(" {kind} Search "),
+    };
     let block = Block::default()
-        .title(" Semantic Search ")
+        .title(title)
         .title_style(
             Style::default()
-                .fg(Color::Magenta)
+                .fg(theme.special)
                 .add_modifier(Modifier::BOLD),
         )
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta));
+        .border_style(Style::default().fg(theme.special));
 
     frame.render_widget(block, modal_area);
 
@@ -104,34 +282,72 @@ fn draw_semantic_modal(frame: &mut Frame, app: &App, area: Rect) {
 
     let prompt = "> ";
     let cursor = "\u{2588}";
-    let input_text = format!("{}{}{}", prompt, app.search_input, cursor);
+    let input_text = format!("{}{}{}", prompt, app.search.input, cursor);
     let input = Paragraph::new(Line::from(vec![Span::styled(
         input_text,
-        Style::default().fg(Color::White),
+        Style::default().fg(theme.text),
     )]));
 
     frame.render_widget(input, input_area);
 
-    // Hint text below input (y+2 = second row inside border)
+    // Closest questions so far, from the background preview search
+    if preview_rows > 0 {
+        let preview_area = Rect::new(
+            input_area.x,
+            modal_area.y + 3,
+            input_area.width,
+            preview_rows,
+        );
+        let lines: Vec<Line> = if app.search.semantic_preview.is_empty() {
+            let hint = if app.semantic_preview.is_some() {
+                "Closest questions show up here as you type"
+            } else if app.model_load.is_some() {
+                "Loading the embedding model\u{2026}"
+            } else {
+                "No preview without the embedding model"
+            };
+            vec![Line::from(Span::styled(hint, styles::dim_style()))]
+        } else {
+            app.search
+                .semantic_preview
+                .iter()
+                .map(|result| {
+                    let title = app
+                        .questions
+                        .iter()
+                        .find(|q| q.id == result.question_id)
+                        .map_or("", |q| q.title.as_str());
+                    Line::from(vec![
+                        Span::styled(format!("{:.2} ", result.distance), styles::dim_style()),
+                        Span::raw(title.to_string()),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), preview_area);
+    }
+
+    // Hint text below input (y+2 = second row inside border), or below the preview
     let hint_area = Rect::new(
         modal_area.x + 2,
-        modal_area.y + 3,
+        modal_area.y + 3 + preview_rows + preview_rows.min(1),
         modal_area.width.saturating_sub(4),
         1,
     );
 
     let hint = Paragraph::new(Line::from(vec![Span::styled(
         "Enter to search · Esc to cancel",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.dim),
     )]));
 
     frame.render_widget(hint, hint_area);
 }
 
 fn draw_column_headers(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
     let get_indicator = |col: SortColumn| -> &str {
-        if app.sort_active && app.sort_column == col {
-            match app.sort_direction {
+        if app.index.sort_active && app.index.sort_column == col {
+            match app.index.sort_direction {
                 SortDirection::Asc => "\u{25b2}",
                 SortDirection::Desc => "\u{25bc}",
             }
@@ -140,65 +356,84 @@ fn draw_column_headers(frame: &mut Frame, app: &App, area: Rect) {
         }
     };
 
-    let headers = Line::from(vec![
-        Span::styled("   ", Style::default().fg(Color::DarkGray)),
+    let mut headers = vec![
+        Span::styled("   ", Style::default().fg(theme.dim)),
         Span::styled(
             format!("{:>7}{} ", "ID", get_indicator(SortColumn::Id)),
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("{:<12}{} ", "Date", get_indicator(SortColumn::Date)),
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("{:>5}{} ", "Score", get_indicator(SortColumn::Score)),
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("{:>6}{} ", "Views", get_indicator(SortColumn::Views)),
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("{:>3}{} ", "A", get_indicator(SortColumn::Answers)),
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
+    ];
+    if app.layout.reading_time {
+        headers.push(Span::styled(
+            format!("{:>width$} ", "Read", width = READING_TIME_WIDTH),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
+        ));
+    }
+    headers.extend([
         Span::styled(
-            "Title".to_string(),
-            Style::default()
-                .fg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
+            format!("{:<width$} ", "Spread", width = SPARKLINE_WIDTH),
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            // Bounty has no column of its own; its sort shows next to the title,
+            // as does the order of an unsorted list
+            match get_indicator(SortColumn::Bounty) {
+                _ if !app.index.sort_active && app.search.has_results() => {
+                    "Title  (by relevance)".to_string()
+                }
+                _ if !app.index.sort_active => "Title  (unsorted)".to_string(),
+                " " => "Title".to_string(),
+                indicator => format!("Title  (by bounty{indicator})"),
+            },
+            Style::default().fg(theme.dim).add_modifier(Modifier::BOLD),
         ),
     ]);
 
-    frame.render_widget(Paragraph::new(headers), area);
+    frame.render_widget(Paragraph::new(Line::from(headers)), area);
 }
 
 fn draw_question_list(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    let (area, scrollbar) = split_scrollbar(area, app.layout.scrollbars);
     let sorted = app.get_sorted_questions();
-    let visible_rows = area.height as usize;
-    let scroll = app.index_scroll;
+    let visible_rows = area.height as usize / app.index.row_height();
+    let scroll = app.index.scroll;
 
-    let fixed_width = 3 + 8 + 13 + 6 + 7 + 4 + 5; // selector + columns + spaces
+    let mut fixed_width = 3 + 8 + 13 + 6 + 7 + 4 + SPARKLINE_WIDTH + 6; // selector + columns + spaces
+    if app.layout.reading_time {
+        fixed_width += READING_TIME_WIDTH + 1;
+    }
     let title_width = (area.width as usize).saturating_sub(fixed_width);
+    let title_pattern = SearchQuery::parse(&app.search.input).text;
 
     let lines: Vec<Line> = sorted
-        .iter()
-        .enumerate()
-        .skip(scroll)
-        .take(visible_rows)
-        .map(|(idx, q)| {
-            let is_selected = idx == app.selected_index;
-            let selector = if is_selected { " > " } else { "   " };
+        .window(scroll, visible_rows)
+        .flat_map(|(idx, q)| {
+            let is_selected = idx == app.index.selected;
+            let is_pinned = app.index.pins.contains(&q.id);
+            let pin_marker = if is_pinned { "\u{2022}" } else { " " };
+            let selector = if is_selected { ">" } else { " " };
+            let bookmark_marker = if app.bookmarks.contains(&(q.site, q.id)) {
+                "\u{2605}"
+            } else {
+                " "
+            };
 
             let id_str = format!("{:>8}", q.id);
             let date_str = format_date(q.creation_date);
@@ -216,9 +451,7 @@ fn draw_question_list(frame: &mut Frame, app: &App, area: Rect) {
             };
 
             let base_style = if is_selected {
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD)
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
@@ -230,106 +463,251 @@ fn draw_question_list(frame: &mut Frame, app: &App, area: Rect) {
             };
 
             let id_style = if is_selected {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(theme.accent)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.dim)
             };
 
             let dim_style = if is_selected {
                 base_style
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.dim)
             };
 
             let score_style = if q.score > 0 {
                 if is_selected {
                     base_style
                 } else {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(theme.good)
                 }
             } else if is_selected {
                 base_style
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.dim)
             };
 
             let answers_style = if q.accepted_answer_id.is_some() {
                 if is_selected {
                     base_style
                 } else {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(theme.good)
                 }
             } else if is_selected {
                 base_style
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.dim)
             };
 
-            // Build title with fuzzy highlighting if applicable
-            let title_spans = if let Some(ref matches) = app.fuzzy_matches {
-                if let Some(m) = matches.iter().find(|m| app.questions[m.index].id == q.id) {
-                    highlight_fuzzy_match(&title, &m.match_indices, base_style)
-                } else {
-                    vec![Span::styled(title.clone(), base_style)]
-                }
+            // Build title with fuzzy highlighting if applicable; the title
+            // search's matches are highlighted only as they're drawn
+            let match_indices = if app.search.fuzzy_matches.is_some() {
+                Some(app.title_matcher.highlight(&title_pattern, &q.title))
+            } else if let Some(ref matches) = app.search.hybrid_results {
+                matches
+                    .iter()
+                    .find(|m| app.questions[m.index].id == q.id)
+                    .and_then(|m| m.fuzzy.as_ref())
+                    .map(|m| m.match_indices.clone())
             } else {
-                vec![Span::styled(title.clone(), base_style)]
+                None
+            };
+            // Questions opened before are dimmed
+            let title_style = if !is_selected && app.viewed.contains(&(q.site, q.id)) {
+                styles::dim_style()
+            } else {
+                base_style
+            };
+            // Hidden ones only show while `V` lists them, struck through
+            let title_style = if app.hidden.contains(&q.id) {
+                title_style.add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                title_style
+            };
+            let title_spans = match match_indices {
+                Some(indices) => highlight_fuzzy_match(&title, &indices, title_style),
+                None => vec![Span::styled(title.clone(), title_style)],
             };
 
             let mut spans = vec![
+                Span::styled(pin_marker, styles::pin_style()),
                 Span::styled(selector.to_string(), selector_style),
+                Span::styled(bookmark_marker, styles::bookmark_style()),
                 Span::styled(format!("{} ", id_str), id_style),
-                Span::styled(format!("{} ", date_str), dim_style),
+                Span::styled(
+                    format!("{} ", date_str),
+                    if is_selected {
+                        base_style
+                    } else {
+                        styles::age_style(
+                            clock::years_since(q.creation_date),
+                            app.config.stale.years,
+                        )
+                    },
+                ),
                 Span::styled(format!("{} ", score_str), score_style),
                 Span::styled(format!("{} ", views_str), dim_style),
                 Span::styled(format!("{} ", answers_str), answers_style),
             ];
+            if app.layout.reading_time {
+                spans.push(Span::styled(
+                    format!(
+                        "{:>width$} ",
+                        format!("{}m", q.reading_minutes()),
+                        width = READING_TIME_WIDTH
+                    ),
+                    dim_style,
+                ));
+            }
+            spans.extend([Span::styled(
+                format!(
+                    "{:<width$} ",
+                    answer_sparkline(&q.answer_scores),
+                    width = SPARKLINE_WIDTH
+                ),
+                if is_selected {
+                    base_style
+                } else {
+                    Style::default().fg(theme.highlight)
+                },
+            )]);
+            // Stack Overflow is the default corpus, so only badge other sites
+            if q.site != Site::StackOverflow {
+                spans.push(Span::styled(
+                    format!("{} ", q.site.badge()),
+                    styles::site_badge_style(),
+                ));
+            }
+            spans.extend(status_badges(q.status));
+            if q.bounty > 0 {
+                spans.push(Span::styled(
+                    format!("+{} bounty ", q.bounty),
+                    styles::bounty_style(),
+                ));
+            }
             spans.extend(title_spans);
+            // Tags fill whatever room the title leaves
+            let mut room = title_width.saturating_sub(title.width());
+            for tag in &q.tags {
+                let tag = format!(" [{tag}]");
+                if tag.width() > room {
+                    break;
+                }
+                room -= tag.width();
+                spans.push(Span::styled(tag, styles::tag_style()));
+            }
 
-            Line::from(spans)
+            let mut rows = vec![Line::from(spans)];
+            if app.index.detailed {
+                // Excerpt lines up under the title
+                let excerpt: String = q.excerpt.chars().take(title_width).collect();
+                rows.push(Line::from(vec![
+                    Span::raw(" ".repeat(fixed_width - 1)),
+                    Span::styled(excerpt, styles::dim_style()),
+                ]));
+            }
+            rows
         })
         .collect();
 
     let list = Paragraph::new(lines);
     frame.render_widget(list, area);
+    if let Some(bar) = scrollbar {
+        let (count, selected) = (sorted.len(), app.index.selected);
+        draw_scrollbar(frame, bar, count, visible_rows, selected, count);
+    }
+}
+
+/// Aggregates of the listed questions, whatever filters and search made the list
+fn draw_stats_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let stats = app.list_stats();
+    let text = match stats.count {
+        0 => " No questions".to_string(),
+        _ => format!(
+            " {} questions \u{00b7} score mean {:.1}, median {} \u{00b7} {} to {} \u{00b7} {:.0}% Erwin-accepted",
+            stats.count,
+            stats.mean_score,
+            stats.median_score,
+            format_date(stats.first_date),
+            format_date(stats.last_date),
+            stats.erwin_accepted * 100.0
+        ),
+    };
+    let line = Line::from(Span::styled(
+        fit_cell(&text, area.width as usize),
+        styles::dim_style(),
+    ));
+    frame.render_widget(Paragraph::new(line), area);
 }
 
 fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
-    let help = match app.search_mode {
-        SearchMode::Title => " Type to search by title, Enter to confirm, Esc to cancel",
+    if let Some(ref message) = app.flash {
+        let status = Line::from(Span::styled(format!(" {}", message), styles::flash_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    let pin_help = if app.index.pins.is_empty() {
+        "  m:bookmark  p:pin"
+    } else {
+        "  m:bookmark  p/P:pin/unpin all  S:save  X:export"
+    };
+    let help = match app.search.mode {
+        SearchMode::Title => {
+            " Type to search by title, or filter with tag: author: score:>N accepted:yes\u{2026}  Enter:confirm  Esc:cancel"
+        }
         SearchMode::Semantic => " Type your question, Enter to search, Esc to cancel",
+        SearchMode::Hybrid => " Type words or a question, Enter to search, Esc to cancel",
         SearchMode::None => {
-            if app.semantic_loading {
-                " Generating embedding and searching..."
-            } else if app.fuzzy_matches.is_some() {
-                " j/k:move  Space/Ctrl-d/u:page  0:relevance  1-5:sort  /:title  Esc:clear  q:back"
-            } else if app.semantic_results.is_some() {
-                " j/k:move  Space/Ctrl-d/u:page  /:title  ?:semantic  Esc:clear  q:back"
+            if app.search.waiting_for_model.is_some() {
+                " Searching once the embedding model has loaded..."
+            } else if app.search.fuzzy_matches.is_some() {
+                " j/k:move  Space/Ctrl-d/u:page  0:relevance  1-6:sort  /:title  Esc:clear  q:back"
+            } else if app.search.semantic_results.is_some() || app.search.hybrid_results.is_some() {
+                " j/k:move  Space/Ctrl-d/u:page  /:title  ?:semantic  H:hybrid  Esc:clear  q:back"
+            } else if app.index.tag_filter.is_some() {
+                " j/k:move  Space/Ctrl-d/u:page  0:unsorted  1-6:sort  /:title  ?:semantic  H:hybrid  t/T:tags  Esc:clear tag"
+            } else if app.index.term_filter.is_some() {
+                " j/k:move  Space/Ctrl-d/u:page  0:unsorted  1-6:sort  /:title  ?:semantic  H:hybrid  F:terms  Esc:clear term"
+            } else if app.page == Page::Bookmarks {
+                " j/k:move  Space/Ctrl-d/u:page  0:unsorted  1-6:sort  /:title  ?:semantic  H:hybrid  t/T:tags  q:back"
             } else {
-                " j/k:move  Space/Ctrl-d/u:page  1-5:sort  /:title  ?:semantic  q:quit"
+                " j/k:move  Space/Ctrl-d/u:page  0:unsorted  1-6:sort  /:title  ?:semantic  H:hybrid  t/T:tags  F:terms  A:alerts  B:bookmarks  h:recent  q:quit"
             }
         }
     };
+    let help = match app.search.mode {
+        SearchMode::None if app.search.hybrid_results.is_some() => {
+            format!("{help}{}", hybrid_ranks(app))
+        }
+        SearchMode::None if app.search.waiting_for_model.is_none() => format!("{help}{pin_help}"),
+        _ => help.to_string(),
+    };
 
     let position = format!(
         "{}/{}",
-        app.selected_index + 1,
+        app.index.selected + 1,
         app.visible_questions_count()
     );
 
     let scroll_pct = if app.visible_questions_count() > 0 {
-        let pct = (app.selected_index * 100) / app.visible_questions_count().max(1);
+        let pct = (app.index.selected * 100) / app.visible_questions_count().max(1);
         format!(" {}%", pct)
     } else {
         String::new()
     };
 
-    let right_side = format!("{}{}", position, scroll_pct);
-    let help_width = (area.width as usize).saturating_sub(right_side.len());
+    let mut segments = app.plugins.status(&app.plugin_context());
+    segments.push(format!("{}{}", position, scroll_pct));
+    let right_side = format!("  {}", segments.join("  "));
+    let help_width = (area.width as usize).saturating_sub(right_side.width());
 
     let status = Line::from(vec![
         Span::styled(
-            format!("{:<width$}", help, width = help_width),
+            format!(
+                "{:<width$}",
+                fit_cell(&help, help_width),
+                width = help_width
+            ),
             styles::status_style(),
         ),
         Span::styled(right_side, styles::status_style()),
@@ -359,6 +737,7 @@ fn format_number(num: i32) -> String {
 }
 
 fn highlight_fuzzy_match(text: &str, indices: &[u32], base_style: Style) -> Vec<Span<'static>> {
+    let theme = styles::theme();
     let mut spans = Vec::new();
     let mut last_end = 0;
     let chars: Vec<char> = text.chars().collect();
@@ -375,7 +754,7 @@ fn highlight_fuzzy_match(text: &str, indices: &[u32], base_style: Style) -> Vec<
         spans.push(Span::styled(
             chars[idx].to_string(),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         ));
         last_end = idx + 1;
@@ -388,3 +767,30 @@ fn highlight_fuzzy_match(text: &str, indices: &[u32], base_style: Style) -> Vec<
 
     spans
 }
+
+/// One bar per answer (highest score first), scaled to the question's top answer.
+/// A single tall bar means one dominant answer; even bars mean a contested thread.
+fn answer_sparkline(scores: &[i32]) -> String {
+    const BARS: [char; 8] = [
+        '\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}',
+        '\u{2588}',
+    ];
+
+    let min = scores.iter().copied().min().unwrap_or(0).min(0);
+    let max = scores.iter().copied().max().unwrap_or(0);
+    let range = (max - min).max(1) as f64;
+
+    let mut line: String = scores
+        .iter()
+        .take(SPARKLINE_WIDTH)
+        .map(|&score| {
+            let level = ((score - min) as f64 / range * (BARS.len() - 1) as f64).round();
+            BARS[level as usize]
+        })
+        .collect();
+    if scores.len() > SPARKLINE_WIDTH {
+        line.pop();
+        line.push('\u{2026}');
+    }
+    line
+}
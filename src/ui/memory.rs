@@ -0,0 +1,78 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::styles;
+use crate::memory::{format_bytes, MemoryReport};
+
+/// `:memory` readout drawn on top of whichever page is active
+pub fn draw_memory(frame: &mut Frame, report: &MemoryReport) {
+    let theme = styles::theme();
+    let area = frame.area();
+    let row = |label: &str, bytes: String, detail: String| {
+        Line::from(vec![
+            Span::styled(format!("{label:<17}"), Style::default().fg(theme.text)),
+            Span::styled(
+                format!("{bytes:>9}"),
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  {detail}"), Style::default().fg(theme.muted)),
+        ])
+    };
+
+    let mut lines: Vec<Line> = report
+        .parts
+        .iter()
+        .map(|(label, bytes, detail)| row(label, format_bytes(*bytes), detail.clone()))
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(row(
+        "Budget",
+        format_bytes(report.budgeted),
+        format!("of {} (thread and bodies)", format_bytes(report.budget)),
+    ));
+    lines.push(row(
+        "Tracked",
+        format_bytes(report.tracked()),
+        String::new(),
+    ));
+    if let Some(resident) = report.resident {
+        lines.push(row(
+            "Process",
+            format_bytes(resident),
+            "resident".to_string(),
+        ));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Any key to close",
+        Style::default().fg(theme.dim),
+    )));
+
+    let modal_width = 64.min(area.width);
+    let modal_height = (lines.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(modal_width)) / 2;
+    let y = (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect::new(x, y, modal_width, modal_height);
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Memory ")
+        .title_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .padding(ratatui::widgets::Padding::horizontal(1));
+
+    frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+}
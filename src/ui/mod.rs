@@ -1,20 +1,127 @@
+mod alerts;
+mod confirm;
+mod errors;
+mod hidden;
 mod index;
+mod memory;
+mod playground;
+mod plugin;
+mod recent;
+mod review;
 mod show;
 pub mod styles;
+mod tags;
+mod techniques;
+mod terms;
+mod too_small;
 
-pub use show::DUAL_PANE_MIN_WIDTH;
+pub use show::{
+    DUAL_PANE_MIN_WIDTH, ERWIN_PANE_BORDER, OUTLINE_MIN_WIDTH, OUTLINE_WIDTH, QUESTION_PANE_PADDING,
+};
 
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState};
 use ratatui::Frame;
 
 use crate::app::{App, Page};
+use crate::db::QuestionStatus;
+
+/// Columns a scrollbar takes at the right edge of the list and of each pane
+pub const SCROLLBAR_WIDTH: u16 = 1;
+
+/// Smallest terminal the pages are drawn in; below it a notice stands in
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 15;
 
 pub fn draw(frame: &mut Frame, app: &mut App) {
     let size = frame.area();
     app.width = size.width;
     app.height = size.height;
+    app.hyperlinks.clear();
 
+    if app.too_small() {
+        too_small::draw_too_small(frame);
+        return;
+    }
     match app.page {
-        Page::Index => index::draw_index(frame, app),
+        Page::Index | Page::Bookmarks => index::draw_index(frame, app),
         Page::Show => show::draw_show(frame, app),
+        Page::Tags => tags::draw_tags(frame, app),
+        Page::Terms => terms::draw_terms(frame, app),
+        Page::Alerts => alerts::draw_alerts(frame, app),
+        Page::Playground => playground::draw_playground(frame, app),
+        Page::Review => review::draw_review(frame, app),
+        Page::Recent => recent::draw_recent(frame, app),
+        Page::Hidden => hidden::draw_hidden(frame, app),
+        Page::Techniques => techniques::draw_techniques(frame, app),
+        Page::Plugin => plugin::draw_plugin(frame, app),
+    }
+
+    if let Some(ref command) = app.command {
+        let line = Rect::new(size.x, size.bottom().saturating_sub(1), size.width, 1);
+        let prompt = Line::from(format!(" :{command}\u{2588}"));
+        frame.render_widget(Clear, line);
+        frame.render_widget(Paragraph::new(prompt).style(styles::status_style()), line);
+    }
+    if let Some(ref report) = app.memory_report {
+        memory::draw_memory(frame, report);
+    }
+    if let Some(ref mut details) = app.error_details {
+        errors::draw_error_details(frame, &app.errors, details);
+    }
+    if let Some(ref confirm) = app.confirm {
+        confirm::draw_confirm(frame, confirm);
     }
 }
+
+/// Split the scrollbar column off the right of `area`, if scrollbars are on
+fn split_scrollbar(area: Rect, scrollbars: bool) -> (Rect, Option<Rect>) {
+    if !scrollbars || area.width <= SCROLLBAR_WIDTH {
+        return (area, None);
+    }
+    let width = area.width - SCROLLBAR_WIDTH;
+    let bar = Rect::new(area.x + width, area.y, SCROLLBAR_WIDTH, area.height);
+    (Rect { width, ..area }, Some(bar))
+}
+
+/// A vertical scrollbar, drawn only when `viewport` rows can't show all
+/// `len`; `position` is the first row shown (or the selected one) out of
+/// `positions`
+fn draw_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    len: usize,
+    viewport: usize,
+    position: usize,
+    positions: usize,
+) {
+    if len <= viewport {
+        return;
+    }
+    // The whole column is track, so a row maps straight onto a position
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_symbol(Some("\u{2502}"))
+        .track_style(styles::scrollbar_track_style())
+        .thumb_style(styles::scrollbar_thumb_style());
+    let mut state = ScrollbarState::new(positions)
+        .position(position)
+        .viewport_content_length(viewport);
+    frame.render_stateful_widget(scrollbar, area, &mut state);
+}
+
+/// "[closed] [locked] " style badges, each followed by a space
+fn status_badges(status: QuestionStatus) -> Vec<Span<'static>> {
+    status
+        .badges()
+        .into_iter()
+        .map(|badge| {
+            Span::styled(
+                format!("[{}] ", badge.label()),
+                styles::status_badge_style(badge),
+            )
+        })
+        .collect()
+}
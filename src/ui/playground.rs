@@ -0,0 +1,184 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::show::{draw_content, draw_header, draw_outline, split_outline};
+use super::styles;
+use crate::app::App;
+use crate::highlight::highlight_code;
+use crate::playground::QueryResult;
+
+/// Widest a result column gets before its values are cut off
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// The question on top (as on the show page), the SQL buffer and its results below
+pub fn draw_playground(frame: &mut Frame, app: &mut App) {
+    let size = frame.area();
+    let can_split = app.show.can_split(size.width);
+    let split_pos = app.show.split_pos(size.width);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),      // Header
+            Constraint::Percentage(40), // Question
+            Constraint::Percentage(25), // SQL buffer
+            Constraint::Min(3),         // Results
+            Constraint::Length(1),      // Status bar
+        ])
+        .split(size);
+
+    let (header_area, outline_header) = split_outline(&app.show, chunks[0]);
+    let (content_area, outline_area) = split_outline(&app.show, chunks[1]);
+
+    draw_header(frame, app, header_area, can_split, split_pos);
+    draw_content(frame, app, content_area, can_split, split_pos);
+    if let (Some(header), Some(area)) = (outline_header, outline_area) {
+        draw_outline(frame, app, header, area);
+    }
+    draw_editor(frame, app, chunks[2]);
+    draw_results(frame, app, chunks[3]);
+    draw_status_bar(frame, app, chunks[4]);
+}
+
+fn draw_editor(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = styles::theme();
+    let playground = &mut app.playground;
+    let block = Block::default()
+        .title(" SQL ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+    let inner = block.inner(area);
+
+    // Keep the cursor line in view
+    let visible_rows = inner.height as usize;
+    if playground.cursor_row < playground.editor_scroll {
+        playground.editor_scroll = playground.cursor_row;
+    } else if visible_rows > 0 && playground.cursor_row >= playground.editor_scroll + visible_rows {
+        playground.editor_scroll = playground.cursor_row + 1 - visible_rows;
+    }
+
+    let mut lines = highlight_code(&playground.sql(), Some("sql"));
+    lines.resize(playground.lines.len(), Line::default());
+    let lines: Vec<Line> = lines
+        .into_iter()
+        .skip(playground.editor_scroll)
+        .take(visible_rows)
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+
+    let line = &playground.lines[playground.cursor_row];
+    let before: String = line.chars().take(playground.cursor_col).collect();
+    let x = (before.width() as u16).min(inner.width.saturating_sub(1));
+    let y = (playground.cursor_row - playground.editor_scroll) as u16;
+    frame.set_cursor_position(Position::new(inner.x + x, inner.y + y));
+}
+
+fn draw_results(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = styles::theme();
+    let playground = &mut app.playground;
+    let title = match (&playground.run, &playground.result) {
+        (Some(_), _) => " Results (running...) ".to_string(),
+        (None, Some(result)) if result.failed.is_some() => " Results (failed) ".to_string(),
+        (None, Some(result)) => format!(" Results ({} rows) ", result.rows.len()),
+        (None, None) => " Results ".to_string(),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.special));
+
+    let Some(ref result) = playground.result else {
+        let hint = Paragraph::new(Span::styled(
+            "Ctrl-R runs the buffer against the [playground] command",
+            styles::dim_style(),
+        ));
+        frame.render_widget(hint.block(block), area);
+        return;
+    };
+
+    let lines = result_lines(result);
+    let visible_rows = area.height.saturating_sub(2) as usize;
+    playground.result_scroll = playground
+        .result_scroll
+        .min(lines.len().saturating_sub(visible_rows));
+
+    let content = Paragraph::new(lines)
+        .block(block)
+        .scroll((playground.result_scroll as u16, 0));
+    frame.render_widget(content, area);
+}
+
+/// Messages from the command, then the rows as an aligned table
+fn result_lines(result: &QueryResult) -> Vec<Line<'static>> {
+    let theme = styles::theme();
+    let message_style = if result.failed.is_some() {
+        Style::default().fg(theme.error)
+    } else {
+        styles::dim_style()
+    };
+    let mut lines: Vec<Line> = result
+        .messages
+        .lines()
+        .map(|line| Line::from(Span::styled(line.to_string(), message_style)))
+        .collect();
+    if result.columns.is_empty() {
+        if result.failed.is_none() && lines.is_empty() {
+            lines.push(Line::from(Span::styled("(no rows)", styles::dim_style())));
+        }
+        return lines;
+    }
+
+    let cell = |value: &str| value.replace('\n', "\u{21b5}");
+    let mut widths: Vec<usize> = result.columns.iter().map(|c| cell(c).width()).collect();
+    for row in &result.rows {
+        for (i, value) in row.iter().enumerate() {
+            if let Some(width) = widths.get_mut(i) {
+                *width = (*width).max(cell(value).width()).min(MAX_COLUMN_WIDTH);
+            }
+        }
+    }
+    let format_row = |row: &[String]| -> String {
+        widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let value = cell(row.get(i).map(String::as_str).unwrap_or(""));
+                let value: String = value.chars().take(*width).collect();
+                let pad = width.saturating_sub(value.width());
+                format!("{value}{}", " ".repeat(pad))
+            })
+            .collect::<Vec<_>>()
+            .join(" \u{2502} ")
+    };
+
+    lines.push(Line::from(Span::styled(
+        format_row(&result.columns),
+        Style::default().add_modifier(Modifier::BOLD),
+    )));
+    let rule: Vec<String> = widths.iter().map(|w| "\u{2500}".repeat(*w)).collect();
+    lines.push(Line::from(Span::styled(
+        rule.join("\u{2500}\u{253c}\u{2500}"),
+        styles::separator_style(),
+    )));
+    lines.extend(result.rows.iter().map(|row| Line::from(format_row(row))));
+    lines
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let status = match app.flash {
+        Some(ref message) => {
+            Line::from(Span::styled(format!(" {}", message), styles::flash_style()))
+        }
+        None => Line::from(Span::styled(
+            " Ctrl-R/F5:run  Ctrl-L:clear  PgUp/PgDn:question  Ctrl-U/D:results  Esc:back",
+            styles::status_style(),
+        )),
+    };
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
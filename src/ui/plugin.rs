@@ -0,0 +1,98 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+
+pub fn draw_plugin(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(size);
+
+    let header = format!(" {} ", app.plugin_page.title);
+    frame.render_widget(
+        Paragraph::new(Line::from(header)).style(styles::header_style()),
+        chunks[0],
+    );
+    draw_lines(frame, app, chunks[1]);
+    draw_status_bar(frame, app, chunks[2]);
+}
+
+fn draw_lines(frame: &mut Frame, app: &App, area: Rect) {
+    let page = &app.plugin_page;
+    if page.lines.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled("  Nothing here.", styles::dim_style())),
+        ]);
+        frame.render_widget(hint, area);
+        return;
+    }
+
+    let lines: Vec<Line> = page
+        .lines
+        .iter()
+        .enumerate()
+        .skip(page.scroll)
+        .take(area.height as usize)
+        .map(|(idx, line)| {
+            let is_selected = idx == page.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(selector, selector_style),
+                Span::raw(line.text.clone()),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    if let Some(ref message) = app.flash {
+        let status = Line::from(Span::styled(format!(" {}", message), styles::flash_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    let help = if app.plugin_page.name.is_some() {
+        " j/k:move  g/G:first/last  Enter:open  r:refresh  q:back"
+    } else {
+        " j/k:move  g/G:first/last  q:back"
+    };
+
+    let total = app.plugin_page.lines.len();
+    let position = if total > 0 {
+        format!("{}/{}", app.plugin_page.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
@@ -0,0 +1,125 @@
+use chrono::{TimeZone, Utc};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+use crate::clock;
+
+pub fn draw_recent(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(size);
+
+    let header = format!(" Recently viewed ({}) ", app.recent.views.len());
+    frame.render_widget(
+        Paragraph::new(Line::from(header)).style(styles::header_style()),
+        chunks[0],
+    );
+    draw_recent_list(frame, app, chunks[1]);
+    draw_status_bar(frame, app, chunks[2]);
+}
+
+fn draw_recent_list(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    if app.recent.views.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "  Nothing yet. Questions you open show up here, latest first.",
+                styles::dim_style(),
+            )),
+        ]);
+        frame.render_widget(hint, area);
+        return;
+    }
+
+    let offset = *clock::now().offset();
+    let lines: Vec<Line> = app
+        .recent
+        .views
+        .iter()
+        .enumerate()
+        .skip(app.recent.scroll)
+        .take(area.height as usize)
+        .map(|(idx, view)| {
+            let is_selected = idx == app.recent.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+
+            let viewed = Utc
+                .timestamp_opt(view.last_viewed, 0)
+                .single()
+                .map(|dt| {
+                    dt.with_timezone(&offset)
+                        .format("%b %d, %Y %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let title = app
+                .questions
+                .iter()
+                .find(|q| q.id == view.question_id && q.site == view.site)
+                .map_or_else(
+                    || format!("#{} (not in this corpus)", view.question_id),
+                    |q| q.title.clone(),
+                );
+
+            Line::from(vec![
+                Span::styled(selector, selector_style),
+                Span::styled(format!("{viewed:<18} "), styles::dim_style()),
+                Span::styled(
+                    format!("{:>3}\u{00d7} ", view.views),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::raw(title),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    if let Some(ref message) = app.flash {
+        let status = Line::from(Span::styled(format!(" {}", message), styles::flash_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    let help = " j/k:move  g/G:first/last  Enter:open  q:back";
+
+    let total = app.recent.views.len();
+    let position = if total > 0 {
+        format!("{}/{}", app.recent.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
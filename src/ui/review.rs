@@ -0,0 +1,135 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+use crate::clock;
+
+pub fn draw_review(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(size);
+
+    let header = format!(
+        " Engaged questions ({}, {} due for review) ",
+        app.review.questions.len(),
+        app.review.due
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(header)).style(styles::header_style()),
+        chunks[0],
+    );
+    draw_review_list(frame, app, chunks[1]);
+    draw_status_bar(frame, app, chunks[2]);
+}
+
+fn draw_review_list(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    if app.review.questions.is_empty() {
+        let hint = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "  Nothing yet. Questions you read for {} minutes or more show up here,",
+                    app.config.engaged.minutes
+                ),
+                styles::dim_style(),
+            )),
+            Line::from(Span::styled(
+                "  due for review a day later, then at growing intervals.",
+                styles::dim_style(),
+            )),
+        ]);
+        frame.render_widget(hint, area);
+        return;
+    }
+
+    let now = clock::now().timestamp();
+    let lines: Vec<Line> = app
+        .review
+        .questions
+        .iter()
+        .enumerate()
+        .skip(app.review.scroll)
+        .take(area.height as usize)
+        .map(|(idx, engaged)| {
+            let is_selected = idx == app.review.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+
+            let due_in_days = (engaged.due_at() - now).div_euclid(86_400);
+            let (due, due_style) = if due_in_days < 0 {
+                ("due".to_string(), Style::default().fg(theme.highlight))
+            } else {
+                (format!("in {}d", due_in_days + 1), styles::dim_style())
+            };
+            let title = app
+                .questions
+                .iter()
+                .find(|q| q.id == engaged.question_id && q.site == engaged.site)
+                .map_or_else(
+                    || format!("#{} (not in this corpus)", engaged.question_id),
+                    |q| q.title.clone(),
+                );
+
+            let mut title_style = Style::default();
+            if due_in_days < 0 {
+                title_style = title_style.add_modifier(Modifier::BOLD);
+            }
+
+            Line::from(vec![
+                Span::styled(selector, selector_style),
+                Span::styled(format!("{due:<6} "), due_style),
+                Span::styled(
+                    format!("{:>4}m ", engaged.seconds / 60),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::styled(
+                    format!("{:>2}\u{00d7} ", engaged.reviews),
+                    styles::dim_style(),
+                ),
+                Span::styled(title, title_style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let help = " j/k:move  Enter:open (counts as a review)  q:back";
+
+    let total = app.review.questions.len();
+    let position = if total > 0 {
+        format!("{}/{}", app.review.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
@@ -1,44 +1,232 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use super::styles;
-use crate::app::App;
-use crate::html::Link;
+use super::{draw_scrollbar, split_scrollbar, status_badges, styles};
+use crate::app::{AnswerSort, App, Pane, Presentation, ShowState};
+use crate::content::wrap_plain_text;
+use crate::html::{decode_html_entities, fit_cell, is_erwin, Link};
+use crate::hyperlink::Hyperlink;
 
-/// Minimum terminal width required for dual-pane (side-by-side) mode
+/// Minimum terminal width required for dual-pane (side-by-side) mode, unless
+/// `[layout] dual_pane_width` says otherwise
 pub const DUAL_PANE_MIN_WIDTH: u16 = 160;
 
+/// Columns before the text starts: the question pane's left padding and the
+/// Erwin pane's left border
+pub const QUESTION_PANE_PADDING: u16 = 1;
+pub const ERWIN_PANE_BORDER: u16 = 1;
+
+/// Minimum terminal width for the thread outline sidebar, and its width
+/// (border included)
+pub const OUTLINE_MIN_WIDTH: u16 = 200;
+pub const OUTLINE_WIDTH: u16 = 34;
+
+/// Width of a presentation's progress bar
+const PROGRESS_WIDTH: usize = 20;
+/// Earlier questions named in the header's breadcrumbs
+const BREADCRUMBS: usize = 3;
+
 pub fn draw_show(frame: &mut Frame, app: &mut App) {
     let size = frame.area();
-    let can_split = size.width >= DUAL_PANE_MIN_WIDTH;
+    let can_split = app.show.can_split(size.width);
 
+    let (header_rows, status_rows) = app.show.chrome_rows();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Header
-            Constraint::Min(1),    // Content
-            Constraint::Length(1), // Status bar
+            Constraint::Length(header_rows as u16), // Header
+            Constraint::Min(1),                     // Content
+            Constraint::Length(status_rows as u16), // Status bar
         ])
         .split(size);
 
-    // Split position for dual-pane mode (simple half of screen width)
-    let split_pos = size.width / 2;
+    // Split position for dual-pane mode (configurable share of the width)
+    let split_pos = app.show.split_pos(size.width);
+    let (header_area, outline_header) = split_outline(&app.show, chunks[0]);
+    let (content_area, outline_area) = split_outline(&app.show, chunks[1]);
+
+    if header_rows > 0 {
+        draw_header(frame, app, header_area, can_split, split_pos);
+    }
+    draw_content(frame, app, content_area, can_split, split_pos);
+    if let (Some(header), Some(area)) = (outline_header, outline_area) {
+        draw_outline(frame, app, header, area);
+    }
+    draw_glossary_popup(frame, app, content_area, can_split, split_pos);
+    draw_translation(frame, app, content_area, can_split, split_pos);
+    if app.show.focus {
+        return;
+    }
+    match app.show.presentation {
+        Some(ref presentation) => draw_presentation_bar(frame, app, presentation, chunks[2]),
+        None => draw_status_bar(frame, app, chunks[2], can_split),
+    }
+}
+
+/// A presentation's bottom row: the collection, how far through it the
+/// question showing is, and the keys that step
+fn draw_presentation_bar(frame: &mut Frame, app: &App, presentation: &Presentation, area: Rect) {
+    let count = presentation.question_ids.len();
+    let position = presentation.index + 1;
+    let filled = position * PROGRESS_WIDTH / count;
+    let left = format!(" \u{25b6} {}  ", presentation.name);
+    let progress = format!("  {position}/{count}  ");
+    let hint = match app.flash {
+        Some(ref message) => Span::styled(format!("  {message} "), styles::flash_style()),
+        None => Span::styled("  \u{2190}/\u{2192}:step  q:end ", styles::status_style()),
+    };
+    let title = app
+        .show
+        .question
+        .as_ref()
+        .map(|q| decode_html_entities(&q.summary.title))
+        .unwrap_or_default();
+    let title_width = (area.width as usize)
+        .saturating_sub(left.width() + PROGRESS_WIDTH + progress.width() + hint.width());
+    let bar = Line::from(vec![
+        Span::styled(left, styles::status_style().add_modifier(Modifier::BOLD)),
+        Span::styled("\u{2588}".repeat(filled), styles::status_style()),
+        Span::styled(
+            "\u{2591}".repeat(PROGRESS_WIDTH - filled),
+            styles::status_style().add_modifier(Modifier::DIM),
+        ),
+        Span::styled(progress, styles::status_style()),
+        Span::styled(
+            format!(
+                "{:<width$}",
+                fit_cell(&title, title_width),
+                width = title_width
+            ),
+            styles::status_style(),
+        ),
+        hint,
+    ]);
+    frame.render_widget(Paragraph::new(bar).style(styles::status_style()), area);
+}
+
+/// Cut the outline sidebar off the right of a row, on terminals wide enough
+pub(super) fn split_outline(show: &ShowState, area: Rect) -> (Rect, Option<Rect>) {
+    if !show.has_outline(area.width) {
+        return (area, None);
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(1), Constraint::Length(OUTLINE_WIDTH)])
+        .split(area);
+    (chunks[0], Some(chunks[1]))
+}
+
+/// Thread outline: the question and each answer with its author, score and
+/// comment count. The section in view is marked; while the outline has focus
+/// (`O`) the selection is highlighted instead.
+pub(super) fn draw_outline(frame: &mut Frame, app: &App, header: Rect, area: Rect) {
+    let theme = styles::theme();
+    let show = &app.show;
+    let header_style = if show.outline_selected.is_some() {
+        Style::default()
+            .bg(theme.accent)
+            .fg(theme.on_color)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        styles::header_style()
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(" Outline")).style(header_style),
+        header,
+    );
+
+    let block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(styles::separator_style());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let question = show.question.as_ref();
+    let mut sections = vec![(
+        "Q",
+        question.map_or("Question", |q| q.summary.author_name.as_str()),
+        question.map_or(0, |q| q.summary.score),
+        show.comments.len(),
+        styles::question_header_style(),
+    )];
+    for (answer, comments) in show.answers.iter().zip(&show.answer_comments) {
+        let (mark, style) = if is_erwin(&answer.author_name) {
+            ("\u{25c6}", styles::erwin_accent_style())
+        } else if answer.is_accepted {
+            ("\u{2713}", styles::answer_header_style())
+        } else {
+            ("\u{00b7}", Style::default())
+        };
+        sections.push((
+            mark,
+            answer.author_name.as_str(),
+            answer.score,
+            comments.len(),
+            style,
+        ));
+    }
+
+    let current = show.current_section();
+    let height = inner.height as usize;
+    let lines: Vec<Line> = sections
+        .iter()
+        .enumerate()
+        .skip(show.outline_scroll(height))
+        .take(height)
+        .map(|(idx, &(mark, author, score, comments, style))| {
+            let pointer = if idx == current { "\u{25b6}" } else { " " };
+            let comments = if comments > 0 {
+                format!("{comments:>3}c")
+            } else {
+                "    ".to_string()
+            };
+            // Pointer, mark and the score/comment columns take 14 cells
+            let name_width = (inner.width as usize).saturating_sub(14);
+            let mut name = String::new();
+            for c in author.chars() {
+                if name.width() + c.width().unwrap_or(0) > name_width {
+                    name.pop();
+                    name.push('\u{2026}');
+                    break;
+                }
+                name.push(c);
+            }
 
-    draw_header(frame, app, chunks[0], can_split, split_pos);
-    draw_content(frame, app, chunks[1], can_split, split_pos);
-    draw_status_bar(frame, app, chunks[2], can_split);
+            let line = Line::from(vec![
+                Span::raw(format!("{pointer}{mark} ")),
+                Span::styled(format!("{name:<name_width$}"), style),
+                Span::raw(format!(" {score:>+5}")),
+                Span::styled(comments, styles::comment_style()),
+            ]);
+            if show.outline_selected == Some(idx) {
+                line.style(styles::selected_style())
+            } else if idx == current {
+                line.style(styles::outline_current_style())
+            } else {
+                line
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
 }
 
-fn draw_header(frame: &mut Frame, app: &App, area: Rect, can_split: bool, split_pos: u16) {
-    let attribution = "SO \u{00b7} CC BY-SA";
+pub(super) fn draw_header(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    can_split: bool,
+    split_pos: u16,
+) {
+    let theme = styles::theme();
+    let attribution = "CC BY-SA";
 
-    if app.erwin_pane_visible && can_split {
+    if app.show.erwin_pane_visible && can_split {
         // Split header into two areas using Layout (matches content split)
         let header_chunks = Layout::default()
             .direction(Direction::Horizontal)
@@ -49,42 +237,45 @@ fn draw_header(frame: &mut Frame, app: &App, area: Rect, can_split: bool, split_
             ])
             .split(area);
 
-        let erwin_count = app.erwin_answer_count();
+        let erwin_count = app.show.erwin_answer_count();
 
-        let left_title = format!(" Question #{} ", app.current_question_id);
+        let left_title = question_title(app);
         let right_title = format!(
             "\u{25c6} Erwin's Answer {}/{} ",
-            app.erwin_answer_index + 1,
+            app.show.erwin_answer_index + 1,
             erwin_count
         );
 
-        let left_style = if app.left_pane_focused {
+        let left_style = if app.show.left_pane_focused {
             Style::default()
-                .bg(Color::Cyan)
-                .fg(Color::Black)
+                .bg(theme.accent)
+                .fg(theme.on_color)
                 .add_modifier(Modifier::BOLD)
         } else {
             styles::header_style()
         };
 
-        let right_style = if !app.left_pane_focused {
+        let right_style = if !app.show.left_pane_focused {
             Style::default()
-                .bg(Color::Yellow)
-                .fg(Color::Black)
+                .bg(theme.highlight)
+                .fg(theme.on_color)
                 .add_modifier(Modifier::BOLD)
         } else {
             styles::header_style()
         };
 
         // Render left header with background filling entire area
-        let left_header = Paragraph::new(Line::from(left_title)).style(left_style);
+        let mut left_spans = vec![Span::raw(left_title)];
+        left_spans.extend(header_badges(app));
+        left_spans.push(Span::raw(reading_summary(app)));
+        let left_header = Paragraph::new(Line::from(left_spans)).style(left_style);
         frame.render_widget(left_header, header_chunks[0]);
 
         // Render half-block transition character
         // ▐ (right half block): left half shows bg color, right half shows fg color
         let transition_style = Style::default()
-            .fg(right_style.bg.unwrap_or(Color::Yellow))
-            .bg(left_style.bg.unwrap_or(Color::Cyan));
+            .fg(right_style.bg.unwrap_or(theme.highlight))
+            .bg(left_style.bg.unwrap_or(theme.accent));
         let transition = Paragraph::new(Line::from("\u{2590}")).style(transition_style);
         frame.render_widget(transition, header_chunks[1]);
 
@@ -102,25 +293,114 @@ fn draw_header(frame: &mut Frame, app: &App, area: Rect, can_split: bool, split_
         .style(right_style);
         frame.render_widget(right_header, header_chunks[2]);
     } else {
-        let title = format!(" Question #{} ", app.current_question_id);
+        let title = question_title(app);
+        let badges = header_badges(app);
+        let badges_width: usize = badges.iter().map(|s| s.width()).sum();
+        let reading = reading_summary(app);
         let padding = (area.width as usize)
             .saturating_sub(title.width())
+            .saturating_sub(badges_width)
+            .saturating_sub(reading.width())
             .saturating_sub(attribution.width());
 
-        let header = Paragraph::new(Line::from(format!(
-            "{}{}{}",
-            title,
-            " ".repeat(padding),
-            attribution
-        )))
-        .style(styles::header_style());
+        let mut spans = vec![Span::raw(title)];
+        spans.extend(badges);
+        spans.push(Span::raw(reading));
+        spans.push(Span::raw(format!("{}{}", " ".repeat(padding), attribution)));
+        let header = Paragraph::new(Line::from(spans)).style(styles::header_style());
 
         frame.render_widget(header, area);
     }
 }
 
-fn draw_content(frame: &mut Frame, app: &mut App, area: Rect, can_split: bool, split_pos: u16) {
-    if app.erwin_pane_visible && can_split {
+/// Header title with the site badge and the trail that led here, e.g.
+/// " DBA · #12 › #345 › Question #1234 › +1 ", the last part counting the
+/// questions Ctrl-n goes forward to
+fn question_title(app: &App) -> String {
+    let site = app
+        .show
+        .question
+        .as_ref()
+        .map(|q| q.summary.site)
+        .unwrap_or_default();
+    let skipped = app.history.len().saturating_sub(BREADCRUMBS);
+    let mut trail = if skipped > 0 {
+        "\u{2026} \u{203a} ".to_string()
+    } else {
+        String::new()
+    };
+    for entry in &app.history[skipped..] {
+        trail.push_str(&format!("#{} \u{203a} ", entry.question_id));
+    }
+    let ahead = match app.forward_history.len() {
+        0 => String::new(),
+        n => format!(" \u{203a} +{n}"),
+    };
+    format!(
+        " {} \u{00b7} {}Question #{}{} ",
+        site.badge(),
+        trail,
+        app.show.question_id,
+        ahead
+    )
+}
+
+/// Size of the thread, e.g. "~14 min read · 3 answers · 2,400 words "
+fn reading_summary(app: &App) -> String {
+    let Some(ref question) = app.show.question else {
+        return String::new();
+    };
+    let answers = match app.show.answers.len() {
+        1 => "1 answer".to_string(),
+        n => format!("{n} answers"),
+    };
+    format!(
+        "~{} min read \u{00b7} {} \u{00b7} {} words ",
+        question.summary.reading_minutes(),
+        answers,
+        group_thousands(question.summary.word_count)
+    )
+}
+
+/// `2400` -> `"2,400"`
+fn group_thousands(n: i32) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// Bookmark star, fetched marker and status badges of the open question
+fn header_badges(app: &App) -> Vec<Span<'static>> {
+    let mut badges = Vec::new();
+    if app.show.fetched.is_some() {
+        badges.push(Span::styled("[fetched] ", styles::dim_style()));
+    }
+    if app
+        .bookmarks
+        .contains(&(app.show.site(), app.show.question_id))
+    {
+        badges.push(Span::styled("\u{2605} ", styles::bookmark_style()));
+    }
+    if let Some(ref q) = app.show.question {
+        badges.extend(status_badges(q.summary.status));
+    }
+    badges
+}
+
+pub(super) fn draw_content(
+    frame: &mut Frame,
+    app: &mut App,
+    area: Rect,
+    can_split: bool,
+    split_pos: u16,
+) {
+    if app.show.erwin_pane_visible && can_split {
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Length(split_pos), Constraint::Min(1)])
@@ -141,9 +421,6 @@ fn build_visible_lines_with_highlights(
     focused_link: Option<&Link>,
     hovered_link: Option<&Link>,
 ) -> Vec<Line<'static>> {
-    let focused = focused_link.map(|link| (link.line_index, link.link_num));
-    let hovered = hovered_link.map(|link| (link.line_index, link.link_num));
-
     lines
         .iter()
         .enumerate()
@@ -151,210 +428,617 @@ fn build_visible_lines_with_highlights(
         .take(visible_rows)
         .map(|(idx, line)| {
             // Focused takes priority over hovered
-            if let Some((line_idx, link_num)) = focused {
-                if idx == line_idx {
-                    return highlight_link_in_line(line, link_num);
-                }
-            }
-            if let Some((line_idx, link_num)) = hovered {
-                if idx == line_idx {
-                    return highlight_link_in_line(line, link_num);
-                }
+            let hitbox = [focused_link, hovered_link]
+                .into_iter()
+                .flatten()
+                .find_map(|link| link.hitboxes.iter().find(|hitbox| hitbox.line_index == idx));
+            match hitbox {
+                Some(hitbox) => highlight_columns(line, hitbox.start_col, hitbox.end_col),
+                None => line.clone(),
             }
-            line.clone()
         })
         .collect()
 }
 
+/// Where a pane's links are on screen, one `Hyperlink` per row they cover;
+/// `x` is the pane's first text column
+fn visible_hyperlinks(
+    links: &[Link],
+    lines: &[Line<'static>],
+    scroll_offset: usize,
+    x: u16,
+    area: Rect,
+) -> Vec<Hyperlink> {
+    let rows = scroll_offset..scroll_offset + area.height as usize;
+    links
+        .iter()
+        .flat_map(|link| link.hitboxes.iter().map(move |hitbox| (link, hitbox)))
+        .filter(|(_, hitbox)| rows.contains(&hitbox.line_index))
+        .filter_map(|(link, hitbox)| {
+            let line = lines.get(hitbox.line_index)?;
+            Some(Hyperlink {
+                x: x + hitbox.start_col as u16,
+                y: area.y + (hitbox.line_index - scroll_offset) as u16,
+                text: columns_text(line, hitbox.start_col, hitbox.end_col),
+                url: link.url.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Link-hint labels over the first visible cell of each of the pane's links
+/// still matching, the letters typed so far dimmed; `x` is the pane's first
+/// text column
+fn draw_link_hints(
+    frame: &mut Frame,
+    show: &ShowState,
+    pane: Pane,
+    scroll_offset: usize,
+    x: u16,
+    area: Rect,
+) {
+    let Some(ref hints) = show.link_hints else {
+        return;
+    };
+    let links = match pane {
+        Pane::Question => &show.content_links,
+        Pane::Erwin => &show.erwin_links,
+    };
+    let rows = scroll_offset..scroll_offset + area.height as usize;
+    let typed = hints.typed.len();
+    for hint in hints.matching().filter(|hint| hint.pane == pane) {
+        let Some(hitbox) = links.get(hint.link_index).and_then(|link| {
+            link.hitboxes
+                .iter()
+                .find(|hitbox| rows.contains(&hitbox.line_index))
+        }) else {
+            continue;
+        };
+        let label_x = x + hitbox.start_col as u16;
+        if label_x >= area.right() {
+            continue;
+        }
+        let y = area.y + (hitbox.line_index - scroll_offset) as u16;
+        let width = (hint.label.len() as u16).min(area.right() - label_x);
+        let (done, rest) = hint.label.split_at(typed.min(hint.label.len()));
+        let label = Line::from(vec![
+            Span::styled(done.to_string(), styles::link_hint_typed_style()),
+            Span::styled(rest.to_string(), styles::link_hint_style()),
+        ]);
+        frame.render_widget(Paragraph::new(label), Rect::new(label_x, y, width, 1));
+    }
+}
+
+/// The text of a line between two display columns
+fn columns_text(line: &Line, start_col: usize, end_col: usize) -> String {
+    let mut col = 0;
+    let mut text = String::new();
+    for c in line.spans.iter().flat_map(|span| span.content.chars()) {
+        if col >= start_col && col < end_col {
+            text.push(c);
+        }
+        col += c.width().unwrap_or(0);
+    }
+    text
+}
+
 fn draw_question_pane(frame: &mut Frame, app: &mut App, area: Rect) {
+    let (area, scrollbar) = split_scrollbar(area, app.show.has_scrollbars());
     let visible_rows = area.height as usize;
-    let lines = &app.rendered_content;
+    let lines = &app.show.rendered_content;
 
     // Clamp scroll offset
     let max_scroll = lines.len().saturating_sub(visible_rows);
-    app.scroll_offset = app.scroll_offset.min(max_scroll);
+    app.show.scroll_offset = app.show.scroll_offset.min(max_scroll);
 
     // Get focused link info if in left pane
-    let focused_link = if app.left_pane_focused || !app.erwin_pane_visible {
-        app.focused_link_index
-            .and_then(|idx| app.content_links.get(idx))
+    let focused_link = if app.show.left_pane_focused || !app.show.erwin_pane_visible {
+        app.show
+            .focused_link_index
+            .and_then(|idx| app.show.content_links.get(idx))
     } else {
         None
     };
 
     let hovered_link = app
+        .show
         .hovered_link_index
-        .and_then(|idx| app.content_links.get(idx));
+        .and_then(|idx| app.show.content_links.get(idx));
 
     let visible_lines = build_visible_lines_with_highlights(
         lines,
-        app.scroll_offset,
+        app.show.scroll_offset,
         visible_rows,
         focused_link,
         hovered_link,
     );
 
-    let content = Paragraph::new(visible_lines)
-        .block(
-            Block::default()
-                .borders(Borders::NONE)
-                .padding(ratatui::widgets::Padding::left(1)),
-        )
-        .wrap(Wrap { trim: false });
+    if app.osc8 {
+        let x = area.x + QUESTION_PANE_PADDING;
+        let scroll_offset = app.show.scroll_offset;
+        let links = visible_hyperlinks(&app.show.content_links, lines, scroll_offset, x, area);
+        app.hyperlinks.extend(links);
+    }
+
+    let content = Paragraph::new(visible_lines).block(
+        Block::default()
+            .borders(Borders::NONE)
+            .padding(ratatui::widgets::Padding::left(QUESTION_PANE_PADDING)),
+    );
 
     frame.render_widget(content, area);
+    let x = area.x + QUESTION_PANE_PADDING;
+    draw_link_hints(
+        frame,
+        &app.show,
+        Pane::Question,
+        app.show.scroll_offset,
+        x,
+        area,
+    );
+    if let Some(bar) = scrollbar {
+        let offset = app.show.scroll_offset;
+        draw_scrollbar(
+            frame,
+            bar,
+            lines.len(),
+            visible_rows,
+            offset,
+            max_scroll + 1,
+        );
+    }
 }
 
 fn draw_erwin_pane(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = styles::theme();
+    let (area, scrollbar) = split_scrollbar(area, app.show.has_scrollbars());
     let visible_rows = area.height as usize;
-    let lines = &app.rendered_erwin_content;
+    let lines = &app.show.rendered_erwin_content;
 
     // Clamp scroll offset
     let max_scroll = lines.len().saturating_sub(visible_rows);
-    app.erwin_scroll_offset = app.erwin_scroll_offset.min(max_scroll);
+    app.show.erwin_scroll_offset = app.show.erwin_scroll_offset.min(max_scroll);
 
     // Get focused link info if in erwin pane
-    let focused_link = if !app.left_pane_focused && app.erwin_pane_visible {
-        app.focused_link_index
-            .and_then(|idx| app.erwin_links.get(idx))
+    let focused_link = if !app.show.left_pane_focused && app.show.erwin_pane_visible {
+        app.show
+            .focused_erwin_link_index
+            .and_then(|idx| app.show.erwin_links.get(idx))
     } else {
         None
     };
 
     let hovered_link = app
+        .show
         .hovered_erwin_link_index
-        .and_then(|idx| app.erwin_links.get(idx));
+        .and_then(|idx| app.show.erwin_links.get(idx));
 
     let visible_lines = build_visible_lines_with_highlights(
         lines,
-        app.erwin_scroll_offset,
+        app.show.erwin_scroll_offset,
         visible_rows,
         focused_link,
         hovered_link,
     );
 
-    let content = Paragraph::new(visible_lines)
-        .block(
-            Block::default()
-                .borders(Borders::LEFT)
-                .border_style(Style::default().fg(Color::Yellow)),
-        )
-        .wrap(Wrap { trim: false });
+    if app.osc8 {
+        let x = area.x + ERWIN_PANE_BORDER;
+        let scroll_offset = app.show.erwin_scroll_offset;
+        let links = visible_hyperlinks(&app.show.erwin_links, lines, scroll_offset, x, area);
+        app.hyperlinks.extend(links);
+    }
+
+    let content = Paragraph::new(visible_lines).block(
+        Block::default()
+            .borders(Borders::LEFT)
+            .border_style(Style::default().fg(theme.highlight)),
+    );
 
     frame.render_widget(content, area);
+    let x = area.x + ERWIN_PANE_BORDER;
+    draw_link_hints(
+        frame,
+        &app.show,
+        Pane::Erwin,
+        app.show.erwin_scroll_offset,
+        x,
+        area,
+    );
+    if let Some(bar) = scrollbar {
+        let offset = app.show.erwin_scroll_offset;
+        draw_scrollbar(
+            frame,
+            bar,
+            lines.len(),
+            visible_rows,
+            offset,
+            max_scroll + 1,
+        );
+    }
 }
 
-/// Highlight the specific link reference [text][n] in a line
-fn highlight_link_in_line(line: &Line, link_num: usize) -> Line<'static> {
-    let link_ref = format!("[{}]", link_num);
-    let mut new_spans: Vec<Span<'static>> = Vec::new();
-    let mut found_link_text = false;
+/// Definition of the focused glossary term, on the row below it (above it on
+/// the last row), with the term itself highlighted
+fn draw_glossary_popup(frame: &mut Frame, app: &App, area: Rect, can_split: bool, split_pos: u16) {
+    let Some((term, definition)) = app.show.get_focused_term() else {
+        return;
+    };
+    let (pane, scroll) = if app.show.erwin_focused() && can_split {
+        let x = area.x + split_pos + ERWIN_PANE_BORDER;
+        let pane = Rect::new(x, area.y, area.right().saturating_sub(x), area.height);
+        (pane, app.show.erwin_scroll_offset)
+    } else {
+        let width = if app.show.erwin_pane_visible && can_split {
+            split_pos
+        } else {
+            area.width
+        };
+        let pane = Rect::new(
+            area.x + QUESTION_PANE_PADDING,
+            area.y,
+            width.saturating_sub(QUESTION_PANE_PADDING),
+            area.height,
+        );
+        (pane, app.show.scroll_offset)
+    };
+    let Some(row) = term
+        .line_index
+        .checked_sub(scroll)
+        .filter(|&row| row < pane.height as usize)
+    else {
+        return;
+    };
+    let row = row as u16;
 
-    for span in &line.spans {
-        let content = span.content.as_ref();
-
-        // Check if this span ends with [ and next might be link text
-        if content.starts_with('[') && content.ends_with(']') && !content.contains(&link_ref) {
-            // This might be the [text] part - check if styled as link (cyan)
-            if span.style.fg == Some(Color::Cyan) {
-                // Mark that we found link text, highlight it
-                new_spans.push(Span::styled(
-                    content.to_string(),
-                    Style::default().bg(Color::Cyan).fg(Color::Black),
-                ));
-                found_link_text = true;
-                continue;
+    let term_x = pane.x + (term.start_col as u16).min(pane.width.saturating_sub(1));
+    let term_area = Rect::new(term_x, pane.y + row, pane.right() - term_x, 1);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            term.term.clone(),
+            styles::focused_link_style(),
+        )),
+        term_area,
+    );
+
+    let text = format!(" {} \u{2014} {} ", term.term, definition);
+    let width = (text.width() as u16).min(pane.width);
+    let text = if (text.width() as u16) > width {
+        let mut cut = String::new();
+        for c in text.chars() {
+            if cut.width() + c.width().unwrap_or(0) + 2 > width as usize {
+                break;
             }
+            cut.push(c);
         }
+        format!("{cut}\u{2026} ")
+    } else {
+        text
+    };
+    let popup_row = if row + 1 < pane.height {
+        row + 1
+    } else {
+        row.saturating_sub(1)
+    };
+    // Start under the term, shifted left as far as needed to fit in the pane
+    let x = term_x.min(pane.right() - width);
+    let popup = Rect::new(x, pane.y + popup_row, width, 1);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(Span::styled(text, styles::glossary_popup_style())),
+        popup,
+    );
+}
 
-        // Check if this is the [n] reference number
-        if content == link_ref {
-            new_spans.push(Span::styled(
-                content.to_string(),
-                Style::default().bg(Color::Cyan).fg(Color::Black),
-            ));
-            found_link_text = false; // Reset for next link
-            continue;
-        }
+/// Translation pane, drawn over the half opposite the focused post (or the
+/// whole content area on narrow terminals)
+fn draw_translation(frame: &mut Frame, app: &mut App, area: Rect, can_split: bool, split_pos: u16) {
+    let theme = styles::theme();
+    let erwin_focused = app.show.erwin_focused();
+    let Some(ref mut translation) = app.show.translation else {
+        return;
+    };
 
-        // If we just found link text and this is the matching [n], highlight it
-        if found_link_text && content == link_ref {
-            new_spans.push(Span::styled(
-                content.to_string(),
-                Style::default().bg(Color::Cyan).fg(Color::Black),
-            ));
-            found_link_text = false;
-            continue;
+    let area = if !can_split {
+        area
+    } else if erwin_focused {
+        Rect {
+            width: split_pos,
+            ..area
         }
+    } else {
+        Rect {
+            x: area.x + split_pos,
+            width: area.width - split_pos,
+            ..area
+        }
+    };
+    frame.render_widget(Clear, area);
 
-        // Keep span as-is
-        new_spans.push(Span::styled(content.to_string(), span.style));
-        if !content.starts_with('[') {
-            found_link_text = false;
+    let block = Block::default()
+        .title(" Translation ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.special))
+        .padding(ratatui::widgets::Padding::horizontal(1));
+
+    if translation.translator.is_some() {
+        let pending = Paragraph::new(Span::styled("Translating...", styles::dim_style()));
+        frame.render_widget(pending.block(block), area);
+        return;
+    }
+
+    // Wrapped up front, as the panes are, so scrolling stops at the last row
+    let lines = wrap_plain_text(&translation.text, block.inner(area).width as usize);
+    let visible_rows = area.height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(visible_rows);
+    translation.scroll = translation.scroll.min(max_scroll);
+
+    let content = Paragraph::new(lines)
+        .block(block)
+        .scroll((translation.scroll as u16, 0));
+    frame.render_widget(content, area);
+}
+
+/// Highlight the cells of a line between two columns (end exclusive)
+fn highlight_columns(line: &Line, start_col: usize, end_col: usize) -> Line<'static> {
+    let theme = styles::theme();
+    let highlight = Style::default().bg(theme.accent).fg(theme.on_color);
+    let mut new_spans: Vec<Span<'static>> = Vec::new();
+    let mut col = 0;
+
+    for span in &line.spans {
+        let mut run = String::new();
+        let mut run_highlighted = false;
+        for c in span.content.chars() {
+            let highlighted = col >= start_col && col < end_col;
+            if highlighted != run_highlighted && !run.is_empty() {
+                let style = if run_highlighted {
+                    highlight
+                } else {
+                    span.style
+                };
+                new_spans.push(Span::styled(std::mem::take(&mut run), style));
+            }
+            run_highlighted = highlighted;
+            run.push(c);
+            col += c.width().unwrap_or(0);
+        }
+        if !run.is_empty() {
+            let style = if run_highlighted {
+                highlight
+            } else {
+                span.style
+            };
+            new_spans.push(Span::styled(run, style));
         }
     }
 
-    Line::from(new_spans)
+    Line::from(new_spans).style(line.style)
+}
+
+/// Status line for a link: key hints, its number in the pane and its URL
+fn link_status(
+    keys: &'static str,
+    link_num: usize,
+    total: usize,
+    url: &str,
+    width: u16,
+) -> Line<'static> {
+    let theme = styles::theme();
+    let link_prefix = format!("[{}/{}] ", link_num, total);
+    let available = (width as usize).saturating_sub(keys.len() + link_prefix.len() + 1);
+    let truncated_url = if url.len() > available {
+        let cut = available.saturating_sub(3).min(url.len());
+        let cut = (0..=cut)
+            .rev()
+            .find(|&i| url.is_char_boundary(i))
+            .unwrap_or(0);
+        format!("{}...", &url[..cut])
+    } else {
+        url.to_string()
+    };
+    let padding_len =
+        (width as usize).saturating_sub(keys.len() + link_prefix.len() + truncated_url.len());
+    let padding = " ".repeat(padding_len);
+
+    Line::from(vec![
+        Span::styled(keys, styles::status_style()),
+        Span::styled(link_prefix, Style::default().bg(theme.dim).fg(theme.text)),
+        Span::styled(
+            format!("{}{}", truncated_url, padding),
+            Style::default().bg(theme.dim).fg(theme.accent),
+        ),
+    ])
 }
 
 fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect, can_split: bool) {
-    let erwin_count = app.erwin_answer_count();
+    let erwin_count = app.show.erwin_answer_count();
+
+    if let Some(ref hints) = app.show.link_hints {
+        let help = format!(
+            " f: type a hint to open its link ({} on screen), in capitals to focus it; Esc:cancel",
+            hints.hints.len()
+        );
+        let status = Line::from(Span::styled(help, styles::status_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
 
-    // If a link is focused, show link info with URL
-    if let Some(link) = app.get_focused_link() {
-        let link_num = app.focused_link_index.map(|i| i + 1).unwrap_or(0);
-        let total = if app.erwin_pane_visible && !app.left_pane_focused {
-            app.erwin_links.len()
+    // The link under the pointer, else the focused one: show its URL
+    if let Some((link, link_num, total)) = app.show.get_hovered_link() {
+        let status = link_status(" click:open ", link_num, total, &link.url, area.width);
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+    if let Some(link) = app.show.get_focused_link() {
+        let link_num = app
+            .show
+            .focused_pane_link_index()
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let total = if app.show.erwin_pane_visible && !app.show.left_pane_focused {
+            app.show.erwin_links.len()
         } else {
-            app.content_links.len()
+            app.show.content_links.len()
         };
 
-        let keys = " Tab o:open Esc ";
-        let link_prefix = format!("[{}/{}] ", link_num, total);
-        let url = &link.url;
-        let available = (area.width as usize).saturating_sub(keys.len() + link_prefix.len() + 1);
-        let truncated_url = if url.len() > available {
-            format!("{}...", &url[..available.saturating_sub(3).min(url.len())])
+        let status = link_status(" Tab o:open Esc ", link_num, total, &link.url, area.width);
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    if let Some(ref message) = app.flash {
+        let status = Line::from(Span::styled(format!(" {}", message), styles::flash_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    if let Some(prefix) = app.show.pending_mark {
+        let help = if prefix == 'm' {
+            " m: press a-z to mark this position".to_string()
         } else {
-            url.clone()
+            let mut marks: Vec<char> = app.show.marks.keys().copied().collect();
+            marks.sort_unstable();
+            match marks.len() {
+                0 => " ': no marks set on this question (m a-z sets one)".to_string(),
+                _ => format!(" ': jump to mark {}", String::from_iter(marks)),
+            }
         };
-        let padding_len = (area.width as usize)
-            .saturating_sub(keys.len() + link_prefix.len() + truncated_url.len());
-        let padding = " ".repeat(padding_len);
-
-        let status = Line::from(vec![
-            Span::styled(keys, styles::status_style()),
-            Span::styled(
-                link_prefix,
-                Style::default().bg(Color::DarkGray).fg(Color::White),
-            ),
-            Span::styled(
-                format!("{}{}", truncated_url, padding),
-                Style::default().bg(Color::DarkGray).fg(Color::Cyan),
-            ),
-        ]);
+        let status = Line::from(Span::styled(help, styles::status_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    if app.show.outline_selected.is_some() {
+        let help = " j/k:select  g/G:first/last  Enter:jump  O/Esc:close outline";
+        let status = Line::from(Span::styled(help, styles::status_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
 
+    if app.show.translation.is_some() {
+        let help = " j/k:scroll  t/Esc:close translation";
+        let status = Line::from(Span::styled(help, styles::status_style()));
         frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
         return;
     }
 
-    let help = if app.erwin_pane_visible && can_split {
-        let focus_indicator = if app.left_pane_focused {
+    let reading = if app.speaker.is_some() {
+        "  \u{266a} r:stop"
+    } else {
+        "  r:read"
+    };
+    let translate = if app.config.translate.command.is_some() {
+        "  t:translate"
+    } else {
+        ""
+    };
+
+    let playground = if app.config.playground.command.is_some() {
+        "  P:playground"
+    } else {
+        ""
+    };
+
+    let sort_hint = match app.show.answer_sort {
+        AnswerSort::Default => "  s:by rep",
+        AnswerSort::Reputation => "  s:by quality",
+        AnswerSort::Quality => "  s:default order",
+    };
+
+    let copy_hint = if app.show.copy.is_some() {
+        "  M:copy"
+    } else if app.show.duplicate.is_some() {
+        "  Enter:duplicate"
+    } else if app.show.superseded_link().is_some() {
+        "  Enter:newer answer"
+    } else {
+        ""
+    };
+
+    let glossary_hint = if app.show.focused_pane_terms().is_empty() {
+        ""
+    } else {
+        "  w:terms"
+    };
+    let outline_hint = if app.show.has_outline(area.width) {
+        "  O:outline"
+    } else {
+        ""
+    };
+
+    let answers_hint = match (
+        app.show.answer_positions.is_empty(),
+        app.show.answers.iter().any(|a| a.is_accepted),
+    ) {
+        (true, false) => "",
+        (true, true) => "  a:accepted",
+        (false, false) => "  n/p:answers",
+        (false, true) => "  n/p:answers  a:accepted",
+    };
+
+    let help = if app.show.erwin_pane_visible && can_split {
+        let focus_indicator = if app.show.left_pane_focused {
             "[Question]"
         } else {
             "[Erwin]"
         };
         format!(
-            " j/k:scroll  e/E:Erwin  Tab:links  o:browser  b/q:back  {}",
+            " j/k:scroll{}  e/E:Erwin  </>:split  Tab/f:links{}  v/V:preview{}{}{}{}  o:browser{}{}  b/q:back  {}",
+            answers_hint,
+            glossary_hint,
+            reading,
+            translate,
+            playground,
+            sort_hint,
+            copy_hint,
+            outline_hint,
             focus_indicator
         )
     } else if erwin_count > 0 {
-        " j/k:scroll  e:Erwin  Tab:links  o:browser  b/q:back".to_string()
+        format!(
+            " j/k:scroll{}  e:Erwin  Tab/f:links{}  v/V:preview{}{}{}{}  o:browser{}{}  b/q:back",
+            answers_hint,
+            glossary_hint,
+            reading,
+            translate,
+            playground,
+            sort_hint,
+            copy_hint,
+            outline_hint
+        )
     } else {
-        " j/k:scroll  Tab:links  o:browser  b/q:back".to_string()
+        format!(
+            " j/k:scroll{}  Tab/f:links{}  v/V:preview{}{}{}{}  o:browser{}{}  b/q:back",
+            answers_hint,
+            glossary_hint,
+            reading,
+            translate,
+            playground,
+            sort_hint,
+            copy_hint,
+            outline_hint
+        )
     };
 
-    let status = Line::from(vec![Span::styled(help, styles::status_style())]);
+    // Plugin segments on the right
+    let segments = app.plugins.status(&app.plugin_context()).join("  ");
+    let right_side = if segments.is_empty() {
+        segments
+    } else {
+        format!("  {segments} ")
+    };
+    let help_width = (area.width as usize).saturating_sub(right_side.width());
+    let status = Line::from(vec![
+        Span::styled(
+            format!(
+                "{:<width$}",
+                fit_cell(&help, help_width),
+                width = help_width
+            ),
+            styles::status_style(),
+        ),
+        Span::styled(right_side, styles::status_style()),
+    ]);
 
     frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
 }
@@ -1,130 +1,440 @@
+use anyhow::{bail, Context, Result};
 use ratatui::style::{Color, Modifier, Style};
+use std::str::FromStr;
+use std::sync::{PoisonError, RwLock};
 
-pub const HEADER_BG: Color = Color::Blue;
-pub const HEADER_FG: Color = Color::White;
-pub const STATUS_BG: Color = Color::DarkGray;
-pub const STATUS_FG: Color = Color::Black;
+use crate::config::ThemeConfig;
+use crate::db::StatusBadge;
+use crate::highlight;
 
-pub const SELECTED_BG: Color = Color::Cyan;
-pub const SELECTED_FG: Color = Color::Black;
+/// Colors of the whole UI. Styles below read the current theme, so switching
+/// it (`set_theme`) recolors everything drawn from then on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    /// syntect theme for code blocks
+    pub syntax: &'static str,
+    pub header_bg: Color,
+    pub header_fg: Color,
+    pub status_bg: Color,
+    pub status_fg: Color,
+    pub selected_bg: Color,
+    pub selected_fg: Color,
+    /// Text on the other colored backgrounds (Erwin headers, popups)
+    pub on_color: Color,
+    /// Erwin's answers: pane border, header background, markers
+    pub erwin: Color,
+    pub title: Color,
+    /// Body text that has to stand out from the terminal's default
+    pub text: Color,
+    pub muted: Color,
+    pub dim: Color,
+    /// Links, counts, and other interactive or informational text
+    pub accent: Color,
+    /// Marks, search matches, warnings
+    pub highlight: Color,
+    /// Question headers, pins, modal borders
+    pub special: Color,
+    /// Answers, recent dates
+    pub good: Color,
+    pub error: Color,
+    pub tag: Color,
+    pub bounty: Color,
+    pub comment: Color,
+    /// Background of inline code in posts
+    pub code_bg: Color,
+}
 
-pub const ERWIN_ACCENT: Color = Color::Yellow;
-pub const ERWIN_BG: Color = Color::Yellow;
-pub const ERWIN_FG: Color = Color::Black;
+impl Theme {
+    /// The look erwindb always had, for dark terminals
+    pub const DARK: Self = Self {
+        name: "dark",
+        syntax: "base16-ocean.dark",
+        header_bg: Color::Blue,
+        header_fg: Color::White,
+        status_bg: Color::DarkGray,
+        status_fg: Color::Black,
+        selected_bg: Color::Cyan,
+        selected_fg: Color::Black,
+        on_color: Color::Black,
+        erwin: Color::Yellow,
+        title: Color::Yellow,
+        text: Color::White,
+        muted: Color::Gray,
+        dim: Color::DarkGray,
+        accent: Color::Cyan,
+        highlight: Color::Yellow,
+        special: Color::Magenta,
+        good: Color::Green,
+        error: Color::LightRed,
+        tag: Color::Blue,
+        bounty: Color::LightBlue,
+        comment: Color::Rgb(180, 170, 150), // Light tan/beige
+        code_bg: Color::Rgb(48, 48, 48),
+    };
 
-pub const TITLE_FG: Color = Color::Yellow;
-#[allow(dead_code)]
-pub const LINK_FG: Color = Color::Cyan;
-#[allow(dead_code)]
-pub const CODE_BG: Color = Color::Rgb(40, 44, 52);
+    /// Darker colors for light terminal backgrounds. RGB rather than the
+    /// terminal's palette, whose yellows and cyans wash out on white.
+    pub const LIGHT: Self = Self {
+        name: "light",
+        syntax: "InspiredGitHub",
+        header_bg: Color::Rgb(0, 87, 174),
+        header_fg: Color::White,
+        status_bg: Color::Rgb(218, 218, 218),
+        status_fg: Color::Black,
+        selected_bg: Color::Rgb(0, 116, 145),
+        selected_fg: Color::White,
+        on_color: Color::White,
+        erwin: Color::Rgb(166, 112, 0),
+        title: Color::Rgb(140, 82, 0),
+        text: Color::Black,
+        muted: Color::Rgb(88, 88, 88),
+        dim: Color::Rgb(128, 128, 128),
+        accent: Color::Rgb(0, 110, 140),
+        highlight: Color::Rgb(166, 100, 0),
+        special: Color::Rgb(145, 40, 145),
+        good: Color::Rgb(0, 125, 50),
+        error: Color::Rgb(190, 30, 30),
+        tag: Color::Rgb(30, 70, 180),
+        bounty: Color::Rgb(20, 95, 210),
+        comment: Color::Rgb(105, 90, 65),
+        code_bg: Color::Rgb(232, 232, 232),
+    };
 
-#[allow(dead_code)]
-pub const POSITIVE_SCORE: Color = Color::Green;
-#[allow(dead_code)]
-pub const ACCEPTED_ANSWER: Color = Color::Green;
-#[allow(dead_code)]
-pub const COMMENT_FG: Color = Color::Gray;
-pub const SEPARATOR_FG: Color = Color::DarkGray;
+    /// Bright colors on black and white bars, nothing dim
+    pub const HIGH_CONTRAST: Self = Self {
+        name: "high-contrast",
+        syntax: "base16-eighties.dark",
+        header_bg: Color::White,
+        header_fg: Color::Black,
+        status_bg: Color::White,
+        status_fg: Color::Black,
+        selected_bg: Color::LightYellow,
+        selected_fg: Color::Black,
+        on_color: Color::Black,
+        erwin: Color::LightYellow,
+        title: Color::LightYellow,
+        text: Color::White,
+        muted: Color::White,
+        dim: Color::Gray,
+        accent: Color::LightCyan,
+        highlight: Color::LightYellow,
+        special: Color::LightMagenta,
+        good: Color::LightGreen,
+        error: Color::Red,
+        tag: Color::LightBlue,
+        bounty: Color::LightBlue,
+        comment: Color::White,
+        code_bg: Color::Rgb(58, 58, 58),
+    };
+
+    /// The built-in themes, in the order the theme key cycles through them
+    pub const BUILTIN: [Self; 3] = [Self::DARK, Self::LIGHT, Self::HIGH_CONTRAST];
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        Self::BUILTIN.into_iter().find(|theme| theme.name == name)
+    }
+
+    /// The `[theme]` config: a built-in theme with the configured overrides
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let Some(mut theme) = Self::builtin(&config.name) else {
+            let names: Vec<&str> = Self::BUILTIN.iter().map(|theme| theme.name).collect();
+            bail!(
+                "Unknown theme `{}` in [theme] (one of {})",
+                config.name,
+                names.join(", ")
+            );
+        };
+        if let Some(ref name) = config.syntax {
+            theme.syntax = highlight::syntax_theme(name).with_context(|| {
+                let names: Vec<&str> = highlight::syntax_theme_names().collect();
+                format!(
+                    "Unknown syntax theme `{name}` in [theme] (one of {})",
+                    names.join(", ")
+                )
+            })?;
+        }
+        for (field, value) in &config.colors {
+            let color = Color::from_str(value)
+                .ok()
+                .with_context(|| format!("Invalid color `{value}` in [theme.colors]"))?;
+            theme
+                .set_color(field, color)
+                .with_context(|| format!("Invalid [theme.colors] entry `{field}`"))?;
+        }
+        Ok(theme)
+    }
+
+    /// Change one color by its field name (`[theme.colors]` keys)
+    fn set_color(&mut self, field: &str, color: Color) -> Result<()> {
+        let slot = match field {
+            "header_bg" => &mut self.header_bg,
+            "header_fg" => &mut self.header_fg,
+            "status_bg" => &mut self.status_bg,
+            "status_fg" => &mut self.status_fg,
+            "selected_bg" => &mut self.selected_bg,
+            "selected_fg" => &mut self.selected_fg,
+            "on_color" => &mut self.on_color,
+            "erwin" => &mut self.erwin,
+            "title" => &mut self.title,
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "dim" => &mut self.dim,
+            "accent" => &mut self.accent,
+            "highlight" => &mut self.highlight,
+            "special" => &mut self.special,
+            "good" => &mut self.good,
+            "error" => &mut self.error,
+            "tag" => &mut self.tag,
+            "bounty" => &mut self.bounty,
+            "comment" => &mut self.comment,
+            "code_bg" => &mut self.code_bg,
+            _ => bail!("unknown color `{field}`"),
+        };
+        *slot = color;
+        Ok(())
+    }
+}
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::DARK);
+
+/// The theme styles are drawn with
+pub fn theme() -> Theme {
+    *THEME.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn set_theme(theme: Theme) {
+    *THEME.write().unwrap_or_else(PoisonError::into_inner) = theme;
+}
 
 pub fn header_style() -> Style {
+    let theme = theme();
     Style::default()
-        .bg(HEADER_BG)
-        .fg(HEADER_FG)
+        .bg(theme.header_bg)
+        .fg(theme.header_fg)
         .add_modifier(Modifier::BOLD)
 }
 
 pub fn search_title_style() -> Style {
+    let theme = theme();
     Style::default()
-        .bg(Color::Yellow)
-        .fg(Color::Black)
+        .bg(theme.highlight)
+        .fg(theme.on_color)
         .add_modifier(Modifier::BOLD)
 }
 
-#[allow(dead_code)]
-pub fn search_semantic_style() -> Style {
+/// Recognized `field:value` operators echoed beside the title search input
+pub fn search_filter_style() -> Style {
+    Style::default().fg(theme().tag)
+}
+
+pub fn status_style() -> Style {
+    let theme = theme();
+    Style::default().bg(theme.status_bg).fg(theme.status_fg)
+}
+
+/// One-off status bar messages (errors from external commands etc.)
+pub fn flash_style() -> Style {
+    let theme = theme();
     Style::default()
-        .bg(Color::Magenta)
-        .fg(Color::White)
+        .bg(theme.status_bg)
+        .fg(theme.error)
         .add_modifier(Modifier::BOLD)
 }
 
-pub fn status_style() -> Style {
-    Style::default().bg(STATUS_BG).fg(STATUS_FG)
+/// Marker for questions pinned to the top of the list
+pub fn pin_style() -> Style {
+    Style::default()
+        .fg(theme().special)
+        .add_modifier(Modifier::BOLD)
+}
+
+pub fn bookmark_style() -> Style {
+    Style::default().fg(theme().highlight)
 }
 
 pub fn selected_style() -> Style {
+    let theme = theme();
     Style::default()
-        .bg(SELECTED_BG)
-        .fg(SELECTED_FG)
+        .bg(theme.selected_bg)
+        .fg(theme.selected_fg)
         .add_modifier(Modifier::BOLD)
 }
 
 pub fn erwin_header_style() -> Style {
+    let theme = theme();
     Style::default()
-        .bg(ERWIN_BG)
-        .fg(ERWIN_FG)
+        .bg(theme.erwin)
+        .fg(theme.on_color)
         .add_modifier(Modifier::BOLD)
 }
 
 pub fn erwin_accent_style() -> Style {
-    Style::default().fg(ERWIN_ACCENT)
+    Style::default().fg(theme().erwin)
 }
 
 pub fn erwin_text_style() -> Style {
-    Style::default().fg(Color::White)
+    Style::default().fg(theme().text)
 }
 
 pub fn title_style() -> Style {
-    Style::default().fg(TITLE_FG).add_modifier(Modifier::BOLD)
+    Style::default()
+        .fg(theme().title)
+        .add_modifier(Modifier::BOLD)
+}
+
+pub fn inline_code_style() -> Style {
+    Style::default().bg(theme().code_bg)
 }
 
-#[allow(dead_code)]
 pub fn link_style() -> Style {
     Style::default()
-        .fg(LINK_FG)
+        .fg(theme().accent)
         .add_modifier(Modifier::UNDERLINED)
 }
 
 #[allow(dead_code)]
 pub fn focused_link_style() -> Style {
+    let theme = theme();
     Style::default()
-        .bg(LINK_FG)
-        .fg(Color::Black)
+        .bg(theme.accent)
+        .fg(theme.selected_fg)
         .add_modifier(Modifier::BOLD)
 }
 
+/// Link-hint labels (`F`), and the letters of them already typed
+pub fn link_hint_style() -> Style {
+    let theme = theme();
+    Style::default()
+        .bg(theme.highlight)
+        .fg(theme.on_color)
+        .add_modifier(Modifier::BOLD)
+}
+
+pub fn link_hint_typed_style() -> Style {
+    Style::default().bg(theme().highlight).fg(theme().dim)
+}
+
 pub fn answer_header_style() -> Style {
     Style::default()
-        .fg(Color::Green)
+        .fg(theme().good)
         .add_modifier(Modifier::BOLD)
 }
 
 pub fn question_header_style() -> Style {
     Style::default()
-        .fg(Color::Magenta)
+        .fg(theme().special)
+        .add_modifier(Modifier::BOLD)
+}
+
+pub fn site_badge_style() -> Style {
+    Style::default()
+        .fg(theme().special)
+        .add_modifier(Modifier::BOLD)
+}
+
+pub fn status_badge_style(badge: StatusBadge) -> Style {
+    let theme = theme();
+    let color = match badge {
+        StatusBadge::Closed => theme.error,
+        StatusBadge::Duplicate => theme.highlight,
+        StatusBadge::Migrated => theme.special,
+        StatusBadge::Locked => theme.muted,
+    };
+    Style::default().fg(color).add_modifier(Modifier::BOLD)
+}
+
+/// Banner on threads whose advice may be out of date
+pub fn stale_warning_style() -> Style {
+    Style::default().fg(theme().highlight)
+}
+
+/// Question dates by age: recent ones stand out, those old enough for the stale
+/// warning (`stale_after` years, 0 = off) are flagged
+pub fn age_style(age_years: u32, stale_after: u32) -> Style {
+    let theme = theme();
+    let color = match age_years {
+        age if stale_after > 0 && age >= stale_after => theme.highlight,
+        0..=1 => theme.good,
+        2..=4 => theme.muted,
+        _ => theme.dim,
+    };
+    Style::default().fg(color)
+}
+
+/// "+150 bounty" markers on questions and answers
+pub fn bounty_style() -> Style {
+    Style::default()
+        .fg(theme().bounty)
+        .add_modifier(Modifier::BOLD)
+}
+
+/// "Superseded by" banner under an answer Erwin has moved on from
+pub fn superseded_style() -> Style {
+    Style::default()
+        .fg(theme().highlight)
         .add_modifier(Modifier::BOLD)
 }
 
+/// "best explanation" badge on an answer header
+pub fn best_explanation_style() -> Style {
+    Style::default()
+        .fg(theme().muted)
+        .add_modifier(Modifier::ITALIC)
+}
+
+/// Glossary terms in post text; the base style is kept so links etc. still read
+pub fn glossary_term_style(base: Style) -> Style {
+    base.add_modifier(Modifier::UNDERLINED)
+}
+
+/// One-line definition shown under a focused glossary term
+pub fn glossary_popup_style() -> Style {
+    let theme = theme();
+    Style::default().bg(theme.highlight).fg(theme.on_color)
+}
+
+/// Outline section currently in view
+pub fn outline_current_style() -> Style {
+    Style::default().add_modifier(Modifier::BOLD)
+}
+
+/// Scrollbars beside the question list and the question page's panes
+pub fn scrollbar_track_style() -> Style {
+    Style::default().fg(theme().dim)
+}
+
+pub fn scrollbar_thumb_style() -> Style {
+    Style::default().fg(theme().muted)
+}
+
+/// Question tags in the list and on the question page
+pub fn tag_style() -> Style {
+    Style::default().fg(theme().tag)
+}
+
 pub fn separator_style() -> Style {
-    Style::default().fg(SEPARATOR_FG)
+    Style::default().fg(theme().dim)
 }
 
 #[allow(dead_code)]
 pub fn comment_style() -> Style {
-    Style::default().fg(COMMENT_FG).add_modifier(Modifier::BOLD)
+    Style::default()
+        .fg(theme().muted)
+        .add_modifier(Modifier::BOLD)
 }
 
 pub fn comment_header_style() -> Style {
     Style::default()
-        .fg(Color::DarkGray)
+        .fg(theme().dim)
         .add_modifier(Modifier::BOLD)
 }
 
 pub fn comment_text_style() -> Style {
-    Style::default().fg(Color::Rgb(180, 170, 150)) // Light tan/beige
+    Style::default().fg(theme().comment)
 }
 
 #[allow(dead_code)]
 pub fn dim_style() -> Style {
-    Style::default().fg(Color::DarkGray)
+    Style::default().fg(theme().dim)
 }
@@ -0,0 +1,148 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+
+/// Width of the frequency bar column
+const BAR_WIDTH: usize = 20;
+
+pub fn draw_tags(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(size);
+
+    draw_header(frame, app, chunks[0]);
+    draw_tag_list(frame, app, chunks[1]);
+    draw_status_bar(frame, app, chunks[2]);
+}
+
+fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
+    let (style, text) = if app.tags.filter_active {
+        (
+            styles::search_title_style(),
+            format!(" /{}\u{2588}", app.tags.filter_input),
+        )
+    } else if app.tags.matches.is_some() {
+        (
+            styles::header_style(),
+            format!(
+                " Tags ({} matching \"{}\") ",
+                app.visible_tags().len(),
+                app.tags.filter_input
+            ),
+        )
+    } else {
+        (
+            styles::header_style(),
+            format!(" Tags ({}) ", app.visible_tags().len()),
+        )
+    };
+
+    frame.render_widget(Paragraph::new(Line::from(text)).style(style), area);
+}
+
+fn draw_tag_list(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    let tags = app.visible_tags();
+    let max_count = app
+        .db
+        .get_tag_counts()
+        .ok()
+        .and_then(|t| t.first())
+        .map(|t| t.count)
+        .unwrap_or(1)
+        .max(1);
+
+    let lines: Vec<Line> = tags
+        .iter()
+        .enumerate()
+        .skip(app.tags.scroll)
+        .take(area.height as usize)
+        .map(|(idx, tag)| {
+            let is_selected = idx == app.tags.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+
+            // Log scale so the long tail of rare tags still gets a visible bar
+            let ratio = (tag.count as f64).ln_1p() / (max_count as f64).ln_1p();
+            let filled = ((ratio * BAR_WIDTH as f64).round() as usize).clamp(1, BAR_WIDTH);
+            let color = frequency_color(ratio);
+
+            let mut name_style = Style::default().fg(color);
+            if ratio >= 0.75 || is_selected {
+                name_style = name_style.add_modifier(Modifier::BOLD);
+            }
+
+            Line::from(vec![
+                Span::styled(selector.to_string(), selector_style),
+                Span::styled(
+                    format!("{:<width$} ", "\u{2588}".repeat(filled), width = BAR_WIDTH),
+                    Style::default().fg(color),
+                ),
+                Span::styled(format!("{:>6} ", tag.count), Style::default().fg(theme.dim)),
+                Span::styled(tag.name.clone(), name_style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Color tags by frequency bucket (ratio is log-scaled, 0.0 - 1.0)
+fn frequency_color(ratio: f64) -> Color {
+    let theme = styles::theme();
+    if ratio >= 0.75 {
+        theme.highlight
+    } else if ratio >= 0.5 {
+        theme.good
+    } else if ratio >= 0.25 {
+        theme.accent
+    } else {
+        theme.dim
+    }
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let help = if app.tags.filter_active {
+        " Type to filter tags, Enter to confirm, Esc to cancel"
+    } else if app.tags.matches.is_some() {
+        " j/k:move  Enter:filter questions  x:explore  c:techniques  /:filter  Esc:clear"
+    } else {
+        " j/k:move  Enter:filter questions  x:explore  c:techniques  /:filter  q:back"
+    };
+
+    let total = app.visible_tags().len();
+    let position = if total > 0 {
+        format!("{}/{}", app.tags.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
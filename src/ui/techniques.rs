@@ -0,0 +1,197 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+
+/// Width of the usage bar in the legend
+const BAR_WIDTH: usize = 12;
+/// Width of each matrix cell
+const CELL_WIDTH: usize = 3;
+
+pub fn draw_techniques(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+    let legend_height = app.techniques.techniques.len() as u16 + 1;
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),             // Header
+            Constraint::Length(legend_height), // Legend and a blank line
+            Constraint::Length(1),             // Column headings
+            Constraint::Min(1),                // Matrix
+            Constraint::Length(1),             // Status bar
+        ])
+        .split(size);
+
+    let state = &app.techniques;
+    let header = format!(
+        " Techniques in Erwin's code for [{}] ({} in {} questions) ",
+        state.tag,
+        state.techniques.len(),
+        state.questions.len()
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(header)).style(styles::header_style()),
+        chunks[0],
+    );
+    draw_legend(frame, app, chunks[1]);
+    draw_headings(frame, app, chunks[2]);
+    draw_matrix(frame, app, chunks[3]);
+    draw_status_bar(frame, app, chunks[4]);
+}
+
+/// `A`, `B`, ... for column `index`
+fn column_letter(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+fn draw_legend(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    let state = &app.techniques;
+    let max_count = state
+        .techniques
+        .first()
+        .map_or(1, |t| t.questions.len())
+        .max(1);
+
+    let lines: Vec<Line> = state
+        .techniques
+        .iter()
+        .enumerate()
+        .map(|(index, technique)| {
+            let is_selected = index == state.column;
+            let letter_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default().fg(theme.accent)
+            };
+            let mut label_style = Style::default().fg(theme.highlight);
+            if is_selected {
+                label_style = label_style.add_modifier(Modifier::BOLD);
+            }
+
+            let count = technique.questions.len();
+            let filled = (count * BAR_WIDTH).div_ceil(max_count).clamp(1, BAR_WIDTH);
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(format!(" {} ", column_letter(index)), letter_style),
+                Span::styled(
+                    format!(" {:<width$}", "\u{2588}".repeat(filled), width = BAR_WIDTH),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::styled(format!("{count:>4} "), styles::dim_style()),
+                Span::styled(technique.label.clone(), label_style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_headings(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    let state = &app.techniques;
+    let mut spans = vec![Span::raw("   ")];
+    for index in 0..state.techniques.len() {
+        let style = if index == state.column {
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            styles::dim_style()
+        };
+        spans.push(Span::styled(
+            format!("{:^width$}", column_letter(index), width = CELL_WIDTH),
+            style,
+        ));
+    }
+    spans.push(Span::styled(" Question", styles::dim_style()));
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn draw_matrix(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    let state = &app.techniques;
+
+    let lines: Vec<Line> = app
+        .visible_technique_questions()
+        .into_iter()
+        .enumerate()
+        .skip(state.scroll)
+        .take(area.height as usize)
+        .map(|(idx, question_id)| {
+            let is_selected = idx == state.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+
+            let mut spans = vec![Span::styled(selector, selector_style)];
+            for (index, technique) in state.techniques.iter().enumerate() {
+                let used = technique.questions.contains(&question_id);
+                let (mark, color) = match (used, index == state.column) {
+                    (true, true) => ("\u{25cf}", theme.accent),
+                    (true, false) => ("\u{25cf}", theme.muted),
+                    (false, _) => ("\u{00b7}", theme.dim),
+                };
+                spans.push(Span::styled(
+                    format!("{mark:^width$}", width = CELL_WIDTH),
+                    Style::default().fg(color),
+                ));
+            }
+            let title = app
+                .questions
+                .iter()
+                .find(|q| q.id == question_id)
+                .map_or_else(String::new, |q| q.title.clone());
+            let mut title_style = Style::default();
+            if is_selected {
+                title_style = title_style.add_modifier(Modifier::BOLD);
+            }
+            spans.push(Span::styled(format!(" {title}"), title_style));
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    if let Some(ref message) = app.flash {
+        let status = Line::from(Span::styled(format!(" {}", message), styles::flash_style()));
+        frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+        return;
+    }
+
+    let help = if app.techniques.only_column {
+        " j/k:move  h/l:technique  f:all questions  Enter:open  q:back"
+    } else {
+        " j/k:move  h/l:technique  f:only this technique  Enter:open  q:back"
+    };
+
+    let total = app.visible_technique_questions().len();
+    let position = if total > 0 {
+        format!("{}/{}", app.techniques.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
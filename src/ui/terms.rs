@@ -0,0 +1,120 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::styles;
+use crate::app::App;
+use crate::terms::TermKind;
+
+/// Width of the frequency bar column
+const BAR_WIDTH: usize = 20;
+
+pub fn draw_terms(frame: &mut Frame, app: &App) {
+    let size = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(1),    // Content
+            Constraint::Length(1), // Status bar
+        ])
+        .split(size);
+
+    draw_header(frame, app, chunks[0]);
+    draw_term_list(frame, app, chunks[1]);
+    draw_status_bar(frame, app, chunks[2]);
+}
+
+fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
+    let what = match app.terms.kind {
+        None => "Functions and keywords",
+        Some(TermKind::Function) => "Functions",
+        Some(TermKind::Keyword) => "Keywords",
+    };
+    let text = format!(
+        " {} in Erwin's code ({}, by answers using them) ",
+        what,
+        app.visible_terms().len()
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(text)).style(styles::header_style()),
+        area,
+    );
+}
+
+fn draw_term_list(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = styles::theme();
+    let terms = app.visible_terms();
+    let max_count = terms.first().map(|t| t.answers).unwrap_or(1).max(1);
+
+    let lines: Vec<Line> = terms
+        .iter()
+        .enumerate()
+        .skip(app.terms.scroll)
+        .take(area.height as usize)
+        .map(|(idx, term)| {
+            let is_selected = idx == app.terms.selected;
+            let selector = if is_selected { " > " } else { "   " };
+            let selector_style = if is_selected {
+                styles::selected_style()
+            } else {
+                Style::default()
+            };
+
+            // Linear scale: the list is cut off before the long tail
+            let ratio = term.answers as f64 / max_count as f64;
+            let filled = ((ratio * BAR_WIDTH as f64).round() as usize).clamp(1, BAR_WIDTH);
+            let color = match term.kind {
+                TermKind::Function => theme.accent,
+                TermKind::Keyword => theme.highlight,
+            };
+
+            let mut name_style = Style::default().fg(color);
+            if is_selected {
+                name_style = name_style.add_modifier(Modifier::BOLD);
+            }
+
+            Line::from(vec![
+                Span::styled(selector.to_string(), selector_style),
+                Span::styled(
+                    format!("{:<width$} ", "\u{2588}".repeat(filled), width = BAR_WIDTH),
+                    Style::default().fg(color),
+                ),
+                Span::styled(
+                    format!("{:>6} ", term.answers),
+                    Style::default().fg(theme.dim),
+                ),
+                Span::styled(term.label(), name_style),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let help = " j/k:move  Enter:questions using it  f:functions/keywords/all  q:back";
+
+    let total = app.visible_terms().len();
+    let position = if total > 0 {
+        format!("{}/{}", app.terms.selected + 1, total)
+    } else {
+        String::new()
+    };
+    let help_width = (area.width as usize).saturating_sub(position.len());
+
+    let status = Line::from(vec![
+        Span::styled(
+            format!("{:<width$}", help, width = help_width),
+            styles::status_style(),
+        ),
+        Span::styled(position, styles::status_style()),
+    ]);
+
+    frame.render_widget(Paragraph::new(status).style(styles::status_style()), area);
+}
@@ -0,0 +1,43 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use super::{styles, MIN_HEIGHT, MIN_WIDTH};
+
+/// Stands in for every page while the terminal is below `MIN_WIDTH` x
+/// `MIN_HEIGHT`; the page comes back on the next resize that fits it
+pub fn draw_too_small(frame: &mut Frame) {
+    let theme = styles::theme();
+    let area = frame.area();
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "need \u{2265}{MIN_WIDTH}x{MIN_HEIGHT}, have {}x{}",
+                area.width, area.height
+            ),
+            Style::default().fg(theme.text),
+        )),
+        Line::from(Span::styled(
+            "Resize to continue, q quits",
+            Style::default().fg(theme.dim),
+        )),
+    ];
+
+    // Centered when the lines fit unwrapped, running on down when they don't
+    let y = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+    let text_area = Rect::new(area.x, y, area.width, area.bottom() - y);
+    frame.render_widget(
+        Paragraph::new(lines).centered().wrap(Wrap { trim: true }),
+        text_area,
+    );
+}
@@ -0,0 +1,121 @@
+//! `erwindb update`: threads Erwin answered or edited since the last update,
+//! pulled from the Stack Exchange API into the extracted corpus. His answers
+//! are listed by activity, oldest first, from where the last update stopped,
+//! so a run cut short by the page limit (or the quota) resumes where it ended.
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::db::{Database, SavedThread};
+use crate::fetch::{FetchedThread, THREAD_FILTER};
+use crate::se_api::{Client, MAX_PAGE_SIZE};
+use crate::search::semantic::SemanticSearch;
+use crate::site::Site;
+use crate::superseded;
+
+/// Pages of his answers listed per site and run
+const MAX_ANSWER_PAGES: u32 = 20;
+
+#[derive(Deserialize)]
+struct ApiAnswerActivity {
+    question_id: i64,
+    last_activity_date: i64,
+}
+
+/// What updating one site did
+pub struct SiteUpdate {
+    pub site: Site,
+    /// Activity time the update started from
+    pub since: i64,
+    pub added: usize,
+    pub updated: usize,
+    /// Threads not stored in the corpus because another site's question has
+    /// their ID: the question ID, that other site, and the thread's API item
+    /// as JSON, to keep with the fetched threads instead
+    pub collisions: Vec<(i64, Site, String)>,
+    /// Questions whose title is new to the corpus, to embed
+    pub titles: Vec<(i64, String)>,
+    /// Answers past the page limit were left for the next run
+    pub more: bool,
+}
+
+/// Fetch and store the threads of Erwin's answers on `site` active since the
+/// last update, then move the sync point up to the latest activity seen
+pub fn update_site(db: &Database, client: &mut Client, site: Site) -> Result<SiteUpdate> {
+    let since = db.synced_until(site)?;
+    let answers = client.get_pages::<ApiAnswerActivity>(
+        site,
+        &format!("users/{}/answers", site.erwin_user_id()),
+        &[
+            ("sort", "activity".to_string()),
+            ("order", "asc".to_string()),
+            ("min", since.to_string()),
+        ],
+        1,
+        MAX_ANSWER_PAGES,
+    )?;
+    let mut question_ids: Vec<i64> = answers.items.iter().map(|a| a.question_id).collect();
+    question_ids.sort_unstable();
+    question_ids.dedup();
+
+    let mut update = SiteUpdate {
+        site,
+        since,
+        added: 0,
+        updated: 0,
+        collisions: Vec::new(),
+        titles: Vec::new(),
+        more: answers.next_page.is_some(),
+    };
+    for ids in question_ids.chunks(MAX_PAGE_SIZE as usize) {
+        let ids = ids.iter().map(i64::to_string).collect::<Vec<_>>().join(";");
+        // Deleted questions are left out of the response
+        let items = client
+            .get::<serde_json::Value>(
+                site,
+                &format!("questions/{ids}"),
+                &[
+                    ("filter", THREAD_FILTER.to_string()),
+                    ("pagesize", MAX_PAGE_SIZE.to_string()),
+                ],
+            )?
+            .items;
+        for item in items {
+            let json = item.to_string();
+            let thread = FetchedThread::from_json(site, &json)?;
+            let newer = superseded::detect(site, &thread.answers, &thread.answer_comments);
+            let saved = db.save_thread(&thread, &newer)?;
+            match saved {
+                SavedThread::Added => update.added += 1,
+                SavedThread::Updated | SavedThread::Retitled => update.updated += 1,
+                SavedThread::Collides(other) => {
+                    update
+                        .collisions
+                        .push((thread.question.summary.id, other, json));
+                    continue;
+                }
+            }
+            if saved != SavedThread::Updated {
+                let summary = thread.question.summary;
+                update.titles.push((summary.id, summary.title));
+            }
+        }
+    }
+
+    if let Some(until) = answers.items.iter().map(|a| a.last_activity_date).max() {
+        db.set_synced_until(site, until)?;
+    }
+    Ok(update)
+}
+
+/// Embed the titles of updated questions for semantic search
+pub fn embed_titles(
+    db: &Database,
+    semantic: &SemanticSearch,
+    titles: &[(i64, String)],
+) -> Result<()> {
+    for (question_id, title) in titles {
+        db.save_embedding(*question_id, &semantic.embed(title)?)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,1450 @@
+use anyhow::{anyhow, bail, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::thread::{self, JoinHandle};
+
+use crate::clock;
+use crate::db::PostKey;
+use crate::dwell::EngagedQuestion;
+use crate::site::Site;
+
+/// Tables for user-owned data. They live in their own file because the corpus
+/// database is overwritten whenever a newer one is embedded.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS collections (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE,
+        created_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS collection_items (
+        collection_id INTEGER NOT NULL REFERENCES collections(id) ON DELETE CASCADE,
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        position INTEGER NOT NULL,
+        PRIMARY KEY (collection_id, site, question_id)
+    );
+    -- Posts already seen, so a corpus update can be diffed (answer_id 0 = the question)
+    CREATE TABLE IF NOT EXISTS known_posts (
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        answer_id INTEGER NOT NULL,
+        PRIMARY KEY (site, question_id, answer_id)
+    );
+    CREATE TABLE IF NOT EXISTS alert_hits (
+        id INTEGER PRIMARY KEY,
+        rule TEXT NOT NULL,
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        answer_id INTEGER,
+        title TEXT NOT NULL,
+        found_at INTEGER NOT NULL,
+        read INTEGER NOT NULL DEFAULT 0
+    );
+    -- Reading time per question (`[engaged]`), and the review schedule of
+    -- those read long enough to count as engaged
+    CREATE TABLE IF NOT EXISTS reading_time (
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        seconds INTEGER NOT NULL,
+        last_read INTEGER NOT NULL,
+        engaged_at INTEGER,
+        reviews INTEGER NOT NULL DEFAULT 0,
+        reviewed_at INTEGER,
+        PRIMARY KEY (site, question_id)
+    );
+    -- Every question opened, for the History page and read markers
+    CREATE TABLE IF NOT EXISTS views (
+        id INTEGER PRIMARY KEY,
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        viewed_at INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS views_question ON views (question_id);
+    -- Vim-style marks (`m{a-z}`): a position in one of a question's panes,
+    -- `line` rows into the answer `answer_id` (the question when null)
+    CREATE TABLE IF NOT EXISTS marks (
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        mark TEXT NOT NULL,
+        erwin_pane INTEGER NOT NULL,
+        line INTEGER NOT NULL,
+        answer_id INTEGER,
+        PRIMARY KEY (site, question_id, mark)
+    );
+    CREATE TABLE IF NOT EXISTS bookmarks (
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        PRIMARY KEY (site, question_id)
+    );
+    -- Questions left out of every list and search (`x` on the list)
+    CREATE TABLE IF NOT EXISTS hidden (
+        site TEXT NOT NULL,
+        question_id INTEGER NOT NULL,
+        hidden_at INTEGER NOT NULL,
+        PRIMARY KEY (site, question_id)
+    );
+    -- Threads fetched from the API because a link led outside the corpus: the
+    -- API's question item as JSON, answers and comments included (`fetch.rs`).
+    -- Question IDs are per site, so two sites' threads may share one.
+    CREATE TABLE IF NOT EXISTS fetched_questions (
+        question_id INTEGER NOT NULL,
+        site TEXT NOT NULL,
+        thread TEXT NOT NULL,
+        fetched_at INTEGER NOT NULL,
+        PRIMARY KEY (site, question_id)
+    );
+    -- Searches that came up short (`W` after a search), saved to hunt down
+    -- canonical answers or widen the ingest filters later
+    CREATE TABLE IF NOT EXISTS wishlist (
+        id INTEGER PRIMARY KEY,
+        query TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        results INTEGER NOT NULL,
+        note TEXT,
+        added_at INTEGER NOT NULL
+    );
+";
+
+/// Columns added since their table was created: (table, column, definition)
+const COLUMN_MIGRATIONS: &[(&str, &str, &str)] = &[
+    ("marks", "answer_id", "INTEGER"),
+    (
+        "alert_hits",
+        "site",
+        "TEXT NOT NULL DEFAULT 'stackoverflow'",
+    ),
+    ("hidden", "site", "TEXT NOT NULL DEFAULT 'stackoverflow'"),
+    ("bookmarks", "site", "TEXT NOT NULL DEFAULT 'stackoverflow'"),
+    ("views", "site", "TEXT NOT NULL DEFAULT 'stackoverflow'"),
+    ("marks", "site", "TEXT NOT NULL DEFAULT 'stackoverflow'"),
+    (
+        "reading_time",
+        "site",
+        "TEXT NOT NULL DEFAULT 'stackoverflow'",
+    ),
+    (
+        "collection_items",
+        "site",
+        "TEXT NOT NULL DEFAULT 'stackoverflow'",
+    ),
+];
+
+/// Tables whose primary key gained a column since they were created: (table,
+/// that column). SQLite can't change a key in place, so they're rebuilt from
+/// `SCHEMA`, keeping their rows if they already had the column.
+const KEY_MIGRATIONS: &[(&str, &str)] = &[
+    ("fetched_questions", "site"),
+    ("known_posts", "site"),
+    ("hidden", "site"),
+    ("bookmarks", "site"),
+    ("marks", "site"),
+    ("reading_time", "site"),
+    ("collection_items", "site"),
+];
+
+/// Smart collection of the questions read longest; a saved collection of the
+/// same name takes precedence
+pub const ENGAGED_COLLECTION: &str = "engaged";
+
+/// New content that matched a keyword or tag alert
+#[derive(Debug, Clone)]
+pub struct AlertHit {
+    pub id: i64,
+    /// The alert that matched, e.g. `MERGE` or `[jsonb]`
+    pub rule: String,
+    pub post: PostKey,
+    pub title: String,
+    pub found_at: i64,
+    pub read: bool,
+}
+
+/// A question in the reading history
+#[derive(Debug, Clone)]
+pub struct RecentView {
+    pub site: Site,
+    pub question_id: i64,
+    pub last_viewed: i64,
+    /// Times it was opened
+    pub views: i64,
+}
+
+/// A question hidden from the lists, for the Hidden page
+#[derive(Debug, Clone, Copy)]
+pub struct HiddenQuestion {
+    pub site: Site,
+    pub question_id: i64,
+    pub hidden_at: i64,
+}
+
+/// Position saved under a mark letter: the post at the top of the pane and
+/// how far into it, so the mark survives rewrapping and answer reordering
+#[derive(Debug, Clone, Copy)]
+pub struct ScrollMark {
+    /// In the Erwin pane rather than the question pane
+    pub erwin_pane: bool,
+    /// Answer the mark is in, or `None` for the question
+    pub answer_id: Option<i64>,
+    /// Lines from the start of that post
+    pub offset: usize,
+}
+
+/// A search saved to the wishlist
+#[derive(Debug, Clone)]
+pub struct WishlistEntry {
+    pub query: String,
+    /// `title`, `semantic` or `hybrid`
+    pub kind: String,
+    /// Questions the search found
+    pub results: usize,
+    pub note: Option<String>,
+    pub added_at: i64,
+}
+
+/// Version of the `erwindb state export` format
+const STATE_VERSION: u32 = 1;
+
+/// What `erwindb state export` writes: the state worth moving between
+/// machines. Alert hits are left out, since they come from diffing the corpus
+/// a machine had before. Each `site` is the `site` column value, missing from
+/// exports made before it was recorded.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserState {
+    pub version: u32,
+    pub bookmarks: Vec<BookmarkState>,
+    pub collections: Vec<CollectionState>,
+    pub views: Vec<ViewState>,
+    pub reading_time: Vec<ReadingTimeState>,
+    pub marks: Vec<MarkState>,
+    /// Missing from exports made before questions could be hidden
+    #[serde(default)]
+    pub hidden: Vec<HiddenState>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BookmarkState {
+    #[serde(default = "default_site")]
+    pub site: String,
+    pub question_id: i64,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HiddenState {
+    #[serde(default = "default_site")]
+    pub site: String,
+    pub question_id: i64,
+    pub hidden_at: i64,
+}
+
+fn default_site() -> String {
+    Site::default().as_db().to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CollectionState {
+    pub name: String,
+    pub created_at: i64,
+    pub question_ids: Vec<i64>,
+    /// Site of each of `question_ids`; missing from exports made before it
+    /// was recorded, whose questions are all Stack Overflow's
+    #[serde(default)]
+    pub sites: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ViewState {
+    #[serde(default = "default_site")]
+    pub site: String,
+    pub question_id: i64,
+    pub viewed_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadingTimeState {
+    #[serde(default = "default_site")]
+    pub site: String,
+    pub question_id: i64,
+    pub seconds: i64,
+    pub last_read: i64,
+    pub engaged_at: Option<i64>,
+    pub reviews: i64,
+    pub reviewed_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkState {
+    #[serde(default = "default_site")]
+    pub site: String,
+    pub question_id: i64,
+    pub mark: String,
+    pub erwin_pane: bool,
+    /// Missing from exports made before marks were kept per post; `line` then
+    /// counts from the top of the pane
+    #[serde(default)]
+    pub answer_id: Option<i64>,
+    pub line: i64,
+}
+
+/// Rows an import added or changed, by kind
+#[derive(Debug, Default)]
+pub struct ImportCounts {
+    pub bookmarks: usize,
+    pub collections: usize,
+    pub collection_items: usize,
+    pub views: usize,
+    pub reading_time: usize,
+    pub marks: usize,
+    pub hidden: usize,
+}
+
+/// How long a connection waits for the other one's write to finish
+const BUSY_TIMEOUT_MS: u32 = 5000;
+
+/// Bookmark collections and other state the user creates while browsing.
+/// Reads run on the caller's connection, after any queued writes to their
+/// table. Writes go to a writer thread with its own connection, so the render
+/// loop never waits on the disk for them, except the few whose outcome the
+/// caller needs (creating a collection, the startup alert check). WAL mode lets
+/// the two connections work side by side.
+pub struct UserDb {
+    conn: Connection,
+    writer: Writer,
+}
+
+/// A queued write
+type Write = Box<dyn FnOnce(&Connection) -> Result<()> + Send>;
+
+/// Queued writes not yet done, per table
+type Pending = Arc<(Mutex<HashMap<&'static str, usize>>, Condvar)>;
+
+/// The writer thread: runs queued writes in order and reports failures back
+struct Writer {
+    sender: Option<Sender<(&'static str, Write)>>,
+    pending: Pending,
+    errors: Receiver<String>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Writer {
+    fn start(conn: Connection) -> Self {
+        let (sender, commands) = mpsc::channel::<(&'static str, Write)>();
+        let (error_sender, errors) = mpsc::channel();
+        let pending = Pending::default();
+        let thread = {
+            let pending = Arc::clone(&pending);
+            thread::spawn(move || {
+                for (table, write) in commands {
+                    // Counted done even if the write panics, so no reader
+                    // waits on it forever
+                    let _done = Done {
+                        pending: &pending,
+                        table,
+                    };
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| write(&conn)))
+                        .unwrap_or_else(|_| Err(anyhow!("the write panicked")));
+                    if let Err(err) = result {
+                        let _ = error_sender.send(format!("Failed to save to {table}: {err:#}"));
+                    }
+                }
+            })
+        };
+        Self {
+            sender: Some(sender),
+            pending,
+            errors,
+            thread: Some(thread),
+        }
+    }
+
+    fn queue(&self, table: &'static str, write: Write) -> Result<()> {
+        let sender = self
+            .sender
+            .as_ref()
+            .context("User database writer stopped")?;
+        *lock(&self.pending.0).entry(table).or_default() += 1;
+        if sender.send((table, write)).is_err() {
+            *lock(&self.pending.0).entry(table).or_default() -= 1;
+            bail!("User database writer stopped");
+        }
+        Ok(())
+    }
+
+    /// Block until the queued writes to `table` are done
+    fn wait_for(&self, table: &str) {
+        let (counts, done) = &*self.pending;
+        let mut counts = lock(counts);
+        while counts.get(table).is_some_and(|&count| count > 0) {
+            counts = done.wait(counts).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// Marks a queued write done when dropped, however the write ended
+struct Done<'a> {
+    pending: &'a Pending,
+    table: &'static str,
+}
+
+impl Drop for Done<'_> {
+    fn drop(&mut self) {
+        let (counts, done) = &**self.pending;
+        if let Some(count) = lock(counts).get_mut(self.table) {
+            *count -= 1;
+        }
+        done.notify_all();
+    }
+}
+
+/// The pending counts, even if a thread panicked holding them: they're
+/// plain counters, always left consistent
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Drop for Writer {
+    /// Finish the queued writes before the process goes
+    fn drop(&mut self) {
+        self.sender = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn connect(path: &Path) -> Result<Connection> {
+    let conn =
+        Connection::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    conn.busy_timeout(std::time::Duration::from_millis(BUSY_TIMEOUT_MS.into()))?;
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")?;
+    Ok(conn)
+}
+
+fn migrate(conn: &Connection) -> Result<()> {
+    for (table, column, definition) in COLUMN_MIGRATIONS {
+        let exists: bool = conn.query_row(
+            &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{table}') WHERE name = ?"),
+            params![column],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            conn.execute_batch(&format!(
+                "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+            ))
+            .with_context(|| format!("Failed to add {table}.{column}"))?;
+        }
+    }
+    for (table, column) in KEY_MIGRATIONS {
+        let keyed: bool = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('{table}') WHERE name = ? AND pk > 0"
+            ),
+            params![column],
+            |row| row.get(0),
+        )?;
+        if !keyed {
+            rebuild_table(conn, table, column)
+                .with_context(|| format!("Failed to add {table}.{column} to its key"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Recreate `table` as `SCHEMA` defines it now. The old rows carry over if
+/// they have the new key `column` (the new table has all their columns);
+/// otherwise they can't be keyed and are dropped, which for `known_posts`
+/// makes the next alert check a first run that only records the corpus.
+fn rebuild_table(conn: &Connection, table: &str, column: &str) -> Result<()> {
+    let columns = conn
+        .prepare(&format!("SELECT name FROM pragma_table_info('{table}')"))?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(&format!("ALTER TABLE {table} RENAME TO {table}_old"))?;
+    tx.execute_batch(SCHEMA)?;
+    if columns.iter().any(|name| name == column) {
+        let columns = columns.join(", ");
+        tx.execute_batch(&format!(
+            "INSERT OR REPLACE INTO {table} ({columns}) SELECT {columns} FROM {table}_old"
+        ))?;
+    }
+    tx.execute_batch(&format!("DROP TABLE {table}_old"))?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Profile picked with `--profile`, whose user data is kept apart
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Keep user data in `user-<name>.db` instead of `user.db` from now on, so
+/// each profile's corpus has its own bookmarks, history and marks
+pub fn use_profile(name: String) {
+    let _ = PROFILE.set(name);
+}
+
+fn get_user_db_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .context("Could not find data directory")?
+        .join("erwindb");
+
+    Ok(match PROFILE.get() {
+        Some(name) => data_dir.join(format!("user-{name}.db")),
+        None => data_dir.join("user.db"),
+    })
+}
+
+impl UserDb {
+    pub fn open() -> Result<Self> {
+        let path = get_user_db_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create data directory")?;
+        }
+        Self::open_at(&path)
+    }
+
+    fn open_at(path: &Path) -> Result<Self> {
+        let conn = connect(path)?;
+        conn.execute_batch(SCHEMA)?;
+        migrate(&conn)?;
+        let writer = Writer::start(connect(path)?);
+        Ok(Self { conn, writer })
+    }
+
+    /// The latest failure of a queued write, if any since the last call
+    pub fn take_write_error(&self) -> Option<String> {
+        self.writer.errors.try_iter().last()
+    }
+
+    /// Create a named collection holding the questions, by site and ID, in
+    /// the given order
+    pub fn create_collection(&self, name: &str, questions: &[(Site, i64)]) -> Result<i64> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO collections (name, created_at) VALUES (?1, ?2)",
+            params![name, clock::now().timestamp()],
+        )
+        .with_context(|| format!("Failed to create collection \"{name}\""))?;
+        let collection_id = tx.last_insert_rowid();
+
+        for (position, (site, question_id)) in questions.iter().enumerate() {
+            tx.execute(
+                "INSERT OR IGNORE INTO collection_items
+                     (collection_id, site, question_id, position)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![collection_id, site.as_db(), question_id, position as i64],
+            )?;
+        }
+        tx.commit()?;
+        Ok(collection_id)
+    }
+
+    /// Questions of the named collection, by site and ID, in their saved
+    /// order, or `None` if there's no such collection
+    pub fn get_collection(&self, name: &str) -> Result<Option<Vec<(Site, i64)>>> {
+        let collection_id: Option<i64> = self
+            .conn
+            .query_row(
+                "SELECT id FROM collections WHERE name = ?1",
+                params![name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(collection_id) = collection_id else {
+            if name == ENGAGED_COLLECTION {
+                let engaged = self.get_engaged_questions()?;
+                return Ok(Some(
+                    engaged.iter().map(|q| (q.site, q.question_id)).collect(),
+                ));
+            }
+            return Ok(None);
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT site, question_id FROM collection_items
+             WHERE collection_id = ?1 ORDER BY position",
+        )?;
+        let questions = stmt
+            .query_map(params![collection_id], |row| {
+                Ok((Site::from_db(&row.get::<_, String>(0)?), row.get(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(Some(questions))
+    }
+
+    pub fn get_known_posts(&self) -> Result<HashSet<PostKey>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT site, question_id, answer_id FROM known_posts")?;
+
+        let posts = stmt
+            .query_map([], |row| {
+                let answer_id: i64 = row.get(2)?;
+                Ok(PostKey {
+                    site: Site::from_db(&row.get::<_, String>(0)?),
+                    question_id: row.get(1)?,
+                    answer_id: (answer_id != 0).then_some(answer_id),
+                })
+            })?
+            .collect::<std::result::Result<HashSet<_>, _>>()?;
+
+        Ok(posts)
+    }
+
+    /// Remember posts as seen, together with any alerts they triggered
+    pub fn record_new_posts(
+        &self,
+        posts: &[PostKey],
+        hits: &[(String, PostKey, String)],
+    ) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT OR IGNORE INTO known_posts (site, question_id, answer_id)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for post in posts {
+                insert.execute(params![
+                    post.site.as_db(),
+                    post.question_id,
+                    post.answer_id.unwrap_or(0)
+                ])?;
+            }
+
+            let mut insert = tx.prepare(
+                "INSERT INTO alert_hits (rule, site, question_id, answer_id, title, found_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            let found_at = clock::now().timestamp();
+            for (rule, post, title) in hits {
+                insert.execute(params![
+                    rule,
+                    post.site.as_db(),
+                    post.question_id,
+                    post.answer_id,
+                    title,
+                    found_at
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Alert hits, newest first
+    pub fn get_alert_hits(&self) -> Result<Vec<AlertHit>> {
+        self.writer.wait_for("alert_hits");
+        let mut stmt = self.conn.prepare(
+            "SELECT id, rule, site, question_id, answer_id, title, found_at, read
+             FROM alert_hits ORDER BY found_at DESC, id DESC",
+        )?;
+
+        let hits = stmt
+            .query_map([], |row| {
+                Ok(AlertHit {
+                    id: row.get(0)?,
+                    rule: row.get(1)?,
+                    post: PostKey {
+                        site: Site::from_db(&row.get::<_, String>(2)?),
+                        question_id: row.get(3)?,
+                        answer_id: row.get(4)?,
+                    },
+                    title: row.get(5)?,
+                    found_at: row.get(6)?,
+                    read: row.get(7)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(hits)
+    }
+
+    pub fn unread_alert_count(&self) -> Result<usize> {
+        self.writer.wait_for("alert_hits");
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM alert_hits WHERE read = 0",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    pub fn set_alert_read(&self, id: i64, read: bool) -> Result<()> {
+        self.writer.queue(
+            "alert_hits",
+            Box::new(move |conn| {
+                conn.execute(
+                    "UPDATE alert_hits SET read = ?1 WHERE id = ?2",
+                    params![read, id],
+                )?;
+                Ok(())
+            }),
+        )
+    }
+
+    pub fn mark_all_alerts_read(&self) -> Result<()> {
+        self.writer.queue(
+            "alert_hits",
+            Box::new(|conn| {
+                conn.execute("UPDATE alert_hits SET read = 1 WHERE read = 0", [])?;
+                Ok(())
+            }),
+        )
+    }
+
+    /// Add reading time to a question; once its total reaches `engaged_seconds`
+    /// it joins the engaged collection
+    pub fn add_reading_time(
+        &self,
+        site: Site,
+        question_id: i64,
+        seconds: i64,
+        engaged_seconds: i64,
+    ) -> Result<()> {
+        let now = clock::now().timestamp();
+        self.writer.queue(
+            "reading_time",
+            Box::new(move |conn| {
+                conn.execute(
+                    "INSERT INTO reading_time (site, question_id, seconds, last_read)
+                     VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT (site, question_id) DO UPDATE
+                     SET seconds = seconds + excluded.seconds, last_read = excluded.last_read",
+                    params![site.as_db(), question_id, seconds, now],
+                )?;
+                conn.execute(
+                    "UPDATE reading_time SET engaged_at = ?1
+                     WHERE site = ?2 AND question_id = ?3
+                       AND engaged_at IS NULL AND seconds >= ?4",
+                    params![now, site.as_db(), question_id, engaged_seconds],
+                )?;
+                Ok(())
+            }),
+        )
+    }
+
+    /// Engaged questions, soonest due for review first
+    pub fn get_engaged_questions(&self) -> Result<Vec<EngagedQuestion>> {
+        self.writer.wait_for("reading_time");
+        let mut stmt = self.conn.prepare(
+            "SELECT site, question_id, seconds, engaged_at, reviews, reviewed_at
+             FROM reading_time WHERE engaged_at IS NOT NULL",
+        )?;
+        let mut questions = stmt
+            .query_map([], |row| {
+                Ok(EngagedQuestion {
+                    site: Site::from_db(&row.get::<_, String>(0)?),
+                    question_id: row.get(1)?,
+                    seconds: row.get(2)?,
+                    engaged_at: row.get(3)?,
+                    reviews: row.get(4)?,
+                    reviewed_at: row.get(5)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        questions.sort_by_key(|q| (q.due_at(), q.question_id, q.site.as_db()));
+        Ok(questions)
+    }
+
+    /// Count a review of an engaged question, pushing its next one further out
+    pub fn mark_reviewed(&self, site: Site, question_id: i64) -> Result<()> {
+        let now = clock::now().timestamp();
+        self.writer.queue(
+            "reading_time",
+            Box::new(move |conn| {
+                conn.execute(
+                    "UPDATE reading_time SET reviews = reviews + 1, reviewed_at = ?1
+                     WHERE site = ?2 AND question_id = ?3",
+                    params![now, site.as_db(), question_id],
+                )?;
+                Ok(())
+            }),
+        )
+    }
+
+    /// Bookmarked questions, by site and ID
+    pub fn get_bookmarks(&self) -> Result<HashSet<(Site, i64)>> {
+        self.writer.wait_for("bookmarks");
+        let mut stmt = self
+            .conn
+            .prepare("SELECT site, question_id FROM bookmarks")?;
+        let questions = stmt
+            .query_map([], |row| {
+                Ok((Site::from_db(&row.get::<_, String>(0)?), row.get(1)?))
+            })?
+            .collect::<std::result::Result<HashSet<_>, _>>()?;
+        Ok(questions)
+    }
+
+    pub fn set_bookmark(&self, site: Site, question_id: i64, bookmarked: bool) -> Result<()> {
+        let now = clock::now().timestamp();
+        self.writer.queue(
+            "bookmarks",
+            Box::new(move |conn| {
+                if bookmarked {
+                    conn.execute(
+                        "INSERT OR IGNORE INTO bookmarks (site, question_id, created_at)
+                         VALUES (?1, ?2, ?3)",
+                        params![site.as_db(), question_id, now],
+                    )?;
+                } else {
+                    conn.execute(
+                        "DELETE FROM bookmarks WHERE site = ?1 AND question_id = ?2",
+                        params![site.as_db(), question_id],
+                    )?;
+                }
+                Ok(())
+            }),
+        )
+    }
+
+    /// Hidden questions, latest first
+    pub fn get_hidden(&self) -> Result<Vec<HiddenQuestion>> {
+        self.writer.wait_for("hidden");
+        let mut stmt = self.conn.prepare(
+            "SELECT site, question_id, hidden_at FROM hidden
+             ORDER BY hidden_at DESC, question_id DESC",
+        )?;
+        let hidden = stmt
+            .query_map([], |row| {
+                Ok(HiddenQuestion {
+                    site: Site::from_db(&row.get::<_, String>(0)?),
+                    question_id: row.get(1)?,
+                    hidden_at: row.get(2)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(hidden)
+    }
+
+    pub fn set_hidden(&self, site: Site, question_id: i64, hidden: bool) -> Result<()> {
+        let now = clock::now().timestamp();
+        self.writer.queue(
+            "hidden",
+            Box::new(move |conn| {
+                if hidden {
+                    conn.execute(
+                        "INSERT OR IGNORE INTO hidden (site, question_id, hidden_at)
+                         VALUES (?1, ?2, ?3)",
+                        params![site.as_db(), question_id, now],
+                    )?;
+                } else {
+                    conn.execute(
+                        "DELETE FROM hidden WHERE site = ?1 AND question_id = ?2",
+                        params![site.as_db(), question_id],
+                    )?;
+                }
+                Ok(())
+            }),
+        )
+    }
+
+    pub fn record_view(&self, site: Site, question_id: i64) -> Result<()> {
+        let now = clock::now().timestamp();
+        self.writer.queue(
+            "views",
+            Box::new(move |conn| {
+                conn.execute(
+                    "INSERT INTO views (site, question_id, viewed_at) VALUES (?1, ?2, ?3)",
+                    params![site.as_db(), question_id, now],
+                )?;
+                Ok(())
+            }),
+        )
+    }
+
+    /// Questions opened at some point, by site and ID, for marking them as read
+    pub fn get_viewed_questions(&self) -> Result<HashSet<(Site, i64)>> {
+        self.writer.wait_for("views");
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT site, question_id FROM views")?;
+        let questions = stmt
+            .query_map([], |row| {
+                Ok((Site::from_db(&row.get::<_, String>(0)?), row.get(1)?))
+            })?
+            .collect::<std::result::Result<HashSet<_>, _>>()?;
+        Ok(questions)
+    }
+
+    /// The most recently opened questions, latest first
+    pub fn get_recent_views(&self, limit: usize) -> Result<Vec<RecentView>> {
+        self.writer.wait_for("views");
+        let mut stmt = self.conn.prepare(
+            "SELECT site, question_id, MAX(viewed_at), COUNT(*) FROM views
+             GROUP BY site, question_id ORDER BY MAX(viewed_at) DESC, MAX(id) DESC LIMIT ?1",
+        )?;
+        let views = stmt
+            .query_map([limit as i64], |row| {
+                Ok(RecentView {
+                    site: Site::from_db(&row.get::<_, String>(0)?),
+                    question_id: row.get(1)?,
+                    last_viewed: row.get(2)?,
+                    views: row.get(3)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(views)
+    }
+
+    /// Store a fetched thread, replacing an earlier fetch of it
+    pub fn save_fetched_question(
+        &self,
+        question_id: i64,
+        site: Site,
+        thread: String,
+    ) -> Result<()> {
+        let now = clock::now().timestamp();
+        self.writer.queue(
+            "fetched_questions",
+            Box::new(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO fetched_questions (question_id, site, thread, fetched_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![question_id, site.as_db(), thread, now],
+                )?;
+                Ok(())
+            }),
+        )
+    }
+
+    /// The API item of a thread fetched from `site`
+    pub fn get_fetched_question(&self, site: Site, question_id: i64) -> Result<Option<String>> {
+        self.writer.wait_for("fetched_questions");
+        let fetched = self
+            .conn
+            .query_row(
+                "SELECT thread FROM fetched_questions WHERE site = ?1 AND question_id = ?2",
+                params![site.as_db(), question_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(fetched)
+    }
+
+    /// The fetched thread an answer belongs to
+    pub fn get_fetched_question_for_answer(
+        &self,
+        site: Site,
+        answer_id: i64,
+    ) -> Result<Option<i64>> {
+        self.writer.wait_for("fetched_questions");
+        let question_id = self
+            .conn
+            .query_row(
+                "SELECT question_id FROM fetched_questions, json_each(thread, '$.answers')
+                 WHERE site = ?1 AND json_extract(value, '$.answer_id') = ?2",
+                params![site.as_db(), answer_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(question_id)
+    }
+
+    /// Marks set on a question, by letter
+    pub fn get_marks(&self, site: Site, question_id: i64) -> Result<HashMap<char, ScrollMark>> {
+        self.writer.wait_for("marks");
+        let mut stmt = self.conn.prepare(
+            "SELECT mark, erwin_pane, answer_id, line FROM marks
+             WHERE site = ?1 AND question_id = ?2",
+        )?;
+        let marks = stmt
+            .query_map(params![site.as_db(), question_id], |row| {
+                let mark: String = row.get(0)?;
+                let offset: i64 = row.get(3)?;
+                Ok((
+                    mark,
+                    ScrollMark {
+                        erwin_pane: row.get(1)?,
+                        answer_id: row.get(2)?,
+                        offset: offset as usize,
+                    },
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(marks
+            .into_iter()
+            .filter_map(|(mark, pos)| Some((mark.chars().next()?, pos)))
+            .collect())
+    }
+
+    pub fn set_mark(
+        &self,
+        site: Site,
+        question_id: i64,
+        mark: char,
+        pos: ScrollMark,
+    ) -> Result<()> {
+        self.writer.queue(
+            "marks",
+            Box::new(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO marks
+                         (site, question_id, mark, erwin_pane, answer_id, line)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        site.as_db(),
+                        question_id,
+                        mark.to_string(),
+                        pos.erwin_pane,
+                        pos.answer_id,
+                        pos.offset as i64
+                    ],
+                )?;
+                Ok(())
+            }),
+        )
+    }
+
+    pub fn add_to_wishlist(&self, entry: WishlistEntry) -> Result<()> {
+        self.writer.queue(
+            "wishlist",
+            Box::new(move |conn| {
+                conn.execute(
+                    "INSERT INTO wishlist (query, kind, results, note, added_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        entry.query,
+                        entry.kind,
+                        entry.results as i64,
+                        entry.note,
+                        entry.added_at
+                    ],
+                )?;
+                Ok(())
+            }),
+        )
+    }
+
+    /// The wishlist, oldest first
+    pub fn get_wishlist(&self) -> Result<Vec<WishlistEntry>> {
+        self.writer.wait_for("wishlist");
+        let mut stmt = self.conn.prepare(
+            "SELECT query, kind, results, note, added_at FROM wishlist ORDER BY added_at, id",
+        )?;
+        let entries = stmt
+            .query_map([], |row| {
+                let results: i64 = row.get(2)?;
+                Ok(WishlistEntry {
+                    query: row.get(0)?,
+                    kind: row.get(1)?,
+                    results: results as usize,
+                    note: row.get(3)?,
+                    added_at: row.get(4)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    /// Everything `UserState` covers
+    pub fn export_state(&self) -> Result<UserState> {
+        for table in ["bookmarks", "views", "reading_time", "marks", "hidden"] {
+            self.writer.wait_for(table);
+        }
+        let conn = &self.conn;
+        let bookmarks = conn
+            .prepare(
+                "SELECT site, question_id, created_at FROM bookmarks
+                 ORDER BY created_at, site, question_id",
+            )?
+            .query_map([], |row| {
+                Ok(BookmarkState {
+                    site: row.get(0)?,
+                    question_id: row.get(1)?,
+                    created_at: row.get(2)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut collections = conn
+            .prepare("SELECT id, name, created_at FROM collections ORDER BY id")?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    CollectionState {
+                        name: row.get(1)?,
+                        created_at: row.get(2)?,
+                        question_ids: Vec::new(),
+                        sites: Vec::new(),
+                    },
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let mut items = conn.prepare(
+            "SELECT site, question_id FROM collection_items
+             WHERE collection_id = ?1 ORDER BY position",
+        )?;
+        for (id, collection) in &mut collections {
+            (collection.sites, collection.question_ids) = items
+                .query_map([*id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+                })?
+                .collect::<std::result::Result<Vec<_>, _>>()?
+                .into_iter()
+                .unzip();
+        }
+
+        let views = conn
+            .prepare("SELECT site, question_id, viewed_at FROM views ORDER BY id")?
+            .query_map([], |row| {
+                Ok(ViewState {
+                    site: row.get(0)?,
+                    question_id: row.get(1)?,
+                    viewed_at: row.get(2)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let reading_time = conn
+            .prepare(
+                "SELECT site, question_id, seconds, last_read, engaged_at, reviews, reviewed_at
+                 FROM reading_time ORDER BY site, question_id",
+            )?
+            .query_map([], |row| {
+                Ok(ReadingTimeState {
+                    site: row.get(0)?,
+                    question_id: row.get(1)?,
+                    seconds: row.get(2)?,
+                    last_read: row.get(3)?,
+                    engaged_at: row.get(4)?,
+                    reviews: row.get(5)?,
+                    reviewed_at: row.get(6)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let marks = conn
+            .prepare(
+                "SELECT site, question_id, mark, erwin_pane, answer_id, line FROM marks
+                 ORDER BY site, question_id, mark",
+            )?
+            .query_map([], |row| {
+                Ok(MarkState {
+                    site: row.get(0)?,
+                    question_id: row.get(1)?,
+                    mark: row.get(2)?,
+                    erwin_pane: row.get(3)?,
+                    answer_id: row.get(4)?,
+                    line: row.get(5)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let hidden = conn
+            .prepare(
+                "SELECT site, question_id, hidden_at FROM hidden
+                 ORDER BY hidden_at, site, question_id",
+            )?
+            .query_map([], |row| {
+                Ok(HiddenState {
+                    site: row.get(0)?,
+                    question_id: row.get(1)?,
+                    hidden_at: row.get(2)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(UserState {
+            version: STATE_VERSION,
+            bookmarks,
+            collections: collections.into_iter().map(|(_, c)| c).collect(),
+            views,
+            reading_time,
+            marks,
+            hidden,
+        })
+    }
+
+    /// Merge exported state into this database, keeping what's here: bookmarks,
+    /// views and hidden questions are added, collections of the same name gain the questions
+    /// they lack (at the end), reading time takes the larger figures, and marks
+    /// already set stay. Importing the same state twice changes nothing.
+    pub fn import_state(&self, state: &UserState) -> Result<ImportCounts> {
+        if state.version > STATE_VERSION {
+            bail!(
+                "State format version {} is newer than this erwindb supports ({STATE_VERSION})",
+                state.version
+            );
+        }
+        for table in ["bookmarks", "views", "reading_time", "marks", "hidden"] {
+            self.writer.wait_for(table);
+        }
+        let mut counts = ImportCounts::default();
+        let tx = self.conn.unchecked_transaction()?;
+
+        for bookmark in &state.bookmarks {
+            counts.bookmarks += tx.execute(
+                "INSERT OR IGNORE INTO bookmarks (site, question_id, created_at)
+                 VALUES (?1, ?2, ?3)",
+                params![
+                    Site::from_db(&bookmark.site).as_db(),
+                    bookmark.question_id,
+                    bookmark.created_at
+                ],
+            )?;
+        }
+
+        for collection in &state.collections {
+            let existing: Option<i64> = tx
+                .query_row(
+                    "SELECT id FROM collections WHERE name = ?1",
+                    params![collection.name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let collection_id = match existing {
+                Some(id) => id,
+                None => {
+                    tx.execute(
+                        "INSERT INTO collections (name, created_at) VALUES (?1, ?2)",
+                        params![collection.name, collection.created_at],
+                    )?;
+                    counts.collections += 1;
+                    tx.last_insert_rowid()
+                }
+            };
+            for (i, question_id) in collection.question_ids.iter().enumerate() {
+                let site = collection
+                    .sites
+                    .get(i)
+                    .map_or(Site::default(), |site| Site::from_db(site));
+                counts.collection_items += tx.execute(
+                    "INSERT OR IGNORE INTO collection_items
+                         (collection_id, site, question_id, position)
+                     SELECT ?1, ?2, ?3, COALESCE(MAX(position) + 1, 0)
+                     FROM collection_items WHERE collection_id = ?1",
+                    params![collection_id, site.as_db(), question_id],
+                )?;
+            }
+        }
+
+        for view in &state.views {
+            counts.views += tx.execute(
+                "INSERT INTO views (site, question_id, viewed_at)
+                 SELECT ?1, ?2, ?3 WHERE NOT EXISTS
+                     (SELECT 1 FROM views WHERE site = ?1 AND question_id = ?2 AND viewed_at = ?3)",
+                params![
+                    Site::from_db(&view.site).as_db(),
+                    view.question_id,
+                    view.viewed_at
+                ],
+            )?;
+        }
+
+        for time in &state.reading_time {
+            counts.reading_time += tx.execute(
+                "INSERT INTO reading_time
+                     (site, question_id, seconds, last_read, engaged_at, reviews, reviewed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT (site, question_id) DO UPDATE SET
+                     seconds = MAX(seconds, excluded.seconds),
+                     last_read = MAX(last_read, excluded.last_read),
+                     engaged_at = COALESCE(MIN(engaged_at, excluded.engaged_at),
+                                           engaged_at, excluded.engaged_at),
+                     reviews = MAX(reviews, excluded.reviews),
+                     reviewed_at = COALESCE(MAX(reviewed_at, excluded.reviewed_at),
+                                            reviewed_at, excluded.reviewed_at)
+                 WHERE excluded.seconds > seconds
+                    OR excluded.last_read > last_read
+                    OR excluded.reviews > reviews
+                    OR (excluded.engaged_at IS NOT NULL
+                        AND (engaged_at IS NULL OR excluded.engaged_at < engaged_at))
+                    OR (excluded.reviewed_at IS NOT NULL
+                        AND (reviewed_at IS NULL OR excluded.reviewed_at > reviewed_at))",
+                params![
+                    Site::from_db(&time.site).as_db(),
+                    time.question_id,
+                    time.seconds,
+                    time.last_read,
+                    time.engaged_at,
+                    time.reviews,
+                    time.reviewed_at
+                ],
+            )?;
+        }
+
+        for mark in &state.marks {
+            counts.marks += tx.execute(
+                "INSERT OR IGNORE INTO marks
+                     (site, question_id, mark, erwin_pane, answer_id, line)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    Site::from_db(&mark.site).as_db(),
+                    mark.question_id,
+                    mark.mark,
+                    mark.erwin_pane,
+                    mark.answer_id,
+                    mark.line
+                ],
+            )?;
+        }
+
+        for hidden in &state.hidden {
+            counts.hidden += tx.execute(
+                "INSERT OR IGNORE INTO hidden (site, question_id, hidden_at) VALUES (?1, ?2, ?3)",
+                params![
+                    Site::from_db(&hidden.site).as_db(),
+                    hidden.question_id,
+                    hidden.hidden_at
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh user database in the temp directory, removed when dropped
+    struct TempUserDb {
+        db: Option<UserDb>,
+        path: PathBuf,
+    }
+
+    impl TempUserDb {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("erwindb-test-{}-{name}.db", std::process::id()));
+            let _ = fs::remove_file(&path);
+            let db = UserDb::open_at(&path).unwrap();
+            Self { db: Some(db), path }
+        }
+    }
+
+    impl std::ops::Deref for TempUserDb {
+        type Target = UserDb;
+
+        fn deref(&self) -> &UserDb {
+            self.db.as_ref().unwrap()
+        }
+    }
+
+    impl Drop for TempUserDb {
+        fn drop(&mut self) {
+            self.db = None;
+            for suffix in ["", "-wal", "-shm"] {
+                let _ = fs::remove_file(format!("{}{suffix}", self.path.display()));
+            }
+        }
+    }
+
+    /// An export made before the `site` fields and hidden questions existed
+    const OLD_EXPORT: &str = r#"{
+        "version": 1,
+        "bookmarks": [{ "question_id": 3800551, "created_at": 100 }],
+        "collections": [{ "name": "groupwise", "created_at": 100, "question_ids": [3800551, 7630564] }],
+        "views": [{ "question_id": 3800551, "viewed_at": 200 }],
+        "reading_time": [{
+            "question_id": 3800551, "seconds": 60, "last_read": 200,
+            "engaged_at": 150, "reviews": 0, "reviewed_at": null
+        }],
+        "marks": [{ "question_id": 3800551, "mark": "a", "erwin_pane": false, "line": 12 }]
+    }"#;
+
+    #[test]
+    fn import_of_an_old_export_is_stack_overflow_and_idempotent() {
+        let db = TempUserDb::new("import-old");
+        let state: UserState = serde_json::from_str(OLD_EXPORT).unwrap();
+
+        let counts = db.import_state(&state).unwrap();
+        assert_eq!(
+            (
+                counts.bookmarks,
+                counts.collections,
+                counts.collection_items,
+                counts.views,
+                counts.reading_time,
+                counts.marks,
+                counts.hidden,
+            ),
+            (1, 1, 2, 1, 1, 1, 0)
+        );
+        assert_eq!(
+            db.get_bookmarks().unwrap(),
+            HashSet::from([(Site::StackOverflow, 3800551)])
+        );
+        assert_eq!(
+            db.get_collection("groupwise").unwrap(),
+            Some(vec![
+                (Site::StackOverflow, 3800551),
+                (Site::StackOverflow, 7630564)
+            ])
+        );
+        assert!(db
+            .get_marks(Site::StackOverflow, 3800551)
+            .unwrap()
+            .contains_key(&'a'));
+
+        let again = db.import_state(&state).unwrap();
+        assert_eq!(
+            again.bookmarks
+                + again.collections
+                + again.collection_items
+                + again.views
+                + again.reading_time
+                + again.marks
+                + again.hidden,
+            0
+        );
+    }
+
+    #[test]
+    fn import_merges_into_existing_state() {
+        let db = TempUserDb::new("import-merge");
+        db.create_collection("groupwise", &[(Site::Dba, 3800551)])
+            .unwrap();
+        db.import_state(&serde_json::from_str(OLD_EXPORT).unwrap())
+            .unwrap();
+
+        // Same collection name: the other site's question is a different
+        // one, so both imported questions go at the end
+        assert_eq!(
+            db.get_collection("groupwise").unwrap(),
+            Some(vec![
+                (Site::Dba, 3800551),
+                (Site::StackOverflow, 3800551),
+                (Site::StackOverflow, 7630564)
+            ])
+        );
+
+        // Reading time keeps the larger figures from either side
+        let mut state = db.export_state().unwrap();
+        let time = &mut state.reading_time[0];
+        time.seconds = 30;
+        time.last_read = 300;
+        time.engaged_at = Some(120);
+        let counts = db.import_state(&state).unwrap();
+        assert_eq!(counts.reading_time, 1);
+        let time = &db.export_state().unwrap().reading_time[0];
+        assert_eq!(
+            (time.seconds, time.last_read, time.engaged_at),
+            (60, 300, Some(120))
+        );
+
+        // A mark already set stays
+        let mut state = db.export_state().unwrap();
+        state.marks[0].line = 99;
+        assert_eq!(db.import_state(&state).unwrap().marks, 0);
+        assert_eq!(db.export_state().unwrap().marks[0].line, 12);
+    }
+
+    #[test]
+    fn import_rejects_newer_formats() {
+        let db = TempUserDb::new("import-newer");
+        let mut state: UserState = serde_json::from_str(OLD_EXPORT).unwrap();
+        state.version = STATE_VERSION + 1;
+        assert!(db.import_state(&state).is_err());
+        assert!(db.get_bookmarks().unwrap().is_empty());
+    }
+}